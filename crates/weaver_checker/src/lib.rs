@@ -110,6 +110,12 @@ pub enum Error {
         provenance: String,
         /// The violation.
         violation: Box<PolicyFinding>,
+        /// The line (1-based) of the definition the violation applies to, if known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+        /// The column (1-based) of the definition the violation applies to, if known.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        column: Option<usize>,
     },
 
     /// A container for multiple errors.
@@ -12,6 +12,7 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Component, Path, PathBuf};
 use std::process::exit;
+use weaver_common::cache::CacheSettings;
 use weaver_common::vdir::VirtualDirectoryPath;
 use weaver_common::MemLog;
 use weaver_forge::config::{Params, WeaverConfig};
@@ -42,13 +43,14 @@ fn main() {
     let registry_path = VirtualDirectoryPath::LocalFolder {
         path: SEMCONV_REGISTRY_PATH.into(),
     };
-    let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])
-        .unwrap_or_else(|e| process_error(&logger, e));
-    let loaded = SchemaResolver::load_semconv_repository(registry_repo, FOLLOW_SYMLINKS)
+    let registry_repo =
+        RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+            .unwrap_or_else(|e| process_error(&logger, e));
+    let loaded = SchemaResolver::load_semconv_repository(registry_repo, FOLLOW_SYMLINKS, None)
         .ignore(|e| matches!(e.severity(), Some(miette::Severity::Warning)))
         .into_result_failing_non_fatal()
         .unwrap_or_else(|e| process_error(&logger, e));
-    let schema = SchemaResolver::resolve(loaded, false)
+    let schema = SchemaResolver::resolve(loaded, false, None)
         .into_result_failing_non_fatal()
         .unwrap_or_else(|e| process_error(&logger, e));
 
@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A content-addressed local cache for remote registries, and the lock file pinning them.
+//!
+//! Git repositories and remote archives referenced by a [`VirtualDirectoryPath`] are stored
+//! in the cache directory (`~/.weaver/cache` by default) under a key derived from their
+//! content:
+//! - `git/<url hash>/<commit>`: a checkout of a Git repository at a given commit.
+//! - `archives/<sha256>`: the unpacked content of an archive with the given digest.
+//! - `refs/<source hash>`: the last commit or digest resolved for a source, used to
+//!   resolve sources that are not pinned by a lock file in offline mode.
+//!
+//! - `resolution/`: the parsed semantic convention files and the resolved dependencies,
+//!   when the resolution cache is enabled (see `weaver_resolver`).
+//!
+//! A lock file (`weaver.lock`) pins each remote source to a commit hash or an archive
+//! digest. A pinned source found in the cache is never fetched again.
+//!
+//! Cache entries are never evicted, the cache directory can be deleted at any time to
+//! reclaim disk space. When the cache is disabled, remote sources are stored in a temporary
+//! directory deleted with the virtual directory using them.
+
+use crate::vdir::VirtualDirectoryPath;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// The default name of the lock file.
+pub const DEFAULT_LOCK_FILE: &str = "weaver.lock";
+
+/// The settings of the cache of remote registries.
+#[derive(Debug, Clone, Default)]
+pub struct CacheSettings {
+    /// The directory of the cache. Defaults to `~/.weaver/cache`.
+    pub cache_dir: Option<PathBuf>,
+    /// When enabled, remote registries are only resolved from the cache.
+    pub offline: bool,
+    /// The path to the lock file. Defaults to `weaver.lock` in the current directory.
+    pub lock_file: Option<PathBuf>,
+    /// When enabled, the parsed semantic convention files and the resolved dependencies are
+    /// cached, so that unchanged files and dependencies are not parsed and resolved again.
+    pub resolution_cache: bool,
+    /// When enabled, remote registries are stored in temporary directories instead of the
+    /// cache directory.
+    pub disabled: bool,
+    /// When enabled, the pins of the lock file are ignored (except in offline mode), so that
+    /// the remote registries are fetched again to update them.
+    pub refresh_lock: bool,
+}
+
+impl CacheSettings {
+    /// Returns the path to the lock file.
+    #[must_use]
+    pub fn lock_file_path(&self) -> PathBuf {
+        self.lock_file
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_LOCK_FILE))
+    }
+
+    /// Returns the directory of the cache.
+    pub fn cache_dir(&self) -> Result<PathBuf, Error> {
+        match &self.cache_dir {
+            Some(cache_dir) => Ok(cache_dir.clone()),
+            None => Ok(dirs::home_dir()
+                .ok_or(Error::HomeDirNotFound)?
+                .join(".weaver/cache")),
+        }
+    }
+
+    /// Returns the directory of the resolution cache, or `None` if it is disabled.
+    pub fn resolution_cache_dir(&self) -> Result<Option<PathBuf>, Error> {
+        if !self.resolution_cache {
+            return Ok(None);
+        }
+        Ok(Some(self.cache_dir()?.join("resolution")))
+    }
+
+    /// Returns the cache described by these settings.
+    pub fn cache(&self) -> Result<RegistryCache, Error> {
+        let lock_file = self.lock_file_path();
+        let lock = if lock_file.exists() && (self.offline || !self.refresh_lock) {
+            LockFile::try_from_file(&lock_file)?
+        } else {
+            LockFile::default()
+        };
+        if self.disabled {
+            let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+            let tmp_path = home.join(".weaver/vdir_cache");
+            create_dir(&tmp_path)?;
+            let tmp_dir = tempfile::Builder::new()
+                .prefix("cache")
+                .tempdir_in(tmp_path)
+                .map_err(|e| Error::CacheDirNotCreated {
+                    message: e.to_string(),
+                })?;
+            return Ok(RegistryCache {
+                root: tmp_dir.path().to_path_buf(),
+                offline: self.offline,
+                lock,
+                tmp_root: Arc::new(Some(tmp_dir)),
+            });
+        }
+        Ok(RegistryCache::new(self.cache_dir()?, self.offline, lock))
+    }
+}
+
+/// The commit or the digest a remote source is pinned to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockedSource {
+    /// The commit hash of a Git repository.
+    Commit(String),
+    /// The SHA-256 digest of an archive, prefixed with `sha256:`.
+    Digest(String),
+}
+
+impl Display for LockedSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockedSource::Commit(commit) => write!(f, "{commit}"),
+            LockedSource::Digest(digest) => write!(f, "{digest}"),
+        }
+    }
+}
+
+/// The content of a `weaver.lock` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LockFile {
+    /// The remote sources, indexed by their lock key (see [`VirtualDirectoryPath::lock_key`]).
+    #[serde(default, with = "serde_yaml::with::singleton_map_recursive")]
+    pub sources: BTreeMap<String, LockedSource>,
+}
+
+impl LockFile {
+    /// Loads a lock file.
+    pub fn try_from_file(path: &Path) -> Result<Self, Error> {
+        let invalid_lock_file = |error: String| Error::InvalidLockFile {
+            path: path.to_path_buf(),
+            error,
+        };
+        let content =
+            std::fs::read_to_string(path).map_err(|e| invalid_lock_file(e.to_string()))?;
+        serde_yaml::from_str(&content).map_err(|e| invalid_lock_file(e.to_string()))
+    }
+
+    /// Writes the lock file.
+    pub fn write_to_file(&self, path: &Path) -> Result<(), Error> {
+        let invalid_lock_file = |error: String| Error::InvalidLockFile {
+            path: path.to_path_buf(),
+            error,
+        };
+        let yaml = serde_yaml::to_string(self).map_err(|e| invalid_lock_file(e.to_string()))?;
+        std::fs::write(
+            path,
+            format!("# Generated by `weaver registry fetch`, do not edit manually.\n{yaml}"),
+        )
+        .map_err(|e| invalid_lock_file(e.to_string()))
+    }
+}
+
+/// A content-addressed cache of remote registries.
+#[derive(Debug, Clone)]
+pub struct RegistryCache {
+    root: PathBuf,
+    offline: bool,
+    lock: LockFile,
+    /// The temporary root directory of a disabled cache, deleted when dropped.
+    tmp_root: Arc<Option<TempDir>>,
+}
+
+impl RegistryCache {
+    /// Creates a cache rooted at the given directory.
+    #[must_use]
+    pub fn new(root: PathBuf, offline: bool, lock: LockFile) -> Self {
+        Self {
+            root,
+            offline,
+            lock,
+            tmp_root: Arc::new(None),
+        }
+    }
+
+    /// Returns the temporary root directory of a disabled cache, which must be kept alive
+    /// as long as its entries are used.
+    #[must_use]
+    pub fn tmp_root(&self) -> Arc<Option<TempDir>> {
+        self.tmp_root.clone()
+    }
+
+    /// Returns true if remote sources can only be resolved from the cache.
+    #[must_use]
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Returns the commit or digest to use for a source, if known without fetching it.
+    /// Lock file pins always apply, the last resolved version is only used offline.
+    #[must_use]
+    pub fn pinned(&self, lock_key: &str) -> Option<LockedSource> {
+        if let Some(locked) = self.lock.sources.get(lock_key) {
+            return Some(locked.clone());
+        }
+        if self.offline {
+            let content = std::fs::read_to_string(self.ref_path(lock_key)).ok()?;
+            let content = content.trim();
+            return Some(if content.starts_with("sha256:") {
+                LockedSource::Digest(content.to_owned())
+            } else {
+                LockedSource::Commit(content.to_owned())
+            });
+        }
+        None
+    }
+
+    /// Records the commit or digest a source has been resolved to.
+    pub fn record(&self, lock_key: &str, locked: &LockedSource) -> Result<(), Error> {
+        let path = self.ref_path(lock_key);
+        create_dir(path.parent().unwrap_or(&self.root))?;
+        std::fs::write(path, locked.to_string()).map_err(|e| Error::CacheDirNotCreated {
+            message: e.to_string(),
+        })
+    }
+
+    /// Returns the directory containing the checkout of a Git repository at a given commit.
+    #[must_use]
+    pub fn git_path(&self, url: &str, commit: &str) -> PathBuf {
+        self.root.join("git").join(hash(url)).join(commit)
+    }
+
+    /// Returns the directory containing the unpacked content of an archive.
+    #[must_use]
+    pub fn archive_path(&self, digest: &str) -> PathBuf {
+        self.root
+            .join("archives")
+            .join(digest.trim_start_matches("sha256:"))
+    }
+
+    /// Creates a temporary directory in the cache, on the same filesystem as the cache
+    /// entries so that it can be moved into place with [`RegistryCache::store`].
+    pub fn create_tmp_dir(&self) -> Result<TempDir, Error> {
+        let tmp_path = self.root.join("tmp");
+        create_dir(&tmp_path)?;
+        tempfile::Builder::new()
+            .prefix("fetch")
+            .tempdir_in(tmp_path)
+            .map_err(|e| Error::CacheDirNotCreated {
+                message: e.to_string(),
+            })
+    }
+
+    /// Moves the content of a temporary directory to a cache entry.
+    /// If the entry already exists, the temporary directory is discarded.
+    pub fn store(&self, tmp_dir: TempDir, entry: &Path) -> Result<(), Error> {
+        if entry.exists() {
+            return Ok(());
+        }
+        create_dir(entry.parent().unwrap_or(&self.root))?;
+        let tmp_path = tmp_dir.keep();
+        if let Err(e) = std::fs::rename(&tmp_path, entry) {
+            // Another process may have stored the same entry in the meantime.
+            _ = std::fs::remove_dir_all(&tmp_path);
+            if !entry.exists() {
+                return Err(Error::CacheDirNotCreated {
+                    message: e.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn ref_path(&self, lock_key: &str) -> PathBuf {
+        self.root.join("refs").join(hash(lock_key))
+    }
+}
+
+/// Returns the SHA-256 digest of the given content, prefixed with `sha256:`.
+#[must_use]
+pub fn digest(content: &[u8]) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(content)))
+}
+
+fn hash(value: &str) -> String {
+    hex::encode(Sha256::digest(value.as_bytes()))
+}
+
+fn create_dir(path: &Path) -> Result<(), Error> {
+    create_dir_all(path).map_err(|e| Error::CacheDirNotCreated {
+        message: e.to_string(),
+    })
+}
+
+impl VirtualDirectoryPath {
+    /// Returns the key identifying this path in a lock file, i.e. the remote source without
+    /// the sub-folder, or `None` for local paths which are never cached.
+    #[must_use]
+    pub fn lock_key(&self) -> Option<String> {
+        match self {
+            VirtualDirectoryPath::GitRepo {
+                url,
+                refspec: Some(refspec),
+                ..
+            } => Some(format!("{url}@{refspec}")),
+            VirtualDirectoryPath::GitRepo { url, .. }
+            | VirtualDirectoryPath::RemoteArchive { url, .. } => Some(url.clone()),
+            VirtualDirectoryPath::LocalFolder { .. }
+            | VirtualDirectoryPath::LocalArchive { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_file_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(DEFAULT_LOCK_FILE);
+        let mut lock = LockFile::default();
+        _ = lock.sources.insert(
+            "https://github.com/org/repo.git@v1.0.0".to_owned(),
+            LockedSource::Commit("0123456789abcdef".to_owned()),
+        );
+        _ = lock.sources.insert(
+            "https://example.com/registry.zip".to_owned(),
+            LockedSource::Digest(digest(b"content")),
+        );
+        lock.write_to_file(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("commit: 0123456789abcdef"));
+        assert!(content.contains("digest: sha256:"));
+        assert_eq!(LockFile::try_from_file(&path).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_pinned_sources() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut lock = LockFile::default();
+        _ = lock
+            .sources
+            .insert("locked".to_owned(), LockedSource::Commit("abc".to_owned()));
+        let online = RegistryCache::new(temp_dir.path().to_path_buf(), false, lock.clone());
+        online
+            .record("resolved", &LockedSource::Digest("sha256:def".to_owned()))
+            .unwrap();
+        assert_eq!(
+            online.pinned("locked"),
+            Some(LockedSource::Commit("abc".to_owned()))
+        );
+        // The last resolved version is only used offline.
+        assert_eq!(online.pinned("resolved"), None);
+        let offline = RegistryCache::new(temp_dir.path().to_path_buf(), true, lock);
+        assert_eq!(
+            offline.pinned("resolved"),
+            Some(LockedSource::Digest("sha256:def".to_owned()))
+        );
+        assert_eq!(offline.pinned("unknown"), None);
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let settings = CacheSettings {
+            disabled: true,
+            ..CacheSettings::default()
+        };
+        let cache = settings.cache().unwrap();
+        let root = cache.root.clone();
+        assert!(root.exists());
+        assert!(cache.tmp_root().is_some());
+        drop(cache);
+        assert!(!root.exists());
+    }
+
+    #[test]
+    fn test_lock_key() {
+        let key = |path: &str| VirtualDirectoryPath::try_from(path).unwrap().lock_key();
+        assert_eq!(
+            key("https://github.com/org/repo.git@v1.0.0[model]"),
+            Some("https://github.com/org/repo.git@v1.0.0".to_owned())
+        );
+        assert_eq!(
+            key("file:///tmp/registry.tar.gz[model]"),
+            Some("file:///tmp/registry.tar.gz".to_owned())
+        );
+        assert_eq!(key("registry/model"), None);
+        assert_eq!(key("registry.zip"), None);
+    }
+}
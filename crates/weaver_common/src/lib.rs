@@ -2,6 +2,7 @@
 
 #![doc = include_str!("../README.md")]
 
+pub mod cache;
 pub mod diagnostic;
 pub mod error;
 pub mod ordered_float;
@@ -17,7 +18,7 @@ use miette::Diagnostic;
 use paris::formatter::colorize_string;
 use serde::Serialize;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
@@ -88,6 +89,37 @@ pub enum Error {
         archive: String,
     },
 
+    /// A remote registry is not available in the cache in offline mode.
+    #[error("The registry `{registry}` is not in the local cache and weaver is running in offline mode.")]
+    #[diagnostic(help("Run `weaver registry fetch` without `--offline` to populate the cache."))]
+    NotInCache {
+        /// The remote registry
+        registry: String,
+    },
+
+    /// A remote registry doesn't match the version pinned by the lock file.
+    #[error("The registry `{registry}` is locked to `{locked}` but `{fetched}` was fetched.")]
+    #[diagnostic(help(
+        "The locked version is no longer reachable, run `weaver registry fetch` to update the lock file."
+    ))]
+    LockMismatch {
+        /// The remote registry
+        registry: String,
+        /// The commit or digest pinned by the lock file
+        locked: String,
+        /// The commit or digest fetched
+        fetched: String,
+    },
+
+    /// An invalid lock file.
+    #[error("The lock file `{path}` is invalid: {error}")]
+    InvalidLockFile {
+        /// The path to the lock file
+        path: PathBuf,
+        /// The error message
+        error: String,
+    },
+
     /// A container for multiple errors.
     #[error("{:?}", format_errors(.0))]
     CompoundError(#[related] Vec<Error>),
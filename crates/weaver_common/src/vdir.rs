@@ -6,10 +6,12 @@
 //! This module handles resolving paths that can point to:
 //! - A local filesystem directory.
 //! - A local archive file (`.tar.gz` or `.zip`).
-//! - A remote archive file (`.tar.gz` or `.zip`) accessible via HTTP(S).
-//! - A Git repository accessible via HTTP(S).
+//! - A remote archive file (`.tar.gz` or `.zip`) accessible via HTTP(S) or `file://`.
+//! - A Git repository accessible via HTTP(S) or `file://`.
 //!
 //! It handles the fetching, extraction, and temporary storage management transparently.
+//! Git repositories and remote archives are stored in a content-addressed cache (see
+//! [`crate::cache`]) and are not fetched again when they are pinned by a lock file.
 //!
 //! It uses a specific string format to represent these sources, potentially including
 //! Git refspecs (tags/branches/commits) or sub-folders within archives/repositories.
@@ -34,7 +36,9 @@
 //! - Git repo (tag `v1.0`, sub-folder `schemas`): `https://github.com/user/repo.git@v1.0[schemas]`
 //! - Remote archive: `https://example.com/archive.tar.gz`
 //! - Remote archive with sub-folder: `https://example.com/archive.zip[data/files]`
+//! - Local Git repo (tag `v1.0`): `file:///path/to/repo@v1.0`
 
+use crate::cache::{digest, CacheSettings, LockedSource, RegistryCache};
 use crate::vdir::VirtualDirectoryPath::{GitRepo, LocalArchive, LocalFolder, RemoteArchive};
 use crate::Error;
 use crate::Error::{GitError, InvalidRegistryArchive, UnsupportedRegistryArchive};
@@ -50,6 +54,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::fs::{create_dir_all, File};
 use std::io;
+use std::io::Read;
 use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
@@ -61,6 +66,8 @@ use tempfile::TempDir;
 const TAR_GZ_EXT: &str = ".tar.gz";
 /// The extension for a zip archive.
 const ZIP_EXT: &str = ".zip";
+/// The URL schemes of remote virtual directories.
+const REMOTE_SCHEMES: [&str; 3] = ["http://", "https://", "file://"];
 
 /// Regex to parse a virtual directory path string.
 ///
@@ -84,8 +91,9 @@ static REGISTRY_REGEX: Lazy<Regex> = Lazy::new(|| {
 /// Supported formats include:
 /// - **Local directories** (`/path/to/directory`)
 /// - **Local archives** (`/path/to/archive.zip` or `/path/to/archive.tar.gz`)
-/// - **Remote archives** (`https://example.com/archive.zip` or `.tar.gz`)
-/// - **Git repositories** (`https://github.com/user/repo.git`)
+/// - **Remote archives** (`https://example.com/archive.zip` or `.tar.gz`, `file://` URLs are
+///   also supported)
+/// - **Git repositories** (`https://github.com/user/repo.git` or `file:///path/to/repo`)
 ///
 /// Paths may optionally specify:
 /// - A sub-folder within the archive or repository via `[sub_folder]`
@@ -116,7 +124,7 @@ pub enum VirtualDirectoryPath {
     },
     /// A virtual directory representing a git repository containing the content of interest.
     GitRepo {
-        /// The URL of the Git repository to clone (supports HTTP(S) URLs).
+        /// The URL of the Git repository to clone (supports HTTP(S) and `file://` URLs).
         url: String,
 
         /// Specific tag, branch, or commit hash to checkout.
@@ -240,7 +248,10 @@ impl FromStr for VirtualDirectoryPath {
         let refspec = captures.name("refspec").map(|m| m.as_str().to_owned());
         let sub_folder = captures.name("sub_folder").map(|m| m.as_str().to_owned());
 
-        if source.starts_with("http://") || source.starts_with("https://") {
+        if REMOTE_SCHEMES
+            .iter()
+            .any(|scheme| source.starts_with(scheme))
+        {
             if source.ends_with(".zip") || source.ends_with(".tar.gz") {
                 Ok(Self::RemoteArchive {
                     url: source.to_owned(),
@@ -306,8 +317,9 @@ impl Display for VirtualDirectoryPath {
 /// This struct is created from a [`VirtualDirectoryPath`]. Depending on the source type,
 /// it might involve:
 /// - Simply pointing to an existing local directory.
-/// - Cloning a Git repository into a temporary cache directory.
-/// - Downloading and extracting an archive into a temporary cache directory.
+/// - Cloning a Git repository into the content-addressed cache.
+/// - Downloading and extracting a remote archive into the content-addressed cache.
+/// - Extracting a local archive into a temporary directory.
 ///
 /// Temporary directories are managed and automatically cleaned up when this struct goes out of scope.
 #[derive(Default, Debug, Clone)]
@@ -323,6 +335,9 @@ pub struct VirtualDirectory {
     /// persists for the lifetime of `VirtualDirectory` and is cleaned up afterwards.
     #[allow(dead_code)]
     tmp_dir: Arc<Option<TempDir>>,
+
+    /// The commit or the digest of the remote source, if any.
+    locked_source: Option<LockedSource>,
 }
 
 impl VirtualDirectory {
@@ -333,39 +348,53 @@ impl VirtualDirectory {
     /// - Downloading and extracting remote archives.
     /// - Extracting local archives.
     ///
+    /// Remote sources are resolved with the given cache settings.
+    ///
     /// Returns an [`Error`] if any operation fails (e.g. network issues, invalid paths, extraction failures).
-    pub fn try_new(vdir_path: &VirtualDirectoryPath) -> Result<Self, Error> {
+    pub fn try_new(
+        vdir_path: &VirtualDirectoryPath,
+        cache_settings: &CacheSettings,
+    ) -> Result<Self, Error> {
         let vdir_path_repr = vdir_path.to_string();
         let vdir = match vdir_path {
             LocalFolder { path } => Ok(Self {
                 vdir_path: vdir_path_repr,
                 path: path.into(),
                 tmp_dir: Arc::new(None),
+                locked_source: None,
             }),
             GitRepo {
                 url,
                 sub_folder,
                 refspec,
-            } => Self::try_from_git_url(url, sub_folder, refspec, vdir_path_repr),
+            } => Self::try_from_git_url(
+                url,
+                sub_folder,
+                refspec,
+                vdir_path_repr,
+                &cache_settings.cache()?,
+            ),
             LocalArchive { path, sub_folder } => {
                 // Create a temporary directory for the virtual directory that will be deleted
                 // when the `VirtualDirectory` goes out of scope.
                 let tmp_dir = Self::create_tmp_repo()?;
                 Self::try_from_local_archive(path, sub_folder.as_ref(), tmp_dir, vdir_path_repr)
             }
-            RemoteArchive { url, sub_folder } => {
-                // Create a temporary directory for the virtual directory that will be deleted
-                // when the `VirtualDirectory` goes out of scope.
-                let tmp_dir = Self::create_tmp_repo()?;
-                Self::try_from_remote_archive(url, sub_folder.as_ref(), tmp_dir, vdir_path_repr)
-            }
+            RemoteArchive { url, sub_folder } => Self::try_from_remote_archive(
+                url,
+                sub_folder.as_ref(),
+                vdir_path_repr,
+                &cache_settings.cache()?,
+            ),
         };
         vdir
     }
 
-    /// Clones a Git repository from the specified URL into a temporary directory.
+    /// Resolves a Git repository from the cache, or clones it into the cache.
     ///
-    /// Performs a shallow clone (depth=1) to optimize disk usage and clone speed.
+    /// The repository is resolved from the cache when its commit is pinned by the lock file,
+    /// when the refspec is a full commit hash, or in offline mode. Otherwise, a shallow clone
+    /// (depth=1) of the refspec is performed and stored in the cache under its commit hash.
     /// Optionally selects a sub-folder within the repository as the virtual directory root.
     ///
     /// # Errors
@@ -373,57 +402,53 @@ impl VirtualDirectory {
     /// Returns [`Error::GitError`] if:
     /// - The repository URL is invalid or inaccessible.
     /// - The sub-folder does not exist within the cloned repository.
+    ///
+    /// Returns [`Error::NotInCache`] if the repository is not cached in offline mode, and
+    /// [`Error::LockMismatch`] if the fetched commit doesn't match the lock file.
     fn try_from_git_url(
         url: &str,
         sub_folder: &Option<String>,
         refspec: &Option<String>,
         vdir_path: String,
+        cache: &RegistryCache,
     ) -> Result<Self, Error> {
-        let tmp_dir = Self::create_tmp_repo()?;
-        let tmp_path = tmp_dir.path().to_path_buf();
-
-        // Clones the repo into the temporary directory.
-        // Use shallow clone to save time and space.
-        let mut fetch = PrepareFetch::new(
-            url,
-            tmp_path.clone(),
-            Kind::WithWorktree,
-            create::Options {
-                destination_must_be_empty: true,
-                fs_capabilities: None,
-            },
-            open::Options::isolated(),
-        )
-        .map_err(|e| GitError {
-            repo_url: url.to_owned(),
-            message: e.to_string(),
-        })?
-        .with_shallow(Shallow::DepthAtRemote(
-            NonZeroU32::new(1).expect("1 is not zero"),
-        ))
-        .with_ref_name(refspec.as_ref())
-        .map_err(|e| GitError {
-            repo_url: url.to_owned(),
-            message: e.to_string(),
-        })?;
-
-        let (mut prepare, _outcome) = fetch
-            .fetch_then_checkout(progress::Discard, &AtomicBool::new(false))
-            .map_err(|e| GitError {
-                repo_url: url.to_owned(),
-                message: e.to_string(),
-            })?;
-
-        let (_repo, _outcome) = prepare
-            .main_worktree(progress::Discard, &AtomicBool::new(false))
-            .map_err(|e| GitError {
-                repo_url: url.to_owned(),
-                message: e.to_string(),
-            })?;
+        let lock_key = match refspec {
+            Some(refspec) => format!("{url}@{refspec}"),
+            None => url.to_owned(),
+        };
+        let pinned = cache.pinned(&lock_key).or_else(|| {
+            refspec
+                .as_ref()
+                .filter(|refspec| is_commit_hash(refspec))
+                .map(|commit| LockedSource::Commit(commit.clone()))
+        });
+
+        let commit = match pinned {
+            Some(LockedSource::Commit(commit)) if cache.git_path(url, &commit).exists() => commit,
+            _ if cache.is_offline() => return Err(Error::NotInCache { registry: lock_key }),
+            pinned => {
+                let tmp_dir = cache.create_tmp_dir()?;
+                let commit = Self::clone_git_repo(url, refspec, tmp_dir.path())?;
+                let fetched = LockedSource::Commit(commit.clone());
+                if let Some(locked) = pinned {
+                    if locked != fetched {
+                        return Err(Error::LockMismatch {
+                            registry: lock_key,
+                            locked: locked.to_string(),
+                            fetched: commit,
+                        });
+                    }
+                }
+                cache.store(tmp_dir, &cache.git_path(url, &commit))?;
+                cache.record(&lock_key, &fetched)?;
+                commit
+            }
+        };
+        let repo_path = cache.git_path(url, &commit);
 
         // Determines the final path to the repo taking into account the sub_folder.
         let path = if let Some(sub_folder) = sub_folder {
-            let path_to_repo = tmp_path.join(sub_folder);
+            let path_to_repo = repo_path.join(sub_folder);
 
             // Checks the existence of the path in the repo.
             // If the path doesn't exist, returns an error.
@@ -436,16 +461,65 @@ impl VirtualDirectory {
 
             path_to_repo
         } else {
-            tmp_path
+            repo_path
         };
 
         Ok(Self {
             vdir_path,
             path,
-            tmp_dir: Arc::new(Some(tmp_dir)),
+            tmp_dir: cache.tmp_root(),
+            locked_source: Some(LockedSource::Commit(commit)),
         })
     }
 
+    /// Clones a Git repository into the given empty directory and returns the commit hash
+    /// that has been checked out.
+    ///
+    /// Performs a shallow clone (depth=1) to optimize disk usage and clone speed.
+    fn clone_git_repo(
+        url: &str,
+        refspec: &Option<String>,
+        target_path: &Path,
+    ) -> Result<String, Error> {
+        let git_error = |message: String| GitError {
+            repo_url: url.to_owned(),
+            message,
+        };
+
+        // Use shallow clone to save time and space.
+        let mut fetch = PrepareFetch::new(
+            url,
+            target_path,
+            Kind::WithWorktree,
+            create::Options {
+                destination_must_be_empty: true,
+                fs_capabilities: None,
+            },
+            open::Options::isolated(),
+        )
+        .map_err(|e| git_error(e.to_string()))?
+        .with_shallow(Shallow::DepthAtRemote(
+            NonZeroU32::new(1).expect("1 is not zero"),
+        ))
+        .with_ref_name(refspec.as_ref())
+        .map_err(|e| git_error(e.to_string()))?;
+
+        let (mut prepare, _outcome) = fetch
+            .fetch_then_checkout(progress::Discard, &AtomicBool::new(false))
+            .map_err(|e| git_error(e.to_string()))?;
+
+        let (repo, _outcome) = prepare
+            .main_worktree(progress::Discard, &AtomicBool::new(false))
+            .map_err(|e| git_error(e.to_string()))?;
+
+        let commit = repo
+            .head_id()
+            .map_err(|e| git_error(e.to_string()))?
+            .detach()
+            .to_string();
+        Ok(commit)
+    }
+
     /// Create a new `VirtualDirectory` from a local archive.
     /// The archive can be in `.tar.gz` or `.zip` format.
     /// The sub_folder is used to filter the entries inside the archive to unpack.
@@ -470,28 +544,40 @@ impl VirtualDirectory {
                 error: "This archive file doesn't exist".to_owned(),
             });
         }
+        let target_path_buf = target_dir.path().to_path_buf();
+        Self::unpack_archive(archive_filename, archive_path, &target_path_buf, sub_folder)?;
+
+        Ok(Self {
+            vdir_path,
+            path: target_path_buf,
+            tmp_dir: Arc::new(Some(target_dir)),
+            locked_source: None,
+        })
+    }
+
+    /// Extracts the contents of a `.tar.gz` or `.zip` archive into the specified directory.
+    /// The format is determined by the extension of `archive_filename`.
+    fn unpack_archive(
+        archive_filename: &str,
+        archive_path: &Path,
+        target_path: &Path,
+        sub_folder: Option<&String>,
+    ) -> Result<(), Error> {
         let archive_file = File::open(archive_path).map_err(|e| InvalidRegistryArchive {
             archive: archive_filename.to_owned(),
             error: e.to_string(),
         })?;
-        let target_path_buf = target_dir.path().to_path_buf();
 
         // Process the supported formats (i.e.: `.tar.gz`, and `.zip`)
         if archive_filename.ends_with(TAR_GZ_EXT) {
-            Self::unpack_tar_gz(archive_filename, archive_file, &target_path_buf, sub_folder)?;
+            Self::unpack_tar_gz(archive_filename, archive_file, target_path, sub_folder)
         } else if archive_filename.ends_with(ZIP_EXT) {
-            Self::unpack_zip(archive_filename, archive_file, &target_path_buf, sub_folder)?;
+            Self::unpack_zip(archive_filename, archive_file, target_path, sub_folder)
         } else {
-            return Err(UnsupportedRegistryArchive {
+            Err(UnsupportedRegistryArchive {
                 archive: archive_filename.to_owned(),
-            });
-        };
-
-        Ok(Self {
-            vdir_path,
-            path: target_path_buf,
-            tmp_dir: Arc::new(Some(target_dir)),
-        })
+            })
+        }
     }
 
     /// Extracts the contents of a `.tar.gz` archive into the specified directory.
@@ -656,76 +742,119 @@ impl VirtualDirectory {
     /// Create a new [`VirtualDirectory`] from a remote archive.
     ///
     /// The archive can be in `.tar.gz` or `.zip` format.
-    /// The sub_folder is used to filter the entries inside the archive to unpack.
-    /// The temporary directory is created in the `.weaver/vdir_cache`.
-    /// The temporary directory is deleted when the [`VirtualDirectory`] goes out of scope.
+    /// The archive is resolved from the cache when its digest is pinned by the lock file or
+    /// in offline mode. Otherwise, it is downloaded and unpacked in the cache under its
+    /// digest.
     ///
     /// Arguments:
-    /// - `id`: The unique identifier for the registry.
     /// - `url`: The URL of the archive.
-    /// - `sub_folder`: The sub-folder to unpack inside the archive.
-    /// - `target_dir`: The temporary target directory where the archive will be unpacked.
+    /// - `sub_folder`: The sub-folder inside the archive to use as the virtual directory root.
     /// - `vdir_path`: The virtual directory path representation (for debug purposes).
+    /// - `cache`: The cache of remote registries.
     fn try_from_remote_archive(
         url: &str,
         sub_folder: Option<&String>,
-        target_dir: TempDir,
         vdir_path: String,
+        cache: &RegistryCache,
     ) -> Result<Self, Error> {
-        let tmp_path = target_dir.path().to_path_buf();
+        let digest = match cache.pinned(url) {
+            Some(LockedSource::Digest(digest)) if cache.archive_path(&digest).exists() => digest,
+            _ if cache.is_offline() => {
+                return Err(Error::NotInCache {
+                    registry: url.to_owned(),
+                })
+            }
+            pinned => {
+                let download_dir = cache.create_tmp_dir()?;
+                let (archive_path, digest) = Self::download_archive(url, download_dir.path())?;
+                if let Some(locked) = pinned {
+                    if locked != LockedSource::Digest(digest.clone()) {
+                        return Err(Error::LockMismatch {
+                            registry: url.to_owned(),
+                            locked: locked.to_string(),
+                            fetched: digest,
+                        });
+                    }
+                }
+                let target_dir = cache.create_tmp_dir()?;
+                Self::unpack_archive(
+                    archive_path.to_str().unwrap_or_default(),
+                    &archive_path,
+                    target_dir.path(),
+                    None,
+                )?;
+                cache.store(target_dir, &cache.archive_path(&digest))?;
+                cache.record(url, &LockedSource::Digest(digest.clone()))?;
+                digest
+            }
+        };
 
-        // Download the archive from the URL
-        let response = ureq::get(url).call().map_err(|e| InvalidRegistryArchive {
-            archive: url.to_owned(),
-            error: e.to_string(),
-        })?;
-        if response.status() != 200 {
-            return Err(InvalidRegistryArchive {
-                archive: url.to_owned(),
-                error: format!("HTTP status code: {}", response.status()),
-            });
+        let mut path = cache.archive_path(&digest);
+        if let Some(sub_folder) = sub_folder {
+            path = path.join(sub_folder);
+            if !path.exists() {
+                return Err(InvalidRegistryArchive {
+                    archive: url.to_owned(),
+                    error: format!("Path `{sub_folder}` not found in archive"),
+                });
+            }
         }
 
-        // Parse the URL to get the file name
-        let parsed_url = Url::parse(url).map_err(|e| InvalidRegistryArchive {
+        Ok(Self {
+            vdir_path,
+            path,
+            tmp_dir: cache.tmp_root(),
+            locked_source: Some(LockedSource::Digest(digest)),
+        })
+    }
+
+    /// Downloads a remote archive (HTTP(S) or `file://` URL) into the given directory.
+    /// Returns the path to the downloaded archive and its digest.
+    fn download_archive(url: &str, target_path: &Path) -> Result<(PathBuf, String), Error> {
+        let archive_error = |error: String| InvalidRegistryArchive {
             archive: url.to_owned(),
-            error: e.to_string(),
-        })?;
+            error,
+        };
+
+        // Parse the URL to get the file name
+        let parsed_url = Url::parse(url).map_err(|e| archive_error(e.to_string()))?;
         let file_name = parsed_url
             .path_segments()
             .and_then(|mut segments| segments.next_back())
             .and_then(|name| if name.is_empty() { None } else { Some(name) })
-            .ok_or("Failed to extract file name from URL")
-            .map_err(|e| InvalidRegistryArchive {
-                archive: url.to_owned(),
-                error: e.to_owned(),
-            })?;
+            .ok_or_else(|| archive_error("Failed to extract file name from URL".to_owned()))?;
 
-        // Create the full path to the save file
-        let save_path = tmp_path.join(file_name);
-
-        // Open a file in write mode
-        let mut file = File::create(save_path.clone()).map_err(|e| InvalidRegistryArchive {
-            archive: url.to_owned(),
-            error: e.to_string(),
-        })?;
-
-        // Write the response body to the file.
-        // The number of bytes written is ignored as the `try_from_local_archive` function
-        // will handle the archive extraction and return an error if the archive is invalid.
-        _ = io::copy(&mut response.into_body().into_reader(), &mut file).map_err(|e| {
-            InvalidRegistryArchive {
-                archive: url.to_owned(),
-                error: e.to_string(),
+        let content = if parsed_url.scheme() == "file" {
+            let path = parsed_url
+                .to_file_path()
+                .map_err(|()| archive_error("Invalid file URL".to_owned()))?;
+            std::fs::read(path).map_err(|e| archive_error(e.to_string()))?
+        } else {
+            // Download the archive from the URL
+            let response = ureq::get(url)
+                .call()
+                .map_err(|e| archive_error(e.to_string()))?;
+            if response.status() != 200 {
+                return Err(archive_error(format!(
+                    "HTTP status code: {}",
+                    response.status()
+                )));
             }
-        })?;
+            let mut content = vec![];
+            // The number of bytes read is ignored, an invalid archive is detected when
+            // it is unpacked.
+            _ = response
+                .into_body()
+                .into_reader()
+                .read_to_end(&mut content)
+                .map_err(|e| archive_error(e.to_string()))?;
+            content
+        };
 
-        Self::try_from_local_archive(
-            save_path.to_str().unwrap_or_default(),
-            sub_folder,
-            target_dir,
-            vdir_path,
-        )
+        // Save the archive with its original file name to preserve its extension.
+        let save_path = target_path.join(file_name);
+        std::fs::write(&save_path, &content).map_err(|e| archive_error(e.to_string()))?;
+        Ok((save_path, digest(&content)))
     }
 
     /// Returns the local filesystem path to the resolved virtual directory content.
@@ -734,6 +863,13 @@ impl VirtualDirectory {
         self.path.as_path()
     }
 
+    /// Returns the commit or the digest of the remote source of this virtual directory, or
+    /// `None` for local sources.
+    #[must_use]
+    pub fn locked_source(&self) -> Option<&LockedSource> {
+        self.locked_source.as_ref()
+    }
+
     /// Returns the original string representation that was used to create this `VirtualDirectory`.
     #[must_use]
     pub fn vdir_path_str(&self) -> &str {
@@ -774,12 +910,19 @@ impl VirtualDirectory {
     }
 }
 
+/// Returns true if the refspec is a full (SHA-1 or SHA-256) commit hash.
+fn is_commit_hash(refspec: &str) -> bool {
+    matches!(refspec.len(), 40 | 64) && refspec.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::cache::{CacheSettings, LockFile, LockedSource, RegistryCache, DEFAULT_LOCK_FILE};
     use crate::test::ServeStaticFiles;
     use crate::vdir::{VirtualDirectory, VirtualDirectoryPath};
-    use crate::Error::GitError;
+    use crate::Error::{GitError, LockMismatch, NotInCache};
     use std::path::Path;
+    use std::process::Command;
 
     #[test]
     fn test_virtual_directory_path() {
@@ -915,13 +1058,23 @@ mod tests {
         count
     }
 
+    /// Returns cache settings storing the cache and the lock file in the given directory.
+    fn cache_settings(dir: &Path) -> CacheSettings {
+        CacheSettings {
+            cache_dir: Some(dir.join("cache")),
+            lock_file: Some(dir.join(DEFAULT_LOCK_FILE)),
+            ..CacheSettings::default()
+        }
+    }
+
     #[test]
     fn test_semconv_registry_local_repo() {
         // A virtual directory created from a local folder.
         let vdir_path = VirtualDirectoryPath::LocalFolder {
             path: "../../crates/weaver_codegen_test/semconv_registry".to_owned(),
         };
-        let repo = VirtualDirectory::try_new(&vdir_path).unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = VirtualDirectory::try_new(&vdir_path, &cache_settings(temp_dir.path())).unwrap();
         let repo_path = repo.path().to_path_buf();
         assert!(repo_path.exists());
         assert!(
@@ -934,8 +1087,9 @@ mod tests {
         assert!(repo_path.exists());
     }
 
-    fn check_archive(vdir_path: VirtualDirectoryPath, file_to_check: Option<&str>) {
-        let repo = VirtualDirectory::try_new(&vdir_path).unwrap();
+    fn check_archive(vdir_path: VirtualDirectoryPath, file_to_check: Option<&str>, cached: bool) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = VirtualDirectory::try_new(&vdir_path, &cache_settings(temp_dir.path())).unwrap();
         let repo_path = repo.path().to_path_buf();
         // At this point, the repo should be cloned into a temporary directory.
         assert!(repo_path.exists());
@@ -947,10 +1101,11 @@ mod tests {
             let file_path = repo_path.join(file_to_check);
             assert!(file_path.exists());
         }
+        assert_eq!(repo.locked_source().is_some(), cached);
         // Simulate a virtual directory going out of scope.
         drop(repo);
-        // The temporary directory should be deleted automatically.
-        assert!(!repo_path.exists());
+        // The temporary directory should be deleted automatically, cached content is kept.
+        assert_eq!(repo_path.exists(), cached);
     }
 
     #[test]
@@ -961,7 +1116,7 @@ mod tests {
             sub_folder: Some("model".to_owned()),
             refspec: Some(String::from("v1.26.0")),
         };
-        check_archive(registry_path, Some("general.yaml"));
+        check_archive(registry_path, Some("general.yaml"), true);
     }
 
     #[test]
@@ -973,7 +1128,8 @@ mod tests {
             sub_folder: Some("model".to_owned()),
             refspec: Some(String::from("invalid")),
         };
-        let repo = VirtualDirectory::try_new(&registry_path);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo = VirtualDirectory::try_new(&registry_path, &cache_settings(temp_dir.path()));
         assert!(repo.is_err());
         assert!(matches!(repo, Err(GitError { repo_url, .. }) if repo_url == url ));
     }
@@ -983,7 +1139,7 @@ mod tests {
         let registry_path = "../../test_data/semantic-conventions-1.26.0.tar.gz[model]"
             .parse::<VirtualDirectoryPath>()
             .unwrap();
-        check_archive(registry_path, Some("general.yaml"));
+        check_archive(registry_path, Some("general.yaml"), false);
     }
 
     #[test]
@@ -991,7 +1147,7 @@ mod tests {
         let registry_path = "../../test_data/semantic-conventions-1.26.0.zip[model]"
             .parse::<VirtualDirectoryPath>()
             .unwrap();
-        check_archive(registry_path, Some("general.yaml"));
+        check_archive(registry_path, Some("general.yaml"), false);
     }
 
     #[test]
@@ -1003,7 +1159,7 @@ mod tests {
         )
         .parse::<VirtualDirectoryPath>()
         .unwrap();
-        check_archive(registry_path, Some("general.yaml"));
+        check_archive(registry_path, Some("general.yaml"), true);
     }
 
     #[test]
@@ -1015,6 +1171,171 @@ mod tests {
         )
         .parse::<VirtualDirectoryPath>()
         .unwrap();
-        check_archive(registry_path, Some("general.yaml"));
+        check_archive(registry_path, Some("general.yaml"), true);
+    }
+
+    fn git(repo_path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=weaver",
+                "-c",
+                "user.email=weaver@example.com",
+            ])
+            .args(args)
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+        String::from_utf8(output.stdout).unwrap().trim().to_owned()
+    }
+
+    fn locked_cache(
+        cache_dir: &Path,
+        offline: bool,
+        lock_key: &str,
+        locked: LockedSource,
+    ) -> RegistryCache {
+        let mut lock = LockFile::default();
+        _ = lock.sources.insert(lock_key.to_owned(), locked);
+        RegistryCache::new(cache_dir.to_path_buf(), offline, lock)
+    }
+
+    #[test]
+    fn test_cached_file_git_repo() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        let cache_dir = temp_dir.path().join("cache");
+        std::fs::create_dir_all(repo_dir.join("model")).unwrap();
+        std::fs::write(repo_dir.join("model/registry.yaml"), "groups: []\n").unwrap();
+        _ = git(&repo_dir, &["init", "--quiet"]);
+        _ = git(&repo_dir, &["add", "."]);
+        _ = git(&repo_dir, &["commit", "--quiet", "-m", "v1"]);
+        _ = git(&repo_dir, &["tag", "v1"]);
+        let v1 = git(&repo_dir, &["rev-parse", "HEAD"]);
+        let url = format!("file://{}", repo_dir.display());
+        let vdir_path: VirtualDirectoryPath = format!("{url}@v1[model]").parse().unwrap();
+        let VirtualDirectoryPath::GitRepo {
+            refspec,
+            sub_folder,
+            ..
+        } = &vdir_path
+        else {
+            panic!("Expected GitRepo, got something else");
+        };
+        let lock_key = vdir_path.lock_key().unwrap();
+        let try_new = |cache: &RegistryCache| {
+            VirtualDirectory::try_from_git_url(
+                &url,
+                sub_folder,
+                refspec,
+                vdir_path.to_string(),
+                cache,
+            )
+        };
+
+        // The repo is cloned into the cache under its commit.
+        let online = RegistryCache::new(cache_dir.clone(), false, LockFile::default());
+        let repo = try_new(&online).unwrap();
+        assert_eq!(
+            repo.locked_source(),
+            Some(&LockedSource::Commit(v1.clone()))
+        );
+        assert!(repo.path().join("registry.yaml").exists());
+        assert!(repo.path().starts_with(&cache_dir));
+        drop(repo);
+
+        // Offline, the last resolved commit is used.
+        let offline = RegistryCache::new(cache_dir.clone(), true, LockFile::default());
+        let repo = try_new(&offline).unwrap();
+        assert_eq!(
+            repo.locked_source(),
+            Some(&LockedSource::Commit(v1.clone()))
+        );
+        let unknown = VirtualDirectory::try_from_git_url(
+            &url,
+            &None,
+            &Some("v2".to_owned()),
+            format!("{url}@v2"),
+            &offline,
+        );
+        assert!(matches!(unknown, Err(NotInCache { .. })));
+
+        // The tag is moved, the lock file still pins the first commit.
+        std::fs::write(repo_dir.join("model/registry.yaml"), "groups: [] # v2\n").unwrap();
+        _ = git(&repo_dir, &["commit", "--quiet", "-am", "v2"]);
+        _ = git(&repo_dir, &["tag", "-f", "v1"]);
+        let mismatch = try_new(&locked_cache(
+            &cache_dir,
+            false,
+            &lock_key,
+            LockedSource::Commit("0".repeat(40)),
+        ));
+        assert!(matches!(mismatch, Err(LockMismatch { .. })));
+
+        // A pinned repo is resolved from the cache without fetching it.
+        std::fs::remove_dir_all(&repo_dir).unwrap();
+        let repo = try_new(&locked_cache(
+            &cache_dir,
+            false,
+            &lock_key,
+            LockedSource::Commit(v1.clone()),
+        ))
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("registry.yaml")).unwrap(),
+            "groups: []\n"
+        );
+    }
+
+    #[test]
+    fn test_cached_file_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        let archive =
+            std::fs::canonicalize("../../test_data/semantic-conventions-1.26.0.tar.gz").unwrap();
+        let url = format!("file://{}", archive.display());
+        let vdir_path: VirtualDirectoryPath = format!("{url}[model]").parse().unwrap();
+        assert!(matches!(
+            vdir_path,
+            VirtualDirectoryPath::RemoteArchive { .. }
+        ));
+        let sub_folder = Some("model".to_owned());
+        let try_new = |url: &str, cache: &RegistryCache| {
+            VirtualDirectory::try_from_remote_archive(
+                url,
+                sub_folder.as_ref(),
+                vdir_path.to_string(),
+                cache,
+            )
+        };
+
+        let online = RegistryCache::new(cache_dir.clone(), false, LockFile::default());
+        let repo = try_new(&url, &online).unwrap();
+        assert!(repo.path().join("general.yaml").exists());
+        let Some(LockedSource::Digest(digest)) = repo.locked_source().cloned() else {
+            panic!("Expected a digest");
+        };
+        assert!(digest.starts_with("sha256:"));
+        drop(repo);
+
+        let offline = RegistryCache::new(cache_dir.clone(), true, LockFile::default());
+        let repo = try_new(&url, &offline).unwrap();
+        assert!(repo.path().join("general.yaml").exists());
+        assert!(matches!(
+            try_new("file:///unknown/registry.tar.gz", &offline),
+            Err(NotInCache { .. })
+        ));
+
+        let mismatch = try_new(
+            &url,
+            &locked_cache(
+                &cache_dir,
+                false,
+                &url,
+                LockedSource::Digest("sha256:unknown".to_owned()),
+            ),
+        );
+        assert!(matches!(mismatch, Err(LockMismatch { .. })));
     }
 }
@@ -2,6 +2,7 @@
 
 //! Set of supported template loaders
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::{fs, io};
 
@@ -224,6 +225,42 @@ impl FileLoader for FileSystemFileLoader {
     }
 }
 
+/// A loader that serves files held in memory, e.g. templates received by the `serve`
+/// command.
+pub struct InMemoryFileLoader {
+    root: PathBuf,
+    files: BTreeMap<String, String>,
+}
+
+impl InMemoryFileLoader {
+    /// Create a new in-memory loader from a map of file names to contents.
+    #[must_use]
+    pub fn new(root: PathBuf, files: BTreeMap<String, String>) -> Self {
+        Self { root, files }
+    }
+}
+
+impl FileLoader for InMemoryFileLoader {
+    /// Returns a textual representation of the root path of the loader.
+    /// This representation is mostly used for debugging and logging purposes.
+    fn root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    /// Returns a list of all files in the loader.
+    fn all_files(&self) -> Vec<PathBuf> {
+        self.files.keys().map(PathBuf::from).collect()
+    }
+
+    /// Returns the content of a file from a given name.
+    fn load_file(&self, file: &str) -> Result<Option<FileContent>, Error> {
+        Ok(self.files.get(file).map(|content| FileContent {
+            path: self.root.join(file),
+            content: content.clone(),
+        }))
+    }
+}
+
 // Combine a root path and a template name, ensuring that the combined path is
 // a subdirectory of the base path.
 fn safe_join(root: &Path, template: &str) -> Result<PathBuf, minijinja::Error> {
@@ -366,6 +403,19 @@ mod tests {
         assert!(fs_content.is_none());
     }
 
+    #[test]
+    fn test_in_memory_loader() {
+        let loader = InMemoryFileLoader::new(
+            PathBuf::from("playground"),
+            BTreeMap::from([("template.j2".to_owned(), "{{ ctx }}".to_owned())]),
+        );
+        assert_eq!(loader.all_files(), vec![PathBuf::from("template.j2")]);
+        let content = loader.load_file("template.j2").unwrap().unwrap();
+        assert_eq!(content.content, "{{ ctx }}");
+        assert_eq!(content.path, PathBuf::from("playground/template.j2"));
+        assert!(loader.load_file("missing.j2").unwrap().is_none());
+    }
+
     #[test]
     fn test_embedded_loader_error() {
         let embedded_loader = EmbeddedFileLoader::try_new(
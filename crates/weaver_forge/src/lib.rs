@@ -45,7 +45,7 @@ pub mod output_processor;
 pub mod registry;
 pub mod v2;
 
-pub use output_processor::{OutputProcessor, OutputTarget};
+pub use output_processor::{GeneratedFile, OutputProcessor, OutputTarget};
 
 /// Name of the Weaver configuration file.
 pub const WEAVER_YAML: &str = "weaver.yaml";
@@ -179,6 +179,10 @@ pub(crate) struct TemplateEngine {
 
     // Global parameters for snippet generation.
     snippet_params: Params,
+
+    /// Maximum number of instructions executed by each template rendering, unlimited if
+    /// `None`.
+    fuel: Option<u64>,
 }
 
 /// Global context for the template engine.
@@ -275,9 +279,16 @@ impl TemplateEngine {
             file_loader: Arc::new(loader),
             target_config: config,
             snippet_params: params,
+            fuel: None,
         })
     }
 
+    /// Limits the number of instructions executed by each template rendering, so that a
+    /// template cannot loop without bound. A rendering running out of fuel fails.
+    pub(crate) fn set_fuel(&mut self, fuel: Option<u64>) {
+        self.fuel = fuel;
+    }
+
     /// Generate a template snippet from serializable context and a snippet identifier.
     ///
     /// # Arguments
@@ -331,6 +342,32 @@ impl TemplateEngine {
     ///
     /// This is useful when the output needs to be captured (e.g., for HTTP responses).
     pub(crate) fn generate_to_string<T: Serialize>(&self, context: &T) -> Result<String, Error> {
+        // The `file_name` of the templates is not rendered, the output is not written to files.
+        let files = self.render_files(context, false)?;
+        Ok(files
+            .into_iter()
+            .map(|file| file.content)
+            .collect::<Vec<_>>()
+            .join(""))
+    }
+
+    /// Generate artifacts from a serializable context and return the rendered
+    /// files, named as they would be in the output directory, instead of writing them.
+    pub(crate) fn generate_to_files<T: Serialize>(
+        &self,
+        context: &T,
+    ) -> Result<Vec<GeneratedFile>, Error> {
+        self.render_files(context, true)
+    }
+
+    /// Renders the templates matching the configuration against a serializable context.
+    /// The files are named after the `file_name` of their template configuration if
+    /// `file_names` is true, and after their template otherwise.
+    fn render_files<T: Serialize>(
+        &self,
+        context: &T,
+        file_names: bool,
+    ) -> Result<Vec<GeneratedFile>, Error> {
         let files = self.file_loader.all_files();
         let tmpl_matcher = self.target_config.template_matcher()?;
 
@@ -346,56 +383,38 @@ impl TemplateEngine {
                 let filter = Filter::new(template.filter.as_str());
                 let filtered_result = filter.apply(context.clone(), &params)?;
 
-                match template.application_mode {
-                    ApplicationMode::Single => {
-                        let is_empty = filtered_result.is_null()
-                            || (filtered_result.is_array()
-                                && filtered_result.as_array().expect("is_array").is_empty());
-                        if !is_empty {
-                            let (output, _) = self.render_template(
-                                NewContext {
-                                    ctx: &filtered_result,
-                                }
-                                .try_into()?,
-                                &yaml_params,
-                                &file_to_process,
-                                None,
-                                &template.auto_escape,
-                            )?;
-                            results.push(output);
-                        }
-                    }
-                    ApplicationMode::Each => {
-                        if let serde_json::Value::Array(values) = &filtered_result {
-                            for value in values {
-                                let (output, _) = self.render_template(
-                                    NewContext { ctx: value }.try_into()?,
-                                    &yaml_params,
-                                    &file_to_process,
-                                    None,
-                                    &template.auto_escape,
-                                )?;
-                                results.push(output);
-                            }
+                let contexts = match (&template.application_mode, &filtered_result) {
+                    (ApplicationMode::Single, ctx) => {
+                        let is_empty = ctx.is_null()
+                            || (ctx.is_array() && ctx.as_array().expect("is_array").is_empty());
+                        if is_empty {
+                            vec![]
                         } else {
-                            let (output, _) = self.render_template(
-                                NewContext {
-                                    ctx: &filtered_result,
-                                }
-                                .try_into()?,
-                                &yaml_params,
-                                &file_to_process,
-                                None,
-                                &template.auto_escape,
-                            )?;
-                            results.push(output);
+                            vec![ctx]
                         }
                     }
+                    (ApplicationMode::Each, serde_json::Value::Array(values)) => {
+                        values.iter().collect()
+                    }
+                    (ApplicationMode::Each, ctx) => vec![ctx],
+                };
+                for ctx in contexts {
+                    let (content, template_object) = self.render_template(
+                        NewContext { ctx }.try_into()?,
+                        &yaml_params,
+                        &file_to_process,
+                        template.file_name.as_ref().filter(|_| file_names),
+                        &template.auto_escape,
+                    )?;
+                    results.push(GeneratedFile {
+                        path: template_object.file_name(),
+                        content,
+                    });
                 }
             }
         }
 
-        Ok(results.join(""))
+        Ok(results)
     }
 
     /// Generate artifacts from a serializable context and a template directory,
@@ -724,6 +743,7 @@ impl TemplateEngine {
     /// Create a new template engine based on the target configuration.
     fn template_engine(&self, auto_escape: &AutoEscapeMode) -> Result<Environment<'_>, Error> {
         let mut env = Environment::new();
+        env.set_fuel(self.fuel);
         // Set the auto-escape mode based on the per-template configuration.
         // Within a template, `{% autoescape false %}` blocks can selectively
         // disable escaping for sections.
@@ -855,6 +875,7 @@ mod tests {
     use globset::Glob;
     use serde::Serialize;
 
+    use weaver_common::cache::CacheSettings;
     use weaver_common::vdir::VirtualDirectoryPath;
     use weaver_diff::diff_dir;
     use weaver_resolver::{LoadedSemconvRegistry, SchemaResolver};
@@ -887,9 +908,9 @@ mod tests {
         let path: VirtualDirectoryPath = "data/registry"
             .try_into()
             .expect("Invalid virtual directory path string");
-        let repo = RegistryRepo::try_new(schema_url, &path, &mut vec![])
+        let repo = RegistryRepo::try_new(schema_url, &path, &CacheSettings::default(), &mut vec![])
             .expect("Failed to construct repository");
-        let registry_result = SchemaResolver::load_semconv_repository(repo, false);
+        let registry_result = SchemaResolver::load_semconv_repository(repo, false, None);
         // SemConvRegistry::try_from_path_pattern(registry_id, "data/*.yaml");
         let registry = if ignore_non_fatal_errors {
             registry_result
@@ -914,7 +935,7 @@ mod tests {
         let config = WeaverConfig::try_from_path(format!("templates/{target}")).unwrap();
         let engine = TemplateEngine::try_new(config, loader, cli_params)
             .expect("Failed to create template engine");
-        let schema = SchemaResolver::resolve(registry, false)
+        let schema = SchemaResolver::resolve(registry, false, None)
             .into_result_failing_non_fatal()
             .expect("Failed to resolve registry");
 
@@ -1136,13 +1157,13 @@ mod tests {
                 .try_into()
                 .expect("Should be valid schema url"),
         );
-        let repo = RegistryRepo::try_new(schema_url, &path, &mut vec![])
+        let repo = RegistryRepo::try_new(schema_url, &path, &CacheSettings::default(), &mut vec![])
             .expect("Failed to construct repository");
-        let loaded = SchemaResolver::load_semconv_repository(repo, false)
+        let loaded = SchemaResolver::load_semconv_repository(repo, false, None)
             .into_result_with_non_fatal()
             .expect("Failed to load registry")
             .0;
-        let schema = SchemaResolver::resolve(loaded, false)
+        let schema = SchemaResolver::resolve(loaded, false, None)
             .into_result_failing_non_fatal()
             .expect("Failed to resolve registry");
 
@@ -1283,9 +1304,14 @@ mod tests {
         let schema_url: SchemaUrl = "https://default/1.0.0"
             .try_into()
             .expect("Should be valid schema url");
-        let repo = RegistryRepo::try_new(Some(schema_url), &path, &mut vec![])
-            .expect("Failed to construct repository");
-        let loaded = SchemaResolver::load_semconv_repository(repo, false)
+        let repo = RegistryRepo::try_new(
+            Some(schema_url),
+            &path,
+            &CacheSettings::default(),
+            &mut vec![],
+        )
+        .expect("Failed to construct repository");
+        let loaded = SchemaResolver::load_semconv_repository(repo, false, None)
             .into_result_with_non_fatal()
             .expect("Failed to load registry")
             .0;
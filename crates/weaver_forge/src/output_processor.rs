@@ -52,6 +52,15 @@ impl OutputTarget {
     }
 }
 
+/// A file rendered by a template, kept in memory instead of being written.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GeneratedFile {
+    /// Path of the file, relative to the output directory.
+    pub path: PathBuf,
+    /// Rendered content of the file.
+    pub content: String,
+}
+
 /// Builtin serialization formats
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BuiltinFormat {
@@ -258,6 +267,16 @@ impl OutputProcessor {
         })
     }
 
+    /// Limit the number of instructions executed by each template rendering (see
+    /// [`minijinja::Environment::set_fuel`]), e.g. to render untrusted templates.
+    ///
+    /// Only applies to `Template` variants.
+    pub fn set_fuel(&mut self, fuel: Option<u64>) {
+        if let OutputKind::Template(t) = &mut self.kind {
+            t.engine.set_fuel(fuel);
+        }
+    }
+
     /// Generate a template snippet from serializable context and a snippet identifier.
     ///
     /// Only valid for `Template` variants. Returns an error for `Builtin` and `Mute`.
@@ -328,6 +347,20 @@ impl OutputProcessor {
         }
     }
 
+    /// Render data into in-memory files, named as they would be in the output directory.
+    ///
+    /// Only valid for `Template` variants. Returns an error for `Builtin` and `Mute`.
+    pub fn generate_to_files<T: Serialize>(&self, data: &T) -> Result<Vec<GeneratedFile>, Error> {
+        match &self.kind {
+            OutputKind::Template(t) => t.engine.generate_to_files(data),
+            OutputKind::Builtin { .. } | OutputKind::Mute => Err(Error::InvalidTemplateDir {
+                template_dir: PathBuf::from("(not a template)"),
+                error: "generate_to_files is only supported for template-based OutputProcessor"
+                    .to_owned(),
+            }),
+        }
+    }
+
     /// Returns the MIME content type for the configured format.
     #[must_use]
     pub fn content_type(&self) -> &'static str {
@@ -415,8 +448,10 @@ fn write_content(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::file_loader::InMemoryFileLoader;
     use include_dir::{include_dir, Dir};
     use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
     use std::fs;
     use tempfile::TempDir;
 
@@ -859,6 +894,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_to_files() {
+        let loader = InMemoryFileLoader::new(
+            PathBuf::from("playground"),
+            BTreeMap::from([
+                (
+                    "weaver.yaml".to_owned(),
+                    "templates:\n  - template: item.j2\n    filter: .items\n    application_mode: each\n    file_name: \"{{ ctx.name }}.txt\"\n".to_owned(),
+                ),
+                ("item.j2".to_owned(), "{{ ctx.name }}={{ ctx.value }}".to_owned()),
+            ]),
+        );
+        let config = WeaverConfig::try_from_loader(&loader).expect("config should load");
+        let output = OutputProcessor::from_template_config(
+            config,
+            loader,
+            Params::default(),
+            OutputTarget::Stdout,
+        )
+        .expect("template config should succeed");
+        let files = output
+            .generate_to_files(&serde_json::json!({
+                "items": [{"name": "a", "value": 1}, {"name": "b", "value": 2}]
+            }))
+            .expect("generate_to_files should succeed");
+        assert_eq!(
+            files,
+            vec![
+                GeneratedFile {
+                    path: PathBuf::from("a.txt"),
+                    content: "a=1".to_owned(),
+                },
+                GeneratedFile {
+                    path: PathBuf::from("b.txt"),
+                    content: "b=2".to_owned(),
+                },
+            ]
+        );
+
+        let json = OutputProcessor::new("json", "test", None, None, OutputTarget::Stdout)
+            .expect("json format should succeed");
+        assert!(json.generate_to_files(&test_data()).is_err());
+    }
+
+    /// An output processor rendering `item.j2` with the given template configuration.
+    fn in_memory_output(template_config: &str, template: &str) -> OutputProcessor {
+        let loader = InMemoryFileLoader::new(
+            PathBuf::from("playground"),
+            BTreeMap::from([
+                ("weaver.yaml".to_owned(), template_config.to_owned()),
+                ("item.j2".to_owned(), template.to_owned()),
+            ]),
+        );
+        let config = WeaverConfig::try_from_loader(&loader).expect("config should load");
+        OutputProcessor::from_template_config(
+            config,
+            loader,
+            Params::default(),
+            OutputTarget::Stdout,
+        )
+        .expect("template config should succeed")
+    }
+
+    #[test]
+    fn test_generate_to_string_ignores_file_name() {
+        // The file name references a missing context, it is only rendered for files.
+        let output = in_memory_output(
+            "templates:\n  - template: item.j2\n    filter: .items\n    application_mode: each\n    file_name: \"{{ ctx.missing.name }}.txt\"\n",
+            "{{ ctx.name }}={{ ctx.value }};",
+        );
+        let data = serde_json::json!({
+            "items": [{"name": "a", "value": 1}, {"name": "b", "value": 2}]
+        });
+        assert_eq!(
+            output
+                .generate_to_string(&data)
+                .expect("generate_to_string should succeed"),
+            "a=1;b=2;"
+        );
+        assert!(output.generate_to_files(&data).is_err());
+    }
+
+    #[test]
+    fn test_fuel() {
+        let mut output = in_memory_output(
+            "templates:\n  - template: item.j2\n    application_mode: single\n",
+            "{% for i in range(1000) %}{{ i }}{% endfor %}",
+        );
+        let data = serde_json::json!({"items": []});
+        assert!(output.generate_to_string(&data).is_ok());
+        output.set_fuel(Some(100));
+        assert!(output.generate_to_string(&data).is_err());
+    }
+
     #[test]
     fn test_content_type() {
         let json = OutputProcessor::new("json", "test", None, None, OutputTarget::Stdout)
@@ -14,15 +14,19 @@ use crate::{
 mod deprecated_advisor;
 mod enum_advisor;
 mod rego_advisor;
+mod required_attribute_advisor;
 mod stability_advisor;
 mod type_advisor;
+mod upgrade_advisor;
 
 // Public re-exports
 pub use deprecated_advisor::DeprecatedAdvisor;
 pub use enum_advisor::EnumAdvisor;
 pub use rego_advisor::RegoAdvisor;
+pub use required_attribute_advisor::RequiredAttributeAdvisor;
 pub use stability_advisor::StabilityAdvisor;
 pub use type_advisor::TypeAdvisor;
+pub use upgrade_advisor::{RenameHistory, RenameStep, UpgradeAdvisor};
 
 /// Provides advice on a sample
 pub trait Advisor {
@@ -199,7 +203,7 @@ mod tests {
         assert_eq!(findings[0].id, crate::DEPRECATED_ADVICE_TYPE);
 
         // Test TypeAdvisor
-        let mut type_advisor = TypeAdvisor;
+        let mut type_advisor = TypeAdvisor::new();
         let int_attr = Rc::new(VersionedAttribute::V1(Attribute {
             name: "int.attr".to_owned(),
             requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Required and recommended attribute presence advisor
+
+use serde_json::json;
+use std::{collections::HashSet, rc::Rc};
+use weaver_checker::{FindingLevel, PolicyFinding};
+use weaver_semconv::attribute::{AttributeType, BasicRequirementLevelSpec, RequirementLevel};
+
+use super::{emit_findings, Advisor};
+use crate::{
+    otlp_logger::OtlpEmitter, sample_attribute::SampleAttribute, Error, Sample, SampleRef,
+    SignalAttribute, VersionedAttribute, VersionedSignal, ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY,
+    CONDITION_ADVICE_CONTEXT_KEY, MISSING_RECOMMENDED_ATTRIBUTE_ADVICE_TYPE,
+    MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE, REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY,
+};
+
+/// An advisor that compares the attributes of a sample with the attributes of
+/// the matching registry signal and reports the missing ones
+pub struct RequiredAttributeAdvisor;
+
+/// Checks if the attributes of a registry signal are present in a list of sample attributes
+///
+/// Returns a list of advice for the missing attributes based on their RequirementLevel.
+///
+/// | RequirementLevel       | Advice type                     | Live-check advice level |
+/// |------------------------|---------------------------------|-------------------------|
+/// | Required               | `missing_required_attribute`    | Violation               |
+/// | Conditionally Required | `missing_required_attribute`    | Information             |
+/// | Recommended            | `missing_recommended_attribute` | Information             |
+/// | Opt-In                 | -                               | -                       |
+///
+/// The condition of a conditionally required attribute cannot be evaluated, so it is
+/// reported in the advice context under the `condition` key.
+fn check_attributes(
+    semconv_attributes: &[SignalAttribute<'_>],
+    sample_attributes: &[SampleAttribute],
+    sample: &Sample,
+) -> Vec<PolicyFinding> {
+    // Create a HashSet of attribute names for O(1) lookups
+    let attribute_set: HashSet<_> = sample_attributes
+        .iter()
+        .map(|attr| attr.name.as_str())
+        .collect();
+
+    let mut advice_list = Vec::new();
+    for semconv_attribute in semconv_attributes {
+        let key = semconv_attribute.name;
+
+        // For template attributes, check if any sample attribute starts with the template prefix
+        // For non-template attributes, check for exact match
+        let is_present = if matches!(semconv_attribute.r#type, AttributeType::Template(_)) {
+            let prefix = format!("{key}.");
+            sample_attributes
+                .iter()
+                .any(|attr| attr.name.starts_with(&prefix))
+        } else {
+            attribute_set.contains(key)
+        };
+        if is_present {
+            continue;
+        }
+
+        let (advice_type, advice_level, context, message) =
+            match semconv_attribute.requirement_level {
+                RequirementLevel::Basic(BasicRequirementLevelSpec::Required) => (
+                    MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE,
+                    FindingLevel::Violation,
+                    json!({
+                        ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: key,
+                        REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY: "required",
+                    }),
+                    format!("Required attribute '{key}' is not present."),
+                ),
+                RequirementLevel::ConditionallyRequired { text } => (
+                    MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE,
+                    FindingLevel::Information,
+                    json!({
+                        ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: key,
+                        REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY: "conditionally_required",
+                        CONDITION_ADVICE_CONTEXT_KEY: text,
+                    }),
+                    format!(
+                        "Conditionally required attribute '{key}' is not present. Condition: {text}"
+                    ),
+                ),
+                RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended)
+                | RequirementLevel::Recommended { .. } => (
+                    MISSING_RECOMMENDED_ATTRIBUTE_ADVICE_TYPE,
+                    FindingLevel::Information,
+                    json!({
+                        ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: key,
+                        REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY: "recommended",
+                    }),
+                    format!("Recommended attribute '{key}' is not present."),
+                ),
+                RequirementLevel::Basic(BasicRequirementLevelSpec::OptIn)
+                | RequirementLevel::OptIn { .. } => continue,
+            };
+        advice_list.push(PolicyFinding {
+            id: advice_type.to_owned(),
+            context: Some(context),
+            message,
+            level: advice_level,
+            signal_type: sample.signal_type(),
+            signal_name: sample.signal_name(),
+        });
+    }
+    advice_list
+}
+
+impl Advisor for RequiredAttributeAdvisor {
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        parent_signal: &Sample,
+        _registry_attribute: Option<Rc<VersionedAttribute>>,
+        registry_group: Option<Rc<VersionedSignal>>,
+        otlp_emitter: Option<Rc<OtlpEmitter>>,
+    ) -> Result<Vec<PolicyFinding>, Error> {
+        let Some(registry_group) = registry_group else {
+            return Ok(Vec::new());
+        };
+        // Metric attributes are carried by the data points, span and log
+        // attributes by the signal itself.
+        let sample_attributes = match &sample {
+            SampleRef::Span(sample_span) => &sample_span.attributes,
+            SampleRef::Log(sample_log) => &sample_log.attributes,
+            SampleRef::NumberDataPoint(sample_data_point) => &sample_data_point.attributes,
+            SampleRef::HistogramDataPoint(sample_data_point) => &sample_data_point.attributes,
+            SampleRef::ExponentialHistogramDataPoint(sample_data_point) => {
+                &sample_data_point.attributes
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        let advice_list = check_attributes(
+            &registry_group.attributes(),
+            sample_attributes,
+            parent_signal,
+        );
+
+        // Emit each finding if emitter available
+        emit_findings(
+            &advice_list,
+            &sample,
+            otlp_emitter.as_deref(),
+            parent_signal,
+        );
+
+        Ok(advice_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::sample_metric::{SampleInstrument, SampleMetric};
+    use weaver_semconv::attribute::{PrimitiveOrArrayTypeSpec, TemplateTypeSpec};
+
+    const STRING_TYPE: AttributeType =
+        AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String);
+
+    fn create_sample_attribute(name: &str) -> SampleAttribute {
+        SampleAttribute {
+            name: name.to_owned(),
+            value: None,
+            r#type: None,
+            live_check_result: None,
+        }
+    }
+
+    fn create_sample() -> Sample {
+        Sample::Metric(SampleMetric {
+            name: "test_metric".to_owned(),
+            unit: "".to_owned(),
+            data_points: None,
+            instrument: SampleInstrument::Supported(weaver_semconv::group::InstrumentSpec::Counter),
+            live_check_result: None,
+            resource: None,
+        })
+    }
+
+    #[test]
+    fn test_check_attributes_all_requirement_levels() {
+        let required = RequirementLevel::Basic(BasicRequirementLevelSpec::Required);
+        let recommended_basic = RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended);
+        let recommended_text = RequirementLevel::Recommended {
+            text: "This is recommended".to_owned(),
+        };
+        let opt_in_basic = RequirementLevel::Basic(BasicRequirementLevelSpec::OptIn);
+        let opt_in_text = RequirementLevel::OptIn {
+            text: "This is opt-in".to_owned(),
+        };
+        let conditional = RequirementLevel::ConditionallyRequired {
+            text: "Required when X".to_owned(),
+        };
+        let semconv_attributes = [
+            ("required_attr", &required),
+            ("recommended_basic", &recommended_basic),
+            ("recommended_text", &recommended_text),
+            ("opt_in_basic", &opt_in_basic),
+            ("opt_in_text", &opt_in_text),
+            ("conditional", &conditional),
+        ]
+        .into_iter()
+        .map(|(name, requirement_level)| SignalAttribute {
+            name,
+            r#type: &STRING_TYPE,
+            requirement_level,
+        })
+        .collect::<Vec<_>>();
+
+        // Provide no attributes
+        let advice = check_attributes(&semconv_attributes, &[], &create_sample());
+        assert_eq!(advice.len(), 4);
+
+        let advice_map: HashMap<_, _> = advice
+            .iter()
+            .map(|a| {
+                let name = a.context.as_ref().expect("context")[ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY]
+                    .as_str()
+                    .expect("attribute name")
+                    .to_owned();
+                (name, a)
+            })
+            .collect();
+
+        let finding = advice_map["required_attr"];
+        assert_eq!(finding.id, MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE);
+        assert_eq!(finding.level, FindingLevel::Violation);
+        assert_eq!(finding.signal_type, Some("metric".to_owned()));
+        assert_eq!(finding.signal_name, Some("test_metric".to_owned()));
+
+        let finding = advice_map["conditional"];
+        assert_eq!(finding.id, MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE);
+        assert_eq!(finding.level, FindingLevel::Information);
+        assert_eq!(
+            finding.context.as_ref().expect("context")[CONDITION_ADVICE_CONTEXT_KEY],
+            "Required when X"
+        );
+
+        for name in ["recommended_basic", "recommended_text"] {
+            let finding = advice_map[name];
+            assert_eq!(finding.id, MISSING_RECOMMENDED_ATTRIBUTE_ADVICE_TYPE);
+            assert_eq!(finding.level, FindingLevel::Information);
+        }
+
+        assert!(!advice_map.contains_key("opt_in_basic"));
+        assert!(!advice_map.contains_key("opt_in_text"));
+    }
+
+    #[test]
+    fn test_check_attributes_no_missing_attributes() {
+        let required = RequirementLevel::Basic(BasicRequirementLevelSpec::Required);
+        let recommended = RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended);
+        let semconv_attributes = vec![
+            SignalAttribute {
+                name: "attr1",
+                r#type: &STRING_TYPE,
+                requirement_level: &required,
+            },
+            SignalAttribute {
+                name: "attr2",
+                r#type: &STRING_TYPE,
+                requirement_level: &recommended,
+            },
+        ];
+        let sample_attributes = vec![
+            create_sample_attribute("attr1"),
+            create_sample_attribute("attr2"),
+        ];
+
+        let advice = check_attributes(&semconv_attributes, &sample_attributes, &create_sample());
+        assert!(advice.is_empty());
+    }
+
+    #[test]
+    fn test_check_attributes_template_type() {
+        let template_type = AttributeType::Template(TemplateTypeSpec::String);
+        let required = RequirementLevel::Basic(BasicRequirementLevelSpec::Required);
+        let semconv_attributes = vec![
+            SignalAttribute {
+                name: "template.attr",
+                r#type: &template_type,
+                requirement_level: &required,
+            },
+            SignalAttribute {
+                name: "regular.attr",
+                r#type: &STRING_TYPE,
+                requirement_level: &required,
+            },
+        ];
+        let sample = create_sample();
+
+        // Template attribute with matching prefix and regular attribute present
+        let sample_attributes = vec![
+            create_sample_attribute("template.attr.key1"),
+            create_sample_attribute("regular.attr"),
+        ];
+        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample);
+        assert!(
+            advice.is_empty(),
+            "Expected no advice when both template and regular attributes are present"
+        );
+
+        // An attribute sharing the template name without the separator is not a match
+        let sample_attributes = vec![
+            create_sample_attribute("template.attribute"),
+            create_sample_attribute("regular.attr"),
+        ];
+        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample);
+        assert_eq!(advice.len(), 1);
+        assert_eq!(advice[0].id, MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE);
+        assert_eq!(
+            advice[0].context.as_ref().expect("context")[ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY],
+            "template.attr"
+        );
+    }
+}
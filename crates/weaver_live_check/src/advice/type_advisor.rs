@@ -22,7 +22,42 @@ use crate::{
 };
 
 /// An advisor that checks if a sample has the correct type
-pub struct TypeAdvisor;
+///
+/// It also reports the attributes missing from data points and logs. When registered alongside
+/// the `RequiredAttributeAdvisor`, create it with [`TypeAdvisor::without_missing_attributes`] so
+/// that the required, recommended and conditionally required attributes are reported only once.
+#[derive(Debug, Clone, Copy)]
+pub struct TypeAdvisor {
+    /// Report the required, recommended and conditionally required attributes missing from a
+    /// sample with the deprecated `*_attribute_not_present` findings.
+    report_missing_attributes: bool,
+}
+
+impl TypeAdvisor {
+    /// Creates a type advisor reporting every missing attribute.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            report_missing_attributes: true,
+        }
+    }
+
+    /// Creates a type advisor leaving the missing required, recommended and conditionally
+    /// required attributes to the `RequiredAttributeAdvisor`. Missing opt-in attributes are
+    /// still reported.
+    #[must_use]
+    pub fn without_missing_attributes() -> Self {
+        Self {
+            report_missing_attributes: false,
+        }
+    }
+}
+
+impl Default for TypeAdvisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// Trait to abstract over different attribute types for checking
 trait CheckableAttribute {
@@ -85,10 +120,15 @@ impl CheckableAttribute for EventAttribute {
 /// | Recommended            | Improvement             |
 /// | Opt-In                 | Information             |
 /// | Conditionally Required | Information             |
+///
+/// These findings are deprecated in favor of the `missing_required_attribute` and
+/// `missing_recommended_attribute` findings of the `RequiredAttributeAdvisor`. Unless
+/// `report_missing_attributes` is set, only the Opt-In attributes are reported.
 fn check_attributes<T: CheckableAttribute>(
     semconv_attributes: &[T],
     sample_attributes: &[SampleAttribute],
     sample: &Sample,
+    report_missing_attributes: bool,
 ) -> Vec<PolicyFinding> {
     // Create a HashSet of attribute names for O(1) lookups
     let attribute_set: HashSet<_> = sample_attributes
@@ -115,7 +155,13 @@ fn check_attributes<T: CheckableAttribute>(
             attribute_set.contains(key)
         };
 
-        if !is_present {
+        let is_opt_in = matches!(
+            semconv_attribute.requirement_level(),
+            RequirementLevel::Basic(BasicRequirementLevelSpec::OptIn)
+                | RequirementLevel::OptIn { .. }
+        );
+
+        if !is_present && (report_missing_attributes || is_opt_in) {
             let (advice_type, advice_level, message) = match semconv_attribute.requirement_level() {
                 RequirementLevel::Basic(BasicRequirementLevelSpec::Required) => (
                     "required_attribute_not_present".to_owned(),
@@ -305,11 +351,13 @@ impl Advisor for TypeAdvisor {
                             &group.attributes,
                             &sample_number_data_point.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Metric(metric) => check_attributes(
                             &metric.attributes,
                             &sample_number_data_point.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Span(_span) => vec![],
                         VersionedSignal::Event(_event) => vec![],
@@ -335,11 +383,13 @@ impl Advisor for TypeAdvisor {
                             &group.attributes,
                             &sample_histogram_data_point.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Metric(metric) => check_attributes(
                             &metric.attributes,
                             &sample_histogram_data_point.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Span(_span) => vec![],
                         VersionedSignal::Event(_event) => vec![],
@@ -365,11 +415,13 @@ impl Advisor for TypeAdvisor {
                             &group.attributes,
                             &sample_log.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Event(event) => check_attributes(
                             &event.attributes,
                             &sample_log.attributes,
                             parent_signal,
+                            self.report_missing_attributes,
                         ),
                         VersionedSignal::Span(_span) => vec![],
                         VersionedSignal::Metric(_metric) => vec![],
@@ -484,7 +536,7 @@ mod tests {
             resource: None,
         });
 
-        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample);
+        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample, true);
         assert_eq!(advice.len(), 6);
 
         // Verify each advice type and level
@@ -529,6 +581,44 @@ mod tests {
         assert_eq!(information, 3);
     }
 
+    #[test]
+    fn test_check_attributes_without_missing_attributes() {
+        let semconv_attributes = vec![
+            create_test_attribute(
+                "required_attr",
+                RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
+            ),
+            create_test_attribute(
+                "recommended_attr",
+                RequirementLevel::Basic(BasicRequirementLevelSpec::Recommended),
+            ),
+            create_test_attribute(
+                "opt_in_attr",
+                RequirementLevel::Basic(BasicRequirementLevelSpec::OptIn),
+            ),
+            create_test_attribute(
+                "conditional",
+                RequirementLevel::ConditionallyRequired {
+                    text: "Required when X".to_owned(),
+                },
+            ),
+        ];
+
+        let sample = Sample::Metric(SampleMetric {
+            name: "test_metric".to_owned(),
+            unit: "".to_owned(),
+            data_points: None,
+            instrument: SampleInstrument::Supported(weaver_semconv::group::InstrumentSpec::Counter),
+            live_check_result: None,
+            resource: None,
+        });
+
+        // The other levels are left to the RequiredAttributeAdvisor
+        let advice = check_attributes(&semconv_attributes, &[], &sample, false);
+        assert_eq!(advice.len(), 1);
+        assert_eq!(advice[0].id, "opt_in_attribute_not_present");
+    }
+
     #[test]
     fn test_check_attributes_no_missing_attributes() {
         let semconv_attributes = vec![
@@ -555,7 +645,7 @@ mod tests {
             live_check_result: None,
             resource: None,
         });
-        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample);
+        let advice = check_attributes(&semconv_attributes, &sample_attributes, &sample, true);
         assert!(advice.is_empty());
     }
 
@@ -599,7 +689,12 @@ mod tests {
             resource: None,
         });
 
-        let advice = check_attributes(&semconv_attributes, &sample_attributes_with_match, &sample);
+        let advice = check_attributes(
+            &semconv_attributes,
+            &sample_attributes_with_match,
+            &sample,
+            true,
+        );
         assert!(
             advice.is_empty(),
             "Expected no advice when template attribute has matching prefixed attributes"
@@ -615,6 +710,7 @@ mod tests {
             &semconv_attributes,
             &sample_attributes_without_match,
             &sample,
+            true,
         );
         assert_eq!(
             advice.len(),
@@ -655,7 +751,12 @@ mod tests {
             create_sample_attribute("regular.attr"),       // Matches regular
         ];
 
-        let advice = check_attributes(&mixed_semconv_attributes, &mixed_sample_attributes, &sample);
+        let advice = check_attributes(
+            &mixed_semconv_attributes,
+            &mixed_sample_attributes,
+            &sample,
+            true,
+        );
         assert!(
             advice.is_empty(),
             "Expected no advice when both template and regular attributes are present"
@@ -0,0 +1,432 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Upgrade advisor suggesting the current name of renamed attributes and metrics
+
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashSet;
+use std::rc::Rc;
+use weaver_checker::{FindingLevel, PolicyFinding};
+use weaver_semconv::deprecated::Deprecated;
+use weaver_version::Versions;
+
+use super::{Advisor, FindingBuilder};
+use crate::{
+    otlp_logger::OtlpEmitter, Error, Sample, SampleRef, VersionedAttribute, VersionedRegistry,
+    VersionedSignal, ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY, CURRENT_VERSION_ADVICE_CONTEXT_KEY,
+    METRIC_NAME_ADVICE_CONTEXT_KEY, RENAMES_ADVICE_CONTEXT_KEY, SUGGESTED_NAME_ADVICE_CONTEXT_KEY,
+    TARGET_VERSION_ADVICE_CONTEXT_KEY, UPGRADE_ADVICE_TYPE,
+};
+
+/// A rename of an attribute or a metric.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RenameStep {
+    /// The version of the schema introducing the rename, if known.
+    pub version: Option<String>,
+    /// The name before the rename.
+    pub old_name: String,
+    /// The name after the rename.
+    pub new_name: String,
+}
+
+/// The rename history of the attributes and metrics of a registry.
+#[derive(Debug, Default, Clone)]
+pub struct RenameHistory {
+    /// The versions of the schema in ascending order, empty if unknown.
+    versions: Vec<String>,
+    /// The attribute renames, in ascending version order when the versions are known.
+    attributes: Vec<RenameStep>,
+    /// The metric renames, in ascending version order when the versions are known.
+    metrics: Vec<RenameStep>,
+}
+
+impl RenameHistory {
+    /// Builds the rename history from the `versions` of a telemetry schema.
+    /// The attribute renames of all the sections (`all`, resources, spans, logs and metrics)
+    /// are considered.
+    #[must_use]
+    pub fn from_versions(versions: &Versions) -> Self {
+        let mut history = RenameHistory::default();
+        for (version, spec) in versions.versions_asc() {
+            let version = version.to_string();
+            let mut add_attribute_renames =
+                |attribute_map: &std::collections::BTreeMap<String, String>| {
+                    for (old_name, new_name) in attribute_map {
+                        let step = RenameStep {
+                            version: Some(version.clone()),
+                            old_name: old_name.clone(),
+                            new_name: new_name.clone(),
+                        };
+                        if !history.attributes.contains(&step) {
+                            history.attributes.push(step);
+                        }
+                    }
+                };
+            for change in spec.all.iter().flat_map(|all| &all.changes) {
+                add_attribute_renames(&change.rename_attributes.attribute_map);
+            }
+            for change in spec.resources.iter().flat_map(|r| &r.changes) {
+                add_attribute_renames(&change.rename_attributes.attribute_map);
+            }
+            for change in spec.spans.iter().flat_map(|spans| &spans.changes) {
+                add_attribute_renames(&change.rename_attributes.attribute_map);
+            }
+            for change in spec.logs.iter().flat_map(|logs| &logs.changes) {
+                add_attribute_renames(&change.rename_attributes.attribute_map);
+            }
+            for change in spec.metrics.iter().flat_map(|metrics| &metrics.changes) {
+                add_attribute_renames(&change.rename_attributes.attribute_map);
+                for (old_name, new_name) in &change.rename_metrics {
+                    history.metrics.push(RenameStep {
+                        version: Some(version.clone()),
+                        old_name: old_name.clone(),
+                        new_name: new_name.clone(),
+                    });
+                }
+            }
+            history.versions.push(version);
+        }
+        history
+    }
+
+    /// Builds the rename history from the `renamed` deprecations of a registry.
+    /// The versions of the renames are unknown.
+    #[must_use]
+    pub fn from_registry(registry: &VersionedRegistry) -> Self {
+        fn renamed_to(deprecated: &Option<Deprecated>) -> Option<&str> {
+            match deprecated {
+                Some(Deprecated::Renamed { renamed_to, .. }) => Some(renamed_to),
+                _ => None,
+            }
+        }
+        fn step(old_name: &str, new_name: &str) -> RenameStep {
+            RenameStep {
+                version: None,
+                old_name: old_name.to_owned(),
+                new_name: new_name.to_owned(),
+            }
+        }
+
+        let mut history = RenameHistory::default();
+        match registry {
+            VersionedRegistry::V1(registry) => {
+                for group in &registry.groups {
+                    if let (Some(metric_name), Some(new_name)) =
+                        (&group.metric_name, renamed_to(&group.deprecated))
+                    {
+                        history.metrics.push(step(metric_name, new_name));
+                    }
+                    for attribute in &group.attributes {
+                        if let Some(new_name) = renamed_to(&attribute.deprecated) {
+                            let step = step(&attribute.name, new_name);
+                            if !history.attributes.contains(&step) {
+                                history.attributes.push(step);
+                            }
+                        }
+                    }
+                }
+            }
+            VersionedRegistry::V2(registry) => {
+                for metric in &registry.registry.metrics {
+                    if let Some(new_name) = renamed_to(&metric.common.deprecated) {
+                        history.metrics.push(step(&metric.name, new_name));
+                    }
+                }
+                for attribute in &registry.registry.attributes {
+                    if let Some(new_name) = renamed_to(&attribute.common.deprecated) {
+                        history.attributes.push(step(&attribute.key, new_name));
+                    }
+                }
+            }
+        }
+        history
+    }
+
+    /// Returns true if the versions of the renames are known.
+    #[must_use]
+    pub fn is_versioned(&self) -> bool {
+        !self.versions.is_empty()
+    }
+
+    /// Returns the current version of the schema, if known.
+    #[must_use]
+    pub fn current_version(&self) -> Option<&str> {
+        self.versions.last().map(String::as_str)
+    }
+
+    /// Returns the chain of renames of an attribute.
+    #[must_use]
+    pub fn attribute_renames(&self, name: &str) -> Vec<&RenameStep> {
+        self.renames(&self.attributes, name)
+    }
+
+    /// Returns the chain of renames of a metric.
+    #[must_use]
+    pub fn metric_renames(&self, name: &str) -> Vec<&RenameStep> {
+        self.renames(&self.metrics, name)
+    }
+
+    /// Returns the most recent version in which the first name of a chain of renames
+    /// was valid, if known.
+    #[must_use]
+    pub fn target_version(&self, renames: &[&RenameStep]) -> Option<&str> {
+        let first_version = renames.first()?.version.as_deref()?;
+        let index = self.versions.iter().position(|v| v == first_version)?;
+        index
+            .checked_sub(1)
+            .map(|index| self.versions[index].as_str())
+    }
+
+    /// Walks the chain of renames starting at `name`. When the versions are known, each rename
+    /// must be introduced by a version following the previous rename.
+    fn renames<'a>(&self, steps: &'a [RenameStep], name: &str) -> Vec<&'a RenameStep> {
+        let mut chain = Vec::new();
+        let mut visited = HashSet::from([name]);
+        let mut current = name;
+        let mut start = 0;
+        while let Some(index) = steps[start..]
+            .iter()
+            .position(|step| step.old_name == current)
+        {
+            let step = &steps[start + index];
+            if !visited.insert(step.new_name.as_str()) {
+                break;
+            }
+            chain.push(step);
+            current = &step.new_name;
+            if self.is_versioned() {
+                start += index + 1;
+            }
+        }
+        chain
+    }
+}
+
+/// An advisor suggesting the current name of renamed attributes and metrics by walking their
+/// rename history.
+///
+/// When the versions of the renames are known (telemetry schema), the advisor reports every
+/// renamed attribute or metric with the schema version the sample appears to target.
+/// Otherwise, only chains of several renames are reported, a single rename being already
+/// reported by the `deprecated` advice.
+pub struct UpgradeAdvisor {
+    history: RenameHistory,
+}
+
+impl UpgradeAdvisor {
+    /// Create a new UpgradeAdvisor
+    #[must_use]
+    pub fn new(history: RenameHistory) -> Self {
+        Self { history }
+    }
+
+    fn finding(
+        &self,
+        kind: &str,
+        name_key: &str,
+        name: &str,
+        renames: &[&RenameStep],
+    ) -> Option<FindingBuilder> {
+        let last = renames.last()?;
+        if !self.history.is_versioned() && renames.len() < 2 {
+            return None;
+        }
+        let target_version = self.history.target_version(renames);
+        let current_version = self.history.current_version();
+        let steps = renames
+            .iter()
+            .map(|step| match &step.version {
+                Some(version) => format!("'{}' in {}", step.new_name, version),
+                None => format!("'{}'", step.new_name),
+            })
+            .collect::<Vec<_>>()
+            .join(", then to ");
+        let message = match (target_version, current_version) {
+            (Some(target), Some(current)) => format!(
+                "{kind} '{name}' targets schema version {target}; use '{}' at version {current} (renamed to {steps}).",
+                last.new_name
+            ),
+            (None, Some(current)) => format!(
+                "{kind} '{name}' targets a schema version older than {}; use '{}' at version {current} (renamed to {steps}).",
+                renames[0].version.as_deref().unwrap_or_default(),
+                last.new_name
+            ),
+            _ => format!(
+                "{kind} '{name}' was renamed several times; use '{}' (renamed to {steps}).",
+                last.new_name
+            ),
+        };
+        Some(
+            FindingBuilder::new(UPGRADE_ADVICE_TYPE)
+                .context(json!({
+                    name_key: name,
+                    SUGGESTED_NAME_ADVICE_CONTEXT_KEY: last.new_name,
+                    TARGET_VERSION_ADVICE_CONTEXT_KEY: target_version,
+                    CURRENT_VERSION_ADVICE_CONTEXT_KEY: current_version,
+                    RENAMES_ADVICE_CONTEXT_KEY: renames,
+                }))
+                .message(message)
+                .level(FindingLevel::Improvement),
+        )
+    }
+}
+
+impl Advisor for UpgradeAdvisor {
+    fn advise(
+        &mut self,
+        sample: SampleRef<'_>,
+        signal: &Sample,
+        _registry_attribute: Option<Rc<VersionedAttribute>>,
+        _registry_group: Option<Rc<VersionedSignal>>,
+        otlp_emitter: Option<Rc<OtlpEmitter>>,
+    ) -> Result<Vec<PolicyFinding>, Error> {
+        let finding = match &sample {
+            SampleRef::Attribute(sample_attribute) => {
+                let name = &sample_attribute.name;
+                self.finding(
+                    "Attribute",
+                    ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY,
+                    name,
+                    &self.history.attribute_renames(name),
+                )
+            }
+            SampleRef::Metric(sample_metric) => {
+                let name = &sample_metric.name;
+                self.finding(
+                    "Metric",
+                    METRIC_NAME_ADVICE_CONTEXT_KEY,
+                    name,
+                    &self.history.metric_renames(name),
+                )
+            }
+            _ => None,
+        };
+        Ok(finding
+            .map(|finding| {
+                finding
+                    .signal(signal)
+                    .build_and_emit(&sample, otlp_emitter.as_deref(), signal)
+            })
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample_attribute::SampleAttribute;
+
+    fn versions() -> Versions {
+        serde_yaml::from_str(
+            r#"
+1.20.0:
+1.22.0:
+  all:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            a: b
+1.24.0:
+  spans:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            b: c
+  metrics:
+    changes:
+      - rename_metrics:
+          old.metric: new.metric
+1.26.0:
+"#,
+        )
+        .unwrap()
+    }
+
+    fn advise(advisor: &mut UpgradeAdvisor, name: &str) -> Vec<PolicyFinding> {
+        let attribute = SampleAttribute {
+            name: name.to_owned(),
+            value: None,
+            r#type: None,
+            live_check_result: None,
+        };
+        let sample = Sample::Attribute(attribute.clone());
+        advisor
+            .advise(SampleRef::Attribute(&attribute), &sample, None, None, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_versioned_renames() {
+        let history = RenameHistory::from_versions(&versions());
+        let renames = history.attribute_renames("a");
+        assert_eq!(renames.len(), 2);
+        assert_eq!(history.target_version(&renames), Some("1.20.0"));
+        assert_eq!(history.current_version(), Some("1.26.0"));
+        assert_eq!(
+            history.target_version(&history.attribute_renames("b")),
+            Some("1.22.0")
+        );
+        assert_eq!(history.metric_renames("old.metric").len(), 1);
+
+        let mut advisor = UpgradeAdvisor::new(history);
+        let findings = advise(&mut advisor, "a");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, UPGRADE_ADVICE_TYPE);
+        assert_eq!(
+            findings[0].message,
+            "Attribute 'a' targets schema version 1.20.0; use 'c' at version 1.26.0 (renamed to 'b' in 1.22.0, then to 'c' in 1.24.0)."
+        );
+        let context = findings[0].context.as_ref().unwrap();
+        assert_eq!(context[SUGGESTED_NAME_ADVICE_CONTEXT_KEY], "c");
+        assert_eq!(context[RENAMES_ADVICE_CONTEXT_KEY][1]["version"], "1.24.0");
+
+        // A single versioned rename is reported.
+        assert_eq!(advise(&mut advisor, "b").len(), 1);
+        assert!(advise(&mut advisor, "c").is_empty());
+    }
+
+    #[test]
+    fn test_unversioned_renames() {
+        let history = RenameHistory {
+            versions: vec![],
+            attributes: vec![
+                RenameStep {
+                    version: None,
+                    old_name: "b".to_owned(),
+                    new_name: "c".to_owned(),
+                },
+                RenameStep {
+                    version: None,
+                    old_name: "a".to_owned(),
+                    new_name: "b".to_owned(),
+                },
+                RenameStep {
+                    version: None,
+                    old_name: "c".to_owned(),
+                    new_name: "a".to_owned(),
+                },
+            ],
+            metrics: vec![],
+        };
+        // The cycle a -> b -> c -> a is stopped.
+        assert_eq!(history.attribute_renames("a").len(), 2);
+        assert_eq!(
+            history.target_version(&history.attribute_renames("a")),
+            None
+        );
+
+        let mut advisor = UpgradeAdvisor::new(RenameHistory {
+            attributes: history.attributes[..2].to_vec(),
+            ..history
+        });
+        let findings = advise(&mut advisor, "a");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "Attribute 'a' was renamed several times; use 'c' (renamed to 'b', then to 'c')."
+        );
+        // A single unversioned rename is left to the deprecated advice.
+        assert!(advise(&mut advisor, "b").is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Baseline of known live check findings.
+//!
+//! A baseline records the findings of a live check session so that later sessions only
+//! report the new ones. Findings are keyed by their id, the signal they apply to and the
+//! context keys identifying the offending telemetry (e.g. the attribute or metric name),
+//! so messages and sample-specific values (e.g. an attribute value of the wrong type) can
+//! change without making a known finding new.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use weaver_checker::{FindingLevel, PolicyFinding};
+
+use crate::{
+    Error, LiveCheckResult, LiveCheckStatistics, ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY,
+    ATTRIBUTE_TYPE_ADVICE_CONTEXT_KEY, ATTRIBUTE_VALUE_ADVICE_CONTEXT_KEY,
+    EVENT_NAME_ADVICE_CONTEXT_KEY, INSTRUMENT_ADVICE_CONTEXT_KEY, METRIC_NAME_ADVICE_CONTEXT_KEY,
+    UNIT_ADVICE_CONTEXT_KEY,
+};
+
+/// The context keys identifying a finding in a baseline.
+///
+/// The built-in advisors only provide `attribute_value` when the value is what is being
+/// reported (undefined enum variants).
+pub const BASELINE_CONTEXT_KEYS: [&str; 7] = [
+    ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY,
+    ATTRIBUTE_VALUE_ADVICE_CONTEXT_KEY,
+    ATTRIBUTE_TYPE_ADVICE_CONTEXT_KEY,
+    METRIC_NAME_ADVICE_CONTEXT_KEY,
+    EVENT_NAME_ADVICE_CONTEXT_KEY,
+    UNIT_ADVICE_CONTEXT_KEY,
+    INSTRUMENT_ADVICE_CONTEXT_KEY,
+];
+
+/// A finding recorded in a baseline.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineFinding {
+    /// The id of the finding e.g. "missing_attribute".
+    pub id: String,
+    /// The signal type the finding applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_type: Option<String>,
+    /// The signal name the finding applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_name: Option<String>,
+    /// The identifying context of the finding (see [`BASELINE_CONTEXT_KEYS`]).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub context: BTreeMap<String, String>,
+}
+
+impl From<&PolicyFinding> for BaselineFinding {
+    fn from(finding: &PolicyFinding) -> Self {
+        let context = finding
+            .context
+            .as_ref()
+            .and_then(|context| context.as_object())
+            .map(|context| {
+                BASELINE_CONTEXT_KEYS
+                    .iter()
+                    .filter_map(|key| {
+                        let value = context.get(*key)?;
+                        let value = value
+                            .as_str()
+                            .map(ToOwned::to_owned)
+                            .unwrap_or_else(|| value.to_string());
+                        Some(((*key).to_owned(), value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        BaselineFinding {
+            id: finding.id.clone(),
+            signal_type: finding.signal_type.clone(),
+            signal_name: finding.signal_name.clone(),
+            context,
+        }
+    }
+}
+
+/// The content of a baseline file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BaselineFile {
+    findings: BTreeSet<BaselineFinding>,
+}
+
+/// The findings of a baseline file, suppressed from the live check results.
+///
+/// When the baseline file does not exist yet, the baseline is in recording mode: every
+/// finding is added to the baseline (and suppressed), and the file is created by
+/// [`Baseline::save`] at the end of the session.
+#[derive(Debug)]
+pub struct Baseline {
+    path: PathBuf,
+    findings: BTreeSet<BaselineFinding>,
+    recording: bool,
+    highest_new_level: Option<FindingLevel>,
+}
+
+impl Baseline {
+    /// Load the baseline file, or start recording a new one if the file does not exist.
+    pub fn load_or_record(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self {
+                path: path.to_path_buf(),
+                findings: BTreeSet::new(),
+                recording: true,
+                highest_new_level: None,
+            });
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| Error::BaselineError {
+            error: format!("Failed to read baseline file {}: {e}", path.display()),
+        })?;
+        let file: BaselineFile =
+            serde_json::from_str(&content).map_err(|e| Error::BaselineError {
+                error: format!("Invalid baseline file {}: {e}", path.display()),
+            })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            findings: file.findings,
+            recording: false,
+            highest_new_level: None,
+        })
+    }
+
+    /// Is the baseline file being created by this session?
+    #[must_use]
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The number of findings in the baseline.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// Is the baseline empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The highest level of the findings not in the baseline.
+    #[must_use]
+    pub fn highest_new_level(&self) -> Option<&FindingLevel> {
+        self.highest_new_level.as_ref()
+    }
+
+    /// Remove the findings of the baseline from a live check result, counting them as
+    /// suppressed in the statistics.
+    pub fn suppress(&mut self, result: &mut LiveCheckResult, stats: &mut LiveCheckStatistics) {
+        let all_advice = std::mem::take(&mut result.all_advice);
+        result.highest_advice_level = None;
+        for advice in all_advice {
+            let key = BaselineFinding::from(&advice);
+            if self.recording {
+                _ = self.findings.insert(key);
+                stats.add_suppressed_advice(&advice);
+            } else if self.findings.contains(&key) {
+                stats.add_suppressed_advice(&advice);
+            } else {
+                if self.highest_new_level.as_ref() < Some(&advice.level) {
+                    self.highest_new_level = Some(advice.level.clone());
+                }
+                result.add_advice(advice);
+            }
+        }
+    }
+
+    /// Write the baseline file if it was recorded during this session.
+    pub fn save(&self) -> Result<(), Error> {
+        if !self.recording {
+            return Ok(());
+        }
+        let file = BaselineFile {
+            findings: self.findings.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file).map_err(|e| Error::BaselineError {
+            error: format!("Failed to serialize the baseline: {e}"),
+        })?;
+        std::fs::write(&self.path, content).map_err(|e| Error::BaselineError {
+            error: format!("Failed to write baseline file {}: {e}", self.path.display()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CumulativeStatistics;
+    use crate::VersionedRegistry;
+    use serde_json::json;
+    use weaver_forge::registry::ResolvedRegistry;
+
+    fn finding(id: &str, level: FindingLevel, context: serde_json::Value) -> PolicyFinding {
+        PolicyFinding {
+            id: id.to_owned(),
+            context: Some(context),
+            message: format!("{id} message"),
+            level,
+            signal_type: Some("span".to_owned()),
+            signal_name: Some("http.client".to_owned()),
+        }
+    }
+
+    fn result(findings: Vec<PolicyFinding>) -> LiveCheckResult {
+        let mut result = LiveCheckResult::new();
+        result.add_advice_list(findings);
+        result
+    }
+
+    fn empty_stats() -> LiveCheckStatistics {
+        LiveCheckStatistics::Cumulative(CumulativeStatistics::new(&VersionedRegistry::V1(
+            Box::new(ResolvedRegistry {
+                groups: vec![],
+                registry_url: String::new(),
+            }),
+        )))
+    }
+
+    #[test]
+    fn test_baseline_key_ignores_volatile_context() {
+        let first = finding(
+            "type_mismatch",
+            FindingLevel::Violation,
+            json!({"attribute_name": "http.response.status_code", "attribute_type": "string", "expected": "int"}),
+        );
+        let mut second = first.clone();
+        second.message = "another message".to_owned();
+        second.context = Some(json!({
+            "attribute_name": "http.response.status_code",
+            "attribute_type": "string",
+            "expected": "int64",
+        }));
+        assert_eq!(
+            BaselineFinding::from(&first),
+            BaselineFinding::from(&second)
+        );
+
+        let mut other_signal = first.clone();
+        other_signal.signal_name = Some("http.server".to_owned());
+        assert_ne!(
+            BaselineFinding::from(&first),
+            BaselineFinding::from(&other_signal)
+        );
+    }
+
+    #[test]
+    fn test_baseline_record_then_suppress() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("baseline.json");
+        let known = finding(
+            "missing_attribute",
+            FindingLevel::Violation,
+            json!({"attribute_name": "custom.id"}),
+        );
+        let new_improvement = finding(
+            "not_stable",
+            FindingLevel::Improvement,
+            json!({"attribute_name": "url.template", "stability": "development"}),
+        );
+
+        // First session: record the baseline
+        let mut baseline = Baseline::load_or_record(&path).unwrap();
+        assert!(baseline.is_recording());
+        let mut stats = empty_stats();
+        let mut first = result(vec![known.clone()]);
+        baseline.suppress(&mut first, &mut stats);
+        assert!(first.all_advice.is_empty());
+        assert_eq!(first.highest_advice_level, None);
+        baseline.save().unwrap();
+        assert!(path.exists());
+
+        // Second session: the known finding is suppressed, the new one is reported
+        let mut baseline = Baseline::load_or_record(&path).unwrap();
+        assert!(!baseline.is_recording());
+        assert_eq!(baseline.len(), 1);
+        let mut stats = empty_stats();
+        let mut second = result(vec![known, new_improvement.clone()]);
+        baseline.suppress(&mut second, &mut stats);
+        assert_eq!(second.all_advice, vec![new_improvement]);
+        assert_eq!(second.highest_advice_level, Some(FindingLevel::Improvement));
+        assert_eq!(
+            baseline.highest_new_level(),
+            Some(&FindingLevel::Improvement)
+        );
+        stats.maybe_add_live_check_result(Some(&second));
+        let LiveCheckStatistics::Cumulative(stats) = stats else {
+            panic!("Expected Cumulative statistics");
+        };
+        assert_eq!(stats.total_advisories, 1);
+        assert_eq!(stats.total_suppressed_advisories, 1);
+        assert_eq!(
+            stats
+                .suppressed_advice_level_counts
+                .get(&FindingLevel::Violation),
+            Some(&1)
+        );
+        assert!(!stats.has_violations());
+    }
+
+    #[test]
+    fn test_invalid_baseline() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("baseline.json");
+        std::fs::write(&path, "{\"unexpected\": []}").unwrap();
+        assert!(matches!(
+            Baseline::load_or_record(&path),
+            Err(Error::BaselineError { .. })
+        ));
+    }
+}
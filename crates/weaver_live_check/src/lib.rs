@@ -23,11 +23,16 @@ use weaver_forge::{
     v2::registry::ForgeResolvedRegistry,
 };
 use weaver_semconv::{
-    attribute::AttributeType, deprecated::Deprecated, group::InstrumentSpec, stability::Stability,
+    attribute::{AttributeType, RequirementLevel},
+    deprecated::Deprecated,
+    group::InstrumentSpec,
+    stability::Stability,
 };
 
 /// Advisors for live checks
 pub mod advice;
+/// Baseline of known findings suppressed from the live check results
+pub mod baseline;
 /// An ingester that reads samples from a JSON file.
 pub mod json_file_ingester;
 /// An ingester that reads samples from standard input.
@@ -46,6 +51,10 @@ pub mod sample_metric;
 pub mod sample_resource;
 /// The intermediary format for spans
 pub mod sample_span;
+/// Conversion of samples between two versions of a telemetry schema
+mod sample_transform;
+/// Matching of sample spans to the span definitions of the registry
+pub mod span_matcher;
 /// Statistics tracking for live check reports
 mod stats;
 /// An ingester that reads attribute names from a text file.
@@ -76,6 +85,12 @@ pub const UNIT_MISMATCH_ADVICE_TYPE: &str = "unit_mismatch";
 pub const UNEXPECTED_INSTRUMENT_ADVICE_TYPE: &str = "unexpected_instrument";
 /// Undefined enum variant advice type
 pub const UNDEFINED_ENUM_VARIANT_ADVICE_TYPE: &str = "undefined_enum_variant";
+/// Missing required attribute advice type
+pub const MISSING_REQUIRED_ATTRIBUTE_ADVICE_TYPE: &str = "missing_required_attribute";
+/// Missing recommended attribute advice type
+pub const MISSING_RECOMMENDED_ATTRIBUTE_ADVICE_TYPE: &str = "missing_recommended_attribute";
+/// Upgrade advice type
+pub const UPGRADE_ADVICE_TYPE: &str = "upgrade";
 
 /// Attribute name key in advice context
 pub const ATTRIBUTE_NAME_ADVICE_CONTEXT_KEY: &str = "attribute_name";
@@ -99,6 +114,18 @@ pub const EXPECTED_VALUE_ADVICE_CONTEXT_KEY: &str = "expected";
 pub const EVENT_NAME_ADVICE_CONTEXT_KEY: &str = "event_name";
 /// Metric name key in advice context
 pub const METRIC_NAME_ADVICE_CONTEXT_KEY: &str = "metric_name";
+/// Requirement level key in advice context
+pub const REQUIREMENT_LEVEL_ADVICE_CONTEXT_KEY: &str = "requirement_level";
+/// Condition key (from conditionally required attributes) in advice context
+pub const CONDITION_ADVICE_CONTEXT_KEY: &str = "condition";
+/// Suggested name key in advice context
+pub const SUGGESTED_NAME_ADVICE_CONTEXT_KEY: &str = "suggested_name";
+/// Targeted schema version key in advice context
+pub const TARGET_VERSION_ADVICE_CONTEXT_KEY: &str = "target_version";
+/// Current schema version key in advice context
+pub const CURRENT_VERSION_ADVICE_CONTEXT_KEY: &str = "current_version";
+/// Rename chain key in advice context
+pub const RENAMES_ADVICE_CONTEXT_KEY: &str = "renames";
 
 /// Embedded default live check rego policies
 pub const DEFAULT_LIVE_CHECK_REGO: &str =
@@ -169,6 +196,17 @@ impl VersionedAttribute {
     }
 }
 
+/// An attribute referenced by a signal, with its requirement level for that signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalAttribute<'a> {
+    /// The name/key of the attribute
+    pub name: &'a str,
+    /// The type of the attribute
+    pub r#type: &'a AttributeType,
+    /// The requirement level of the attribute for the signal
+    pub requirement_level: &'a RequirementLevel,
+}
+
 /// Versioned enum for the signal
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
@@ -227,6 +265,49 @@ impl VersionedSignal {
             VersionedSignal::Event(_) => None,
         }
     }
+
+    /// Get the attributes of the signal with their requirement levels
+    #[must_use]
+    pub fn attributes(&self) -> Vec<SignalAttribute<'_>> {
+        match self {
+            VersionedSignal::Group(group) => group
+                .attributes
+                .iter()
+                .map(|attr| SignalAttribute {
+                    name: &attr.name,
+                    r#type: &attr.r#type,
+                    requirement_level: &attr.requirement_level,
+                })
+                .collect(),
+            VersionedSignal::Metric(metric) => metric
+                .attributes
+                .iter()
+                .map(|attr| SignalAttribute {
+                    name: &attr.base.key,
+                    r#type: &attr.base.r#type,
+                    requirement_level: &attr.requirement_level,
+                })
+                .collect(),
+            VersionedSignal::Span(span) => span
+                .attributes
+                .iter()
+                .map(|attr| SignalAttribute {
+                    name: &attr.base.key,
+                    r#type: &attr.base.r#type,
+                    requirement_level: &attr.requirement_level,
+                })
+                .collect(),
+            VersionedSignal::Event(event) => event
+                .attributes
+                .iter()
+                .map(|attr| SignalAttribute {
+                    name: &attr.base.key,
+                    r#type: &attr.base.r#type,
+                    requirement_level: &attr.requirement_level,
+                })
+                .collect(),
+        }
+    }
 }
 
 /// Weaver live check errors
@@ -257,6 +338,13 @@ pub enum Error {
         /// The error that occurred.
         error: String,
     },
+
+    /// Baseline error.
+    #[error("Baseline error. {error}")]
+    BaselineError {
+        /// The error that occurred.
+        error: String,
+    },
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -372,8 +460,8 @@ impl Sample {
     #[must_use]
     pub fn signal_name(&self) -> Option<String> {
         match self {
-            Sample::Attribute(_) => None,                  // not a signal
-            Sample::Span(span) => Some(span.name.clone()), // TODO: update to type once added
+            Sample::Attribute(_) => None, // not a signal
+            Sample::Span(span) => Some(span.span_type.clone().unwrap_or_else(|| span.name.clone())),
             Sample::SpanEvent(_) => None,
             Sample::SpanLink(_) => None,
             Sample::Resource(_) => None,
@@ -466,8 +554,10 @@ impl Default for LiveCheckResult {
 
 /// A live check report for a set of samples
 #[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LiveCheckReport {
     /// The live check samples
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<Object>))]
     pub samples: Vec<Sample>,
     /// The statistics for the report
     pub statistics: LiveCheckStatistics,
@@ -529,6 +619,7 @@ pub trait Advisable {
             )?;
             result.add_advice_list(advice_list);
         }
+        live_checker.suppress_baseline_findings(&mut result, stats);
 
         stats.inc_entity_count(self.entity_type());
         stats.maybe_add_live_check_result(Some(&result));
@@ -9,8 +9,9 @@ use std::sync::Arc;
 use weaver_semconv::{attribute::AttributeType, group::GroupType};
 
 use crate::{
-    advice::Advisor, otlp_logger::OtlpEmitter, VersionedAttribute, VersionedRegistry,
-    VersionedSignal,
+    advice::Advisor, baseline::Baseline, otlp_logger::OtlpEmitter, sample_span::SampleSpan,
+    span_matcher::SpanMatcher, LiveCheckResult, LiveCheckStatistics, VersionedAttribute,
+    VersionedRegistry, VersionedSignal,
 };
 
 #[cfg(test)]
@@ -25,6 +26,8 @@ pub struct LiveChecker {
     semconv_templates: HashMap<String, Rc<VersionedAttribute>>,
     semconv_metrics: HashMap<String, Rc<VersionedSignal>>,
     semconv_events: HashMap<String, Rc<VersionedSignal>>,
+    #[serde(skip)]
+    semconv_spans: SpanMatcher,
     /// The advisors to run
     #[serde(skip)]
     pub advisors: Vec<Box<dyn Advisor>>,
@@ -33,6 +36,9 @@ pub struct LiveChecker {
     /// Optional OTLP emitter for emitting findings as log records
     #[serde(skip)]
     pub otlp_emitter: Option<Rc<OtlpEmitter>>,
+    /// Optional baseline of known findings to suppress
+    #[serde(skip)]
+    pub baseline: Option<Baseline>,
 }
 
 impl LiveChecker {
@@ -47,6 +53,8 @@ impl LiveChecker {
         let mut semconv_metrics = HashMap::new();
         // Hashmap of events by name
         let mut semconv_events = HashMap::new();
+        // Span definitions by type
+        let mut semconv_spans = SpanMatcher::default();
 
         match registry.as_ref() {
             VersionedRegistry::V1(registry) => {
@@ -63,6 +71,15 @@ impl LiveChecker {
                             let _ = semconv_events.insert(event_name.clone(), group_rc);
                         }
                     }
+                    if group.r#type == GroupType::Span {
+                        let group_rc = Rc::new(VersionedSignal::Group(Box::new(group.clone())));
+                        semconv_spans.add_span(
+                            group.id.clone(),
+                            group.span_kind.clone(),
+                            None,
+                            group_rc,
+                        );
+                    }
                     for attribute in &group.attributes {
                         let attribute_rc = Rc::new(VersionedAttribute::V1(attribute.clone()));
                         match attribute.r#type {
@@ -91,6 +108,15 @@ impl LiveChecker {
                     let event_rc = Rc::new(VersionedSignal::Event(event.clone()));
                     let _ = semconv_events.insert(event_name, event_rc);
                 }
+                for span in &registry.registry.spans {
+                    let span_rc = Rc::new(VersionedSignal::Span(span.clone()));
+                    semconv_spans.add_span(
+                        span.r#type.to_string(),
+                        Some(span.kind.clone()),
+                        Some(&span.name.note),
+                        span_rc,
+                    );
+                }
                 for attribute in &registry.registry.attributes {
                     let attribute_rc = Rc::new(VersionedAttribute::V2(attribute.clone()));
                     match &attribute.r#type {
@@ -108,6 +134,7 @@ impl LiveChecker {
 
         // Sort templates by name length in descending order
         templates_by_length.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        semconv_spans.finalize();
 
         LiveChecker {
             registry,
@@ -115,9 +142,22 @@ impl LiveChecker {
             semconv_templates,
             semconv_metrics,
             semconv_events,
+            semconv_spans,
             advisors,
             templates_by_length,
             otlp_emitter: None,
+            baseline: None,
+        }
+    }
+
+    /// Remove the findings of the baseline (if any) from a live check result
+    pub(crate) fn suppress_baseline_findings(
+        &mut self,
+        result: &mut LiveCheckResult,
+        stats: &mut LiveCheckStatistics,
+    ) {
+        if let Some(baseline) = &mut self.baseline {
+            baseline.suppress(result, stats);
         }
     }
 
@@ -144,6 +184,13 @@ impl LiveChecker {
         self.semconv_events.get(name).map(Rc::clone)
     }
 
+    /// Find the span definition matching a sample span in the registry.
+    /// Returns the span type and the span definition.
+    #[must_use]
+    pub fn find_span(&self, span: &SampleSpan) -> Option<(String, Rc<VersionedSignal>)> {
+        self.semconv_spans.find_span(span)
+    }
+
     /// Find a template in the registry
     #[must_use]
     pub fn find_template(&self, attribute_name: &str) -> Option<Rc<VersionedAttribute>> {
@@ -162,12 +209,16 @@ mod tests {
     use std::fs::File;
 
     use crate::{
-        advice::{DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, StabilityAdvisor, TypeAdvisor},
+        advice::{
+            DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, RequiredAttributeAdvisor,
+            StabilityAdvisor, TypeAdvisor,
+        },
         sample_attribute::SampleAttribute,
         sample_metric::{
             DataPoints, SampleExemplar, SampleExponentialHistogramDataPoint, SampleInstrument,
             SampleMetric, SampleNumberDataPoint,
         },
+        sample_span::SampleSpan,
         LiveCheckRunner, LiveCheckStatistics, Sample,
     };
 
@@ -240,7 +291,8 @@ mod tests {
         let advisors: Vec<Box<dyn Advisor>> = vec![
             Box::new(DeprecatedAdvisor),
             Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
+            Box::new(TypeAdvisor::without_missing_attributes()),
+            Box::new(RequiredAttributeAdvisor),
             Box::new(EnumAdvisor),
         ];
 
@@ -507,7 +559,13 @@ mod tests {
             assert_eq!(cumulative_stats.seen_registry_attributes.len(), 3);
             assert_eq!(cumulative_stats.seen_registry_attributes["test.enum"], 4);
             assert_eq!(cumulative_stats.seen_non_registry_attributes.len(), 6);
-            assert_eq!(cumulative_stats.registry_coverage, 1.0);
+            if use_v2 {
+                assert_eq!(cumulative_stats.registry_coverage, 1.0);
+            } else {
+                // The span group of the registry is not covered by attribute samples
+                assert_eq!(cumulative_stats.seen_registry_spans.len(), 1);
+                assert_eq!(cumulative_stats.registry_coverage, 0.75);
+            }
         } else {
             panic!("Expected Cumulative statistics");
         }
@@ -1207,7 +1265,8 @@ mod tests {
         let advisors: Vec<Box<dyn Advisor>> = vec![
             Box::new(DeprecatedAdvisor),
             Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
+            Box::new(TypeAdvisor::without_missing_attributes()),
+            Box::new(RequiredAttributeAdvisor),
             Box::new(EnumAdvisor),
         ];
 
@@ -1254,6 +1313,101 @@ mod tests {
         }
     }
 
+    fn make_span(name: &str, attributes: Vec<SampleAttribute>) -> Sample {
+        Sample::Span(SampleSpan {
+            name: name.to_owned(),
+            kind: SpanKindSpec::Internal,
+            status: None,
+            attributes,
+            span_events: vec![],
+            span_links: vec![],
+            live_check_result: None,
+            resource: None,
+            span_type: None,
+        })
+    }
+
+    #[test]
+    fn test_span_matching() {
+        run_span_matching_test(false);
+    }
+
+    #[test]
+    fn test_span_matching_v2() {
+        run_span_matching_test(true);
+    }
+
+    fn run_span_matching_test(use_v2: bool) {
+        let registry = make_custom_rego_registry(use_v2);
+        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(RequiredAttributeAdvisor)];
+        let mut live_checker = LiveChecker::new(Arc::new(registry), advisors);
+
+        let mut samples = vec![
+            // Matched by the name pattern (v2 only, v1 groups do not define one)
+            make_span("custom.comprehensive.internal", vec![]),
+            // Matched by the explicit hint
+            make_span(
+                "do_work",
+                vec![
+                    SampleAttribute::try_from("weaver.span.type=custom.comprehensive.internal")
+                        .expect("valid attribute"),
+                ],
+            ),
+            // Not matched
+            make_span("unknown", vec![]),
+        ];
+
+        let mut stats =
+            LiveCheckStatistics::Cumulative(CumulativeStatistics::new(&live_checker.registry));
+        for sample in &mut samples {
+            let result =
+                sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone());
+            assert!(result.is_ok());
+        }
+        stats.finalize();
+
+        let span_types: Vec<_> = samples
+            .iter()
+            .map(|sample| match sample {
+                Sample::Span(span) => span.span_type.clone(),
+                _ => None,
+            })
+            .collect();
+        let expected_type = Some("custom.comprehensive.internal".to_owned());
+        let expected_first = if use_v2 { expected_type.clone() } else { None };
+        assert_eq!(
+            span_types,
+            vec![expected_first.clone(), expected_type.clone(), None]
+        );
+
+        // The matched span definition drives the required attribute checks and the signal name
+        let Sample::Span(span) = &samples[1] else {
+            panic!("Expected a span sample");
+        };
+        let advice = &span
+            .live_check_result
+            .as_ref()
+            .expect("live check result")
+            .all_advice;
+        assert!(!advice.is_empty());
+        for finding in advice {
+            assert_eq!(finding.id, "missing_recommended_attribute");
+            assert_eq!(finding.signal_type, Some("span".to_owned()));
+            assert_eq!(finding.signal_name, expected_type);
+        }
+
+        if let LiveCheckStatistics::Cumulative(cumulative_stats) = &stats {
+            let expected_count = if use_v2 { 2 } else { 1 };
+            assert_eq!(
+                cumulative_stats.seen_registry_spans["custom.comprehensive.internal"],
+                expected_count
+            );
+            assert_eq!(cumulative_stats.seen_non_registry_spans["unknown"], 1);
+        } else {
+            panic!("Expected Cumulative statistics");
+        }
+    }
+
     #[test]
     fn test_json_span_rego() {
         run_json_span_rego_test(false);
@@ -1326,7 +1480,8 @@ mod tests {
         let advisors: Vec<Box<dyn Advisor>> = vec![
             Box::new(DeprecatedAdvisor),
             Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
+            Box::new(TypeAdvisor::without_missing_attributes()),
+            Box::new(RequiredAttributeAdvisor),
             Box::new(EnumAdvisor),
         ];
 
@@ -1363,6 +1518,12 @@ mod tests {
                 cumulative_stats
                     .advice_type_counts
                     .get("recommended_attribute_not_present"),
+                None
+            );
+            assert_eq!(
+                cumulative_stats
+                    .advice_type_counts
+                    .get("missing_recommended_attribute"),
                 Some(&2)
             );
             assert_eq!(
@@ -1745,7 +1906,8 @@ mod tests {
         let advisors: Vec<Box<dyn Advisor>> = vec![
             Box::new(DeprecatedAdvisor),
             Box::new(StabilityAdvisor),
-            Box::new(TypeAdvisor),
+            Box::new(TypeAdvisor::without_missing_attributes()),
+            Box::new(RequiredAttributeAdvisor),
             Box::new(EnumAdvisor),
         ];
 
@@ -1775,7 +1937,7 @@ mod tests {
             // Check advisor advice types
             // Expected advice:
             // - missing_attribute: 1 (session.idd does not exist in registry)
-            // - required_attribute_not_present: 1 (session.id is required but not present)
+            // - missing_required_attribute: 1 (session.id is required but not present)
             // - not_stable: 2 (1 for session.start event, 1 for session.previous_id attribute)
             // - deprecated: 1 (session.start event is deprecated)
             // - missing_event: 1 (session.test event does not exist in registry)
@@ -1788,8 +1950,15 @@ mod tests {
                 cumulative_stats
                     .advice_type_counts
                     .get("required_attribute_not_present"),
+                None,
+                "Expected session.id to be reported once, by the required attribute advisor"
+            );
+            assert_eq!(
+                cumulative_stats
+                    .advice_type_counts
+                    .get("missing_required_attribute"),
                 Some(&1),
-                "Expected 1 required_attribute_not_present advice for session.id"
+                "Expected 1 missing_required_attribute advice for session.id"
             );
             assert_eq!(
                 cumulative_stats.advice_type_counts.get("not_stable"),
@@ -1893,7 +2062,7 @@ mod tests {
             resource: None,
         });
         let mut samples = vec![sample];
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor::new())];
         let mut live_checker = LiveChecker::new(Arc::new(registry), advisors);
 
         let mut stats =
@@ -1969,7 +2138,7 @@ mod tests {
             live_check_result: None,
             resource: None,
         });
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor::new())];
         let mut live_checker = LiveChecker::new(Arc::new(registry), advisors);
 
         let rego_advisor = RegoAdvisor::new(
@@ -2027,7 +2196,7 @@ mod tests {
                 resource: None,
             }),
         ];
-        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor)];
+        let advisors: Vec<Box<dyn Advisor>> = vec![Box::new(TypeAdvisor::new())];
         let mut live_checker = LiveChecker::new(Arc::new(registry), advisors);
 
         let mut stats =
@@ -288,6 +288,7 @@ mod tests {
             span_links: vec![],
             live_check_result: None,
             resource: None,
+            span_type: None,
         }
     }
 
@@ -233,6 +233,7 @@ impl LiveCheckRunner for SampleAttribute {
             )?;
             result.add_advice_list(advice_list);
         }
+        live_checker.suppress_baseline_findings(&mut result, stats);
         self.live_check_result = Some(result);
         self.update_stats(stats);
         Ok(())
@@ -89,6 +89,7 @@ impl LiveCheckRunner for SampleLog {
             parent_signal,
         )?;
 
+        live_checker.suppress_baseline_findings(&mut result, stats);
         self.live_check_result = Some(result);
         stats.inc_entity_count("log");
         stats.maybe_add_live_check_result(self.live_check_result.as_ref());
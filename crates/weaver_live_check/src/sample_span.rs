@@ -58,6 +58,9 @@ pub struct SampleSpan {
     /// Reference to the parent resource (not serialized)
     #[serde(skip)]
     pub resource: Option<Rc<SampleResource>>,
+    /// The type of the matching span definition in the registry, set by the live check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span_type: Option<String>,
 }
 
 impl Advisable for SampleSpan {
@@ -78,10 +81,29 @@ impl LiveCheckRunner for SampleSpan {
         parent_group: Option<Rc<VersionedSignal>>,
         parent_signal: &Sample,
     ) -> Result<(), Error> {
+        // Resolve the span definition, this determines the signal name of the findings
+        let semconv_span = match live_checker.find_span(self) {
+            Some((span_type, semconv_span)) => {
+                self.span_type = Some(span_type);
+                Some(semconv_span)
+            }
+            None => None,
+        };
+        let matched_signal;
+        let parent_signal = match parent_signal {
+            Sample::Span(parent_span) if parent_span.span_type != self.span_type => {
+                let mut parent_span = parent_span.clone();
+                parent_span.span_type.clone_from(&self.span_type);
+                matched_signal = Sample::Span(parent_span);
+                &matched_signal
+            }
+            _ => parent_signal,
+        };
+
         self.live_check_result =
-            Some(self.run_advisors(live_checker, stats, parent_group.clone(), parent_signal)?);
+            Some(self.run_advisors(live_checker, stats, semconv_span.clone(), parent_signal)?);
         self.attributes
-            .run_live_check(live_checker, stats, parent_group.clone(), parent_signal)?;
+            .run_live_check(live_checker, stats, semconv_span, parent_signal)?;
         self.span_events.run_live_check(
             live_checker,
             stats,
@@ -90,6 +112,7 @@ impl LiveCheckRunner for SampleSpan {
         )?;
         self.span_links
             .run_live_check(live_checker, stats, parent_group.clone(), parent_signal)?;
+        stats.add_span_to_coverage(self.span_type.clone(), &self.name);
         Ok(())
     }
 }
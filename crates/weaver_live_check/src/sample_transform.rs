@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of samples between two versions of a telemetry schema.
+
+use std::rc::Rc;
+
+use weaver_version::transform::Transformation;
+
+use crate::sample_attribute::SampleAttribute;
+use crate::sample_metric::{DataPoints, SampleExemplar, SampleMetric};
+use crate::sample_resource::SampleResource;
+use crate::Sample;
+
+impl Sample {
+    /// Renames the attributes and the metric names of the sample with the given
+    /// transformation.
+    pub fn transform(&mut self, transformation: &Transformation) {
+        match self {
+            Sample::Attribute(attribute) => {
+                attribute.name = transformation.attribute_name(&attribute.name);
+            }
+            Sample::Span(span) => {
+                rename_attributes(&mut span.attributes, |name| {
+                    transformation.span_attribute_name(name)
+                });
+                for event in &mut span.span_events {
+                    rename_attributes(&mut event.attributes, |name| {
+                        transformation.span_attribute_name(name)
+                    });
+                }
+                for link in &mut span.span_links {
+                    rename_attributes(&mut link.attributes, |name| {
+                        transformation.span_attribute_name(name)
+                    });
+                }
+                transform_resource(&mut span.resource, transformation);
+            }
+            Sample::SpanEvent(event) => {
+                rename_attributes(&mut event.attributes, |name| {
+                    transformation.span_attribute_name(name)
+                });
+            }
+            Sample::SpanLink(link) => {
+                rename_attributes(&mut link.attributes, |name| {
+                    transformation.span_attribute_name(name)
+                });
+            }
+            Sample::Resource(resource) => {
+                rename_attributes(&mut resource.attributes, |name| {
+                    transformation.resource_attribute_name(name)
+                });
+            }
+            Sample::Metric(metric) => transform_metric(metric, transformation),
+            Sample::Log(log) => {
+                rename_attributes(&mut log.attributes, |name| {
+                    transformation.log_attribute_name(name)
+                });
+                transform_resource(&mut log.resource, transformation);
+            }
+        }
+    }
+}
+
+fn transform_metric(metric: &mut SampleMetric, transformation: &Transformation) {
+    let metric_name = metric.name.clone();
+    let rename = |name: &str| transformation.metric_attribute_name(&metric_name, name);
+    let rename_exemplars = |exemplars: &mut Vec<SampleExemplar>| {
+        for exemplar in exemplars {
+            rename_attributes(&mut exemplar.filtered_attributes, rename);
+        }
+    };
+
+    match &mut metric.data_points {
+        Some(DataPoints::Number(points)) => {
+            for point in points {
+                rename_attributes(&mut point.attributes, rename);
+                rename_exemplars(&mut point.exemplars);
+            }
+        }
+        Some(DataPoints::Histogram(points)) => {
+            for point in points {
+                rename_attributes(&mut point.attributes, rename);
+                rename_exemplars(&mut point.exemplars);
+            }
+        }
+        Some(DataPoints::ExponentialHistogram(points)) => {
+            for point in points {
+                rename_attributes(&mut point.attributes, rename);
+                rename_exemplars(&mut point.exemplars);
+            }
+        }
+        None => {}
+    }
+    metric.name = transformation.metric_name(&metric_name);
+    transform_resource(&mut metric.resource, transformation);
+}
+
+fn transform_resource(resource: &mut Option<Rc<SampleResource>>, transformation: &Transformation) {
+    if let Some(resource) = resource {
+        rename_attributes(&mut Rc::make_mut(resource).attributes, |name| {
+            transformation.resource_attribute_name(name)
+        });
+    }
+}
+
+fn rename_attributes(attributes: &mut [SampleAttribute], rename: impl Fn(&str) -> String) {
+    for attribute in attributes {
+        attribute.name = rename(&attribute.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weaver_version::{Version, Versions};
+
+    fn transformation(from: &str, to: &str) -> Transformation {
+        let versions: Versions = serde_yaml::from_str(
+            r#"
+1.20.0:
+1.26.0:
+  all:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            http.method: http.request.method
+  metrics:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            state: db.client.connection.state
+          apply_to_metrics:
+            - db.client.connections.usage
+      - rename_metrics:
+          db.client.connections.usage: db.client.connection.count
+"#,
+        )
+        .unwrap();
+        versions
+            .transformation(
+                &from.parse::<Version>().unwrap(),
+                &to.parse::<Version>().unwrap(),
+            )
+            .unwrap()
+    }
+
+    fn samples() -> Vec<Sample> {
+        serde_json::from_str(
+            r#"[
+                {"span": {"name": "GET", "kind": "client", "attributes": [{"name": "http.method", "value": "GET"}],
+                    "span_events": [{"name": "event", "attributes": [{"name": "http.method", "value": "GET"}]}]}},
+                {"metric": {"name": "db.client.connections.usage", "instrument": "updowncounter", "unit": "{connection}",
+                    "data_points": [{"attributes": [{"name": "state", "value": "idle"}], "value": 1}]}},
+                {"log": {"event_name": "", "attributes": [{"name": "state", "value": "idle"}]}}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_upgrade_and_downgrade() {
+        let mut samples = samples();
+        let upgrade = transformation("1.20.0", "1.26.0");
+        for sample in &mut samples {
+            sample.transform(&upgrade);
+        }
+
+        let Sample::Span(span) = &samples[0] else {
+            panic!("Expected a span");
+        };
+        assert_eq!(span.attributes[0].name, "http.request.method");
+        assert_eq!(
+            span.span_events[0].attributes[0].name,
+            "http.request.method"
+        );
+        let Sample::Metric(metric) = &samples[1] else {
+            panic!("Expected a metric");
+        };
+        assert_eq!(metric.name, "db.client.connection.count");
+        let Some(DataPoints::Number(points)) = &metric.data_points else {
+            panic!("Expected number data points");
+        };
+        assert_eq!(points[0].attributes[0].name, "db.client.connection.state");
+        let Sample::Log(log) = &samples[2] else {
+            panic!("Expected a log");
+        };
+        assert_eq!(log.attributes[0].name, "state");
+
+        let downgrade = transformation("1.26.0", "1.20.0");
+        for sample in &mut samples {
+            sample.transform(&downgrade);
+        }
+        assert_eq!(samples, self::samples());
+    }
+}
@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolves a sample span to the span definition (span type) of the registry.
+//!
+//! Span names are not identifiers, they are built from a pattern described in the
+//! `name.note` of the span definition (e.g. `{http.request.method} {http.route}`).
+//! A sample span is therefore matched in the following order:
+//!
+//! 1. An explicit hint: the span type given in the `weaver.span.type` attribute of the span
+//!    or of its resource.
+//! 2. The best candidate among the span definitions with the same span kind. A candidate
+//!    qualifies when all of its identifying (required) attributes are present on the span,
+//!    or when the span name matches its name pattern. Candidates identified by their
+//!    attributes are preferred over name matches, remaining ties are broken by the number
+//!    of attributes of the definition present on the span.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use regex::Regex;
+use weaver_semconv::{
+    attribute::{AttributeType, BasicRequirementLevelSpec, RequirementLevel},
+    group::SpanKindSpec,
+};
+
+use crate::{sample_attribute::SampleAttribute, sample_span::SampleSpan, VersionedSignal};
+
+/// The span or resource attribute that can be set to explicitly give the span type
+pub const SPAN_TYPE_HINT_ATTRIBUTE: &str = "weaver.span.type";
+
+/// A span definition of the registry prepared for matching
+#[derive(Debug)]
+struct SpanCandidate {
+    /// The span type
+    r#type: String,
+    /// The span kind, if specified by the definition
+    kind: Option<SpanKindSpec>,
+    /// The name pattern derived from the `name.note` of the definition
+    name_pattern: Option<Regex>,
+    /// The required attributes identifying the span
+    identifying_attributes: Vec<String>,
+    /// All the (non-template) attributes of the definition
+    attributes: Vec<String>,
+    /// The registry signal
+    signal: Rc<VersionedSignal>,
+}
+
+/// Matches sample spans to the span definitions of the registry
+#[derive(Debug, Default)]
+pub(crate) struct SpanMatcher {
+    /// Sorted by span type for deterministic matching
+    candidates: Vec<SpanCandidate>,
+}
+
+/// The quality of a match (identified, name matches, matched attributes), compared lexicographically
+type MatchScore = (bool, bool, usize);
+
+impl SpanMatcher {
+    /// Add a span definition to the matcher
+    pub(crate) fn add_span(
+        &mut self,
+        r#type: String,
+        kind: Option<SpanKindSpec>,
+        name_note: Option<&str>,
+        signal: Rc<VersionedSignal>,
+    ) {
+        let mut identifying_attributes = Vec::new();
+        let mut attributes = Vec::new();
+        for attribute in signal.attributes() {
+            if matches!(attribute.r#type, AttributeType::Template(_)) {
+                continue;
+            }
+            if matches!(
+                attribute.requirement_level,
+                RequirementLevel::Basic(BasicRequirementLevelSpec::Required)
+            ) {
+                identifying_attributes.push(attribute.name.to_owned());
+            }
+            attributes.push(attribute.name.to_owned());
+        }
+        self.candidates.push(SpanCandidate {
+            r#type,
+            kind,
+            name_pattern: name_note.and_then(name_pattern),
+            identifying_attributes,
+            attributes,
+            signal,
+        });
+    }
+
+    /// Sort the candidates, must be called once all the span definitions are added
+    pub(crate) fn finalize(&mut self) {
+        self.candidates.sort_by(|a, b| a.r#type.cmp(&b.r#type));
+    }
+
+    /// Find a span definition by type
+    pub(crate) fn find_by_type(&self, span_type: &str) -> Option<(String, Rc<VersionedSignal>)> {
+        self.candidates
+            .iter()
+            .find(|candidate| candidate.r#type == span_type)
+            .map(|candidate| (candidate.r#type.clone(), Rc::clone(&candidate.signal)))
+    }
+
+    /// Find the span definition for a sample span, returns the span type and the signal
+    pub(crate) fn find_span(&self, span: &SampleSpan) -> Option<(String, Rc<VersionedSignal>)> {
+        if let Some(span_type) = span_type_hint(span) {
+            if let Some(found) = self.find_by_type(&span_type) {
+                return Some(found);
+            }
+        }
+
+        let present: HashSet<&str> = span
+            .attributes
+            .iter()
+            .map(|attribute| attribute.name.as_str())
+            .collect();
+
+        let mut best: Option<(MatchScore, &SpanCandidate)> = None;
+        for candidate in &self.candidates {
+            if candidate
+                .kind
+                .as_ref()
+                .is_some_and(|kind| *kind != span.kind)
+            {
+                continue;
+            }
+            let name_matches = candidate
+                .name_pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.is_match(&span.name));
+            let identified = !candidate.identifying_attributes.is_empty()
+                && candidate
+                    .identifying_attributes
+                    .iter()
+                    .all(|name| present.contains(name.as_str()));
+            if !name_matches && !identified {
+                continue;
+            }
+            let matched_attributes = candidate
+                .attributes
+                .iter()
+                .filter(|name| present.contains(name.as_str()))
+                .count();
+            let score = (identified, name_matches, matched_attributes);
+            let is_better = match &best {
+                Some((best_score, _)) => score > *best_score,
+                None => true,
+            };
+            if is_better {
+                best = Some((score, candidate));
+            }
+        }
+        best.map(|(_, candidate)| (candidate.r#type.clone(), Rc::clone(&candidate.signal)))
+    }
+}
+
+/// Returns the span type hint from the span attributes, or from its resource attributes
+fn span_type_hint(span: &SampleSpan) -> Option<String> {
+    let find = |attributes: &[SampleAttribute]| {
+        attributes
+            .iter()
+            .find(|attribute| attribute.name == SPAN_TYPE_HINT_ATTRIBUTE)
+            .and_then(|attribute| attribute.value.as_ref())
+            .and_then(|value| value.as_str())
+            .map(ToOwned::to_owned)
+    };
+    find(&span.attributes).or_else(|| {
+        span.resource
+            .as_ref()
+            .and_then(|resource| find(&resource.attributes))
+    })
+}
+
+/// Builds a regex from a span name note.
+///
+/// Placeholders in curly braces (e.g. `{http.route}`) match any non-empty text, everything
+/// else must match literally. Multi-line notes are descriptions rather than patterns and
+/// are ignored.
+fn name_pattern(note: &str) -> Option<Regex> {
+    let note = note.trim();
+    if note.is_empty() || note.contains('\n') {
+        return None;
+    }
+    let mut pattern = String::from("^");
+    let mut rest = note;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        pattern.push_str(&regex::escape(&rest[..start]));
+        pattern.push_str(".+");
+        rest = &rest[start + end + 1..];
+    }
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+    Regex::new(&pattern).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use weaver_forge::v2::{
+        attribute::Attribute,
+        span::{Span, SpanAttribute},
+    };
+    use weaver_semconv::{
+        attribute::PrimitiveOrArrayTypeSpec,
+        stability::Stability,
+        v2::{span::SpanName, CommonFields},
+    };
+
+    fn common_fields() -> CommonFields {
+        CommonFields {
+            brief: "".to_owned(),
+            note: "".to_owned(),
+            stability: Stability::Stable,
+            deprecated: None,
+            annotations: BTreeMap::new(),
+        }
+    }
+
+    fn span_definition(
+        r#type: &str,
+        kind: SpanKindSpec,
+        note: &str,
+        required: &[&str],
+    ) -> VersionedSignal {
+        VersionedSignal::Span(Span {
+            r#type: r#type.to_owned().into(),
+            kind,
+            name: SpanName {
+                note: note.to_owned(),
+            },
+            attributes: required
+                .iter()
+                .map(|key| SpanAttribute {
+                    base: Attribute {
+                        key: (*key).to_owned(),
+                        r#type: AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String),
+                        examples: None,
+                        common: common_fields(),
+                    },
+                    requirement_level: RequirementLevel::Basic(BasicRequirementLevelSpec::Required),
+                    sampling_relevant: None,
+                })
+                .collect(),
+            entity_associations: vec![],
+            common: common_fields(),
+        })
+    }
+
+    fn matcher() -> SpanMatcher {
+        let mut matcher = SpanMatcher::default();
+        for (r#type, kind, note, required) in [
+            (
+                "http.client",
+                SpanKindSpec::Client,
+                "{http.request.method}",
+                &["http.request.method", "server.address"][..],
+            ),
+            (
+                "http.server",
+                SpanKindSpec::Server,
+                "{http.request.method} {http.route}",
+                &["http.request.method"][..],
+            ),
+            (
+                "db.client",
+                SpanKindSpec::Client,
+                "{db.operation.name} {db.collection.name}",
+                &["db.system.name"][..],
+            ),
+        ] {
+            let signal = span_definition(r#type, kind.clone(), note, required);
+            matcher.add_span(r#type.to_owned(), Some(kind), Some(note), Rc::new(signal));
+        }
+        matcher.finalize();
+        matcher
+    }
+
+    fn sample_span(name: &str, kind: SpanKindSpec, attributes: &[(&str, &str)]) -> SampleSpan {
+        SampleSpan {
+            name: name.to_owned(),
+            kind,
+            status: None,
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| SampleAttribute {
+                    name: (*name).to_owned(),
+                    value: Some((*value).into()),
+                    r#type: None,
+                    live_check_result: None,
+                })
+                .collect(),
+            span_events: vec![],
+            span_links: vec![],
+            live_check_result: None,
+            resource: None,
+            span_type: None,
+        }
+    }
+
+    fn matched_type(matcher: &SpanMatcher, span: &SampleSpan) -> Option<String> {
+        matcher.find_span(span).map(|(r#type, _)| r#type)
+    }
+
+    #[test]
+    fn test_name_pattern() {
+        let pattern = name_pattern("{http.request.method} {http.route}").expect("pattern");
+        assert!(pattern.is_match("GET /users/{id}"));
+        assert!(!pattern.is_match("GET"));
+        let pattern = name_pattern("process (1.2)").expect("pattern");
+        assert!(pattern.is_match("process (1.2)"));
+        assert!(!pattern.is_match("process 1x2"));
+        assert!(name_pattern("first line\nsecond line").is_none());
+        assert!(name_pattern("").is_none());
+    }
+
+    #[test]
+    fn test_match_by_kind_and_attributes() {
+        let matcher = matcher();
+        assert_eq!(matcher.candidates.len(), 3);
+
+        // Same name pattern for both client definitions, identifying attributes decide.
+        let span = sample_span(
+            "SELECT",
+            SpanKindSpec::Client,
+            &[("db.system.name", "postgresql")],
+        );
+        assert_eq!(matched_type(&matcher, &span), Some("db.client".to_owned()));
+
+        let span = sample_span(
+            "GET",
+            SpanKindSpec::Client,
+            &[("http.request.method", "GET"), ("server.address", "foo")],
+        );
+        assert_eq!(
+            matched_type(&matcher, &span),
+            Some("http.client".to_owned())
+        );
+
+        // Kind must match
+        let span = sample_span(
+            "GET /",
+            SpanKindSpec::Internal,
+            &[("http.request.method", "GET")],
+        );
+        assert_eq!(matched_type(&matcher, &span), None);
+
+        // Name pattern is enough
+        let span = sample_span("GET /users", SpanKindSpec::Server, &[]);
+        assert_eq!(
+            matched_type(&matcher, &span),
+            Some("http.server".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_match_by_hint() {
+        let matcher = matcher();
+        let span = sample_span(
+            "anything",
+            SpanKindSpec::Internal,
+            &[(SPAN_TYPE_HINT_ATTRIBUTE, "db.client")],
+        );
+        assert_eq!(matched_type(&matcher, &span), Some("db.client".to_owned()));
+
+        // Unknown hint falls back to regular matching
+        let span = sample_span(
+            "anything",
+            SpanKindSpec::Internal,
+            &[(SPAN_TYPE_HINT_ATTRIBUTE, "unknown")],
+        );
+        assert_eq!(matched_type(&matcher, &span), None);
+    }
+}
@@ -12,8 +12,17 @@ use std::collections::HashMap;
 use crate::{FindingLevel, LiveCheckResult, PolicyFinding, VersionedRegistry};
 use weaver_semconv::group::GroupType;
 
+/// The items of the registry tracked for coverage, with a zero count
+struct RegistryItems {
+    attributes: HashMap<String, usize>,
+    metrics: HashMap<String, usize>,
+    events: HashMap<String, usize>,
+    spans: HashMap<String, usize>,
+}
+
 /// Cumulative statistics that track all telemetry data
 #[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CumulativeStatistics {
     /// The total number of sample entities
     pub(crate) total_entities: usize,
@@ -22,8 +31,10 @@ pub struct CumulativeStatistics {
     /// The total number of advisories
     pub(crate) total_advisories: usize,
     /// The number of each advice level
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, usize>))]
     pub(crate) advice_level_counts: HashMap<FindingLevel, usize>,
     /// The number of entities with each highest advice level
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, usize>))]
     pub(crate) highest_advice_level_counts: HashMap<FindingLevel, usize>,
     /// The number of entities with no advice
     pub(crate) no_advice_count: usize,
@@ -31,6 +42,13 @@ pub struct CumulativeStatistics {
     pub(crate) advice_type_counts: HashMap<String, usize>,
     /// The number of entities with each advice message
     pub(crate) advice_message_counts: HashMap<String, usize>,
+    /// The total number of advisories suppressed by the baseline
+    pub(crate) total_suppressed_advisories: usize,
+    /// The number of each advice level suppressed by the baseline
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, usize>))]
+    pub(crate) suppressed_advice_level_counts: HashMap<FindingLevel, usize>,
+    /// The number of each advice type suppressed by the baseline
+    pub(crate) suppressed_advice_type_counts: HashMap<String, usize>,
     /// The number of each attribute seen from the registry
     pub(crate) seen_registry_attributes: HashMap<String, usize>,
     /// The number of each non-registry attribute seen
@@ -43,7 +61,11 @@ pub struct CumulativeStatistics {
     pub(crate) seen_registry_events: HashMap<String, usize>,
     /// The number of each non-registry event seen
     pub(crate) seen_non_registry_events: HashMap<String, usize>,
-    /// Fraction of the registry covered by the attributes, metrics, and events
+    /// The number of each span (by span type) seen from the registry
+    pub(crate) seen_registry_spans: HashMap<String, usize>,
+    /// The number of each non-registry span (by span name) seen
+    pub(crate) seen_non_registry_spans: HashMap<String, usize>,
+    /// Fraction of the registry covered by the attributes, metrics, events, and spans
     pub(crate) registry_coverage: f32,
 }
 
@@ -51,7 +73,12 @@ impl CumulativeStatistics {
     /// Create a new CumulativeStatistics initialized with registry structure
     #[must_use]
     pub fn new(registry: &VersionedRegistry) -> Self {
-        let (seen_attributes, seen_metrics, seen_events) = Self::extract_registry_items(registry);
+        let RegistryItems {
+            attributes: seen_attributes,
+            metrics: seen_metrics,
+            events: seen_events,
+            spans: seen_spans,
+        } = Self::extract_registry_items(registry);
 
         CumulativeStatistics {
             total_entities: 0,
@@ -62,27 +89,27 @@ impl CumulativeStatistics {
             no_advice_count: 0,
             advice_type_counts: HashMap::new(),
             advice_message_counts: HashMap::new(),
+            total_suppressed_advisories: 0,
+            suppressed_advice_level_counts: HashMap::new(),
+            suppressed_advice_type_counts: HashMap::new(),
             seen_registry_attributes: seen_attributes,
             seen_non_registry_attributes: HashMap::new(),
             seen_registry_metrics: seen_metrics,
             seen_non_registry_metrics: HashMap::new(),
             seen_registry_events: seen_events,
             seen_non_registry_events: HashMap::new(),
+            seen_registry_spans: seen_spans,
+            seen_non_registry_spans: HashMap::new(),
             registry_coverage: 0.0,
         }
     }
 
     /// Extract registry items for tracking
-    fn extract_registry_items(
-        registry: &VersionedRegistry,
-    ) -> (
-        HashMap<String, usize>,
-        HashMap<String, usize>,
-        HashMap<String, usize>,
-    ) {
+    fn extract_registry_items(registry: &VersionedRegistry) -> RegistryItems {
         let mut seen_attributes = HashMap::new();
         let mut seen_metrics = HashMap::new();
         let mut seen_events = HashMap::new();
+        let mut seen_spans = HashMap::new();
 
         match registry {
             VersionedRegistry::V1(reg) => {
@@ -102,6 +129,9 @@ impl CumulativeStatistics {
                             let _ = seen_events.insert(event_name.clone(), 0);
                         }
                     }
+                    if group.r#type == GroupType::Span && group.deprecated.is_none() {
+                        let _ = seen_spans.insert(group.id.clone(), 0);
+                    }
                 }
             }
             VersionedRegistry::V2(reg) => {
@@ -120,10 +150,20 @@ impl CumulativeStatistics {
                         let _ = seen_events.insert(event.name.to_string(), 0);
                     }
                 }
+                for span in &reg.registry.spans {
+                    if span.common.deprecated.is_none() {
+                        let _ = seen_spans.insert(span.r#type.to_string(), 0);
+                    }
+                }
             }
         }
 
-        (seen_attributes, seen_metrics, seen_events)
+        RegistryItems {
+            attributes: seen_attributes,
+            metrics: seen_metrics,
+            events: seen_events,
+            spans: seen_spans,
+        }
     }
 
     /// Increment the total number of entities by type
@@ -152,6 +192,19 @@ impl CumulativeStatistics {
         self.total_advisories += 1;
     }
 
+    /// Add an advice suppressed by the baseline to the statistics
+    pub(crate) fn add_suppressed_advice(&mut self, advice: &PolicyFinding) {
+        *self
+            .suppressed_advice_level_counts
+            .entry(advice.level.clone())
+            .or_insert(0) += 1;
+        *self
+            .suppressed_advice_type_counts
+            .entry(advice.id.clone())
+            .or_insert(0) += 1;
+        self.total_suppressed_advisories += 1;
+    }
+
     /// Add a highest advice level to the statistics
     pub(crate) fn add_highest_advice_level(&mut self, advice: &FindingLevel) {
         *self
@@ -211,16 +264,45 @@ impl CumulativeStatistics {
         }
     }
 
+    /// Add span to coverage, by span type if it matched a registry span or by name otherwise
+    pub(crate) fn add_span_to_coverage(
+        &mut self,
+        seen_span_type: Option<String>,
+        seen_span_name: &str,
+    ) {
+        if let Some(count) = seen_span_type
+            .as_ref()
+            .and_then(|span_type| self.seen_registry_spans.get_mut(span_type))
+        {
+            // This is a registry span
+            *count += 1;
+        } else {
+            // This is a non-registry span
+            *self
+                .seen_non_registry_spans
+                .entry(seen_span_type.unwrap_or_else(|| seen_span_name.to_owned()))
+                .or_insert(0) += 1;
+        }
+    }
+
     /// Are there any violations in the statistics?
     pub(crate) fn has_violations(&self) -> bool {
         self.highest_advice_level_counts
             .contains_key(&FindingLevel::Violation)
     }
 
+    /// Are there any findings of the given level or higher in the statistics?
+    pub(crate) fn has_findings(&self, level: &FindingLevel) -> bool {
+        self.highest_advice_level_counts
+            .keys()
+            .any(|highest_level| highest_level >= level)
+    }
+
     /// Finalize the statistics by calculating registry coverage
     pub(crate) fn finalize(&mut self) {
         // Calculate the registry coverage
-        // (non-zero attributes + non-zero metrics + non-zero events) / (total attributes + total metrics + total events)
+        // (non-zero attributes + non-zero metrics + non-zero events + non-zero spans) /
+        // (total attributes + total metrics + total events + total spans)
         let non_zero_attributes = self
             .seen_registry_attributes
             .values()
@@ -242,13 +324,23 @@ impl CumulativeStatistics {
             .count();
         let total_registry_events = self.seen_registry_events.len();
 
-        let total_registry_items =
-            total_registry_attributes + total_registry_metrics + total_registry_events;
+        let non_zero_spans = self
+            .seen_registry_spans
+            .values()
+            .filter(|&&count| count > 0)
+            .count();
+        let total_registry_spans = self.seen_registry_spans.len();
+
+        let total_registry_items = total_registry_attributes
+            + total_registry_metrics
+            + total_registry_events
+            + total_registry_spans;
 
         if total_registry_items > 0 {
-            self.registry_coverage = ((non_zero_attributes + non_zero_metrics + non_zero_events)
-                as f32)
-                / (total_registry_items as f32);
+            self.registry_coverage =
+                ((non_zero_attributes + non_zero_metrics + non_zero_events + non_zero_spans)
+                    as f32)
+                    / (total_registry_items as f32);
         } else {
             self.registry_coverage = 0.0;
         }
@@ -257,10 +349,12 @@ impl CumulativeStatistics {
 
 /// Disabled statistics that perform no accumulation (for long-running sessions)
 #[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct DisabledStatistics;
 
 /// The statistics for a live check report
 #[derive(Debug, Clone, PartialEq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
 pub enum LiveCheckStatistics {
@@ -295,6 +389,13 @@ impl LiveCheckStatistics {
         }
     }
 
+    /// Add an advice suppressed by the baseline to the stats
+    pub fn add_suppressed_advice(&mut self, advice: &PolicyFinding) {
+        if let Self::Cumulative(stats) = self {
+            stats.add_suppressed_advice(advice);
+        }
+    }
+
     /// Increment the total number of entities by type
     pub fn inc_entity_count(&mut self, entity_type: &str) {
         if let Self::Cumulative(stats) = self {
@@ -323,6 +424,13 @@ impl LiveCheckStatistics {
         }
     }
 
+    /// Add span to coverage, by span type if it matched a registry span or by name otherwise
+    pub fn add_span_to_coverage(&mut self, seen_span_type: Option<String>, seen_span_name: &str) {
+        if let Self::Cumulative(stats) = self {
+            stats.add_span_to_coverage(seen_span_type, seen_span_name);
+        }
+    }
+
     /// Are there any violations in the statistics?
     #[must_use]
     pub fn has_violations(&self) -> bool {
@@ -332,6 +440,25 @@ impl LiveCheckStatistics {
         }
     }
 
+    /// Are there any findings of the given level or higher in the statistics?
+    #[must_use]
+    pub fn has_findings(&self, level: &FindingLevel) -> bool {
+        match self {
+            Self::Cumulative(stats) => stats.has_findings(level),
+            Self::Disabled(_) => false,
+        }
+    }
+
+    /// The fraction of the registry covered by the samples, once finalized
+    /// (`None` when statistics are disabled).
+    #[must_use]
+    pub fn registry_coverage(&self) -> Option<f32> {
+        match self {
+            Self::Cumulative(stats) => Some(stats.registry_coverage),
+            Self::Disabled(_) => None,
+        }
+    }
+
     /// Finalize the statistics
     pub fn finalize(&mut self) {
         if let Self::Cumulative(stats) = self {
@@ -396,5 +523,34 @@ mod tests {
         // Verify has_violations works for both
         assert!(!disabled_stats.has_violations()); // Always false for disabled
         assert!(!normal_stats.has_violations()); // No violations added yet
+
+        assert!(!disabled_stats.has_findings(&FindingLevel::Information));
+        assert!(!normal_stats.has_findings(&FindingLevel::Information));
+        assert_eq!(disabled_stats.registry_coverage(), None);
+        assert_eq!(normal_stats.registry_coverage(), Some(0.0));
+    }
+
+    #[test]
+    fn test_has_findings() {
+        let registry = VersionedRegistry::V1(Box::new(ResolvedRegistry {
+            groups: vec![],
+            registry_url: String::new(),
+        }));
+        let mut stats = LiveCheckStatistics::Cumulative(CumulativeStatistics::new(&registry));
+        let mut result = LiveCheckResult::new();
+        result.add_advice(PolicyFinding {
+            id: "not_stable".to_owned(),
+            context: None,
+            message: "Not stable".to_owned(),
+            level: FindingLevel::Improvement,
+            signal_type: None,
+            signal_name: None,
+        });
+        stats.maybe_add_live_check_result(Some(&result));
+
+        assert!(stats.has_findings(&FindingLevel::Information));
+        assert!(stats.has_findings(&FindingLevel::Improvement));
+        assert!(!stats.has_findings(&FindingLevel::Violation));
+        assert!(!stats.has_violations());
     }
 }
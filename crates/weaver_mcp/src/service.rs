@@ -17,13 +17,14 @@ use serde::Deserialize;
 use serde_json::json;
 use weaver_forge::v2::registry::ForgeResolvedRegistry;
 use weaver_live_check::advice::{
-    Advisor, DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, StabilityAdvisor, TypeAdvisor,
+    Advisor, DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, RequiredAttributeAdvisor,
+    StabilityAdvisor, TypeAdvisor,
 };
 use weaver_live_check::live_checker::LiveChecker;
 use weaver_live_check::{
     DisabledStatistics, LiveCheckRunner, LiveCheckStatistics, Sample, VersionedRegistry,
 };
-use weaver_search::{SearchContext, SearchType};
+use weaver_search::{SearchContext, SearchResults, SearchType};
 use weaver_semconv::stability::Stability;
 
 use crate::McpConfig;
@@ -95,7 +96,8 @@ fn default_advisors() -> Vec<Box<dyn Advisor>> {
     vec![
         Box::new(DeprecatedAdvisor),
         Box::new(StabilityAdvisor),
-        Box::new(TypeAdvisor),
+        Box::new(TypeAdvisor::without_missing_attributes()),
+        Box::new(RequiredAttributeAdvisor),
         Box::new(EnumAdvisor),
     ]
 }
@@ -240,7 +242,9 @@ impl WeaverMcpService {
         name = "search",
         description = "Search OpenTelemetry and custom semantic conventions. Supports searching by keywords \
                        across attributes, metrics, spans, events, and entities. Query terms are AND-matched \
-                       (all must appear). Returns matching definitions with relevance scores. \
+                       (all must appear), tolerate typos and also find the replacements of renamed \
+                       definitions. Returns matching definitions with relevance scores and the number \
+                       of matches per type and stability. \
                        Use short queries like 'http.request', 'db system', or 'server duration'."
     )]
     fn search(&self, Parameters(params): Parameters<SearchParams>) -> String {
@@ -248,7 +252,11 @@ impl WeaverMcpService {
         let stability = params.stability.map(Stability::from);
         let limit = params.limit.min(100);
 
-        let (results, total) = self.search_context.search(
+        let SearchResults {
+            results,
+            total,
+            facets,
+        } = self.search_context.search_with_facets(
             params.query.as_deref(),
             search_type,
             stability,
@@ -260,6 +268,7 @@ impl WeaverMcpService {
             "results": results,
             "count": results.len(),
             "total": total,
+            "facets": facets,
         });
 
         serde_json::to_string_pretty(&result_json).unwrap_or_else(|e| format!("Error: {e}"))
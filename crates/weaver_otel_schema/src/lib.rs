@@ -4,7 +4,7 @@
 //! Please refer to the [OpenTelemetry Schema OTEP](https://github.com/open-telemetry/oteps/blob/main/text/0152-telemetry-schemas.md)
 //! for more information.
 
-use crate::Error::{InvalidTelemetrySchema, TelemetrySchemaNotFound};
+use crate::Error::{InvalidTelemetrySchema, TelemetrySchemaNotFound, TelemetrySchemaWrite};
 use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
@@ -28,8 +28,20 @@ pub enum Error {
         /// The error that occurred.
         error: String,
     },
+
+    /// The OTel Telemetry schema could not be written.
+    #[error("Failed to write the OTel telemetry schema (path: {path:?}). {error}")]
+    TelemetrySchemaWrite {
+        /// The path to the telemetry schema file.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
 }
 
+/// The version of the file structure produced by this crate.
+pub const FILE_FORMAT: &str = "1.1.0";
+
 impl From<Error> for DiagnosticMessages {
     fn from(error: Error) -> Self {
         DiagnosticMessages::new(vec![DiagnosticMessage::new(error)])
@@ -55,6 +67,16 @@ pub struct TelemetrySchema {
 }
 
 impl TelemetrySchema {
+    /// Creates an empty telemetry schema published at the given URL.
+    #[must_use]
+    pub fn new(schema_url: String) -> Self {
+        Self {
+            file_format: FILE_FORMAT.to_owned(),
+            schema_url,
+            versions: None,
+        }
+    }
+
     /// Attempts to load a telemetry schema from a file.
     pub fn try_from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
         let schema_path_buf = path.as_ref().to_path_buf();
@@ -78,11 +100,25 @@ impl TelemetrySchema {
 
         Ok(schema)
     }
+
+    /// Writes the telemetry schema to a file.
+    pub fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let write_error = |error: String| TelemetrySchemaWrite {
+            path: path.to_string_lossy().to_string(),
+            error,
+        };
+        let yaml = serde_yaml::to_string(self).map_err(|e| write_error(e.to_string()))?;
+        std::fs::write(path, yaml).map_err(|e| write_error(e.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::TelemetrySchema;
+    use std::collections::BTreeMap;
+    use weaver_version::all_changes::{AllChange, AllChanges, RenameAttributes};
+    use weaver_version::{Version, VersionSpec, Versions};
 
     #[test]
     fn test_try_from_file() {
@@ -90,4 +126,45 @@ mod tests {
         assert_eq!(schema.file_format, "1.1.0");
         assert_eq!(schema.schema_url, "https://opentelemetry.io/schemas/1.27.0");
     }
+
+    #[test]
+    fn test_write_to_file() {
+        let mut schema = TelemetrySchema::try_from_file("tests/test_data/1.27.0.yaml").unwrap();
+        let version: Version = "1.28.0".parse().unwrap();
+        let spec = VersionSpec {
+            all: Some(AllChanges {
+                changes: vec![AllChange {
+                    rename_attributes: RenameAttributes {
+                        attribute_map: BTreeMap::from([(
+                            "old.attr".to_owned(),
+                            "new.attr".to_owned(),
+                        )]),
+                    },
+                }],
+            }),
+            ..Default::default()
+        };
+        let versions = schema.versions.get_or_insert_with(Versions::default);
+        let count = versions.len();
+        assert!(versions.insert(version, spec).is_none());
+        schema.schema_url = "https://opentelemetry.io/schemas/1.28.0".to_owned();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("1.28.0");
+        schema.write_to_file(&path).unwrap();
+
+        let schema = TelemetrySchema::try_from_file(&path).unwrap();
+        assert_eq!(schema.file_format, "1.1.0");
+        assert_eq!(schema.schema_url, "https://opentelemetry.io/schemas/1.28.0");
+        let versions = schema.versions.unwrap();
+        assert_eq!(versions.len(), count + 1);
+        let (latest, spec) = versions.versions_desc().remove(0);
+        assert_eq!(latest.to_string(), "1.28.0");
+        assert_eq!(
+            spec.all.as_ref().unwrap().changes[0]
+                .rename_attributes
+                .attribute_map["old.attr"],
+            "new.attr"
+        );
+    }
 }
@@ -63,6 +63,13 @@ impl Catalog {
             .map(|(attr, group_id)| (attr, group_id.as_str()))
     }
 
+    /// Returns the root attribute definitions with their name and group id.
+    pub fn root_attributes(&self) -> impl Iterator<Item = (&str, &Attribute, &str)> {
+        self.root_attributes
+            .iter()
+            .map(|(name, (attr, group_id))| (name.as_str(), attr, group_id.as_str()))
+    }
+
     /// Counts the number of attributes in the catalog.
     #[must_use]
     pub fn count_attributes(&self) -> usize {
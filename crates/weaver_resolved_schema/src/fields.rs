@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flattened view of the fields of a telemetry object used to detect field-level changes
+//! between two versions of a registry.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use weaver_semconv::attribute::AttributeType;
+use weaver_version::schema_changes::FieldChange;
+
+/// The fields of a telemetry object indexed by path, values are rendered as strings.
+#[derive(Debug, Default)]
+pub(crate) struct Fields(BTreeMap<String, String>);
+
+impl Fields {
+    /// Inserts a field, `None` and `null` values are ignored.
+    pub(crate) fn insert<T: Serialize + ?Sized>(&mut self, path: impl Into<String>, value: &T) {
+        let value = match serde_json::to_value(value) {
+            Ok(Value::Null) | Err(_) => return,
+            Ok(Value::String(value)) => value,
+            Ok(value) => value.to_string(),
+        };
+        let _ = self.0.insert(path.into(), value);
+    }
+
+    /// Inserts the type of an attribute, enum members are inserted as `members[<id>]`.
+    pub(crate) fn insert_attribute_type(&mut self, r#type: &AttributeType) {
+        match r#type {
+            AttributeType::Enum { members } => {
+                self.insert("type", "enum");
+                for member in members {
+                    self.insert(format!("members[{}]", member.id), &member.value);
+                }
+            }
+            other => self.insert("type", &other.to_string()),
+        }
+    }
+
+    /// Returns the changes from these (baseline) fields to the head fields.
+    pub(crate) fn diff(&self, head: &Fields) -> Vec<FieldChange> {
+        FieldChange::diff(&self.0, &head.0)
+    }
+}
@@ -7,20 +7,25 @@
 
 use crate::attribute::Attribute;
 use crate::catalog::Catalog;
+use crate::fields::Fields;
 use crate::instrumentation_library::InstrumentationLibrary;
 use crate::registry::{Group, Registry};
 use crate::resource::Resource;
 use serde::Serialize;
 use std::collections::HashMap;
+use weaver_semconv::attribute::AttributeType;
 use weaver_semconv::deprecated::Deprecated;
 use weaver_semconv::group::GroupType;
 use weaver_semconv::manifest::RegistryManifest;
+use weaver_semconv::stability::Stability;
+use weaver_version::compatibility::{classify_changes, ItemSnapshot};
 use weaver_version::schema_changes::{SchemaChanges, SchemaItemChange, SchemaItemType};
 use weaver_version::Versions;
 
 pub mod attribute;
 pub mod catalog;
 pub mod error;
+mod fields;
 pub mod instrumentation_library;
 pub mod lineage;
 pub mod metric;
@@ -322,6 +327,7 @@ impl ResolvedTelemetrySchema {
         let baseline_signals = baseline_schema.groups_by_name(GroupType::Metric);
         self.diff_signals(
             SchemaItemType::Metrics,
+            baseline_schema,
             &latest_signals,
             &baseline_signals,
             &mut changes,
@@ -330,6 +336,7 @@ impl ResolvedTelemetrySchema {
         let baseline_signals = baseline_schema.groups_by_name(GroupType::Event);
         self.diff_signals(
             SchemaItemType::Events,
+            baseline_schema,
             &latest_signals,
             &baseline_signals,
             &mut changes,
@@ -341,6 +348,7 @@ impl ResolvedTelemetrySchema {
         let baseline_signals = baseline_schema.groups_by_name(GroupType::Span);
         self.diff_signals(
             SchemaItemType::Spans,
+            baseline_schema,
             &latest_signals,
             &baseline_signals,
             &mut changes,
@@ -349,14 +357,69 @@ impl ResolvedTelemetrySchema {
         let baseline_signals = baseline_schema.groups_by_name(GroupType::Entity);
         self.diff_signals(
             SchemaItemType::Entities,
+            baseline_schema,
             &latest_signals,
             &baseline_signals,
             &mut changes,
         );
 
+        // Classify the changes as breaking or non-breaking
+        self.classify_changes(baseline_schema, &mut changes);
+
         changes
     }
 
+    fn classify_changes(
+        &self,
+        baseline_schema: &ResolvedTelemetrySchema,
+        changes: &mut SchemaChanges,
+    ) {
+        fn attribute_snapshots(schema: &ResolvedTelemetrySchema) -> HashMap<&str, ItemSnapshot> {
+            schema
+                .registry_attribute_map()
+                .into_iter()
+                .map(|(name, attr)| (name, attribute_snapshot(attr)))
+                .collect()
+        }
+        fn group_snapshots(
+            schema: &ResolvedTelemetrySchema,
+            group_type: GroupType,
+        ) -> HashMap<&str, ItemSnapshot> {
+            schema
+                .groups_by_name(group_type)
+                .into_iter()
+                .map(|(name, group)| (name, group_snapshot(group)))
+                .collect()
+        }
+
+        let mut findings = classify_changes(
+            SchemaItemType::RegistryAttributes,
+            changes
+                .changes_by_type(SchemaItemType::RegistryAttributes)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+            &attribute_snapshots(self),
+            &attribute_snapshots(baseline_schema),
+        );
+        for (item_type, group_type) in [
+            (SchemaItemType::Metrics, GroupType::Metric),
+            (SchemaItemType::Events, GroupType::Event),
+            (SchemaItemType::Spans, GroupType::Span),
+            (SchemaItemType::Entities, GroupType::Entity),
+        ] {
+            findings.extend(classify_changes(
+                item_type,
+                changes
+                    .changes_by_type(item_type)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+                &group_snapshots(self, group_type.clone()),
+                &group_snapshots(baseline_schema, group_type),
+            ));
+        }
+        changes.add_compatibility_findings(findings);
+    }
+
     fn diff_attributes(
         &self,
         baseline_schema: &ResolvedTelemetrySchema,
@@ -365,14 +428,22 @@ impl ResolvedTelemetrySchema {
         let latest_attributes = self.registry_attribute_map();
         let baseline_attributes = baseline_schema.registry_attribute_map();
 
-        // ToDo for future PR, process differences at the field level (not required for the schema update)
-
         // Collect all the information related to the attributes that have been
-        // deprecated in the latest schema.
+        // updated or deprecated in the latest schema.
         for (attr_name, attr) in latest_attributes.iter() {
             let baseline_attr = baseline_attributes.get(attr_name);
 
             if let Some(baseline_attr) = baseline_attr {
+                let fields = attribute_fields(baseline_attr).diff(&attribute_fields(attr));
+                if !fields.is_empty() {
+                    changes.add_change(
+                        SchemaItemType::RegistryAttributes,
+                        SchemaItemChange::Updated {
+                            name: attr.name.clone(),
+                            fields,
+                        },
+                    );
+                }
                 if let Some(deprecated) = attr.deprecated.as_ref() {
                     // is this a change from the baseline?
                     if let Some(baseline_deprecated) = baseline_attr.deprecated.as_ref() {
@@ -446,16 +517,28 @@ impl ResolvedTelemetrySchema {
     fn diff_signals(
         &self,
         schema_item_type: SchemaItemType,
+        baseline_schema: &ResolvedTelemetrySchema,
         latest_signals: &HashMap<&str, &Group>,
         baseline_signals: &HashMap<&str, &Group>,
         changes: &mut SchemaChanges,
     ) {
         // Collect all the information related to the signals that have been
-        // deprecated in the latest schema.
+        // updated or deprecated in the latest schema.
         for (signal_name, group) in latest_signals.iter() {
             let baseline_group = baseline_signals.get(signal_name);
 
             if let Some(baseline_group) = baseline_group {
+                let fields = group_fields(baseline_group, &baseline_schema.catalog)
+                    .diff(&group_fields(group, &self.catalog));
+                if !fields.is_empty() {
+                    changes.add_change(
+                        schema_item_type,
+                        SchemaItemChange::Updated {
+                            name: (*signal_name).to_owned(),
+                            fields,
+                        },
+                    );
+                }
                 if let Some(deprecated) = group.deprecated.as_ref() {
                     // is this a change from the baseline?
                     if let Some(baseline_deprecated) = baseline_group.deprecated.as_ref() {
@@ -525,6 +608,66 @@ impl ResolvedTelemetrySchema {
     }
 }
 
+/// Returns the fields of a registry attribute compared to detect updates.
+fn attribute_fields(attribute: &Attribute) -> Fields {
+    let mut fields = Fields::default();
+    fields.insert_attribute_type(&attribute.r#type);
+    fields.insert("brief", attribute.brief.trim());
+    fields.insert("requirement_level", &attribute.requirement_level);
+    fields.insert("examples", &attribute.examples);
+    fields.insert("stability", &attribute.stability);
+    fields
+}
+
+/// Returns the fields of a signal compared to detect updates.
+/// Attributes are indexed by name, their value is the requirement level.
+fn group_fields(group: &Group, catalog: &Catalog) -> Fields {
+    let mut fields = Fields::default();
+    fields.insert("brief", group.brief.trim());
+    fields.insert("stability", &group.stability);
+    fields.insert("unit", &group.unit);
+    fields.insert("instrument", &group.instrument);
+    fields.insert("span_kind", &group.span_kind);
+    for attribute in group
+        .attributes
+        .iter()
+        .filter_map(|attr_ref| catalog.attribute(attr_ref))
+    {
+        fields.insert(
+            format!("attributes[{}]", attribute.name),
+            &attribute.requirement_level,
+        );
+    }
+    fields
+}
+
+/// Returns the fields of an attribute inspected by the compatibility rules.
+fn attribute_snapshot(attribute: &Attribute) -> ItemSnapshot {
+    let (r#type, enum_members) = match &attribute.r#type {
+        AttributeType::Enum { members, .. } => (
+            "enum".to_owned(),
+            Some(members.iter().map(|m| m.value.to_string()).collect()),
+        ),
+        other => (other.to_string(), None),
+    };
+    ItemSnapshot {
+        stable: attribute.stability == Some(Stability::Stable),
+        r#type: Some(r#type),
+        enum_members,
+        ..Default::default()
+    }
+}
+
+/// Returns the fields of a signal inspected by the compatibility rules.
+fn group_snapshot(group: &Group) -> ItemSnapshot {
+    ItemSnapshot {
+        stable: group.stability == Some(Stability::Stable),
+        unit: group.unit.clone(),
+        instrument: group.instrument.as_ref().map(ToString::to_string),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::attribute::Attribute;
@@ -616,9 +759,10 @@ mod tests {
         );
 
         let changes = latest_schema.diff(&prior_schema);
-        assert_eq!(changes.count_changes(), 2);
-        assert_eq!(changes.count_registry_attribute_changes(), 2);
+        assert_eq!(changes.count_changes(), 4);
+        assert_eq!(changes.count_registry_attribute_changes(), 4);
         assert_eq!(changes.count_obsoleted_registry_attributes(), 2);
+        assert_eq!(changes.count_updated_registry_attributes(), 2);
         for attr_change in changes
             .changes_by_type(SchemaItemType::RegistryAttributes)
             .unwrap()
@@ -633,6 +777,11 @@ mod tests {
                         panic!("Unexpected attribute name.");
                     }
                 }
+                SchemaItemChange::Updated { name, fields } => {
+                    assert!(name == "attr2" || name == "attr3");
+                    assert_eq!(fields.len(), 1);
+                    assert_eq!(fields[0].path, "brief");
+                }
                 _ => panic!("Unexpected change type."),
             }
         }
@@ -719,9 +868,11 @@ mod tests {
         latest_schema.add_attribute_group("group2", [Attribute::string("attr5", "brief", "note")]);
 
         let changes = latest_schema.diff(&prior_schema);
-        assert_eq!(changes.count_changes(), 2);
-        assert_eq!(changes.count_registry_attribute_changes(), 2);
+        // attr3 is also updated from string to int.
+        assert_eq!(changes.count_changes(), 3);
+        assert_eq!(changes.count_registry_attribute_changes(), 3);
         assert_eq!(changes.count_renamed_registry_attributes(), 2);
+        assert_eq!(changes.count_updated_registry_attributes(), 1);
         dbg!(&changes);
     }
 
@@ -760,10 +911,12 @@ mod tests {
         );
 
         let changes = latest_schema.diff(&prior_schema);
-        assert_eq!(changes.count_changes(), 3);
-        assert_eq!(changes.count_registry_attribute_changes(), 3);
+        // attr3 is also updated from string to int.
+        assert_eq!(changes.count_changes(), 4);
+        assert_eq!(changes.count_registry_attribute_changes(), 4);
         assert_eq!(changes.count_renamed_registry_attributes(), 2);
         assert_eq!(changes.count_added_registry_attributes(), 1);
+        assert_eq!(changes.count_updated_registry_attributes(), 1);
         dbg!(&changes);
     }
 
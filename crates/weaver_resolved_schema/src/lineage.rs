@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use weaver_semconv::attribute::{AttributeRole, AttributeSpec, Examples, RequirementLevel};
 use weaver_semconv::deprecated::Deprecated;
 use weaver_semconv::provenance::Provenance;
+use weaver_semconv::source_location::SourceLocation;
 use weaver_semconv::stability::Stability;
 use weaver_semconv::YamlValue;
 
@@ -29,6 +30,16 @@ pub struct AttributeLineage {
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     #[serde(default)]
     pub locally_overridden_fields: BTreeSet<String>,
+
+    /// The schema URL of the dependency the attribute is coming from, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub dependency: Option<String>,
+
+    /// The location of the attribute reference in the source file of the group, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub location: Option<SourceLocation>,
 }
 
 /// Group lineage.
@@ -38,11 +49,21 @@ pub struct GroupLineage {
     /// The provenance of the source file where the group is defined.
     provenance: Provenance,
 
+    /// The location of the group definition in its source file, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    location: Option<SourceLocation>,
+
     /// The group that this group extended, if available.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub extends_group: Option<String>,
 
+    /// The schema URL of the dependency this group was imported from, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub dependency: Option<String>,
+
     /// The lineage per attribute.
     ///
     /// Note: Use a BTreeMap to ensure a deterministic order of attributes.
@@ -65,6 +86,8 @@ impl AttributeLineage {
             source_group: source_group.to_owned(),
             inherited_fields: Default::default(),
             locally_overridden_fields: Default::default(),
+            dependency: None,
+            location: None,
         }
     }
 
@@ -76,6 +99,8 @@ impl AttributeLineage {
             source_group: source_group.to_owned(),
             inherited_fields: Default::default(),
             locally_overridden_fields: Default::default(),
+            dependency: None,
+            location: None,
         };
         match attr_spec {
             AttributeSpec::Ref {
@@ -480,17 +505,30 @@ impl GroupLineage {
     pub fn new(provenance: Provenance) -> Self {
         Self {
             provenance,
+            location: None,
             extends_group: None,
+            dependency: None,
             attributes: Default::default(),
             includes_group: Default::default(),
         }
     }
 
+    /// Sets the location of the group definition in its source file.
+    pub fn with_location(mut self, location: Option<SourceLocation>) -> Self {
+        self.location = location;
+        self
+    }
+
     /// Declares this group extended another group.
     pub fn extends(&mut self, extends_group: &str) {
         self.extends_group = Some(extends_group.to_owned());
     }
 
+    /// Declares this group was imported from the dependency identified by `schema_url`.
+    pub fn imported_from(&mut self, schema_url: &str) {
+        self.dependency = Some(schema_url.to_owned());
+    }
+
     /// Records what attribute groups were included (v2 only).
     pub fn includes_group(&mut self, group_id: &str) {
         self.includes_group.push(group_id.to_owned());
@@ -518,4 +556,10 @@ impl GroupLineage {
     pub fn provenance(&self) -> &Provenance {
         &self.provenance
     }
+
+    /// Returns the location of the group definition in its source file, if known.
+    #[must_use]
+    pub fn location(&self) -> Option<SourceLocation> {
+        self.location
+    }
 }
@@ -6,10 +6,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use weaver_semconv::{
     attribute::{AttributeType, Examples},
+    stability::Stability,
     v2::CommonFields,
 };
+use weaver_version::compatibility::ItemSnapshot;
 
-use crate::v2::Signal;
+use crate::fields::Fields;
+use crate::v2::{insert_common_fields, Signal, SignalFields};
 
 /// The definition of an Attribute.
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, PartialEq, Hash, Eq)]
@@ -54,4 +57,30 @@ impl Signal for Attribute {
     fn common(&self) -> &CommonFields {
         &self.common
     }
+
+    fn compatibility_snapshot(&self) -> ItemSnapshot {
+        let (r#type, enum_members) = match &self.r#type {
+            AttributeType::Enum { members, .. } => (
+                "enum".to_owned(),
+                Some(members.iter().map(|m| m.value.to_string()).collect()),
+            ),
+            other => (other.to_string(), None),
+        };
+        ItemSnapshot {
+            stable: self.common.stability == Stability::Stable,
+            r#type: Some(r#type),
+            enum_members,
+            ..Default::default()
+        }
+    }
+}
+
+impl SignalFields for Attribute {
+    fn fields(&self, _catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        fields.insert_attribute_type(&self.r#type);
+        fields.insert("examples", &self.examples);
+        fields
+    }
 }
@@ -6,7 +6,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use weaver_semconv::v2::{signal_id::SignalId, CommonFields};
 
-use crate::v2::{attribute::AttributeRef, Signal};
+use crate::fields::Fields;
+use crate::v2::{
+    attribute::{Attribute, AttributeRef},
+    catalog::AttributeCatalog,
+    insert_common_fields, Signal, SignalFields,
+};
 
 /// Public attribute group.
 ///
@@ -37,3 +42,18 @@ impl Signal for AttributeGroup {
         &self.common
     }
 }
+
+impl SignalFields for AttributeGroup {
+    fn fields(&self, catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        for key in self
+            .attributes
+            .iter()
+            .filter_map(|attr| catalog.attribute_key(attr))
+        {
+            fields.insert(format!("attributes[{key}]"), key);
+        }
+        fields
+    }
+}
@@ -7,7 +7,11 @@ use weaver_semconv::{
     v2::{signal_id::SignalId, CommonFields},
 };
 
-use crate::v2::{attribute::AttributeRef, Signal};
+use crate::fields::Fields;
+use crate::v2::{
+    attribute::{Attribute, AttributeRef},
+    insert_attribute_refs, insert_common_fields, Signal, SignalFields,
+};
 
 /// The definition of an Entity signal.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -50,3 +54,27 @@ impl Signal for Entity {
         &self.common
     }
 }
+
+impl SignalFields for Entity {
+    fn fields(&self, catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        insert_attribute_refs(
+            &mut fields,
+            "identity",
+            catalog,
+            self.identity
+                .iter()
+                .map(|attr| (&attr.base, &attr.requirement_level)),
+        );
+        insert_attribute_refs(
+            &mut fields,
+            "description",
+            catalog,
+            self.description
+                .iter()
+                .map(|attr| (&attr.base, &attr.requirement_level)),
+        );
+        fields
+    }
+}
@@ -7,7 +7,11 @@ use weaver_semconv::{
     v2::{signal_id::SignalId, CommonFields},
 };
 
-use crate::v2::{attribute::AttributeRef, Signal};
+use crate::fields::Fields;
+use crate::v2::{
+    attribute::{Attribute, AttributeRef},
+    insert_attribute_refs, insert_common_fields, Signal, SignalFields,
+};
 
 /// The definition of an Event signal.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -76,3 +80,19 @@ impl Signal for Event {
         &self.common
     }
 }
+
+impl SignalFields for Event {
+    fn fields(&self, catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        insert_attribute_refs(
+            &mut fields,
+            "attributes",
+            catalog,
+            self.attributes
+                .iter()
+                .map(|attr| (&attr.base, &attr.requirement_level)),
+        );
+        fields
+    }
+}
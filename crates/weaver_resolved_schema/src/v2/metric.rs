@@ -1,13 +1,19 @@
 //! Metric related definitions structs.
 
-use crate::v2::{attribute::AttributeRef, Signal};
+use crate::fields::Fields;
+use crate::v2::{
+    attribute::{Attribute, AttributeRef},
+    insert_attribute_refs, insert_common_fields, Signal, SignalFields,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use weaver_semconv::{
     attribute::RequirementLevel,
     group::InstrumentSpec,
+    stability::Stability,
     v2::{signal_id::SignalId, CommonFields},
 };
+use weaver_version::compatibility::ItemSnapshot;
 
 /// The definition of a metric signal.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -87,4 +93,31 @@ impl Signal for Metric {
     fn common(&self) -> &CommonFields {
         &self.common
     }
+
+    fn compatibility_snapshot(&self) -> ItemSnapshot {
+        ItemSnapshot {
+            stable: self.common.stability == Stability::Stable,
+            unit: Some(self.unit.clone()),
+            instrument: Some(self.instrument.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl SignalFields for Metric {
+    fn fields(&self, catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        fields.insert("unit", &self.unit);
+        fields.insert("instrument", &self.instrument);
+        insert_attribute_refs(
+            &mut fields,
+            "attributes",
+            catalog,
+            self.attributes
+                .iter()
+                .map(|attr| (&attr.base, &attr.requirement_level)),
+        );
+        fields
+    }
 }
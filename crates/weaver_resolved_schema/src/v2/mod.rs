@@ -13,11 +13,16 @@ use weaver_semconv::{
         CommonFields,
     },
 };
-use weaver_version::v2::{RegistryChanges, SchemaChanges, SchemaItemChange};
+use weaver_version::{
+    compatibility::{classify_changes, CompatibilityFinding, ItemSnapshot},
+    schema_changes::SchemaItemType,
+    v2::{RegistryChanges, SchemaChanges, SchemaItemChange},
+};
 
 use crate::{
+    fields::Fields,
     v2::{
-        attribute::Attribute,
+        attribute::{Attribute, AttributeRef},
         attribute_group::AttributeGroup,
         catalog::{AttributeCatalog, Catalog},
         entity::Entity,
@@ -73,26 +78,91 @@ impl ResolvedTelemetrySchema {
     #[must_use]
     pub fn diff(&self, baseline_schema: &ResolvedTelemetrySchema) -> SchemaChanges {
         // TODO - get manifests
+        let registry = self.registry_diff(baseline_schema);
+        let compatibility = self.classify_changes(baseline_schema, &registry);
         SchemaChanges {
-            registry: self.registry_diff(baseline_schema),
+            registry,
+            compatibility,
         }
     }
 
+    /// Classifies the registry changes as breaking or non-breaking.
+    /// Attribute groups are not part of the telemetry and are not classified.
+    #[must_use]
+    fn classify_changes(
+        &self,
+        baseline_schema: &ResolvedTelemetrySchema,
+        changes: &RegistryChanges,
+    ) -> Vec<CompatibilityFinding> {
+        let mut findings = classify_changes(
+            SchemaItemType::RegistryAttributes,
+            &changes.attribute_changes,
+            &snapshots(&self.registry_attribute_map()),
+            &snapshots(&baseline_schema.registry_attribute_map()),
+        );
+        findings.extend(classify_signal_changes(
+            SchemaItemType::Metrics,
+            &changes.metric_changes,
+            &self.registry.metrics,
+            &baseline_schema.registry.metrics,
+        ));
+        findings.extend(classify_signal_changes(
+            SchemaItemType::Events,
+            &changes.event_changes,
+            &self.registry.events,
+            &baseline_schema.registry.events,
+        ));
+        findings.extend(classify_signal_changes(
+            SchemaItemType::Spans,
+            &changes.span_changes,
+            &self.registry.spans,
+            &baseline_schema.registry.spans,
+        ));
+        findings.extend(classify_signal_changes(
+            SchemaItemType::Entities,
+            &changes.entity_changes,
+            &self.registry.entities,
+            &baseline_schema.registry.entities,
+        ));
+        findings
+    }
+
     #[must_use]
     fn registry_diff(&self, baseline_schema: &ResolvedTelemetrySchema) -> RegistryChanges {
+        let latest_catalog = &self.attribute_catalog;
+        let baseline_catalog = &baseline_schema.attribute_catalog;
         RegistryChanges {
             attribute_changes: self.registry_attribute_diff(baseline_schema),
             attribute_group_changes: diff_signals(
                 &self.registry.attribute_groups,
+                latest_catalog,
                 &baseline_schema.registry.attribute_groups,
+                baseline_catalog,
             ),
             entity_changes: diff_signals(
                 &self.registry.entities,
+                latest_catalog,
                 &baseline_schema.registry.entities,
+                baseline_catalog,
+            ),
+            event_changes: diff_signals(
+                &self.registry.events,
+                latest_catalog,
+                &baseline_schema.registry.events,
+                baseline_catalog,
+            ),
+            metric_changes: diff_signals(
+                &self.registry.metrics,
+                latest_catalog,
+                &baseline_schema.registry.metrics,
+                baseline_catalog,
+            ),
+            span_changes: diff_signals(
+                &self.registry.spans,
+                latest_catalog,
+                &baseline_schema.registry.spans,
+                baseline_catalog,
             ),
-            event_changes: diff_signals(&self.registry.events, &baseline_schema.registry.events),
-            metric_changes: diff_signals(&self.registry.metrics, &baseline_schema.registry.metrics),
-            span_changes: diff_signals(&self.registry.spans, &baseline_schema.registry.spans),
         }
     }
 
@@ -103,7 +173,12 @@ impl ResolvedTelemetrySchema {
     ) -> Vec<SchemaItemChange> {
         let latest_attributes = self.registry_attribute_map();
         let baseline_attributes = baseline_schema.registry_attribute_map();
-        diff_signals_by_hash(&latest_attributes, &baseline_attributes)
+        diff_signals_by_hash(
+            &latest_attributes,
+            &self.attribute_catalog,
+            &baseline_attributes,
+            &baseline_schema.attribute_catalog,
+        )
     }
 
     /// Get the registry attributes of the resolved telemetry schema in a fast lookup map.
@@ -527,25 +602,110 @@ pub trait Signal {
     fn id(&self) -> &str;
     /// The common fields for the signal.
     fn common(&self) -> &CommonFields;
+    /// The fields of the signal inspected by the compatibility rules.
+    fn compatibility_snapshot(&self) -> ItemSnapshot {
+        ItemSnapshot {
+            stable: self.common().stability == weaver_semconv::stability::Stability::Stable,
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns the compatibility snapshots of signals indexed by id.
+fn snapshots<'a, T: Signal>(signals: &HashMap<&'a str, &T>) -> HashMap<&'a str, ItemSnapshot> {
+    signals
+        .iter()
+        .map(|(&id, signal)| (id, signal.compatibility_snapshot()))
+        .collect()
+}
+
+/// Classifies the changes of a signal registry.
+#[must_use]
+fn classify_signal_changes<T: Signal>(
+    item_type: SchemaItemType,
+    changes: &[SchemaItemChange],
+    latest: &[T],
+    baseline: &[T],
+) -> Vec<CompatibilityFinding> {
+    let baseline_signals: HashMap<&str, &T> = baseline.iter().map(|s| (s.id(), s)).collect();
+    let latest_signals: HashMap<&str, &T> = latest.iter().map(|s| (s.id(), s)).collect();
+    classify_changes(
+        item_type,
+        changes,
+        &snapshots(&latest_signals),
+        &snapshots(&baseline_signals),
+    )
+}
+
+/// A trait flattening the fields of a signal, used to detect field-level updates.
+pub(crate) trait SignalFields {
+    /// The fields of the signal, attribute references are resolved with the catalog.
+    fn fields(&self, catalog: &[Attribute]) -> Fields;
+}
+
+/// Inserts the fields shared by all the signals.
+pub(crate) fn insert_common_fields(fields: &mut Fields, common: &CommonFields) {
+    fields.insert("brief", common.brief.trim());
+    fields.insert("stability", &common.stability);
+}
+
+/// Inserts attribute references as `<path>[<attribute key>]`, the value is the requirement level.
+pub(crate) fn insert_attribute_refs<'a>(
+    fields: &mut Fields,
+    path: &str,
+    catalog: &[Attribute],
+    attributes: impl IntoIterator<
+        Item = (
+            &'a AttributeRef,
+            &'a weaver_semconv::attribute::RequirementLevel,
+        ),
+    >,
+) {
+    for (attribute_ref, requirement_level) in attributes {
+        if let Some(key) = catalog.attribute_key(attribute_ref) {
+            fields.insert(format!("{path}[{key}]"), requirement_level);
+        }
+    }
 }
 
 /// Diffs signal registries.
 #[must_use]
-fn diff_signals<T: Signal>(latest: &[T], baseline: &[T]) -> Vec<SchemaItemChange> {
+fn diff_signals<T: Signal + SignalFields>(
+    latest: &[T],
+    latest_catalog: &[Attribute],
+    baseline: &[T],
+    baseline_catalog: &[Attribute],
+) -> Vec<SchemaItemChange> {
     let baseline_signals: HashMap<&str, &T> = baseline.iter().map(|s| (s.id(), s)).collect();
     let latest_signals: HashMap<&str, &T> = latest.iter().map(|s| (s.id(), s)).collect();
-    diff_signals_by_hash(&latest_signals, &baseline_signals)
+    diff_signals_by_hash(
+        &latest_signals,
+        latest_catalog,
+        &baseline_signals,
+        baseline_catalog,
+    )
 }
 
 /// Finds the difference between two signal registries using a hash into the signal id.
-fn diff_signals_by_hash<T: Signal>(
+fn diff_signals_by_hash<T: Signal + SignalFields>(
     latest: &HashMap<&str, &T>,
+    latest_catalog: &[Attribute],
     baseline: &HashMap<&str, &T>,
+    baseline_catalog: &[Attribute],
 ) -> Vec<SchemaItemChange> {
     let mut changes: Vec<SchemaItemChange> = Vec::new();
     for (&signal_id, latest_signal) in latest.iter() {
         let baseline_signal = baseline.get(signal_id);
         if let Some(baseline_signal) = baseline_signal {
+            let fields = baseline_signal
+                .fields(baseline_catalog)
+                .diff(&latest_signal.fields(latest_catalog));
+            if !fields.is_empty() {
+                changes.push(SchemaItemChange::Updated {
+                    name: signal_id.to_owned(),
+                    fields,
+                });
+            }
             if let Some(deprecated) = latest_signal.common().deprecated.as_ref() {
                 // is this a change from the baseline?
                 if let Some(baseline_deprecated) = baseline_signal.common().deprecated.as_ref() {
@@ -1172,6 +1332,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn v2_detect_metric_updated() {
+        let metric = |unit: &str, instrument, brief: &str| Metric {
+            name: "http.duration".to_owned().into(),
+            instrument,
+            unit: unit.to_owned(),
+            attributes: vec![],
+            entity_associations: vec![],
+            common: CommonFields {
+                brief: brief.to_owned(),
+                ..Default::default()
+            },
+        };
+        let mut baseline = empty_v2_schema();
+        baseline.registry.metrics.push(metric(
+            "s",
+            weaver_semconv::group::InstrumentSpec::Histogram,
+            "Duration",
+        ));
+        let mut latest = empty_v2_schema();
+        latest.registry.metrics.push(metric(
+            "ms",
+            weaver_semconv::group::InstrumentSpec::Gauge,
+            "Duration",
+        ));
+        let diff = latest.diff(&baseline);
+        assert_eq!(diff.registry.metric_changes.len(), 1);
+        let SchemaItemChange::Updated { name, fields } = &diff.registry.metric_changes[0] else {
+            panic!("Unexpected change: {:?}", diff.registry.metric_changes[0]);
+        };
+        assert_eq!(name, "http.duration");
+        let fields: Vec<_> = fields
+            .iter()
+            .map(|f| {
+                (
+                    f.path.as_str(),
+                    f.old_value.as_deref(),
+                    f.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("instrument", Some("histogram"), Some("gauge")),
+                ("unit", Some("s"), Some("ms")),
+            ]
+        );
+    }
+
     #[test]
     fn v2_detect_entity_uncategorized_deprecation() {
         // Test a user deprecating an entity with unknown change type.
@@ -8,7 +8,11 @@ use weaver_semconv::{
     v2::{signal_id::SignalId, span::SpanName, CommonFields},
 };
 
-use crate::v2::{attribute::AttributeRef, Signal};
+use crate::fields::Fields;
+use crate::v2::{
+    attribute::{Attribute, AttributeRef},
+    insert_attribute_refs, insert_common_fields, Signal, SignalFields,
+};
 
 /// The definition of a Span signal.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
@@ -85,3 +89,21 @@ impl Signal for Span {
         &self.common
     }
 }
+
+impl SignalFields for Span {
+    fn fields(&self, catalog: &[Attribute]) -> Fields {
+        let mut fields = Fields::default();
+        insert_common_fields(&mut fields, &self.common);
+        fields.insert("kind", &self.kind);
+        fields.insert("name", self.name.note.trim());
+        insert_attribute_refs(
+            &mut fields,
+            "attributes",
+            catalog,
+            self.attributes
+                .iter()
+                .map(|attr| (&attr.base, &attr.requirement_level)),
+        );
+        fields
+    }
+}
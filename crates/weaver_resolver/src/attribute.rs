@@ -13,6 +13,7 @@ use weaver_resolved_schema::lineage::{AttributeLineage, GroupLineage};
 use weaver_resolved_schema::v2::ResolvedTelemetrySchema as V2Schema;
 use weaver_resolved_schema::ResolvedTelemetrySchema as V1Schema;
 use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::source_location::SourceLocation;
 
 use crate::dependency::ResolvedDependency;
 
@@ -34,6 +35,8 @@ pub struct AttributeWithGroupId {
     pub attribute: attribute::Attribute,
     /// The group ID.
     pub group_id: String,
+    /// The schema URL of the dependency defining the attribute, if any.
+    pub dependency: Option<String>,
 }
 
 impl AttributeCatalog {
@@ -122,6 +125,7 @@ impl AttributeCatalog {
         group_id: &str,
         group_prefix: &str,
         attr: &AttributeSpec,
+        location: Option<SourceLocation>,
         lineage: Option<&mut GroupLineage>,
         dependencies: &Vec<ResolvedDependency>,
     ) -> Option<AttributeRef> {
@@ -151,6 +155,8 @@ impl AttributeCatalog {
                 }
                 if let Some(root_attr) = root_attr {
                     let mut attr_lineage = AttributeLineage::new(&root_attr.group_id);
+                    attr_lineage.dependency.clone_from(&root_attr.dependency);
+                    attr_lineage.location = location;
 
                     if *prefix {
                         // depending on the prefix we either create embedded attribute or normal reference
@@ -205,6 +211,7 @@ impl AttributeCatalog {
                             AttributeWithGroupId {
                                 attribute: resolved_attr,
                                 group_id: group_id.to_owned(),
+                                dependency: None,
                             },
                         );
                     }
@@ -255,6 +262,7 @@ impl AttributeCatalog {
                     AttributeWithGroupId {
                         attribute: attr.clone(),
                         group_id: group_id.to_owned(),
+                        dependency: None,
                     },
                 );
                 Some(self.attribute_ref(attr))
@@ -291,10 +299,14 @@ impl AttributeLookup for Vec<ResolvedDependency> {
 
 impl AttributeLookup for ResolvedDependency {
     fn lookup_attribute(&self, key: &str) -> Option<AttributeWithGroupId> {
-        match self {
+        let attribute = match self {
             ResolvedDependency::V1(schema) => schema.lookup_attribute(key),
             ResolvedDependency::V2(schema) => schema.lookup_attribute(key),
-        }
+        };
+        attribute.map(|attribute| AttributeWithGroupId {
+            dependency: Some(self.schema_url().to_owned()),
+            ..attribute
+        })
     }
 }
 
@@ -305,6 +317,7 @@ impl AttributeLookup for V1Schema {
             .map(|(attr, group_id)| AttributeWithGroupId {
                 attribute: attr.clone(),
                 group_id: group_id.to_owned(),
+                dependency: None,
             })
     }
 }
@@ -336,6 +349,7 @@ impl AttributeLookup for V2Schema {
                         role: None,
                     },
                     group_id: fake_group_id.clone(),
+                    dependency: None,
                 })
             } else {
                 None
@@ -0,0 +1,419 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An on-disk cache of the parsed semantic convention files and of the resolved
+//! dependencies, enabled with the `resolution_cache` cache setting.
+//!
+//! The cache directory contains:
+//! - `specs/<key>.json`: a parsed semantic convention file, keyed by the digest of its
+//!   content, its registry id and its path.
+//! - `resolved/<schema url hash>/<key>.json`: a resolved dependency, keyed by the
+//!   fingerprint of the registry (its files, its manifest, its dependencies and the
+//!   `follow_symlinks` flag) and the `include_unreferenced` flag. Only the latest entry of
+//!   a schema URL is kept.
+//!
+//! All the keys include the version of weaver, so entries written by another version are
+//! never reused. The cache is a best effort: entries that cannot be read or written are
+//! ignored and the files are parsed and resolved as usual. Files and registries producing
+//! warnings are never cached, so their warnings are reported on each run.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use weaver_common::cache::{digest, CacheSettings};
+use weaver_resolved_schema::attribute::Attribute;
+use weaver_resolved_schema::catalog::Catalog;
+use weaver_resolved_schema::registry::Registry;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_semconv::manifest::RegistryManifest;
+use weaver_semconv::provenance::Provenance;
+use weaver_semconv::registry_repo::RegistryRepo;
+use weaver_semconv::semconv::{SemConvSpecWithProvenance, Versioned};
+use weaver_semconv::source_location::{SourceLocations, SourceLocationsIndex};
+
+use crate::LoadedSemconvRegistry;
+
+/// Identifies the version of weaver and the format of the cache entries.
+const CACHE_VERSION: &str = concat!("weaver/", env!("CARGO_PKG_VERSION"), "/1");
+
+/// A parsed semantic convention file. The source locations are restored from the content of
+/// the file.
+#[derive(Serialize, Deserialize)]
+struct CachedSpec {
+    spec: Versioned,
+    source_locations: SourceLocationsIndex,
+}
+
+/// A resolved registry. The resource, the instrumentation library, the dependencies and the
+/// versions of the schema are not set by the resolution, and the manifest is the one of the
+/// loaded registry.
+#[derive(Serialize, Deserialize)]
+struct CachedSchema {
+    file_format: String,
+    schema_url: String,
+    registry_id: String,
+    registry: Registry,
+    attributes: Vec<Attribute>,
+    root_attributes: HashMap<String, (Attribute, String)>,
+}
+
+/// The cache of the parsed semantic convention files and of the resolved dependencies.
+pub struct ResolutionCache {
+    root: PathBuf,
+}
+
+impl ResolutionCache {
+    /// Creates a cache rooted at the given directory.
+    #[must_use]
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Returns the cache configured in the given cache settings, if enabled.
+    #[must_use]
+    pub fn from_settings(cache_settings: &CacheSettings) -> Option<Self> {
+        match cache_settings.resolution_cache_dir() {
+            Ok(root) => root.map(Self::new),
+            Err(e) => {
+                log::warn!("The resolution cache is disabled: {e}");
+                None
+            }
+        }
+    }
+
+    /// Returns the key of a semantic convention file.
+    pub(crate) fn spec_key(provenance: &Provenance, content: &[u8]) -> String {
+        let mut data = format!(
+            "{CACHE_VERSION}\n{}\n{}\n",
+            provenance.registry_id, provenance.path
+        )
+        .into_bytes();
+        data.extend_from_slice(content);
+        hex_digest(&data)
+    }
+
+    /// Returns a cached semantic convention file, `content` being the content of the file.
+    pub(crate) fn load_spec(
+        &self,
+        key: &str,
+        provenance: Provenance,
+        content: &str,
+    ) -> Option<SemConvSpecWithProvenance> {
+        let cached: CachedSpec = read(&self.spec_path(key))?;
+        Some(SemConvSpecWithProvenance {
+            spec: cached.spec,
+            source_locations: SourceLocations::from_index(
+                &provenance.path,
+                content,
+                cached.source_locations,
+            ),
+            provenance,
+        })
+    }
+
+    /// Stores a parsed semantic convention file.
+    pub(crate) fn store_spec(&self, key: &str, spec: &SemConvSpecWithProvenance) {
+        write(
+            &self.spec_path(key),
+            &CachedSpec {
+                spec: spec.spec.clone(),
+                source_locations: spec.source_locations.index(),
+            },
+        );
+    }
+
+    /// Returns the fingerprint of a loaded registry, `spec_keys` being the keys of its
+    /// files. Returns `None` if one of its dependencies has no fingerprint (e.g. an
+    /// already resolved registry).
+    pub(crate) fn fingerprint(
+        repo: &RegistryRepo,
+        follow_symlinks: bool,
+        mut spec_keys: Vec<String>,
+        dependencies: &[LoadedSemconvRegistry],
+    ) -> Option<String> {
+        spec_keys.sort();
+        let mut data = format!(
+            "{CACHE_VERSION}\n{}\n{}\n{follow_symlinks}\n{:?}\n",
+            repo.schema_url(),
+            repo.registry_path_repr(),
+            repo.manifest()
+        );
+        for key in spec_keys {
+            data.push_str(&key);
+            data.push('\n');
+        }
+        for dependency in dependencies {
+            match dependency {
+                LoadedSemconvRegistry::Unresolved {
+                    fingerprint: Some(fingerprint),
+                    ..
+                } => {
+                    data.push_str(fingerprint);
+                    data.push('\n');
+                }
+                _ => return None,
+            }
+        }
+        Some(hex_digest(data.as_bytes()))
+    }
+
+    /// Returns the key of a resolved registry.
+    pub(crate) fn resolved_key(fingerprint: &str, include_unreferenced: bool) -> String {
+        hex_digest(format!("{CACHE_VERSION}\n{fingerprint}\n{include_unreferenced}").as_bytes())
+    }
+
+    /// Returns a cached resolved registry, `manifest` being the manifest of the registry.
+    pub(crate) fn load_resolved(
+        &self,
+        schema_url: &str,
+        key: &str,
+        manifest: Option<&RegistryManifest>,
+    ) -> Option<ResolvedTelemetrySchema> {
+        let cached: CachedSchema =
+            read(&self.resolved_dir(schema_url).join(format!("{key}.json")))?;
+        Some(ResolvedTelemetrySchema {
+            file_format: cached.file_format,
+            schema_url: cached.schema_url,
+            registry_id: cached.registry_id,
+            registry: cached.registry,
+            catalog: Catalog::new(cached.attributes, cached.root_attributes),
+            resource: None,
+            instrumentation_library: None,
+            dependencies: vec![],
+            versions: None,
+            registry_manifest: manifest.cloned(),
+        })
+    }
+
+    /// Stores a resolved registry, replacing the previous entry of its schema URL.
+    pub(crate) fn store_resolved(
+        &self,
+        schema_url: &str,
+        key: &str,
+        schema: &ResolvedTelemetrySchema,
+    ) {
+        let dir = self.resolved_dir(schema_url);
+        let path = dir.join(format!("{key}.json"));
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                if entry.path() != path {
+                    _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+        let root_attributes = schema
+            .catalog
+            .root_attributes()
+            .map(|(name, attribute, group_id)| {
+                (name.to_owned(), (attribute.clone(), group_id.to_owned()))
+            })
+            .collect();
+        write(
+            &path,
+            &CachedSchema {
+                file_format: schema.file_format.clone(),
+                schema_url: schema.schema_url.clone(),
+                registry_id: schema.registry_id.clone(),
+                registry: schema.registry.clone(),
+                attributes: schema.catalog.attributes().cloned().collect(),
+                root_attributes,
+            },
+        );
+    }
+
+    fn spec_path(&self, key: &str) -> PathBuf {
+        self.root.join("specs").join(format!("{key}.json"))
+    }
+
+    fn resolved_dir(&self, schema_url: &str) -> PathBuf {
+        self.root
+            .join("resolved")
+            .join(hex_digest(schema_url.as_bytes()))
+    }
+}
+
+fn hex_digest(data: &[u8]) -> String {
+    digest(data).trim_start_matches("sha256:").to_owned()
+}
+
+/// Reads a cache entry, `None` if it does not exist or cannot be read.
+fn read<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let content = std::fs::read(path).ok()?;
+    match serde_json::from_slice(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::debug!("Ignoring the invalid cache entry {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Writes a cache entry. The entry is written to a temporary file first, so concurrent
+/// readers never see a partially written entry.
+fn write<T: Serialize>(path: &Path, value: &T) {
+    let write_entry = || -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension(format!("{}.tmp", rand::random::<u64>()));
+        std::fs::write(&tmp_path, serde_json::to_vec(value)?)?;
+        std::fs::rename(&tmp_path, path).inspect_err(|_| {
+            _ = std::fs::remove_file(&tmp_path);
+        })
+    };
+    if let Err(e) = write_entry() {
+        log::debug!("Unable to write the cache entry {}: {e}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use weaver_common::cache::CacheSettings;
+    use weaver_common::vdir::VirtualDirectoryPath;
+    use weaver_resolved_schema::ResolvedTelemetrySchema;
+    use weaver_semconv::registry_repo::RegistryRepo;
+
+    use crate::cache::ResolutionCache;
+    use crate::{LoadedSemconvRegistry, SchemaResolver};
+
+    fn load(
+        registry: &Path,
+        follow_symlinks: bool,
+        cache: Option<&ResolutionCache>,
+    ) -> LoadedSemconvRegistry {
+        let registry_path = VirtualDirectoryPath::LocalFolder {
+            path: registry.display().to_string(),
+        };
+        let repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+                .unwrap();
+        SchemaResolver::load_semconv_repository(repo, follow_symlinks, cache)
+            .into_result_failing_non_fatal()
+            .unwrap()
+    }
+
+    fn resolve(
+        registry: &Path,
+        include_unreferenced: bool,
+        cache: Option<&ResolutionCache>,
+    ) -> ResolvedTelemetrySchema {
+        SchemaResolver::resolve(load(registry, false, cache), include_unreferenced, cache)
+            .into_result_failing_non_fatal()
+            .unwrap()
+    }
+
+    fn assert_same_schema(actual: &ResolvedTelemetrySchema, expected: &ResolvedTelemetrySchema) {
+        assert_eq!(actual.registry, expected.registry);
+        assert_eq!(actual.catalog, expected.catalog);
+        assert_eq!(actual.schema_url, expected.schema_url);
+    }
+
+    fn entry_count(dir: &Path) -> usize {
+        walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .count()
+    }
+
+    #[test]
+    fn test_resolution_cache() {
+        // A registry with a dependency, both without warnings so they can be cached.
+        let dir = tempfile::tempdir().unwrap();
+        let otel = dir.path().join("otel_registry");
+        let custom = dir.path().join("custom_registry");
+        std::fs::create_dir_all(&otel).unwrap();
+        std::fs::create_dir_all(&custom).unwrap();
+        std::fs::write(
+            otel.join("otel_registry.yaml"),
+            r#"groups:
+  - id: registry.error
+    type: attribute_group
+    brief: Error attributes.
+    attributes:
+      - id: error.type
+        type: string
+        brief: The error type.
+        stability: stable
+        examples: ["timeout"]
+  - id: registry.unused
+    type: attribute_group
+    brief: Unused attributes.
+    attributes:
+      - id: unused
+        type: string
+        brief: Unused attribute.
+        stability: stable
+        examples: ["unused"]
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            custom.join("custom_registry.yaml"),
+            r#"groups:
+  - id: metric.auction.bid.count
+    type: metric
+    metric_name: auction.bid.count
+    stability: stable
+    brief: Count of all bids.
+    instrument: counter
+    unit: "{bid}"
+    attributes:
+      - ref: error.type
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            custom.join("registry_manifest.yaml"),
+            format!(
+                "name: acme\nsemconv_version: 0.1.0\nschema_base_url: https://acme.com/schemas/\n\
+                 dependencies:\n  - schema_url: https://opentelemetry.io/schemas/1.30.0\n    \
+                 registry_path: {}\n",
+                otel.display()
+            ),
+        )
+        .unwrap();
+        let cache_dir = dir.path().join("cache");
+        let cache = ResolutionCache::new(cache_dir.clone());
+        let expected = resolve(&custom, false, None);
+
+        // The first run populates the cache, the second one reads it.
+        assert_same_schema(&resolve(&custom, false, Some(&cache)), &expected);
+        assert_eq!(entry_count(&cache_dir.join("specs")), 2);
+        assert_eq!(entry_count(&cache_dir.join("resolved")), 1);
+        assert_same_schema(&resolve(&custom, false, Some(&cache)), &expected);
+        assert_eq!(entry_count(&cache_dir.join("specs")), 2);
+        assert_eq!(entry_count(&cache_dir.join("resolved")), 1);
+
+        // The resolved dependency is keyed by the resolution flags.
+        let with_unreferenced = resolve(&custom, true, None);
+        assert_ne!(with_unreferenced.registry, expected.registry);
+        assert_same_schema(&resolve(&custom, true, Some(&cache)), &with_unreferenced);
+        assert_same_schema(&resolve(&custom, false, Some(&cache)), &expected);
+        assert_eq!(entry_count(&cache_dir.join("resolved")), 1);
+
+        // The fingerprint of the registries depends on the `follow_symlinks` flag.
+        let fingerprint = |follow_symlinks| match load(&custom, follow_symlinks, Some(&cache)) {
+            LoadedSemconvRegistry::Unresolved { fingerprint, .. } => fingerprint.unwrap(),
+            _ => panic!("Expected an unresolved registry"),
+        };
+        assert_ne!(fingerprint(false), fingerprint(true));
+
+        // A modified dependency is parsed and resolved again.
+        let otel_file = otel.join("otel_registry.yaml");
+        let content = std::fs::read_to_string(&otel_file).unwrap();
+        std::fs::write(
+            &otel_file,
+            content.replace("brief: The error type.", "brief: The updated error type."),
+        )
+        .unwrap();
+        let updated = resolve(&custom, false, None);
+        assert_ne!(updated.catalog, expected.catalog);
+        assert_same_schema(&resolve(&custom, false, Some(&cache)), &updated);
+        assert_eq!(entry_count(&cache_dir.join("specs")), 3);
+        assert_eq!(entry_count(&cache_dir.join("resolved")), 1);
+    }
+}
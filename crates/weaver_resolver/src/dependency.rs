@@ -4,6 +4,7 @@
 
 use globset::GlobSet;
 use weaver_resolved_schema::attribute::Attribute;
+use weaver_resolved_schema::lineage::GroupLineage;
 use weaver_resolved_schema::registry::Group;
 use weaver_resolved_schema::v2::catalog::AttributeCatalog as V2Catalog;
 use weaver_resolved_schema::v2::ResolvedTelemetrySchema as V2Schema;
@@ -13,6 +14,7 @@ use weaver_semconv::attribute::{AttributeRole, RequirementLevel};
 use weaver_semconv::deprecated::Deprecated;
 use weaver_semconv::group::{GroupType, InstrumentSpec, SpanKindSpec};
 use weaver_semconv::group::{GroupWildcard, ImportsWithProvenance};
+use weaver_semconv::provenance::Provenance;
 use weaver_semconv::stability::Stability;
 
 use crate::{attribute::AttributeCatalog, Error};
@@ -75,6 +77,82 @@ pub(crate) enum ResolvedDependency {
 }
 
 impl ResolvedDependency {
+    /// Returns the schema URL identifying this dependency.
+    pub(crate) fn schema_url(&self) -> &str {
+        match self {
+            ResolvedDependency::V1(schema) => &schema.schema_url,
+            ResolvedDependency::V2(schema) => schema.schema_url.as_str(),
+        }
+    }
+
+    /// Returns the name of the registry of this dependency.
+    fn registry_name(&self) -> &str {
+        match self {
+            ResolvedDependency::V1(schema) => &schema.registry_id,
+            ResolvedDependency::V2(schema) => schema.schema_url.name(),
+        }
+    }
+
+    /// Returns the attribute definitions available in this dependency, by name.
+    fn attribute_definitions(&self) -> Vec<(String, Attribute)> {
+        match self {
+            ResolvedDependency::V1(schema) => schema
+                .catalog
+                .root_attributes()
+                .map(|(name, attr, _)| (name.to_owned(), attr.clone()))
+                .collect(),
+            ResolvedDependency::V2(schema) => schema
+                .registry
+                .attributes
+                .iter()
+                .filter_map(|attr_ref| schema.attribute_catalog.attribute(attr_ref))
+                .map(|attr| {
+                    (
+                        attr.key.clone(),
+                        convert_v2_attribute(attr, RequirementLevel::default(), None),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the ids of the groups available in this dependency, along with the name
+    /// of the registry defining them.
+    fn group_definitions(&self) -> Vec<(String, String)> {
+        match self {
+            ResolvedDependency::V1(schema) => schema
+                .registry
+                .groups
+                .iter()
+                .map(|g| {
+                    let registry = g.lineage.as_ref().map_or_else(
+                        || self.registry_name().to_owned(),
+                        |lineage| lineage.provenance().registry_id.to_string(),
+                    );
+                    (g.id.clone(), registry)
+                })
+                .collect(),
+            ResolvedDependency::V2(schema) => {
+                let registry = &schema.registry;
+                registry
+                    .metrics
+                    .iter()
+                    .map(|m| m.id().to_owned())
+                    .chain(registry.events.iter().map(|e| e.id().to_owned()))
+                    .chain(registry.entities.iter().map(|e| e.id().to_owned()))
+                    .chain(registry.spans.iter().map(|s| s.id().to_owned()))
+                    .chain(
+                        registry
+                            .attribute_groups
+                            .iter()
+                            .map(|ag| ag.id().to_owned()),
+                    )
+                    .map(|id| (id, self.registry_name().to_owned()))
+                    .collect()
+            }
+        }
+    }
+
     /// Looks up a group summary on this dependency.
     pub(crate) fn lookup_group_summary(&self, id: &str) -> Option<GroupSummary> {
         match self {
@@ -478,14 +556,24 @@ impl ImportableDependency for ResolvedDependency {
         include_all: bool,
         attribute_catalog: &mut AttributeCatalog,
     ) -> Result<Vec<Group>, Error> {
-        match self {
+        let mut groups = match self {
             ResolvedDependency::V1(schema) => {
                 schema.import_groups(imports, include_all, attribute_catalog)
             }
             ResolvedDependency::V2(schema) => {
                 schema.import_groups(imports, include_all, attribute_catalog)
             }
+        }?;
+        // Record the dependency each imported group comes from.
+        for group in &mut groups {
+            group
+                .lineage
+                .get_or_insert_with(|| {
+                    GroupLineage::new(Provenance::new(self.registry_name(), self.schema_url()))
+                })
+                .imported_from(self.schema_url());
         }
+        Ok(groups)
     }
 }
 
@@ -506,6 +594,66 @@ impl ImportableDependency for Vec<ResolvedDependency> {
     }
 }
 
+/// Checks that the attributes and the groups available in the dependencies of a registry
+/// are not defined by several of them.
+///
+/// Identical attribute definitions, and groups defined by the same registry, are not
+/// collisions: they are typically shared by two dependencies depending on the same registry.
+/// For the other collisions, the definition of the first dependency declared in the
+/// manifest is used during the resolution.
+pub(crate) fn check_dependency_collisions(dependencies: &[ResolvedDependency]) -> Vec<Error> {
+    let mut attributes: std::collections::BTreeMap<String, Vec<(&str, Attribute)>> =
+        Default::default();
+    let mut groups: std::collections::BTreeMap<String, Vec<(&str, String)>> = Default::default();
+    for dependency in dependencies {
+        for (name, attr) in dependency.attribute_definitions() {
+            attributes
+                .entry(name)
+                .or_default()
+                .push((dependency.schema_url(), attr));
+        }
+        for (id, registry) in dependency.group_definitions() {
+            groups
+                .entry(id)
+                .or_default()
+                .push((dependency.schema_url(), registry));
+        }
+    }
+
+    let collision = |kind: &str, name: String, dependencies: Vec<&str>| {
+        let mut dependencies: Vec<String> =
+            dependencies.into_iter().map(ToOwned::to_owned).collect();
+        dependencies.dedup();
+        (dependencies.len() > 1).then(|| Error::DependencyNameCollision {
+            kind: kind.to_owned(),
+            name,
+            dependencies,
+        })
+    };
+    let mut errors = vec![];
+    for (name, definitions) in attributes {
+        let (_, first) = &definitions[0];
+        if definitions.iter().any(|(_, attr)| attr != first) {
+            errors.extend(collision(
+                "attribute",
+                name,
+                definitions.iter().map(|(url, _)| *url).collect(),
+            ));
+        }
+    }
+    for (id, definitions) in groups {
+        let (_, first) = &definitions[0];
+        if definitions.iter().any(|(_, registry)| registry != first) {
+            errors.extend(collision(
+                "group",
+                id,
+                definitions.iter().map(|(url, _)| *url).collect(),
+            ));
+        }
+    }
+    errors
+}
+
 /// Helper trait for abstracting over V1 and V2 schema.
 pub(crate) trait GroupRefinementLookup {
     /// Looks up a group summary on this repo.
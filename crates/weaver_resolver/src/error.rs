@@ -7,6 +7,7 @@ use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_common::error::{format_errors, WeaverError};
 use weaver_common::log_error;
 use weaver_semconv::provenance::Provenance;
+use weaver_semconv::source_location::SourceDefinition;
 
 /// An error that can occur while resolving a telemetry schema.
 #[derive(thiserror::Error, Debug, Clone, Serialize, Diagnostic)]
@@ -92,6 +93,11 @@ pub enum Error {
         attribute_ref: String,
         /// The provenance of the reference (URL or path).
         provenance: Provenance,
+        /// The definition of the attribute reference, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "attribute reference defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// An unresolved `extends` clause reference.
@@ -103,6 +109,11 @@ pub enum Error {
         extends_ref: String,
         /// The provenance of the reference (URL or path).
         provenance: Provenance,
+        /// The definition of the group, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "group defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// An unresolved `include` reference.
@@ -173,6 +184,25 @@ pub enum Error {
         group_ids: Vec<String>,
         /// The attribute id.
         attribute_id: String,
+        /// The definition of the group, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "group defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
+    },
+
+    /// A name defined by several dependencies of a registry.
+    #[error("The {kind} `{name}` is defined differently by multiple dependencies: {dependencies:?}. The definition of `{}` is used.", dependencies.first().map(String::as_str).unwrap_or_default())]
+    #[diagnostic(help(
+        "Remove one of the definitions, or remove one of the dependencies from the registry manifest."
+    ))]
+    DependencyNameCollision {
+        /// The kind of the colliding item (`attribute` or `group`).
+        kind: String,
+        /// The colliding name.
+        name: String,
+        /// The schema URLs of the dependencies defining the name, in declaration order.
+        dependencies: Vec<String>,
     },
 
     /// Invalid import wildcard.
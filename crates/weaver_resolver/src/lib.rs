@@ -14,6 +14,7 @@ use weaver_semconv::registry_repo::RegistryRepo;
 use weaver_semconv::semconv::SemConvSpecWithProvenance;
 
 mod attribute;
+mod cache;
 mod dependency;
 mod error;
 mod loader;
@@ -21,6 +22,7 @@ pub(crate) mod merge;
 mod registry;
 
 // Make helper portions of this create public APIs.
+pub use crate::cache::ResolutionCache;
 pub use crate::error::Error;
 pub use crate::loader::LoadedSemconvRegistry;
 
@@ -30,9 +32,11 @@ pub struct SchemaResolver {}
 
 impl SchemaResolver {
     /// Resolves a loaded semantic convention registry and returns the corresponding resolved schema.
+    /// The dependencies found in the resolution `cache`, if any, are not resolved again.
     pub fn resolve(
         loaded: LoadedSemconvRegistry,
         include_unreferenced: bool,
+        cache: Option<&ResolutionCache>,
     ) -> WResult<ResolvedTelemetrySchema, Error> {
         // TODO - can we deprecate include_unreferenced?
         match loaded {
@@ -41,7 +45,15 @@ impl SchemaResolver {
                 specs,
                 imports,
                 dependencies,
-            } => Self::resolve_registry(repo, specs, imports, dependencies, include_unreferenced),
+                ..
+            } => Self::resolve_registry(
+                repo,
+                specs,
+                imports,
+                dependencies,
+                include_unreferenced,
+                cache,
+            ),
             LoadedSemconvRegistry::Resolved(resolved_telemetry_schema) => {
                 WResult::Ok(resolved_telemetry_schema)
             }
@@ -58,6 +70,7 @@ impl SchemaResolver {
         imports: Vec<ImportsWithProvenance>,
         dependencies: Vec<LoadedSemconvRegistry>,
         include_unreferenced: bool,
+        cache: Option<&ResolutionCache>,
     ) -> WResult<ResolvedTelemetrySchema, Error> {
         // First, let's make sure all dependencies are resolved.
         let mut opt_resolved_dependencies: Vec<WResult<ResolvedDependency, Error>> = vec![];
@@ -69,17 +82,39 @@ impl SchemaResolver {
                     specs,
                     imports,
                     dependencies,
+                    fingerprint,
                 } => {
-                    opt_resolved_dependencies.push(
-                        Self::resolve_registry(
-                            repo,
-                            specs,
-                            imports,
-                            dependencies,
-                            include_unreferenced,
-                        )
-                        .map(|s| s.into()),
+                    // Unchanged dependencies are loaded from the resolution cache.
+                    let cached = cache.zip(fingerprint).map(|(cache, fingerprint)| {
+                        let key = ResolutionCache::resolved_key(&fingerprint, include_unreferenced);
+                        (cache, repo.schema_url().to_string(), key)
+                    });
+                    if let Some((cache, schema_url, key)) = &cached {
+                        if let Some(schema) = cache.load_resolved(schema_url, key, repo.manifest())
+                        {
+                            opt_resolved_dependencies.push(WResult::Ok(schema.into()));
+                            continue;
+                        }
+                    }
+                    let resolved = Self::resolve_registry(
+                        repo,
+                        specs,
+                        imports,
+                        dependencies,
+                        include_unreferenced,
+                        cache,
                     );
+                    // Dependencies with warnings are not cached, so they are reported again.
+                    if let (
+                        Some((cache, schema_url, key)),
+                        WResult::Ok(schema) | WResult::OkWithNFEs(schema, _),
+                    ) = (&cached, &resolved)
+                    {
+                        if !resolved.has_errors() {
+                            cache.store_resolved(schema_url, key, schema);
+                        }
+                    }
+                    opt_resolved_dependencies.push(resolved.map(|s| s.into()));
                 }
                 LoadedSemconvRegistry::Resolved(schema) => {
                     opt_resolved_dependencies.push(WResult::Ok(schema.into()));
@@ -142,11 +177,13 @@ impl SchemaResolver {
     ///
     /// Note: This may load in a definition (raw) repository *or* an already resolved repository.
     ///       When loading a raw repository, dependencies will also be loaded.
+    ///       The files found in the resolution `cache`, if any, are not parsed again.
     pub fn load_semconv_repository(
         registry_repo: RegistryRepo,
         follow_symlinks: bool,
+        cache: Option<&ResolutionCache>,
     ) -> WResult<LoadedSemconvRegistry, Error> {
-        loader::load_semconv_repository(registry_repo, follow_symlinks)
+        loader::load_semconv_repository(registry_repo, follow_symlinks, cache)
     }
 }
 
@@ -154,6 +191,7 @@ impl SchemaResolver {
 mod tests {
     use crate::SchemaResolver;
     use std::collections::HashSet;
+    use weaver_common::cache::CacheSettings;
     use weaver_common::diagnostic::DiagnosticMessages;
     use weaver_common::result::WResult;
     use weaver_common::vdir::VirtualDirectoryPath;
@@ -166,11 +204,11 @@ mod tests {
         /// Helper to load a specific repository and resolve with the given include flag.
         fn check_semconv_load_and_resolve(registry_repo: RegistryRepo, include_unreferenced: bool) {
             let mut diag_msgs = DiagnosticMessages::empty();
-            let loaded = SchemaResolver::load_semconv_repository(registry_repo, false)
+            let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
                 .capture_non_fatal_errors(&mut diag_msgs)
                 .expect("Failed to load the registry");
             // println!("Loaded registry: {loaded}");
-            let resolved = SchemaResolver::resolve(loaded, include_unreferenced);
+            let resolved = SchemaResolver::resolve(loaded, include_unreferenced, None);
             match resolved {
                 WResult::Ok(resolved_registry) | WResult::OkWithNFEs(resolved_registry, _) => {
                     // TODO - handle includes *and* include unreferenced.
@@ -266,7 +304,8 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/multi-registry/custom_registry".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
         // test with the `include_unreferenced` flag set to false
         check_semconv_load_and_resolve(registry_repo.clone(), false);
         // test with the `include_unreferenced` flag set to true
@@ -280,8 +319,9 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/multi-registry/app_registry".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
-        let result = SchemaResolver::load_semconv_repository(registry_repo, true);
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
+        let result = SchemaResolver::load_semconv_repository(registry_repo, true, None);
 
         match result {
             WResult::Ok(loaded) | WResult::OkWithNFEs(loaded, _) => {
@@ -313,7 +353,7 @@ mod tests {
                 );
 
                 // Now test the resolved registry content
-                let resolved_result = SchemaResolver::resolve(loaded, false);
+                let resolved_result = SchemaResolver::resolve(loaded, false, None);
 
                 match resolved_result {
                     WResult::Ok(resolved_registry) | WResult::OkWithNFEs(resolved_registry, _) => {
@@ -403,13 +443,14 @@ mod tests {
             path: "data/registry-test-v2-dep/consumer_registry".to_owned(),
         };
 
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
         let mut diag_msgs = DiagnosticMessages::empty();
-        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false)
+        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
             .capture_non_fatal_errors(&mut diag_msgs)
             .expect("Failed to load consumer registry");
 
-        let resolved = SchemaResolver::resolve(loaded, false);
+        let resolved = SchemaResolver::resolve(loaded, false, None);
         match resolved {
             WResult::Ok(resolved_registry) | WResult::OkWithNFEs(resolved_registry, _) => {
                 let metrics = resolved_registry.groups(GroupType::Metric);
@@ -477,13 +518,14 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/registry-test-v2-dep/app_registry".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
         let mut diag_msgs = DiagnosticMessages::empty();
-        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false)
+        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
             .capture_non_fatal_errors(&mut diag_msgs)
             .expect("Failed to load app registry");
 
-        let resolved = SchemaResolver::resolve(loaded, false);
+        let resolved = SchemaResolver::resolve(loaded, false, None);
         match resolved {
             WResult::Ok(resolved_registry) | WResult::OkWithNFEs(resolved_registry, _) => {
                 let metrics = resolved_registry.groups(GroupType::Metric);
@@ -512,4 +554,117 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_multiple_dependencies() -> Result<(), weaver_semconv::Error> {
+        // app -> [otel, platform -> otel]
+        let registry_path = VirtualDirectoryPath::LocalFolder {
+            path: "data/multi-dependency/app_registry".to_owned(),
+        };
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
+        let mut diag_msgs = DiagnosticMessages::empty();
+        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
+            .capture_non_fatal_errors(&mut diag_msgs)
+            .expect("Failed to load app registry");
+        let registry_names = loaded.registry_names();
+        assert_eq!(
+            registry_names,
+            vec![
+                "app.example.com/schemas",
+                "opentelemetry.io/schemas",
+                "platform.example.com/schemas",
+                "opentelemetry.io/schemas",
+            ]
+        );
+
+        let WResult::OkWithNFEs(resolved_registry, errors) =
+            SchemaResolver::resolve(loaded, false, None)
+        else {
+            panic!("Failed to resolve app registry");
+        };
+        assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
+
+        // Attributes are resolved across the dependencies.
+        let group = resolved_registry
+            .group("app.example")
+            .expect("app.example not found");
+        let lineage = group.lineage.as_ref().expect("Missing lineage");
+        assert_eq!(
+            lineage
+                .attribute("error.type")
+                .and_then(|l| l.dependency.as_deref()),
+            Some("https://opentelemetry.io/schemas/1.30.0")
+        );
+        assert_eq!(
+            lineage
+                .attribute("platform.team")
+                .and_then(|l| l.dependency.as_deref()),
+            Some("https://platform.example.com/schemas/1.0.0")
+        );
+
+        // Groups are imported from all the dependencies.
+        for (group_id, dependency) in [
+            (
+                "metric.example.counter",
+                "https://opentelemetry.io/schemas/1.30.0",
+            ),
+            (
+                "metric.platform.request.count",
+                "https://platform.example.com/schemas/1.0.0",
+            ),
+        ] {
+            let group = resolved_registry
+                .group(group_id)
+                .unwrap_or_else(|| panic!("{group_id} not found"));
+            assert_eq!(
+                group.lineage.as_ref().and_then(|l| l.dependency.as_deref()),
+                Some(dependency)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dependency_name_collision() -> Result<(), weaver_semconv::Error> {
+        let registry_path = VirtualDirectoryPath::LocalFolder {
+            path: "data/multi-dependency/conflict_registry".to_owned(),
+        };
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
+        let mut diag_msgs = DiagnosticMessages::empty();
+        let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
+            .capture_non_fatal_errors(&mut diag_msgs)
+            .expect("Failed to load conflict registry");
+
+        let WResult::OkWithNFEs(resolved_registry, errors) =
+            SchemaResolver::resolve(loaded, false, None)
+        else {
+            panic!("Failed to resolve conflict registry");
+        };
+        let collisions: Vec<_> = errors
+            .iter()
+            .filter_map(|e| match e {
+                crate::Error::DependencyNameCollision {
+                    kind,
+                    name,
+                    dependencies,
+                } => Some((kind.as_str(), name.as_str(), dependencies.len())),
+                _ => None,
+            })
+            .collect();
+        assert!(collisions.contains(&("attribute", "error.type", 2)));
+        assert!(collisions.contains(&("group", "otel.registry", 2)));
+
+        // The definition of the first dependency is used.
+        let group = resolved_registry
+            .group("conflict.example")
+            .expect("conflict.example not found");
+        let attr = resolved_registry
+            .catalog
+            .attribute(&group.attributes[0])
+            .expect("Failed to resolve attribute ref");
+        assert_eq!(attr.brief, "The error type.");
+        Ok(())
+    }
 }
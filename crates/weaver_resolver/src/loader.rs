@@ -5,7 +5,8 @@ use rayon::iter::ParallelIterator;
 use rayon::iter::{IntoParallelIterator, ParallelBridge};
 use std::collections::HashSet;
 use std::fmt::Display;
-use std::path::MAIN_SEPARATOR;
+use std::path::{Path, MAIN_SEPARATOR};
+use weaver_common::cache::CacheSettings;
 use weaver_common::vdir::{VirtualDirectory, VirtualDirectoryPath};
 use weaver_semconv::registry::SemConvRegistry;
 
@@ -13,9 +14,11 @@ use walkdir::DirEntry;
 use weaver_common::result::WResult;
 use weaver_resolved_schema::v2::ResolvedTelemetrySchema as V2Schema;
 use weaver_resolved_schema::ResolvedTelemetrySchema as V1Schema;
+use weaver_semconv::provenance::Provenance;
 use weaver_semconv::registry_repo::{RegistryRepo, LEGACY_REGISTRY_MANIFEST, REGISTRY_MANIFEST};
 use weaver_semconv::{group::ImportsWithProvenance, semconv::SemConvSpecWithProvenance};
 
+use crate::cache::ResolutionCache;
 use crate::Error;
 
 /// Maximum allowed depth for registry dependency chains.
@@ -34,6 +37,9 @@ pub enum LoadedSemconvRegistry {
         imports: Vec<ImportsWithProvenance>,
         /// The dependencies of this repository.
         dependencies: Vec<LoadedSemconvRegistry>,
+        /// The fingerprint of the files of this repository and of its dependencies, used as
+        /// the key of the resolution cache (`None` when the cache is disabled).
+        fingerprint: Option<String>,
     },
     /// The semconv repository is already resolved and can be used as-is.
     Resolved(V1Schema),
@@ -48,8 +54,8 @@ impl LoadedSemconvRegistry {
         use std::io::Write;
         use weaver_common::vdir::VirtualDirectoryPath;
         let path: VirtualDirectoryPath = "data".try_into().expect("Bad fake path for test");
-        let repo =
-            RegistryRepo::try_new(None, &path, &mut vec![]).map_err(|e| Error::InvalidUrl {
+        let repo = RegistryRepo::try_new(None, &path, &CacheSettings::default(), &mut vec![])
+            .map_err(|e| Error::InvalidUrl {
                 url: "test string".to_owned(),
                 error: format!("{e}"),
             })?;
@@ -76,6 +82,7 @@ impl LoadedSemconvRegistry {
             specs: vec![spec_with_provenance],
             imports: vec![],
             dependencies: vec![],
+            fingerprint: None,
         })
     }
 
@@ -137,10 +144,7 @@ impl Display for LoadedSemconvRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LoadedSemconvRegistry::Unresolved {
-                repo,
-                specs: _,
-                imports: _,
-                dependencies,
+                repo, dependencies, ..
             } => write!(
                 f,
                 "{} - [{}]",
@@ -157,9 +161,11 @@ impl Display for LoadedSemconvRegistry {
 ///
 /// Note: This may load in a definition (raw) repository *or* an already resolved repository.
 ///       When loading a raw repository, dependencies will also be loaded.
+/// The files found in the resolution `cache`, if any, are not parsed again.
 pub(crate) fn load_semconv_repository(
     registry_repo: RegistryRepo,
     follow_symlinks: bool,
+    cache: Option<&ResolutionCache>,
 ) -> WResult<LoadedSemconvRegistry, Error> {
     // This method simply sets up the resolution state and delegates to the actual work.
     let mut visited_registries = HashSet::new();
@@ -167,6 +173,7 @@ pub(crate) fn load_semconv_repository(
     load_semconv_repository_recursive(
         registry_repo,
         follow_symlinks,
+        cache,
         MAX_DEPENDENCY_DEPTH,
         &mut visited_registries,
         &mut dependency_chain,
@@ -174,10 +181,12 @@ pub(crate) fn load_semconv_repository(
 }
 
 /// Recursively iterates over semconv dependencies and loads their definition.
-/// Note: Prevents circular dependencies.
+/// Note: Prevents circular dependencies. A registry can still be reached through several
+/// dependencies (e.g. two dependencies extending the same registry).
 fn load_semconv_repository_recursive(
     registry_repo: RegistryRepo,
     follow_symlinks: bool,
+    cache: Option<&ResolutionCache>,
     max_dependency_depth: u32,
     visited_registries: &mut HashSet<String>,
     dependency_chain: &mut Vec<String>,
@@ -202,20 +211,46 @@ fn load_semconv_repository_recursive(
     let _ = visited_registries.insert(registry_name.clone());
     dependency_chain.push(registry_name.clone());
 
+    let result = load_semconv_repository_with_dependencies(
+        registry_repo,
+        follow_symlinks,
+        cache,
+        max_dependency_depth,
+        visited_registries,
+        dependency_chain,
+    );
+
+    // The registry is no longer part of the current dependency chain.
+    let _ = visited_registries.remove(&registry_name);
+    let _ = dependency_chain.pop();
+    result
+}
+
+/// Loads a semantic convention repository and its dependencies, in the order in which
+/// they are declared in the manifest.
+fn load_semconv_repository_with_dependencies(
+    registry_repo: RegistryRepo,
+    follow_symlinks: bool,
+    cache: Option<&ResolutionCache>,
+    max_dependency_depth: u32,
+    visited_registries: &mut HashSet<String>,
+    dependency_chain: &mut Vec<String>,
+) -> WResult<LoadedSemconvRegistry, Error> {
     // Either load a fully resolved repository, or read in raw files.
     if let Some(manifest) = registry_repo.manifest() {
         if let Some(resolved_url) = registry_repo.resolved_schema_uri() {
-            load_resolved_repository(&resolved_url)
+            load_resolved_repository(&resolved_url, registry_repo.cache_settings())
         } else {
-            if manifest.dependencies().len() > 1 {
-                todo!("Multiple dependencies is not supported yet.")
-            }
             // Load dependencies.
             let mut loaded_dependencies = vec![];
             let mut non_fatal_errors: Vec<Error> = vec![];
             for d in manifest.dependencies().iter() {
                 let mut semconv_nfes: Vec<weaver_semconv::Error> = vec![];
-                match RegistryRepo::try_new_dependency(d, &mut semconv_nfes) {
+                match RegistryRepo::try_new_dependency(
+                    d,
+                    registry_repo.cache_settings(),
+                    &mut semconv_nfes,
+                ) {
                     Ok(d_repo) => {
                         non_fatal_errors
                             .extend(semconv_nfes.into_iter().map(Error::FailToResolveDefinition));
@@ -223,6 +258,7 @@ fn load_semconv_repository_recursive(
                         match load_semconv_repository_recursive(
                             d_repo,
                             follow_symlinks,
+                            cache,
                             max_dependency_depth - 1,
                             visited_registries,
                             dependency_chain,
@@ -240,28 +276,34 @@ fn load_semconv_repository_recursive(
             }
             // Now load the raw repository.
             // TODO - Allow ignoring dependency warnings - https://github.com/open-telemetry/weaver/issues/1126.
-            load_definition_repository(registry_repo, follow_symlinks, loaded_dependencies)
+            load_definition_repository(registry_repo, follow_symlinks, cache, loaded_dependencies)
                 .extend_non_fatal_errors(non_fatal_errors)
         }
     } else {
         // This is a raw repository with *no* manifest.
         // TODO - issue a warning that manifest will be required w/ 2.0 to allow publishing.
-        load_definition_repository(registry_repo, follow_symlinks, vec![])
+        load_definition_repository(registry_repo, follow_symlinks, cache, vec![])
     }
 }
 
 /// Loads a resolved repository.
-fn load_resolved_repository(path: &VirtualDirectoryPath) -> WResult<LoadedSemconvRegistry, Error> {
+fn load_resolved_repository(
+    path: &VirtualDirectoryPath,
+    cache_settings: &CacheSettings,
+) -> WResult<LoadedSemconvRegistry, Error> {
     // TODO - should we handle V1 and V2?
-    match from_vdir(path) {
+    match from_vdir(path, cache_settings) {
         Ok(resolved) => WResult::Ok(LoadedSemconvRegistry::ResolvedV2(resolved)),
         Err(err) => WResult::FatalErr(err),
     }
 }
 
 /// Reads a serialized object with serde from the given virtual directory path.
-fn from_vdir<T: serde::de::DeserializeOwned>(f: &VirtualDirectoryPath) -> Result<T, Error> {
-    let path = VirtualDirectory::try_new(f).map_err(|e| Error::InvalidUrl {
+fn from_vdir<T: serde::de::DeserializeOwned>(
+    f: &VirtualDirectoryPath,
+    cache_settings: &CacheSettings,
+) -> Result<T, Error> {
+    let path = VirtualDirectory::try_new(f, cache_settings).map_err(|e| Error::InvalidUrl {
         url: f.to_string(),
         error: format!("Invalid weaver path reference: {e}"),
     })?;
@@ -278,6 +320,7 @@ fn from_vdir<T: serde::de::DeserializeOwned>(f: &VirtualDirectoryPath) -> Result
 fn load_definition_repository(
     registry_repo: RegistryRepo,
     follow_symlinks: bool,
+    cache: Option<&ResolutionCache>,
     dependencies: Vec<LoadedSemconvRegistry>,
 ) -> WResult<LoadedSemconvRegistry, Error> {
     // Define helper functions for filtering files.
@@ -300,6 +343,21 @@ fn load_definition_repository(
     }
     let local_path = registry_repo.path().to_path_buf();
     let registry_path_repr = registry_repo.registry_path_repr();
+    // Replace the local path with the git URL combined with the relative path
+    // of the semantic convention file.
+    let map_path = |path: String| {
+        let prefix = local_path
+            .to_str()
+            .map(|s| s.to_owned())
+            .unwrap_or_default();
+        if registry_path_repr.ends_with(MAIN_SEPARATOR) {
+            let relative_path = &path[prefix.len()..];
+            format!("{registry_path_repr}{relative_path}")
+        } else {
+            let relative_path = &path[prefix.len() + 1..];
+            format!("{registry_path_repr}/{relative_path}")
+        }
+    };
 
     // Loads the semantic convention specifications from the git repo.
     // All yaml files are recursively loaded and parsed in parallel from
@@ -317,30 +375,31 @@ fn load_definition_repository(
                     }
 
                     // TODO - less confusing way to load semconv specs.
-                    vec![SemConvRegistry::semconv_spec_from_file(
-                        registry_repo.name(),
-                        entry.path(),
-                        |path| {
-                            // Replace the local path with the git URL combined with the relative path
-                            // of the semantic convention file.
-                            let prefix = local_path
-                                .to_str()
-                                .map(|s| s.to_owned())
-                                .unwrap_or_default();
-                            if registry_path_repr.ends_with(MAIN_SEPARATOR) {
-                                let relative_path = &path[prefix.len()..];
-                                format!("{registry_path_repr}{relative_path}")
-                            } else {
-                                let relative_path = &path[prefix.len() + 1..];
-                                format!("{registry_path_repr}/{relative_path}")
-                            }
-                        },
-                    )]
+                    let load = || {
+                        SemConvRegistry::semconv_spec_from_file(
+                            registry_repo.name(),
+                            entry.path(),
+                            map_path,
+                        )
+                    };
+                    match cache {
+                        Some(cache) => vec![load_cached_spec(
+                            cache,
+                            registry_repo.name(),
+                            entry.path(),
+                            map_path,
+                            load,
+                        )],
+                        None => vec![(load(), None)],
+                    }
                     .into_par_iter()
                 }
-                Err(e) => vec![WResult::FatalErr(weaver_semconv::Error::SemConvSpecError {
-                    error: e.to_string(),
-                })]
+                Err(e) => vec![(
+                    WResult::FatalErr(weaver_semconv::Error::SemConvSpecError {
+                        error: e.to_string(),
+                    }),
+                    None,
+                )]
                 .into_par_iter(),
             }
         })
@@ -348,12 +407,14 @@ fn load_definition_repository(
 
     let mut non_fatal_errors = vec![];
     let mut specs = vec![];
+    let mut spec_keys = vec![];
     let mut imports = vec![];
     // Process all the results of the previous parallel processing.
     // The first fatal error will stop the processing and return the error.
     // Otherwise, all non-fatal errors will be collected and returned along
     // with the result.
-    for r in result {
+    for (r, spec_key) in result {
+        spec_keys.extend(spec_key);
         match r {
             WResult::Ok(t) => specs.push(t),
             WResult::OkWithNFEs(t, nfes) => {
@@ -375,6 +436,13 @@ fn load_definition_repository(
         });
     }
 
+    // The fingerprint is only known if the key of every file is.
+    let fingerprint = cache
+        .filter(|_| spec_keys.len() == specs.len())
+        .and_then(|_| {
+            ResolutionCache::fingerprint(&registry_repo, follow_symlinks, spec_keys, &dependencies)
+        });
+
     // Create loaded repository, pulling imports, specs, etc.
     WResult::OkWithNFEs(
         LoadedSemconvRegistry::Unresolved {
@@ -382,6 +450,7 @@ fn load_definition_repository(
             specs,
             imports,
             dependencies,
+            fingerprint,
         },
         non_fatal_errors
             .into_iter()
@@ -390,12 +459,50 @@ fn load_definition_repository(
     )
 }
 
+/// Loads a semantic convention file from the resolution cache, or with `load` and stores
+/// it in the cache if it has no warnings. Returns the key of the file in the cache, if any.
+fn load_cached_spec<F, L>(
+    cache: &ResolutionCache,
+    registry_id: &str,
+    path: &Path,
+    map_path: F,
+    load: L,
+) -> (
+    WResult<SemConvSpecWithProvenance, weaver_semconv::Error>,
+    Option<String>,
+)
+where
+    F: Fn(String) -> String,
+    L: Fn() -> WResult<SemConvSpecWithProvenance, weaver_semconv::Error>,
+{
+    let Some(content) = std::fs::read(path)
+        .ok()
+        .and_then(|content| String::from_utf8(content).ok())
+    else {
+        // The error, if any, is reported by the regular loading.
+        return (load(), None);
+    };
+    let provenance = Provenance::new(registry_id, &map_path(path.display().to_string()));
+    let key = ResolutionCache::spec_key(&provenance, content.as_bytes());
+    if let Some(spec) = cache.load_spec(&key, provenance, &content) {
+        return (WResult::Ok(spec), Some(key));
+    }
+    let result = load();
+    if let WResult::Ok(spec) | WResult::OkWithNFEs(spec, _) = &result {
+        if !result.has_errors() {
+            cache.store_spec(&key, spec);
+        }
+    }
+    (result, Some(key))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
     use weaver_common::{
-        diagnostic::DiagnosticMessages, result::WResult, vdir::VirtualDirectoryPath,
+        cache::CacheSettings, diagnostic::DiagnosticMessages, result::WResult,
+        vdir::VirtualDirectoryPath,
     };
     use weaver_semconv::registry_repo::RegistryRepo;
 
@@ -409,9 +516,10 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/multi-registry/custom_registry".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
         let mut diag_msgs = DiagnosticMessages::empty();
-        let loaded = load_semconv_repository(registry_repo, false)
+        let loaded = load_semconv_repository(registry_repo, false, None)
             .capture_non_fatal_errors(&mut diag_msgs)?;
         // Assert that we've loaded the ACME repository and the dependency of OTEL.
         if let LoadedSemconvRegistry::Unresolved {
@@ -419,6 +527,7 @@ mod tests {
             specs,
             imports,
             dependencies,
+            ..
         } = loaded
         {
             assert_eq!("acme.com/schemas", repo.name());
@@ -430,6 +539,7 @@ mod tests {
                 specs,
                 imports,
                 dependencies,
+                ..
             }] = &dependencies.as_slice()
             {
                 assert_eq!("opentelemetry.io/schemas", repo.name());
@@ -451,7 +561,8 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/multi-registry/app_registry".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
 
         // Try with depth limit of 1 - should fail at acme->otel transition
         let mut visited_registries = HashSet::new();
@@ -459,6 +570,7 @@ mod tests {
         let result = load_semconv_repository_recursive(
             registry_repo,
             true,
+            None,
             1,
             &mut visited_registries,
             &mut dependency_chain,
@@ -486,8 +598,9 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "data/circular-registry-test/registry_a".to_owned(),
         };
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
-        let result = load_semconv_repository(registry_repo, true);
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
+        let result = load_semconv_repository(registry_repo, true, None);
 
         match result {
             WResult::FatalErr(fatal) => {
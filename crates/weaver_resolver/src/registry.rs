@@ -3,7 +3,7 @@
 //! Functions to resolve a semantic convention registry.
 
 use crate::attribute::AttributeCatalog;
-use crate::dependency::{ImportableDependency, ResolvedDependency};
+use crate::dependency::{check_dependency_collisions, ImportableDependency, ResolvedDependency};
 use crate::Error;
 use crate::Error::{DuplicateGroupId, DuplicateGroupName, DuplicateMetricName};
 use itertools::Itertools;
@@ -20,6 +20,7 @@ use weaver_semconv::group::{GroupSpecWithProvenance, GroupType, ImportsWithProve
 use weaver_semconv::provenance::Provenance;
 use weaver_semconv::registry_repo::RegistryRepo;
 use weaver_semconv::semconv::{SemConvSpecV1WithProvenance, SemConvSpecWithProvenance};
+use weaver_semconv::source_location::{GroupSourceLocations, SourceDefinition};
 use weaver_semconv::v2::attribute_group::AttributeGroupVisibilitySpec;
 
 use crate::dependency::GroupSummary;
@@ -65,6 +66,19 @@ pub struct UnresolvedGroup {
 
     /// The provenance of the group (URL or path).
     pub provenance: Provenance,
+
+    /// The definitions of the group and of its attributes in the source file (if known).
+    #[serde(skip)]
+    pub source_locations: Option<GroupSourceLocations>,
+}
+
+impl UnresolvedGroup {
+    /// Returns the definition of the group in its source file (if known).
+    fn group_definition(&self) -> Option<Box<SourceDefinition>> {
+        self.source_locations
+            .as_ref()
+            .map(|locations| Box::new(locations.definition.clone()))
+    }
 }
 
 /// Resolves the semantic convention registry passed as argument and returns
@@ -97,17 +111,26 @@ pub(crate) fn resolve_registry_with_dependencies(
     let groups = specs
         .into_iter()
         .map(|g| g.into_v1())
-        .flat_map(|SemConvSpecV1WithProvenance { spec, provenance }| {
-            spec.groups()
-                .iter()
-                .map(|group| GroupSpecWithProvenance {
-                    spec: group.clone(),
-                    provenance: provenance.clone(),
-                })
-                .collect::<Vec<_>>()
-        })
+        .flat_map(
+            |SemConvSpecV1WithProvenance {
+                 spec,
+                 provenance,
+                 source_locations,
+             }| {
+                spec.groups()
+                    .iter()
+                    .map(|group| GroupSpecWithProvenance {
+                        spec: group.clone(),
+                        provenance: provenance.clone(),
+                        source_locations: source_locations.group(&group.id).cloned(),
+                    })
+                    .collect::<Vec<_>>()
+            },
+        )
         .map(group_from_spec)
         .collect();
+    // Names defined differently by several dependencies are ambiguous.
+    let mut errors = check_dependency_collisions(&dependencies);
     let mut ureg = UnresolvedRegistry {
         registry: Registry::new(repo.registry_path_repr()),
         groups,
@@ -136,7 +159,6 @@ pub(crate) fn resolve_registry_with_dependencies(
     }
 
     // Now we do validations.
-    let mut errors = vec![];
 
     // Note: this will remove all the `groups` from UnresolvedRegistry and create
     // a complete `Registry` that is returned.
@@ -282,6 +304,7 @@ pub fn check_root_attribute_id_duplicates(
         .map(|(attr_id, group_ids)| Error::DuplicateAttributeId {
             attribute_id: attr_id,
             group_ids,
+            definition: None,
         })
         .collect();
     errors.extend(local_errors);
@@ -314,7 +337,14 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
             instrument: group.spec.instrument,
             unit: group.spec.unit,
             name: group.spec.name,
-            lineage: Some(GroupLineage::new(group.provenance.clone())),
+            lineage: Some(
+                GroupLineage::new(group.provenance.clone()).with_location(
+                    group
+                        .source_locations
+                        .as_ref()
+                        .map(GroupSourceLocations::location),
+                ),
+            ),
             display_name: group.spec.display_name,
             body: group.spec.body,
             annotations: group.spec.annotations,
@@ -327,6 +357,7 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
         include_groups: group.spec.include_groups,
         visibility: group.spec.visibility,
         is_v2: group.spec.is_v2,
+        source_locations: group.source_locations,
     }
 }
 
@@ -370,6 +401,7 @@ fn resolve_dependency_imports(
             visibility: None,
             is_v2,
             provenance,
+            source_locations: None,
         });
     }
     Ok(())
@@ -407,10 +439,17 @@ fn resolve_attribute_references(
                 .clone()
                 .into_iter()
                 .filter_map(|attr| {
+                    // Attributes inherited from other groups are not defined in the
+                    // source file of this group.
+                    let definition = unresolved_group
+                        .source_locations
+                        .as_ref()
+                        .and_then(|locations| locations.attributes.get(&attr.spec.id()));
                     let attr_ref = attr_catalog.resolve(
                         &unresolved_group.group.id,
                         &unresolved_group.group.prefix,
                         &attr.spec,
+                        definition.map(|definition| definition.location),
                         unresolved_group.group.lineage.as_mut(),
                         &ureg.dependencies,
                     );
@@ -431,6 +470,10 @@ fn resolve_attribute_references(
                                 group_id: unresolved_group.group.id.clone(),
                                 attribute_ref: r#ref.clone(),
                                 provenance: unresolved_group.provenance.clone(),
+                                definition: definition
+                                    .cloned()
+                                    .map(Box::new)
+                                    .or_else(|| unresolved_group.group_definition()),
                             });
                         }
                         Some(attr)
@@ -587,6 +630,7 @@ fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error
                         group_id: unresolved_group.group.id.clone(),
                         extends_ref: extends.clone(),
                         provenance: unresolved_group.provenance.clone(),
+                        definition: unresolved_group.group_definition(),
                     });
                 }
             } else if !unresolved_group.include_groups.is_empty() {
@@ -605,6 +649,7 @@ fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error
                                 errors.push(Error::DuplicateAttributeId {
                                     group_ids: unresolved_group.include_groups.clone(),
                                     attribute_id: attr.spec.id().clone(),
+                                    definition: unresolved_group.group_definition(),
                                 });
                                 all_resolved = false;
                             } else {
@@ -623,6 +668,7 @@ fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<(), Error
                             group_id: unresolved_group.group.id.clone(),
                             extends_ref: include_group.clone(),
                             provenance: unresolved_group.provenance.clone(),
+                            definition: unresolved_group.group_definition(),
                         });
                         all_resolved = false;
                     }
@@ -929,6 +975,7 @@ mod tests {
 
     use glob::glob;
     use serde::Serialize;
+    use weaver_common::cache::CacheSettings;
     use weaver_common::result::WResult;
     use weaver_common::vdir::VirtualDirectoryPath;
     use weaver_diff::canonicalize_json_string;
@@ -1028,9 +1075,15 @@ mod tests {
                 .try_into()
                 .expect("Failed to parse file directory");
             let loaded = SchemaResolver::load_semconv_repository(
-                RegistryRepo::try_new(Some(schema_url), &location, &mut vec![])
-                    .expect("Failed to load registry"),
+                RegistryRepo::try_new(
+                    Some(schema_url),
+                    &location,
+                    &CacheSettings::default(),
+                    &mut vec![],
+                )
+                .expect("Failed to load registry"),
                 true,
+                None,
             )
             .ignore(|e| {
                 // Ignore prefix errors on tests of prefix.
@@ -1076,7 +1129,8 @@ mod tests {
             .expect("Failed to load semconv specs");
 
             // We need to resolve dependencies.
-            let schema = SchemaResolver::resolve(loaded, false).into_result_failing_non_fatal();
+            let schema =
+                SchemaResolver::resolve(loaded, false, None).into_result_failing_non_fatal();
 
             // Check presence of an `expected-errors.json` file.
             // If the file is present, the test is expected to fail with the errors in the file.
@@ -1201,7 +1255,7 @@ mod tests {
     fn create_registry_from_string(registry_spec: &str) -> WResult<Registry, crate::Error> {
         let loaded = LoadedSemconvRegistry::create_from_string(registry_spec)
             .expect("Failed to load semconv spec");
-        SchemaResolver::resolve(loaded, false).map(|schema| schema.registry)
+        SchemaResolver::resolve(loaded, false, None).map(|schema| schema.registry)
     }
 
     #[test]
@@ -1269,12 +1323,17 @@ groups:
         let schema_url: SchemaUrl = "https://local/registry/1.0.0"
             .try_into()
             .expect("Should be valid schema url");
-        let repo = RegistryRepo::try_new(Some(schema_url), &path, &mut vec![])
-            .expect("Failed to load registry");
-        let loaded =
-            SchemaResolver::load_semconv_repository(repo, true).into_result_failing_non_fatal()?;
+        let repo = RegistryRepo::try_new(
+            Some(schema_url),
+            &path,
+            &CacheSettings::default(),
+            &mut vec![],
+        )
+        .expect("Failed to load registry");
+        let loaded = SchemaResolver::load_semconv_repository(repo, true, None)
+            .into_result_failing_non_fatal()?;
         let resolved_schema =
-            SchemaResolver::resolve(loaded, false).into_result_failing_non_fatal()?;
+            SchemaResolver::resolve(loaded, false, None).into_result_failing_non_fatal()?;
 
         // Get the resolved registry by its ID.
         let resolved_registry = &resolved_schema.registry;
@@ -1371,6 +1430,7 @@ groups:
                     registry_id: Default::default(),
                     path: Default::default(),
                 },
+                source_locations: None,
             }],
             imports: vec![],
             dependencies: vec![],
@@ -1459,6 +1519,7 @@ groups:
                         registry_id: Default::default(),
                         path: Default::default(),
                     },
+                    source_locations: None,
                 },
                 UnresolvedGroup {
                     group: Group {
@@ -1493,6 +1554,7 @@ groups:
                         registry_id: Default::default(),
                         path: Default::default(),
                     },
+                    source_locations: None,
                 },
                 UnresolvedGroup {
                     group: Group {
@@ -1527,6 +1589,7 @@ groups:
                         registry_id: Default::default(),
                         path: Default::default(),
                     },
+                    source_locations: None,
                 },
             ],
             imports: vec![],
@@ -0,0 +1,368 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Inverted index ranking the searchable items with BM25.
+//!
+//! Each item is indexed as a document made of several fields (id, aliases, brief, note,
+//! enum members and annotations), each field being weighted by a boost. A query matches
+//! the documents containing all its terms. A query term matches the indexed terms:
+//! - equal to it,
+//! - starting with it (search-as-you-type), with a lower weight,
+//! - within a small edit distance of it when it is not an indexed term (typos), with an
+//!   even lower weight.
+
+use std::collections::HashMap;
+
+/// BM25 term frequency saturation.
+const K1: f64 = 1.2;
+/// BM25 field length normalization.
+const B: f64 = 0.75;
+
+/// Weight of an indexed term starting with the query term.
+const PREFIX_WEIGHT: f64 = 0.7;
+/// Weight of an indexed term one edit away from the query term.
+const FUZZY_WEIGHT_1: f64 = 0.5;
+/// Weight of an indexed term two edits away from the query term.
+const FUZZY_WEIGHT_2: f64 = 0.3;
+
+/// Bonus for a query equal to the id of the item.
+const EXACT_ID_BONUS: f64 = 100.0;
+/// Bonus for a query equal to an alias of the item (a name it has been renamed from).
+const EXACT_ALIAS_BONUS: f64 = 80.0;
+/// Bonus for an id starting with the query.
+const ID_PREFIX_BONUS: f64 = 10.0;
+
+/// The fields of an indexed document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Field {
+    /// The key, name or type of the item.
+    Id,
+    /// The names the item has been renamed from (see `renamed_to`).
+    Alias,
+    /// The brief description.
+    Brief,
+    /// The note.
+    Note,
+    /// The ids, values and briefs of the enum members.
+    Members,
+    /// The keys and the values of the annotations.
+    Annotations,
+}
+
+const FIELD_COUNT: usize = 6;
+
+impl Field {
+    fn index(self) -> usize {
+        match self {
+            Field::Id => 0,
+            Field::Alias => 1,
+            Field::Brief => 2,
+            Field::Note => 3,
+            Field::Members => 4,
+            Field::Annotations => 5,
+        }
+    }
+
+    fn boost(self) -> f64 {
+        match self {
+            Field::Id => 4.0,
+            Field::Alias => 3.0,
+            Field::Brief => 1.5,
+            Field::Members => 1.0,
+            Field::Note => 0.5,
+            Field::Annotations => 0.5,
+        }
+    }
+}
+
+/// The text of a document, per field.
+#[derive(Debug, Default)]
+pub(crate) struct Document {
+    id: String,
+    aliases: Vec<String>,
+    fields: Vec<(Field, String)>,
+}
+
+impl Document {
+    /// Creates a document for the item with the given id.
+    pub(crate) fn new(id: &str) -> Self {
+        Self {
+            id: id.to_lowercase(),
+            aliases: vec![],
+            fields: vec![(Field::Id, id.to_owned())],
+        }
+    }
+
+    /// Adds a name the item has been renamed from.
+    pub(crate) fn add_alias(&mut self, alias: &str) {
+        self.aliases.push(alias.to_lowercase());
+        self.add(Field::Alias, alias);
+    }
+
+    /// Adds some text to a field.
+    pub(crate) fn add(&mut self, field: Field, text: &str) {
+        if !text.is_empty() {
+            self.fields.push((field, text.to_owned()));
+        }
+    }
+}
+
+/// An occurrence of a term in a field of a document.
+#[derive(Debug)]
+struct Posting {
+    document: usize,
+    field: Field,
+    frequency: u32,
+}
+
+/// An inverted index of documents, identified by their position.
+#[derive(Debug, Default)]
+pub(crate) struct InvertedIndex {
+    /// The occurrences of each term.
+    postings: HashMap<String, Vec<Posting>>,
+    /// The number of documents containing each term.
+    document_frequencies: HashMap<String, usize>,
+    /// The indexed terms, sorted for prefix lookups.
+    terms: Vec<String>,
+    /// The number of terms in each field of each document.
+    field_lengths: Vec<[u32; FIELD_COUNT]>,
+    /// The average number of terms of each field.
+    average_field_lengths: [f64; FIELD_COUNT],
+    /// The lowercase id and aliases of each document.
+    names: Vec<(String, Vec<String>)>,
+}
+
+impl InvertedIndex {
+    /// Indexes the given documents.
+    pub(crate) fn new(documents: Vec<Document>) -> Self {
+        let mut index = InvertedIndex::default();
+        let mut totals = [0u64; FIELD_COUNT];
+        for (position, document) in documents.into_iter().enumerate() {
+            let mut lengths = [0u32; FIELD_COUNT];
+            let mut frequencies: HashMap<(String, Field), u32> = HashMap::new();
+            for (field, text) in &document.fields {
+                for term in tokenize(text) {
+                    lengths[field.index()] += 1;
+                    *frequencies.entry((term, *field)).or_default() += 1;
+                }
+            }
+            let mut document_terms: Vec<&String> = vec![];
+            for ((term, field), frequency) in &frequencies {
+                index
+                    .postings
+                    .entry(term.clone())
+                    .or_default()
+                    .push(Posting {
+                        document: position,
+                        field: *field,
+                        frequency: *frequency,
+                    });
+                if !document_terms.contains(&term) {
+                    document_terms.push(term);
+                }
+            }
+            for term in document_terms {
+                *index.document_frequencies.entry(term.clone()).or_default() += 1;
+            }
+            for (total, length) in totals.iter_mut().zip(lengths) {
+                *total += u64::from(length);
+            }
+            index.field_lengths.push(lengths);
+            index.names.push((document.id, document.aliases));
+        }
+        let document_count = index.field_lengths.len().max(1) as f64;
+        for (average, total) in index.average_field_lengths.iter_mut().zip(totals) {
+            *average = total as f64 / document_count;
+        }
+        index.terms = index.postings.keys().cloned().collect();
+        index.terms.sort();
+        index
+    }
+
+    /// Returns the score of each document matching all the terms of the query.
+    pub(crate) fn search(&self, query: &str) -> HashMap<usize, f64> {
+        let query_terms = tokenize(query);
+        if query_terms.is_empty() {
+            return HashMap::new();
+        }
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        let mut matched_terms: HashMap<usize, usize> = HashMap::new();
+        for query_term in &query_terms {
+            // The score of a query term in a document is the best score of its expansions.
+            let mut term_scores: HashMap<usize, f64> = HashMap::new();
+            for (term, weight) in self.expand(query_term) {
+                for (document, score) in self.term_scores(term) {
+                    let score = score * weight;
+                    let best = term_scores.entry(document).or_default();
+                    if score > *best {
+                        *best = score;
+                    }
+                }
+            }
+            for (document, score) in term_scores {
+                *scores.entry(document).or_default() += score;
+                *matched_terms.entry(document).or_default() += 1;
+            }
+        }
+        scores.retain(|document, _| matched_terms.get(document) == Some(&query_terms.len()));
+
+        let query = query.trim().to_lowercase();
+        for (document, score) in scores.iter_mut() {
+            let (id, aliases) = &self.names[*document];
+            if *id == query {
+                *score += EXACT_ID_BONUS;
+            } else if aliases.contains(&query) {
+                *score += EXACT_ALIAS_BONUS;
+            } else if id.starts_with(&query) {
+                *score += ID_PREFIX_BONUS;
+            }
+        }
+        scores
+    }
+
+    /// Returns the indexed terms matching a query term, with their weight.
+    fn expand<'a>(&'a self, query_term: &str) -> Vec<(&'a str, f64)> {
+        let mut expansions = vec![];
+        let exact = self.postings.get_key_value(query_term);
+        if let Some((term, _)) = exact {
+            expansions.push((term.as_str(), 1.0));
+        }
+        if query_term.chars().count() >= 2 {
+            let start = self
+                .terms
+                .partition_point(|term| term.as_str() < query_term);
+            expansions.extend(
+                self.terms[start..]
+                    .iter()
+                    .take_while(|term| term.starts_with(query_term))
+                    .filter(|term| term.as_str() != query_term)
+                    .map(|term| (term.as_str(), PREFIX_WEIGHT)),
+            );
+        }
+        if exact.is_none() {
+            let length = query_term.chars().count();
+            let max_distance = match length {
+                0..=3 => 0,
+                4..=7 => 1,
+                _ => 2,
+            };
+            if max_distance > 0 {
+                for term in &self.terms {
+                    if term.chars().count().abs_diff(length) > max_distance {
+                        continue;
+                    }
+                    match strsim::levenshtein(query_term, term) {
+                        1 => expansions.push((term.as_str(), FUZZY_WEIGHT_1)),
+                        2 if max_distance >= 2 => expansions.push((term.as_str(), FUZZY_WEIGHT_2)),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        expansions
+    }
+
+    /// Returns the BM25 score of a term in each document containing it.
+    fn term_scores(&self, term: &str) -> HashMap<usize, f64> {
+        let mut scores = HashMap::new();
+        let Some(postings) = self.postings.get(term) else {
+            return scores;
+        };
+        let document_count = self.field_lengths.len() as f64;
+        let document_frequency = self.document_frequencies.get(term).copied().unwrap_or(0) as f64;
+        let idf =
+            ((document_count - document_frequency + 0.5) / (document_frequency + 0.5)).ln_1p();
+        for posting in postings {
+            let field = posting.field.index();
+            let length = f64::from(self.field_lengths[posting.document][field]);
+            let average = self.average_field_lengths[field].max(1.0);
+            let frequency = f64::from(posting.frequency);
+            let score =
+                idf * frequency * (K1 + 1.0) / (frequency + K1 * (1.0 - B + B * length / average));
+            *scores.entry(posting.document).or_default() += posting.field.boost() * score;
+        }
+        scores
+    }
+}
+
+/// Splits a text into lowercase alphanumeric terms.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(id: &str, brief: &str) -> Document {
+        let mut document = Document::new(id);
+        document.add(Field::Brief, brief);
+        document
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("http.request.method_original (HTTP)"),
+            vec!["http", "request", "method", "original", "http"]
+        );
+        assert!(tokenize(" ._ ").is_empty());
+    }
+
+    #[test]
+    fn test_search_ranks_with_bm25() {
+        let index = InvertedIndex::new(vec![
+            document("http.request.method", "HTTP request method."),
+            document("http.response.status_code", "HTTP response status code."),
+            document("db.system", "The database system, e.g. an HTTP based one."),
+        ]);
+
+        // All the terms of the query must match.
+        let scores = index.search("http method");
+        assert_eq!(scores.keys().collect::<Vec<_>>(), vec![&0]);
+
+        // Terms found in the id rank higher than terms found in the brief.
+        let scores = index.search("http");
+        assert_eq!(scores.len(), 3);
+        assert!(scores[&0] > scores[&2]);
+        assert!(scores[&1] > scores[&2]);
+
+        // Exact ids get a bonus.
+        let scores = index.search("db.system");
+        assert!(scores[&2] > EXACT_ID_BONUS);
+    }
+
+    #[test]
+    fn test_search_prefix_and_fuzzy_terms() {
+        let index = InvertedIndex::new(vec![
+            document("http.request.method", "HTTP request method."),
+            document("http.response.status_code", "HTTP response status code."),
+        ]);
+
+        // Prefixes match (search-as-you-type).
+        let scores = index.search("http.req");
+        assert_eq!(scores.keys().collect::<Vec<_>>(), vec![&0]);
+
+        // Typos match, with a lower score than the exact terms.
+        let fuzzy = index.search("http.reqest.methd");
+        assert_eq!(fuzzy.keys().collect::<Vec<_>>(), vec![&0]);
+        let exact = index.search("http.request.method");
+        assert!(exact[&0] > fuzzy[&0]);
+
+        // Short terms must match exactly or by prefix.
+        assert!(index.search("htp").is_empty());
+    }
+
+    #[test]
+    fn test_search_aliases() {
+        let mut renamed = document("http.request.method", "HTTP request method.");
+        renamed.add_alias("http.method");
+        let index = InvertedIndex::new(vec![renamed, document("http.route", "The route.")]);
+
+        let scores = index.search("http.method");
+        assert_eq!(scores.keys().collect::<Vec<_>>(), vec![&0]);
+        assert!(scores[&0] > EXACT_ALIAS_BONUS);
+    }
+}
@@ -3,32 +3,38 @@
 //! Search functionality for the semantic convention registry.
 //!
 //! This crate provides a search engine for querying OpenTelemetry semantic
-//! convention registries. It supports fuzzy matching, type filtering, and
-//! stability filtering.
+//! convention registries. Results are ranked with BM25 over an inverted index,
+//! with prefix and fuzzy matching of the query terms, and can be filtered by type
+//! and stability.
 
 #![doc = include_str!("../README.md")]
 
+mod index;
 mod types;
 
-pub use types::{ScoredResult, SearchResult, SearchType};
+pub use types::{ScoredResult, SearchFacets, SearchResult, SearchResults, SearchType};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use index::{Document, Field, InvertedIndex};
+
 use weaver_forge::v2::{
     attribute::Attribute, entity::Entity, event::Event, metric::Metric,
     registry::ForgeResolvedRegistry, span::Span,
 };
 use weaver_semconv::attribute::AttributeType;
+use weaver_semconv::deprecated::Deprecated;
 use weaver_semconv::stability::Stability;
-
-//TODO: Consider using a fuzzy matching crate for improved search capabilities.
-// e.g. Tantivy - https://github.com/open-telemetry/weaver/pull/1076#discussion_r2640681775
+use weaver_semconv::v2::CommonFields;
+use weaver_semconv::YamlValue;
 
 /// Search context for performing fuzzy searches and O(1) lookups across the registry.
 pub struct SearchContext {
     /// All searchable items for fuzzy search.
     items: Vec<SearchableItem>,
+    /// Inverted index of the items (documents are identified by their position in `items`).
+    index: InvertedIndex,
 
     // O(1) lookup indices (following LiveChecker pattern)
     /// Attributes indexed by key.
@@ -119,8 +125,11 @@ impl SearchContext {
             let _ = entity_index.insert(entity.r#type.to_string(), arc_entity);
         }
 
+        let index = InvertedIndex::new(documents(&items));
+
         Self {
             items,
+            index,
             attr_index,
             template_index,
             templates_by_length,
@@ -153,39 +162,91 @@ impl SearchContext {
         limit: usize,
         offset: usize,
     ) -> (Vec<SearchResult>, usize) {
-        let limit = limit.min(200); // Cap at 200
-
-        // Filter by type
-        let mut items: Vec<&SearchableItem> = self
-            .items
-            .iter()
-            .filter(|item| search_type == SearchType::All || item.search_type() == search_type)
-            .collect();
+        let SearchResults { results, total, .. } =
+            self.search_with_facets(query, search_type, stability, limit, offset);
+        (results, total)
+    }
 
-        // Filter by stability if provided
-        if let Some(stability_filter) = stability {
-            items.retain(|item| item.stability() == &stability_filter);
-        }
+    /// Search for items matching the query, or list all items if query is None, and count
+    /// the matching items per type and per stability level.
+    ///
+    /// In search mode, all the terms of the query must match an item. The items are ranked
+    /// with BM25 over their id, the names they have been renamed from, their brief, note,
+    /// enum members and annotations. Query terms also match the indexed terms they are a
+    /// prefix of, or (for terms of 4 characters or more) the indexed terms within a small
+    /// edit distance, with a lower weight. Deprecated items are heavily demoted.
+    ///
+    /// In browse mode, the items are listed in registry order.
+    #[must_use]
+    pub fn search_with_facets(
+        &self,
+        query: Option<&str>,
+        search_type: SearchType,
+        stability: Option<Stability>,
+        limit: usize,
+        offset: usize,
+    ) -> SearchResults {
+        let limit = limit.min(200); // Cap at 200
 
         // Branch based on whether we have a search query
-        let (results, total) = if let Some(q) = query {
-            if q.is_empty() {
-                // Empty query - browse mode
-                let total = items.len();
-                let results = browse_mode(items, limit, offset);
-                (results, total)
-            } else {
-                // Non-empty query - search mode with scoring
-                search_mode_with_total(items, q, limit)
+        let matches: Vec<(u32, &SearchableItem)> = match query.map(str::trim) {
+            Some(q) if !q.is_empty() => {
+                // Search mode with scoring, most relevant first
+                let mut matches: Vec<(u32, usize)> = self
+                    .index
+                    .search(q)
+                    .into_iter()
+                    .map(|(position, score)| (self.items[position].score(score), position))
+                    .collect();
+                matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+                matches
+                    .into_iter()
+                    .map(|(score, position)| (score, &self.items[position]))
+                    .collect()
             }
-        } else {
-            // No query - browse mode
-            let total = items.len();
-            let results = browse_mode(items, limit, offset);
-            (results, total)
+            // No query - browse mode, score 0
+            _ => self.items.iter().map(|item| (0, item)).collect(),
         };
 
-        (results, total)
+        let type_matches = |item: &SearchableItem| {
+            search_type == SearchType::All || item.search_type() == search_type
+        };
+        let stability_matches = |item: &SearchableItem| {
+            stability
+                .as_ref()
+                .map_or(true, |stability| item.stability() == stability)
+        };
+
+        let mut facets = SearchFacets::default();
+        for (_, item) in &matches {
+            if stability_matches(item) {
+                *facets.types.entry(item.search_type()).or_default() += 1;
+            }
+            if type_matches(item) {
+                *facets
+                    .stability
+                    .entry(item.stability().clone())
+                    .or_default() += 1;
+            }
+        }
+
+        let matches: Vec<(u32, &SearchableItem)> = matches
+            .into_iter()
+            .filter(|(_, item)| type_matches(item) && stability_matches(item))
+            .collect();
+        let total = matches.len();
+        let results = matches
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(score, item)| item.to_search_result(score))
+            .collect();
+
+        SearchResults {
+            results,
+            total,
+            facets,
+        }
     }
 
     // ==========================================================================
@@ -242,50 +303,86 @@ impl SearchContext {
     }
 }
 
-/// Search mode with total count: perform fuzzy matching with scoring and return (results, total).
-fn search_mode_with_total(
-    items: Vec<&SearchableItem>,
-    query: &str,
-    limit: usize,
-) -> (Vec<SearchResult>, usize) {
-    let mut scored_items: Vec<(u32, &SearchableItem)> = items
-        .into_iter()
-        .filter_map(|item| {
-            let score = score_match(query, item);
-            if score > 0 {
-                Some((score, item))
-            } else {
-                None
-            }
-        })
-        .collect();
-
-    // Sort by score descending
-    scored_items.sort_by(|a, b| b.0.cmp(&a.0));
-
-    // Calculate total before taking limit
-    let total = scored_items.len();
-
-    // Take top N and convert to results
-    let results = scored_items
-        .into_iter()
-        .take(limit)
-        .map(|(score, item)| item.to_search_result(score))
+/// Builds the documents indexing the given items.
+///
+/// An item renamed (see `renamed_to`) into another item of the same type is an alias of
+/// it, and of the items it has been renamed into in turn, so that searching for an old
+/// name finds its replacements.
+fn documents(items: &[SearchableItem]) -> Vec<Document> {
+    let positions: HashMap<(SearchType, &str), usize> = items
+        .iter()
+        .enumerate()
+        .map(|(position, item)| ((item.search_type(), item.id()), position))
         .collect();
+    let mut aliases: Vec<Vec<&str>> = vec![vec![]; items.len()];
+    for item in items {
+        let mut visited = HashSet::new();
+        let mut current = item;
+        while let Some(Deprecated::Renamed { renamed_to, .. }) = &current.common().deprecated {
+            let Some(&position) = positions.get(&(item.search_type(), renamed_to.as_str())) else {
+                break;
+            };
+            if !visited.insert(position) || items[position].id() == item.id() {
+                break;
+            }
+            aliases[position].push(item.id());
+            current = &items[position];
+        }
+    }
 
-    (results, total)
-}
-
-/// Browse mode: return all items in natural order with pagination.
-fn browse_mode(items: Vec<&SearchableItem>, limit: usize, offset: usize) -> Vec<SearchResult> {
     items
-        .into_iter()
-        .skip(offset)
-        .take(limit)
-        .map(|item| item.to_search_result(0)) // Score 0 in browse mode
+        .iter()
+        .zip(aliases)
+        .map(|(item, aliases)| {
+            let common = item.common();
+            let mut document = Document::new(item.id());
+            for alias in aliases {
+                document.add_alias(alias);
+            }
+            document.add(Field::Brief, &common.brief);
+            document.add(Field::Note, &common.note);
+            if let SearchableItem::Attribute(attr) = item {
+                if let AttributeType::Enum { members } = &attr.r#type {
+                    for member in members {
+                        document.add(Field::Members, &member.id);
+                        document.add(Field::Members, &member.value.to_string());
+                        document.add(Field::Members, member.brief.as_deref().unwrap_or(""));
+                    }
+                }
+            }
+            for (key, value) in &common.annotations {
+                document.add(Field::Annotations, key);
+                add_annotation_value(&mut document, value);
+            }
+            document
+        })
         .collect()
 }
 
+/// Adds the scalar values found in an annotation value to a document.
+fn add_annotation_value(document: &mut Document, value: &YamlValue) {
+    fn add(document: &mut Document, value: &serde_yaml::Value) {
+        match value {
+            serde_yaml::Value::String(text) => document.add(Field::Annotations, text),
+            serde_yaml::Value::Sequence(values) => {
+                for value in values {
+                    add(document, value);
+                }
+            }
+            serde_yaml::Value::Mapping(mapping) => {
+                for (key, value) in mapping {
+                    add(document, key);
+                    add(document, value);
+                }
+            }
+            serde_yaml::Value::Tagged(tagged) => add(document, &tagged.value),
+            serde_yaml::Value::Bool(_) | serde_yaml::Value::Number(_) | serde_yaml::Value::Null => {
+            }
+        }
+    }
+    add(document, &value.0);
+}
+
 impl SearchableItem {
     /// Get the search type of this item.
     fn search_type(&self) -> SearchType {
@@ -309,48 +406,32 @@ impl SearchableItem {
         }
     }
 
-    /// Get the brief description for scoring.
-    fn brief(&self) -> &str {
-        match self {
-            SearchableItem::Attribute(attr) => &attr.common.brief,
-            SearchableItem::Metric(metric) => &metric.common.brief,
-            SearchableItem::Span(span) => &span.common.brief,
-            SearchableItem::Event(event) => &event.common.brief,
-            SearchableItem::Entity(entity) => &entity.common.brief,
-        }
-    }
-
-    /// Get the note for scoring.
-    fn note(&self) -> &str {
+    /// Get the fields shared by all the item types.
+    fn common(&self) -> &CommonFields {
         match self {
-            SearchableItem::Attribute(attr) => &attr.common.note,
-            SearchableItem::Metric(metric) => &metric.common.note,
-            SearchableItem::Span(span) => &span.common.note,
-            SearchableItem::Event(event) => &event.common.note,
-            SearchableItem::Entity(entity) => &entity.common.note,
+            SearchableItem::Attribute(attr) => &attr.common,
+            SearchableItem::Metric(metric) => &metric.common,
+            SearchableItem::Span(span) => &span.common,
+            SearchableItem::Event(event) => &event.common,
+            SearchableItem::Entity(entity) => &entity.common,
         }
     }
 
-    /// Check if this item is deprecated.
-    fn is_deprecated(&self) -> bool {
-        match self {
-            SearchableItem::Attribute(attr) => attr.common.deprecated.is_some(),
-            SearchableItem::Metric(metric) => metric.common.deprecated.is_some(),
-            SearchableItem::Span(span) => span.common.deprecated.is_some(),
-            SearchableItem::Event(event) => event.common.deprecated.is_some(),
-            SearchableItem::Entity(entity) => entity.common.deprecated.is_some(),
+    /// Convert a BM25 score of this item to a relevance score. Deprecated items are
+    /// heavily demoted (score divided by 10).
+    fn score(&self, bm25: f64) -> u32 {
+        let score = ((bm25 * 10.0).round() as u32).max(1);
+        if self.common().deprecated.is_some() {
+            // Ensure at least 1 as there was a match
+            (score / 10).max(1)
+        } else {
+            score
         }
     }
 
     /// Get the stability level of this item.
     fn stability(&self) -> &Stability {
-        match self {
-            SearchableItem::Attribute(attr) => &attr.common.stability,
-            SearchableItem::Metric(metric) => &metric.common.stability,
-            SearchableItem::Span(span) => &span.common.stability,
-            SearchableItem::Event(event) => &event.common.stability,
-            SearchableItem::Entity(entity) => &entity.common.stability,
-        }
+        &self.common().stability
     }
 
     /// Convert to a search result with the given score.
@@ -380,111 +461,16 @@ impl SearchableItem {
     }
 }
 
-/// Calculate a relevance score for a search match.
-///
-/// Scoring weights:
-/// - Exact name/key match: 100 points
-/// - Name/key starts with query: 80 points
-/// - Name/key contains query: 70 points
-/// - All query tokens found in name: 60 points
-/// - Brief contains query: 40 points
-/// - Note contains query: 20 points
-/// - Deprecated items: score divided by 10 (heavily demoted)
-fn score_match(query: &str, item: &SearchableItem) -> u32 {
-    let query_lower = query.to_lowercase();
-    let id_lower = item.id().to_lowercase();
-    let brief_lower = item.brief().to_lowercase();
-    let note_lower = item.note().to_lowercase();
-
-    let mut score = 0;
-
-    // Exact match
-    if id_lower == query_lower {
-        score = 100;
-    }
-    // Name starts with query
-    else if id_lower.starts_with(&query_lower) {
-        score = 80;
-    }
-    // Name contains query
-    else if id_lower.contains(&query_lower) {
-        score = 70;
-    } else {
-        // Token matching - all query tokens found in name
-        let query_tokens: Vec<&str> = query_lower
-            .split(|c: char| c == '.' || c == '_' || c.is_whitespace())
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        if !query_tokens.is_empty() {
-            let id_tokens: Vec<&str> = id_lower.split(['.', '_']).collect();
-
-            let all_tokens_match = query_tokens
-                .iter()
-                .all(|qt| id_tokens.iter().any(|it| it.contains(qt)));
-
-            if all_tokens_match {
-                score = 60;
-            }
-            // Brief contains query
-            else if brief_lower.contains(&query_lower) {
-                score = 40;
-            }
-            // Note contains query
-            else if note_lower.contains(&query_lower) {
-                score = 20;
-            }
-            // Also check if individual query tokens appear in brief/note
-            else {
-                let all_in_brief = query_tokens.iter().all(|qt| brief_lower.contains(qt));
-                if all_in_brief {
-                    score = 35;
-                } else {
-                    let all_in_note = query_tokens.iter().all(|qt| note_lower.contains(qt));
-                    if all_in_note {
-                        score = 15;
-                    }
-                }
-            }
-        } else {
-            // Brief contains query
-            if brief_lower.contains(&query_lower) {
-                score = 40;
-            }
-            // Note contains query
-            else if note_lower.contains(&query_lower) {
-                score = 20;
-            }
-        }
-    }
-
-    // Heavily demote deprecated items - divide score by 10
-    if item.is_deprecated() && score > 0 {
-        score /= 10;
-        // Ensure at least 1 if there was a match
-        if score == 0 {
-            score = 1;
-        }
-    }
-
-    score
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::BTreeMap;
     use weaver_forge::v2::registry::{ForgeResolvedRegistry, Refinements, Registry};
-    use weaver_semconv::attribute::AttributeType;
+    use weaver_semconv::attribute::{AttributeType, EnumEntriesSpec, ValueSpec};
     use weaver_semconv::deprecated::Deprecated;
     use weaver_semconv::group::{InstrumentSpec, SpanKindSpec};
     use weaver_semconv::stability::Stability;
     use weaver_semconv::v2::span::SpanName;
-    use weaver_semconv::v2::CommonFields;
-
-    fn make_test_attribute(key: &str, brief: &str, note: &str, deprecated: bool) -> SearchableItem {
-        SearchableItem::Attribute(Arc::new(make_attribute(key, brief, note, deprecated)))
-    }
 
     fn make_attribute(key: &str, brief: &str, note: &str, deprecated: bool) -> Attribute {
         Attribute {
@@ -628,55 +614,247 @@ mod tests {
         }
     }
 
+    fn make_context(attributes: Vec<Attribute>) -> SearchContext {
+        let mut registry = make_test_registry();
+        registry.registry.attributes = attributes;
+        SearchContext::from_registry(&registry)
+    }
+
+    fn result_ids(results: &[SearchResult]) -> Vec<&str> {
+        results
+            .iter()
+            .map(|result| match result {
+                SearchResult::Attribute(r) => r.item.key.as_str(),
+                SearchResult::Metric(r) => &r.item.name,
+                SearchResult::Span(r) => &r.item.r#type,
+                SearchResult::Event(r) => &r.item.name,
+                SearchResult::Entity(r) => &r.item.r#type,
+            })
+            .collect()
+    }
+
+    fn result_score(result: &SearchResult) -> u32 {
+        match result {
+            SearchResult::Attribute(r) => r.score,
+            SearchResult::Metric(r) => r.score,
+            SearchResult::Span(r) => r.score,
+            SearchResult::Event(r) => r.score,
+            SearchResult::Entity(r) => r.score,
+        }
+    }
+
     #[test]
-    fn test_exact_match_scores_highest() {
-        let item = make_test_attribute("http.request.method", "HTTP request method", "", false);
+    fn test_exact_match_ranks_first() {
+        let ctx = make_context(vec![
+            make_attribute("http.request.method", "HTTP request method", "", false),
+            make_attribute(
+                "http.request.method_original",
+                "Original HTTP method sent by the client in the request line",
+                "",
+                false,
+            ),
+        ]);
 
-        assert_eq!(score_match("http.request.method", &item), 100);
+        let (results, total) = ctx.search(
+            Some("http.request.method"),
+            SearchType::Attribute,
+            None,
+            10,
+            0,
+        );
+        assert_eq!(total, 2);
+        assert_eq!(
+            result_ids(&results),
+            vec!["http.request.method", "http.request.method_original"]
+        );
+        assert!(result_score(&results[0]) > result_score(&results[1]));
     }
 
     #[test]
-    fn test_starts_with_scores_high() {
-        let item = make_test_attribute("http.request.method", "HTTP request method", "", false);
+    fn test_id_matches_rank_above_brief_and_note_matches() {
+        let ctx = make_context(vec![
+            make_attribute("db.query.text", "The query", "Sent by the client.", false),
+            make_attribute("client.address", "Client address", "", false),
+            make_attribute(
+                "server.address",
+                "Server address, a client connects to",
+                "",
+                false,
+            ),
+        ]);
 
-        assert_eq!(score_match("http.request", &item), 80);
+        let (results, _) = ctx.search(Some("client"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(
+            result_ids(&results),
+            vec!["client.address", "server.address", "db.query.text"]
+        );
     }
 
     #[test]
-    fn test_contains_scores_medium() {
-        let item = make_test_attribute("http.request.method", "HTTP request method", "", false);
+    fn test_all_query_terms_must_match() {
+        let ctx = make_context(vec![
+            make_attribute("http.request.method", "HTTP request method", "", false),
+            make_attribute("http.route", "The matched route", "", false),
+        ]);
+
+        let (results, _) = ctx.search(Some("http method"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(result_ids(&results), vec!["http.request.method"]);
+    }
 
-        assert_eq!(score_match("request.method", &item), 70);
+    #[test]
+    fn test_prefix_match() {
+        let ctx = make_context(vec![
+            make_attribute("http.request.method", "HTTP request method", "", false),
+            make_attribute("http.response.status_code", "HTTP status code", "", false),
+        ]);
+
+        let (results, _) = ctx.search(Some("http.req"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(result_ids(&results), vec!["http.request.method"]);
     }
 
     #[test]
-    fn test_brief_match_scores_lower() {
-        let item = make_test_attribute(
-            "http.request.method",
-            "The HTTP verb used in the request",
-            "",
-            false,
+    fn test_fuzzy_match() {
+        let ctx = make_context(vec![
+            make_attribute("http.request.method", "HTTP request method", "", false),
+            make_attribute("http.response.status_code", "HTTP status code", "", false),
+        ]);
+
+        let (results, _) = ctx.search(
+            Some("http.reqest.method"),
+            SearchType::Attribute,
+            None,
+            10,
+            0,
         );
-
-        assert_eq!(score_match("verb", &item), 40);
+        assert_eq!(result_ids(&results), vec!["http.request.method"]);
     }
 
     #[test]
-    fn test_no_match_scores_zero() {
-        let item = make_test_attribute("http.request.method", "HTTP request method", "", false);
+    fn test_enum_members_and_annotations_are_indexed() {
+        let mut method = make_attribute("http.request.method", "HTTP request method", "", false);
+        method.r#type = AttributeType::Enum {
+            members: vec![EnumEntriesSpec {
+                id: "connect".to_owned(),
+                value: ValueSpec::String("CONNECT".to_owned()),
+                brief: Some("CONNECT method.".to_owned()),
+                note: None,
+                stability: None,
+                deprecated: None,
+                annotations: None,
+            }],
+        };
+        let mut route = make_attribute("http.route", "The matched route", "", false);
+        _ = route.common.annotations.insert(
+            "code_generation".to_owned(),
+            YamlValue(serde_yaml::from_str("{exclude: [templating]}").unwrap()),
+        );
+        let ctx = make_context(vec![method, route]);
 
-        assert_eq!(score_match("database", &item), 0);
+        let (results, _) = ctx.search(Some("connect"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(result_ids(&results), vec!["http.request.method"]);
+        let (results, _) = ctx.search(Some("templating"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(result_ids(&results), vec!["http.route"]);
     }
 
     #[test]
     fn test_deprecated_items_score_much_lower() {
-        let item = make_test_attribute("http.request.method", "HTTP request method", "", true);
+        let ctx = make_context(vec![
+            make_attribute("http.request.method", "HTTP request method", "", true),
+            make_attribute("http.request.header", "HTTP request header", "", false),
+        ]);
+
+        // The exact match is deprecated, so it ranks below a partial match.
+        let (results, _) = ctx.search(Some("http.request"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(
+            result_ids(&results),
+            vec!["http.request.header", "http.request.method"]
+        );
+        assert!(result_score(&results[1]) >= 1);
+        assert!(result_score(&results[0]) > 5 * result_score(&results[1]));
+    }
+
+    #[test]
+    fn test_renamed_items_are_aliases() {
+        let renamed = |key: &str, renamed_to: &str| {
+            let mut attr = make_attribute(key, "Deprecated", "", false);
+            attr.common.deprecated = Some(Deprecated::Renamed {
+                renamed_to: renamed_to.to_owned(),
+                note: String::new(),
+            });
+            attr
+        };
+        let ctx = make_context(vec![
+            // http.method -> http.request.method_v1 -> http.request.method
+            renamed("http.method", "http.request.method_v1"),
+            renamed("http.request.method_v1", "http.request.method"),
+            make_attribute("http.request.method", "HTTP request method", "", false),
+            // Renaming cycles are ignored.
+            renamed("cycle.a", "cycle.b"),
+            renamed("cycle.b", "cycle.a"),
+        ]);
+
+        // The replacement of the old name ranks first.
+        let (results, _) = ctx.search(Some("http.method"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(
+            result_ids(&results),
+            vec![
+                "http.request.method",
+                "http.method",
+                "http.request.method_v1"
+            ]
+        );
 
-        // Exact match for deprecated item: 100 / 10 = 10
-        assert_eq!(score_match("http.request.method", &item), 10);
+        let (results, _) = ctx.search(Some("cycle.a"), SearchType::Attribute, None, 10, 0);
+        assert_eq!(result_ids(&results), vec!["cycle.a", "cycle.b"]);
+    }
+
+    #[test]
+    fn test_search_facets() {
+        let registry = make_test_registry();
+        let ctx = SearchContext::from_registry(&registry);
+
+        let results = ctx.search_with_facets(
+            Some("http"),
+            SearchType::Attribute,
+            Some(Stability::Stable),
+            10,
+            0,
+        );
+        assert_eq!(results.total, 2);
+        // Type counts ignore the type filter.
+        assert_eq!(
+            results.facets.types,
+            BTreeMap::from([
+                (SearchType::Attribute, 2),
+                (SearchType::Metric, 1),
+                (SearchType::Span, 1),
+            ])
+        );
+        // Stability counts ignore the stability filter.
+        assert_eq!(
+            results.facets.stability,
+            BTreeMap::from([(Stability::Stable, 2)])
+        );
+
+        // Browse mode counts all the items.
+        let results = ctx.search_with_facets(None, SearchType::All, None, 1, 0);
+        assert_eq!(results.results.len(), 1);
+        assert_eq!(results.facets.types[&SearchType::Attribute], 5);
+        assert_eq!(
+            results.facets.stability,
+            BTreeMap::from([(Stability::Stable, 8), (Stability::Development, 1)])
+        );
+    }
+
+    #[test]
+    fn test_search_mode_pagination() {
+        let registry = make_test_registry();
+        let ctx = SearchContext::from_registry(&registry);
 
-        // Starts with for deprecated item: 80 / 10 = 8
-        assert_eq!(score_match("http.request", &item), 8);
+        let (all, total) = ctx.search(Some("http"), SearchType::All, None, 10, 0);
+        let (page, page_total) = ctx.search(Some("http"), SearchType::All, None, 2, 1);
+        assert_eq!(total, page_total);
+        assert_eq!(result_ids(&page), result_ids(&all)[1..3]);
     }
 
     // =========================================================================
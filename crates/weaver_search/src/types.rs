@@ -2,6 +2,7 @@
 
 //! Core search types for the weaver search engine.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,7 @@ use weaver_forge::v2::entity::Entity;
 use weaver_forge::v2::event::Event;
 use weaver_forge::v2::metric::Metric;
 use weaver_forge::v2::span::Span;
+use weaver_semconv::stability::Stability;
 
 /// Generic wrapper that adds a relevance score to any searchable object.
 #[derive(Debug, Serialize, Clone, ToSchema)]
@@ -24,7 +26,20 @@ pub struct ScoredResult<T> {
 }
 
 /// Search type filter.
-#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    ToSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchType {
     /// Search all types.
@@ -57,3 +72,27 @@ pub enum SearchResult {
     /// An entity result.
     Entity(ScoredResult<Entity>),
 }
+
+/// Number of matching items per type and per stability level.
+///
+/// The counts per type ignore the type filter of the search and the counts per stability
+/// level ignore its stability filter, so that they tell how many results each value of
+/// the filter would return.
+#[derive(Debug, Default, Serialize, Clone, PartialEq, Eq, ToSchema)]
+pub struct SearchFacets {
+    /// Number of matching items per type.
+    pub types: BTreeMap<SearchType, usize>,
+    /// Number of matching items per stability level.
+    pub stability: BTreeMap<Stability, usize>,
+}
+
+/// A page of search results.
+#[derive(Debug)]
+pub struct SearchResults {
+    /// The results of the page, most relevant first.
+    pub results: Vec<SearchResult>,
+    /// Total number of matching items (before pagination).
+    pub total: usize,
+    /// Number of matching items per type and per stability level.
+    pub facets: SearchFacets,
+}
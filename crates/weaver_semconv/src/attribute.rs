@@ -564,6 +564,7 @@ impl Examples {
                         group_id: group_id.to_owned(),
                         attribute_id: attr_id.to_owned(),
                         error: format!("All examples SHOULD be of type `{attr_type}`"),
+                        definition: None,
                     }],
                 )
             }
@@ -590,6 +591,7 @@ impl Examples {
                     group_id: group_id.to_owned(),
                     attribute_id: attr_id.to_owned(),
                     error: format!("All examples SHOULD be of type `{attr_type}`"),
+                    definition: None,
                 }],
             ),
             _ => WResult::OkWithNFEs(
@@ -599,6 +601,7 @@ impl Examples {
                     group_id: group_id.to_owned(),
                     attribute_id: attr_id.to_owned(),
                     error: format!("All examples MUST be of type `{attr_type}`"),
+                    definition: None,
                 }],
             ),
         }
@@ -16,6 +16,7 @@ use crate::deprecated::Deprecated;
 use crate::group::InstrumentSpec::{Counter, Gauge, Histogram, UpDownCounter};
 use crate::provenance::Provenance;
 use crate::semconv::Imports;
+use crate::source_location::GroupSourceLocations;
 use crate::stability::Stability;
 use crate::v2::attribute_group::AttributeGroupVisibilitySpec;
 use crate::{Error, YamlValue};
@@ -202,6 +203,7 @@ impl GroupSpec {
                     group_id: self.id.clone(),
                     error: "This group contains a span_kind field but the type is not set to span."
                         .to_owned(),
+                    definition: None,
                 });
             }
             if !self.events.is_empty() {
@@ -210,6 +212,7 @@ impl GroupSpec {
                     group_id: self.id.clone(),
                     error: "This group contains an events field but the type is not set to span."
                         .to_owned(),
+                    definition: None,
                 });
             }
         }
@@ -240,6 +243,7 @@ impl GroupSpec {
                     path_or_url: path_or_url.to_owned(),
                     group_id: self.id.clone(),
                     error: "This group contains an event type with a body definition but the name is not set.".to_owned(),
+                    definition: None,
                 });
             }
             if self.body.is_none() && self.name.is_none() && self.prefix.is_empty() {
@@ -249,6 +253,7 @@ impl GroupSpec {
                     path_or_url: path_or_url.to_owned(),
                     group_id: self.id.clone(),
                     error: "This group contains an event type but the name is not set and no prefix is defined.".to_owned(),
+                    definition: None,
                 });
             }
 
@@ -271,6 +276,7 @@ impl GroupSpec {
                 group_id: self.id.clone(),
                 error: "This group contains a body field but the type is not set to event."
                     .to_owned(),
+                definition: None,
             });
         }
 
@@ -282,6 +288,7 @@ impl GroupSpec {
                     group_id: self.id.clone(),
                     error: "This group contains a metric type but the metric_name is not set."
                         .to_owned(),
+                    definition: None,
                 });
             }
             if self.instrument.is_none() {
@@ -290,6 +297,7 @@ impl GroupSpec {
                     group_id: self.id.clone(),
                     error: "This group contains a metric type but the instrument is not set."
                         .to_owned(),
+                    definition: None,
                 });
             }
             if self.unit.is_none() {
@@ -297,6 +305,7 @@ impl GroupSpec {
                     path_or_url: path_or_url.to_owned(),
                     group_id: self.id.clone(),
                     error: "This group contains a metric type but the unit is not set.".to_owned(),
+                    definition: None,
                 });
             }
         }
@@ -325,6 +334,7 @@ impl GroupSpec {
                             group_id: self.id.clone(),
                             attribute_id: attribute.id(),
                             error: "This attribute is not deprecated and does not contain a brief field.".to_owned(),
+                            definition: None,
                         });
                     }
 
@@ -334,6 +344,7 @@ impl GroupSpec {
                             group_id: self.id.clone(),
                             attribute_id: attribute.id(),
                             error: "Missing stability field.".to_owned(),
+                            definition: None,
                         });
                     } else if stability.clone() == Some(Stability::Deprecated) {
                         errors.push(Error::InvalidAttributeWarning {
@@ -341,6 +352,7 @@ impl GroupSpec {
                             group_id: self.id.clone(),
                             attribute_id: attribute.id(),
                             error: "Attribute stability is set to 'deprecated' which is no longer supported.".to_owned(),
+                            definition: None,
                         });
                     }
 
@@ -355,6 +367,7 @@ impl GroupSpec {
                                         "Missing stability field on enum member {}.",
                                         member.id
                                     ),
+                                    definition: None,
                                 });
                             } else if member.stability == Some(Stability::Deprecated) {
                                 errors.push(Error::InvalidAttributeWarning {
@@ -365,6 +378,7 @@ impl GroupSpec {
                                         "Member {} stability is set to 'deprecated' which is no longer supported.",
                                         member.id
                                     ),
+                                    definition: None,
                                 });
                             }
 
@@ -421,6 +435,7 @@ impl GroupSpec {
                             error:
                                 "This attribute is a string but it does not contain any examples."
                                     .to_owned(),
+                            definition: None,
                         });
                     }
 
@@ -434,6 +449,7 @@ impl GroupSpec {
                             error:
                             "This attribute is a string array but it does not contain any examples."
                                 .to_owned(),
+                            definition: None,
                         });
                     }
                 }
@@ -449,6 +465,7 @@ impl GroupSpec {
                     group_id: self.id.clone(),
                     // TODO - use JSON/YAML friendly render of t.
                     error: format!("Group with entity_associations cannot have type: {t:?}"),
+                    definition: None,
                 }),
             }
         }
@@ -657,6 +674,9 @@ pub struct GroupSpecWithProvenance {
     pub spec: GroupSpec,
     /// The provenance of the group spec (path or URL).
     pub provenance: Provenance,
+    /// The definitions of the group and of its attributes in the semantic convention
+    /// file (if known).
+    pub source_locations: Option<GroupSourceLocations>,
 }
 
 /// Imports with its provenance (path or URL).
@@ -779,29 +799,34 @@ mod tests {
                     group_id: "test".to_owned(),
                     error: "This group contains a span_kind field but the type is not set to span."
                         .to_owned(),
+                    definition: None,
                 },
                 InvalidGroup {
                     path_or_url: "<test>".to_owned(),
                     group_id: "test".to_owned(),
                     error: "This group contains an events field but the type is not set to span."
                         .to_owned(),
+                    definition: None,
                 },
                 InvalidMetric {
                     path_or_url: "<test>".to_owned(),
                     group_id: "test".to_owned(),
                     error: "This group contains a metric type but the metric_name is not set."
                         .to_owned(),
+                    definition: None,
                 },
                 InvalidMetric {
                     path_or_url: "<test>".to_owned(),
                     group_id: "test".to_owned(),
                     error: "This group contains a metric type but the instrument is not set."
                         .to_owned(),
+                    definition: None,
                 },
                 InvalidMetric {
                     path_or_url: "<test>".to_owned(),
                     group_id: "test".to_owned(),
                     error: "This group contains a metric type but the unit is not set.".to_owned(),
+                    definition: None,
                 },
             ],),),
             result
@@ -819,16 +844,19 @@ mod tests {
                         path_or_url: "<test>".to_owned(),
                         group_id: "test".to_owned(),
                         error: "This group contains a span_kind field but the type is not set to span.".to_owned(),
+                        definition: None,
                     },
                     InvalidGroup {
                         path_or_url: "<test>".to_owned(),
                         group_id: "test".to_owned(),
                         error: "This group contains an events field but the type is not set to span.".to_owned(),
+                        definition: None,
                     },
                     InvalidGroup {
                         path_or_url: "<test>".to_owned(),
                         group_id: "test".to_owned(),
                         error: "This group contains an event type but the name is not set and no prefix is defined.".to_owned(),
+                        definition: None,
                     },
                 ],
             ),
@@ -925,6 +953,7 @@ mod tests {
                 attribute_id: "test".to_owned(),
                 error: "This attribute is a string but it does not contain any examples."
                     .to_owned(),
+                definition: None,
             },),
             result
         );
@@ -954,6 +983,7 @@ mod tests {
                 attribute_id: "test".to_owned(),
                 error: "This attribute is a string array but it does not contain any examples."
                     .to_owned(),
+                definition: None,
             },),
             result
         );
@@ -982,6 +1012,7 @@ mod tests {
                 group_id: "test".to_owned(),
                 attribute_id: "test".to_owned(),
                 error: "Missing stability field.".to_owned(),
+                definition: None,
             },),
             result
         );
@@ -1011,6 +1042,7 @@ mod tests {
                 attribute_id: "test".to_owned(),
                 error: "Attribute stability is set to 'deprecated' which is no longer supported."
                     .to_owned(),
+                definition: None,
             },),
             result
         );
@@ -1049,6 +1081,7 @@ mod tests {
                 group_id: "test".to_owned(),
                 attribute_id: "test".to_owned(),
                 error: "Missing stability field on enum member member_id.".to_owned(),
+                definition: None,
             },),
             result
         );
@@ -1087,6 +1120,7 @@ mod tests {
                 group_id: "test".to_owned(),
                 attribute_id: "test".to_owned(),
                 error: "Member member_id stability is set to 'deprecated' which is no longer supported.".to_owned(),
+                definition: None,
             }, ),
             result
         );
@@ -1982,6 +2016,7 @@ mod tests {
                 path_or_url: "<test>".to_owned(),
                 group_id: "test".to_owned(),
                 error: "Group with entity_associations cannot have type: AttributeGroup".to_owned(),
+                definition: None,
             }),
             result
         );
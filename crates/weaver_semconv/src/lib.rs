@@ -2,6 +2,7 @@
 
 #![doc = include_str!("../README.md")]
 
+use crate::source_location::{SourceDefinition, SourceLocations};
 use crate::Error::CompoundError;
 use miette::{Diagnostic, NamedSource, SourceSpan};
 use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
@@ -23,6 +24,7 @@ pub mod registry;
 pub mod registry_repo;
 pub mod schema_url;
 pub mod semconv;
+pub mod source_location;
 pub mod stability;
 pub mod stats;
 pub mod v2;
@@ -96,6 +98,11 @@ pub enum Error {
         group_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the group, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "group defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// The semantic convention spec contains a group with duplicate attribute references.
@@ -169,6 +176,11 @@ pub enum Error {
         attribute_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the attribute, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "attribute defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// The semantic convention asset contains an invalid attribute definition.
@@ -183,6 +195,11 @@ pub enum Error {
         attribute_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the attribute, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "attribute defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// This error occurs when a semantic convention asset contains an invalid example.
@@ -198,6 +215,11 @@ pub enum Error {
         attribute_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the attribute, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "attribute defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// This warning indicates that a semantic convention asset contains an invalid example.
@@ -214,6 +236,11 @@ pub enum Error {
         attribute_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the attribute, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "attribute defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// This warning indicates usage of `prefix` on a group.
@@ -236,6 +263,11 @@ pub enum Error {
         group_id: String,
         /// The reason of the error.
         error: String,
+        /// The definition of the metric, rendered as a labelled source snippet.
+        #[source_code]
+        #[label(collection, "metric defined here")]
+        #[serde(skip_serializing)]
+        definition: Option<Box<SourceDefinition>>,
     },
 
     /// This indicates that any_value is invalid.
@@ -410,6 +442,66 @@ pub struct InvalidSemConvSpecError {
     pub advice: Option<String>,
 }
 
+impl Error {
+    /// Attaches to the error the definition of the group or attribute it is about, so
+    /// that its diagnostic renders a labelled snippet of the YAML source.
+    #[must_use]
+    pub fn with_source_definition(mut self, locations: &SourceLocations) -> Self {
+        match &mut self {
+            Error::InvalidGroup {
+                group_id,
+                definition,
+                ..
+            }
+            | Error::InvalidMetric {
+                group_id,
+                definition,
+                ..
+            } => {
+                *definition = locations
+                    .group(group_id)
+                    .map(|group| Box::new(group.definition.clone()));
+            }
+            Error::InvalidAttribute {
+                group_id,
+                attribute_id,
+                definition,
+                ..
+            }
+            | Error::InvalidAttributeWarning {
+                group_id,
+                attribute_id,
+                definition,
+                ..
+            }
+            | Error::InvalidExampleError {
+                group_id,
+                attribute_id,
+                definition,
+                ..
+            }
+            | Error::InvalidExampleWarning {
+                group_id,
+                attribute_id,
+                definition,
+                ..
+            } => {
+                *definition = locations
+                    .attribute_definition(group_id, attribute_id)
+                    .map(|attribute| Box::new(attribute.clone()));
+            }
+            CompoundError(errors) => {
+                *errors = std::mem::take(errors)
+                    .into_iter()
+                    .map(|e| e.with_source_definition(locations))
+                    .collect();
+            }
+            _ => {}
+        }
+        self
+    }
+}
+
 impl WeaverError<Error> for Error {
     fn compound(errors: Vec<Error>) -> Error {
         CompoundError(
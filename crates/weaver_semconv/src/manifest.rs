@@ -48,8 +48,8 @@ pub struct DefinitionRegistryManifest {
     pub description: Option<String>,
 
     /// List of the registry's dependencies.
-    /// Note: In the current phase, we only support zero or one dependency.
-    /// See this GH issue for more details: <https://github.com/open-telemetry/weaver/issues/604>
+    /// Attribute references, `extends` clauses and imports are resolved across all the
+    /// dependencies, in the order in which they are declared.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub dependencies: Vec<Dependency>,
 
@@ -208,27 +208,34 @@ impl SemConvRegistry {
     pub fn unresolved_group_with_provenance_iter(
         &self,
     ) -> impl Iterator<Item = GroupSpecWithProvenance> + '_ {
-        self.specs
-            .iter()
-            .flat_map(|SemConvSpecV1WithProvenance { spec, provenance }| {
+        self.specs.iter().flat_map(
+            |SemConvSpecV1WithProvenance {
+                 spec,
+                 provenance,
+                 source_locations,
+             }| {
                 spec.groups.iter().map(|group| GroupSpecWithProvenance {
                     spec: group.clone(),
                     provenance: provenance.clone(),
+                    source_locations: source_locations.group(&group.id).cloned(),
                 })
-            })
+            },
+        )
     }
 
     /// Returns an iterator over all the unresolved imports defined in the semantic convention
     /// registry. Each import is associated with its provenance (path or URL).
     pub fn unresolved_imports_iter(&self) -> impl Iterator<Item = ImportsWithProvenance> + '_ {
-        self.specs
-            .iter()
-            .flat_map(|SemConvSpecV1WithProvenance { spec, provenance }| {
+        self.specs.iter().flat_map(
+            |SemConvSpecV1WithProvenance {
+                 spec, provenance, ..
+             }| {
                 spec.imports.iter().map(|imports| ImportsWithProvenance {
                     imports: imports.clone(),
                     provenance: provenance.clone(),
                 })
-            })
+            },
+        )
     }
 
     /// Returns a set of stats about the semantic convention registry.
@@ -259,6 +266,7 @@ mod tests {
     use crate::semconv::{SemConvSpecV1, SemConvSpecWithProvenance, Versioned};
     use crate::Error;
 
+    use weaver_common::cache::CacheSettings;
     use weaver_common::vdir::VirtualDirectoryPath;
 
     #[test]
@@ -285,6 +293,7 @@ mod tests {
         let semconv_specs = vec![
             SemConvSpecWithProvenance {
                 provenance: Provenance::new("main", "data/c1.yaml"),
+                source_locations: Default::default(),
                 spec: Versioned::V1(SemConvSpecV1 {
                     groups: vec![GroupSpec {
                         id: "group1".to_owned(),
@@ -330,6 +339,7 @@ mod tests {
             },
             SemConvSpecWithProvenance {
                 provenance: Provenance::new("main", "data/c2.yaml"),
+                source_locations: Default::default(),
                 spec: Versioned::V1(SemConvSpecV1 {
                     groups: vec![GroupSpec {
                         id: "group2".to_owned(),
@@ -369,6 +379,7 @@ mod tests {
                     .expect("Should be valid schema url"),
             ),
             &registry_path,
+            &CacheSettings::default(),
             &mut vec![],
         )
         .unwrap();
@@ -8,6 +8,7 @@ use std::path::{Path, PathBuf};
 use crate::manifest::{Dependency, RegistryManifest};
 use crate::schema_url::SchemaUrl;
 use crate::Error;
+use weaver_common::cache::CacheSettings;
 use weaver_common::vdir::{VirtualDirectory, VirtualDirectoryPath};
 use weaver_common::{get_path_type, log_info};
 
@@ -72,12 +73,17 @@ pub struct RegistryRepo {
 
     // Cached path to the manifest file (if it exists).
     manifest_path: Option<PathBuf>,
+
+    // The cache settings used to resolve this registry and its dependencies.
+    cache_settings: CacheSettings,
 }
 
 impl RegistryRepo {
     /// Creates a new `RegistryRepo` from a `Dependency` object that specifies the schema URL and path.
+    /// Remote registries are resolved with the given cache settings.
     pub fn try_new_dependency(
         dependency: &Dependency,
+        cache_settings: &CacheSettings,
         nfes: &mut Vec<Error>,
     ) -> Result<Self, Error> {
         let path = dependency.registry_path.clone().unwrap_or_else(|| {
@@ -87,20 +93,28 @@ impl RegistryRepo {
                 sub_folder: None,
             }
         });
-        Self::try_new(Some(dependency.schema_url.clone()), &path, nfes)
+        Self::try_new(
+            Some(dependency.schema_url.clone()),
+            &path,
+            cache_settings,
+            nfes,
+        )
     }
 
     /// Creates a new `RegistryRepo` from a schema URL and `RegistryPath` object that
     /// specifies the location of the registry.
     /// If there is no manifest and schema URL is not provided, registry
     /// name and version are set to "unknown".
+    /// Remote registries, including the dependencies of this registry, are resolved with the
+    /// given cache settings.
     pub fn try_new(
         schema_url: Option<SchemaUrl>,
         registry_path: &VirtualDirectoryPath,
+        cache_settings: &CacheSettings,
         nfes: &mut Vec<Error>,
     ) -> Result<Self, Error> {
-        let registry =
-            VirtualDirectory::try_new(registry_path).map_err(Error::VirtualDirectoryError)?;
+        let registry = VirtualDirectory::try_new(registry_path, cache_settings)
+            .map_err(Error::VirtualDirectoryError)?;
         // Try to load manifest
         let manifest_path = find_manifest_path(registry.path());
         if let Some(ref path) = manifest_path {
@@ -111,6 +125,7 @@ impl RegistryRepo {
                 registry,
                 manifest: Some(registry_manifest),
                 manifest_path,
+                cache_settings: cache_settings.clone(),
             })
         } else {
             // No manifest
@@ -120,6 +135,7 @@ impl RegistryRepo {
                 registry,
                 manifest: None,
                 manifest_path: None,
+                cache_settings: cache_settings.clone(),
             })
         }
     }
@@ -142,6 +158,12 @@ impl RegistryRepo {
         self.registry.path()
     }
 
+    /// Returns the virtual directory containing the registry.
+    #[must_use]
+    pub fn virtual_directory(&self) -> &VirtualDirectory {
+        &self.registry
+    }
+
     /// Returns the registry path textual representation.
     #[must_use]
     pub fn registry_path_repr(&self) -> &str {
@@ -188,6 +210,12 @@ impl RegistryRepo {
     pub fn schema_url(&self) -> &SchemaUrl {
         &self.schema_url
     }
+
+    /// Returns the cache settings used to resolve this registry and its dependencies.
+    #[must_use]
+    pub fn cache_settings(&self) -> &CacheSettings {
+        &self.cache_settings
+    }
 }
 
 impl Default for RegistryRepo {
@@ -197,6 +225,7 @@ impl Default for RegistryRepo {
             registry: VirtualDirectory::default(),
             manifest: None,
             manifest_path: None,
+            cache_settings: CacheSettings::default(),
         }
     }
 }
@@ -221,7 +250,9 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "../../crates/weaver_codegen_test/semconv_registry".to_owned(),
         };
-        let repo = RegistryRepo::try_new(None, &registry_path, &mut vec![]).unwrap();
+        let repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+                .unwrap();
         let repo_path = repo.path().to_path_buf();
         assert!(repo_path.exists());
         assert!(
@@ -241,8 +272,9 @@ mod tests {
             path: "tests/published_repository/resolved/1.0.0".to_owned(),
         };
 
-        let repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])
-            .expect("Failed to load test repository.");
+        let repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+                .expect("Failed to load test repository.");
 
         let Some(manifest) = repo.manifest() else {
             panic!("Did not resolve manifest for repo: {repo:?}");
@@ -264,8 +296,9 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "tests/published_repository/resolved/2.0.0".to_owned(),
         };
-        let repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])
-            .expect("Failed to load test repository.");
+        let repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+                .expect("Failed to load test repository.");
         let Some(resolved_path) = repo.resolved_schema_uri() else {
             panic!(
                 "Should find a resolved schema path from manifest in {}",
@@ -278,8 +311,9 @@ mod tests {
         let registry_path = VirtualDirectoryPath::LocalFolder {
             path: "tests/published_repository/3.0.0".to_owned(),
         };
-        let repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])
-            .expect("Failed to load test repository.");
+        let repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])
+                .expect("Failed to load test repository.");
         let Some(resolved_path) = repo.resolved_schema_uri() else {
             panic!(
                 "Should find a resolved schema path from manifest in {}",
@@ -5,11 +5,11 @@
 use crate::group::{GroupSpec, GroupWildcard};
 use crate::json_schema::JsonSchemaValidator;
 use crate::provenance::Provenance;
+use crate::source_location::SourceLocations;
 use crate::v2::SemConvSpecV2;
 use crate::Error;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::fs::File;
 use std::path::Path;
 use std::sync::OnceLock;
 use weaver_common::result::WResult;
@@ -18,7 +18,7 @@ static VALIDATOR_V1: OnceLock<JsonSchemaValidator> = OnceLock::new();
 static VALIDATOR_V2: OnceLock<JsonSchemaValidator> = OnceLock::new();
 
 /// A versioned semantic convention file.
-#[derive(Serialize, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
 #[serde(tag = "file_format")]
 #[allow(
     clippy::large_enum_variant,
@@ -79,6 +79,8 @@ pub struct SemConvSpecWithProvenance {
     pub spec: Versioned,
     /// The provenance of the semantic convention spec (path or URL).
     pub provenance: Provenance,
+    /// The locations of the groups defined in the semantic convention file.
+    pub source_locations: SourceLocations,
 }
 
 /// A wrapper for a [`SemConvSpecV1`] with its provenance.
@@ -88,6 +90,8 @@ pub struct SemConvSpecV1WithProvenance {
     pub spec: SemConvSpecV1,
     /// The provenance of the semantic convention spec (path or URL).
     pub provenance: Provenance,
+    /// The locations of the groups defined in the semantic convention file.
+    pub source_locations: SourceLocations,
 }
 
 impl SemConvSpecV1 {
@@ -143,7 +147,7 @@ impl Versioned {
 // This is a helper method to pull "normal" parts of a file path
 // to give a relatively unique name to the attribute group registry
 // when converting from V1 to V2.
-fn provenance_path_to_name(path: &str) -> String {
+pub(crate) fn provenance_path_to_name(path: &str) -> String {
     // At least allocate the full path.
     let mut result = String::with_capacity(path.len());
     let mut need_dot = false;
@@ -329,6 +333,7 @@ impl SemConvSpecWithProvenance {
         SemConvSpecV1WithProvenance {
             spec: self.spec.into_v1(&file_name),
             provenance: self.provenance,
+            source_locations: self.source_locations,
         }
     }
     // pub fn into_v1(self) -> SemConvSpecV1
@@ -367,24 +372,30 @@ impl SemConvSpecWithProvenance {
         P: AsRef<Path>,
         F: Fn(String) -> String,
     {
-        fn read_yaml_file(path: &Path, provenance: &str) -> Result<serde_yaml::Value, Error> {
-            let semconv_file = File::open(path).map_err(|e| Error::RegistryNotFound {
+        fn read_yaml_file(
+            path: &Path,
+            provenance: &str,
+        ) -> Result<(serde_yaml::Value, String), Error> {
+            let content = std::fs::read_to_string(path).map_err(|e| Error::RegistryNotFound {
                 path_or_url: provenance.to_owned(),
                 error: e.to_string(),
             })?;
 
-            serde_yaml::from_reader(semconv_file).map_err(|e| Error::DeserializationError {
-                path_or_url: provenance.to_owned(),
-                error: e.to_string(),
-            })
+            let value =
+                serde_yaml::from_str(&content).map_err(|e| Error::DeserializationError {
+                    path_or_url: provenance.to_owned(),
+                    error: e.to_string(),
+                })?;
+            Ok((value, content))
         }
 
         let path = path.as_ref().display().to_string();
         let provenance = Provenance::new(registry_id, &path_fixer(path.clone()));
-        let yaml_value = match read_yaml_file(path.as_ref(), &path) {
+        let (yaml_value, content) = match read_yaml_file(path.as_ref(), &path) {
             Ok(value) => value,
             Err(e) => return WResult::FatalErr(e),
         };
+        let source_locations = SourceLocations::from_yaml_str(&provenance.path, &content);
         let mut warnings = Vec::new();
 
         let raw_spec = match from_yaml_value(yaml_value, &path, &mut warnings) {
@@ -395,9 +406,21 @@ impl SemConvSpecWithProvenance {
             }
             Err(e) => WResult::FatalErr(e),
         };
+        // Point the errors at the definitions they are about.
+        let raw_spec = match raw_spec {
+            WResult::Ok(spec) => WResult::Ok(spec),
+            WResult::OkWithNFEs(spec, errs) => WResult::OkWithNFEs(
+                spec,
+                errs.into_iter()
+                    .map(|e| e.with_source_definition(&source_locations))
+                    .collect(),
+            ),
+            WResult::FatalErr(e) => WResult::FatalErr(e.with_source_definition(&source_locations)),
+        };
         let result = raw_spec.map(|spec| SemConvSpecWithProvenance {
             spec,
             provenance: provenance.clone(),
+            source_locations,
         });
         if warnings.is_empty() {
             result
@@ -614,8 +637,19 @@ mod tests {
         let temp_file = make_temp_file(spec);
         let semconv_spec = SemConvSpecWithProvenance::from_file("test", temp_file.path())
             .into_result_failing_non_fatal();
-        if let Err(CompoundError(errors)) = semconv_spec {
+        if let Err(CompoundError(mut errors)) = semconv_spec {
             assert_eq!(errors.len(), 7);
+            // The attribute errors point at the definition of the attribute.
+            let mut lines = vec![];
+            for error in errors.iter_mut() {
+                if let InvalidAttribute { definition, .. }
+                | InvalidAttributeWarning { definition, .. }
+                | InvalidExampleWarning { definition, .. } = error
+                {
+                    lines.push(definition.take().map(|d| d.location.line));
+                }
+            }
+            assert_eq!(lines, vec![Some(7), Some(7), Some(16), Some(16)]);
             assert_eq!(
                 errors,
                 vec![
@@ -636,6 +670,7 @@ mod tests {
                         error:
                             "This attribute is not deprecated and does not contain a brief field."
                                 .to_owned(),
+                        definition: None,
                     },
                     InvalidExampleWarning {
                         path_or_url: temp_file.path().display().to_string(),
@@ -643,6 +678,7 @@ mod tests {
                         attribute_id: "attr1".to_owned(),
                         error: "This attribute is a string but it does not contain any examples."
                             .to_owned(),
+                        definition: None,
                     },
                     InvalidAttribute {
                         path_or_url: temp_file.path().display().to_string(),
@@ -651,12 +687,14 @@ mod tests {
                         error:
                             "This attribute is not deprecated and does not contain a brief field."
                                 .to_owned(),
+                        definition: None,
                     },
                     InvalidAttributeWarning {
                         path_or_url: temp_file.path().display().to_string(),
                         group_id: "group2".to_owned(),
                         attribute_id: "attr2".to_owned(),
                         error: "Missing stability field.".to_owned(),
+                        definition: None,
                     },
                     InvalidGroupMissingType {
                         path_or_url: temp_file.path().display().to_string(),
@@ -670,6 +708,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_errors_label_source_definition() {
+        use miette::Diagnostic;
+
+        let spec = r#"groups:
+  - id: group1
+    type: attribute_group
+    brief: "description1"
+    attributes:
+      - id: attr1
+        type: int
+        stability: stable
+"#;
+        let temp_file = make_temp_file(spec);
+        let error = SemConvSpecWithProvenance::from_file("test", temp_file.path())
+            .into_result_failing_non_fatal()
+            .unwrap_err();
+        assert!(matches!(error, InvalidAttribute { .. }), "{error:?}");
+
+        let label = error.labels().unwrap().next().unwrap();
+        assert_eq!(label.label(), Some("attribute defined here"));
+        let snippet = error
+            .source_code()
+            .unwrap()
+            .read_span(label.inner(), 0, 0)
+            .unwrap();
+        assert_eq!(snippet.data(), b"attr1");
+        assert_eq!(snippet.line(), 5);
+        assert_eq!(
+            snippet.name(),
+            Some(temp_file.path().display().to_string().as_str())
+        );
+    }
+
     #[test]
     fn test_semconv_spec_with_provenance_from_file() {
         let path = PathBuf::from("data/database.yaml");
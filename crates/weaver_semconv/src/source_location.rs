@@ -0,0 +1,485 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Locations of the definitions of a semantic convention file.
+//!
+//! `serde_yaml` does not keep track of where a value was defined, so the file is also
+//! loaded as a [`MarkedYaml`] document to index the line, the column and the byte span of
+//! each group and of the attributes it defines or references. Groups defined with the
+//! `definition/2` file format are indexed under the id of the group they are translated
+//! into (e.g. `metric.{name}` or `registry.{file_name}` for the attributes of the file).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use miette::{MietteError, NamedSource, SourceCode, SourceSpan, SpanContents};
+use saphyr::{LoadableYamlNode, MarkedYaml};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::semconv::provenance_path_to_name;
+
+/// A position in a semantic convention file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct SourceLocation {
+    /// The line number (1-based).
+    pub line: usize,
+    /// The column number (1-based).
+    pub column: usize,
+}
+
+/// A definition in a semantic convention file: its location and the source it comes
+/// from, so diagnostics can render it as a labelled snippet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceDefinition {
+    /// The location of the definition.
+    pub location: SourceLocation,
+    source: Arc<NamedSource<Arc<str>>>,
+    span: SourceSpan,
+}
+
+impl SourceCode for SourceDefinition {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        self.source
+            .read_span(span, context_lines_before, context_lines_after)
+    }
+}
+
+// Errors box their definition to stay small, so miette's `source_code` and `label`
+// attributes are implemented for the boxed definition.
+impl SourceCode for Box<SourceDefinition> {
+    fn read_span<'a>(
+        &'a self,
+        span: &SourceSpan,
+        context_lines_before: usize,
+        context_lines_after: usize,
+    ) -> Result<Box<dyn SpanContents<'a> + 'a>, MietteError> {
+        self.as_ref()
+            .read_span(span, context_lines_before, context_lines_after)
+    }
+}
+
+impl From<Box<SourceDefinition>> for SourceSpan {
+    fn from(definition: Box<SourceDefinition>) -> Self {
+        definition.span
+    }
+}
+
+/// The definitions of a group and of its attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupSourceLocations {
+    /// The definition of the group.
+    pub definition: SourceDefinition,
+    /// The definition of each attribute defined (`id`, `key`) or referenced (`ref`) by
+    /// the group.
+    pub attributes: HashMap<String, SourceDefinition>,
+}
+
+impl GroupSourceLocations {
+    /// Returns the location of the group definition.
+    #[must_use]
+    pub fn location(&self) -> SourceLocation {
+        self.definition.location
+    }
+
+    /// Returns the definition of an attribute of the group, falling back to the
+    /// definition of the group.
+    #[must_use]
+    pub fn attribute(&self, attribute: &str) -> &SourceDefinition {
+        self.attributes.get(attribute).unwrap_or(&self.definition)
+    }
+}
+
+/// The locations of the groups defined in a semantic convention file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceLocations {
+    groups: HashMap<String, GroupSourceLocations>,
+}
+
+/// The sections of a semantic convention file defining groups, with the key holding
+/// the name of each group and the prefix turning this name into a group id.
+const GROUP_SECTIONS: [(&str, &str, &str); 10] = [
+    ("groups", "id", ""),
+    ("attribute_groups", "id", ""),
+    ("entities", "type", "entity."),
+    ("events", "name", "event."),
+    ("metrics", "name", "metric."),
+    ("spans", "type", "span."),
+    ("entity_refinements", "id", ""),
+    ("event_refinements", "id", ""),
+    ("metric_refinements", "id", ""),
+    ("span_refinements", "id", ""),
+];
+
+/// The keys of a group holding the attributes it defines or references.
+const ATTRIBUTE_SECTIONS: [&str; 3] = ["attributes", "identity", "description"];
+
+/// The keys holding the name of an attribute.
+const ATTRIBUTE_NAME_KEYS: [&str; 3] = ["id", "ref", "key"];
+
+impl SourceLocations {
+    /// Indexes the groups defined in a YAML semantic convention file, `path` being the
+    /// path (or URL) of the file.
+    ///
+    /// Locations are a best effort: an invalid document produces an empty index, the
+    /// parsing errors being reported by the deserialization of the file.
+    #[must_use]
+    pub fn from_yaml_str(path: &str, yaml: &str) -> Self {
+        let Ok(docs) = MarkedYaml::load_from_str(yaml) else {
+            return Self::default();
+        };
+        let Some(doc) = docs.first() else {
+            return Self::default();
+        };
+        let indexer = Indexer::new(path, yaml);
+        let mut groups = HashMap::new();
+
+        for (section, name_key, prefix) in GROUP_SECTIONS {
+            let group_nodes = doc
+                .data
+                .as_mapping_get(section)
+                .and_then(|groups| groups.data.as_sequence());
+            for group in group_nodes.into_iter().flatten() {
+                let Some(name) = group.data.as_mapping_get(name_key) else {
+                    continue;
+                };
+                let Some(name_str) = name.data.as_str() else {
+                    continue;
+                };
+                let attributes = ATTRIBUTE_SECTIONS
+                    .iter()
+                    .filter_map(|key| group.data.as_mapping_get(key))
+                    .filter_map(|attributes| attributes.data.as_sequence())
+                    .flatten()
+                    .filter_map(|attribute| indexer.attribute(attribute))
+                    .collect();
+                _ = groups.insert(
+                    format!("{prefix}{name_str}"),
+                    GroupSourceLocations {
+                        definition: indexer.definition(group, name),
+                        attributes,
+                    },
+                );
+            }
+        }
+
+        // Attributes declared at the top level of a `definition/2` file are grouped into
+        // a synthetic `registry.{file_name}` group.
+        let registry_attributes = doc.data.as_mapping().and_then(|mapping| {
+            mapping
+                .iter()
+                .find(|(key, _)| key.data.as_str() == Some("attributes"))
+        });
+        if let Some((key, attributes)) = registry_attributes {
+            let attributes = attributes
+                .data
+                .as_sequence()
+                .into_iter()
+                .flatten()
+                .filter_map(|attribute| indexer.attribute(attribute))
+                .collect();
+            _ = groups.insert(
+                format!("registry.{}", provenance_path_to_name(path)),
+                GroupSourceLocations {
+                    definition: indexer.definition(key, key),
+                    attributes,
+                },
+            );
+        }
+
+        Self { groups }
+    }
+
+    /// Returns the definitions of a group and of its attributes.
+    #[must_use]
+    pub fn group(&self, group_id: &str) -> Option<&GroupSourceLocations> {
+        self.groups.get(group_id)
+    }
+
+    /// Returns the location of an attribute defined or referenced by a group, falling
+    /// back to the location of the group.
+    #[must_use]
+    pub fn attribute(&self, group_id: &str, attribute: &str) -> Option<SourceLocation> {
+        self.attribute_definition(group_id, attribute)
+            .map(|definition| definition.location)
+    }
+
+    /// Returns the definition of an attribute defined or referenced by a group, falling
+    /// back to the definition of the group.
+    #[must_use]
+    pub fn attribute_definition(
+        &self,
+        group_id: &str,
+        attribute: &str,
+    ) -> Option<&SourceDefinition> {
+        Some(self.groups.get(group_id)?.attribute(attribute))
+    }
+
+    /// Returns the id and the locations of every group of the file.
+    pub fn groups(&self) -> impl Iterator<Item = (&String, &GroupSourceLocations)> {
+        self.groups.iter()
+    }
+
+    /// Returns the locations without the source of the file, e.g. to store them in a cache.
+    #[must_use]
+    pub fn index(&self) -> SourceLocationsIndex {
+        let definition_index = |definition: &SourceDefinition| DefinitionIndex {
+            location: definition.location,
+            offset: definition.span.offset(),
+            len: definition.span.len(),
+        };
+        SourceLocationsIndex {
+            groups: self
+                .groups
+                .iter()
+                .map(|(id, group)| {
+                    let index = GroupIndex {
+                        definition: definition_index(&group.definition),
+                        attributes: group
+                            .attributes
+                            .iter()
+                            .map(|(name, attribute)| (name.clone(), definition_index(attribute)))
+                            .collect(),
+                    };
+                    (id.clone(), index)
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the locations of a file from their index and the content of the file, `path`
+    /// being the path (or URL) of the file.
+    #[must_use]
+    pub fn from_index(path: &str, yaml: &str, index: SourceLocationsIndex) -> Self {
+        let source = Arc::new(NamedSource::new(path, Arc::<str>::from(yaml)));
+        let definition = |index: DefinitionIndex| SourceDefinition {
+            location: index.location,
+            source: source.clone(),
+            span: (index.offset, index.len).into(),
+        };
+        Self {
+            groups: index
+                .groups
+                .into_iter()
+                .map(|(id, group)| {
+                    let locations = GroupSourceLocations {
+                        definition: definition(group.definition),
+                        attributes: group
+                            .attributes
+                            .into_iter()
+                            .map(|(name, attribute)| (name, definition(attribute)))
+                            .collect(),
+                    };
+                    (id, locations)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The locations of the groups defined in a semantic convention file, without the source
+/// of the file (see [`SourceLocations::index`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceLocationsIndex {
+    groups: HashMap<String, GroupIndex>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct GroupIndex {
+    definition: DefinitionIndex,
+    attributes: HashMap<String, DefinitionIndex>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct DefinitionIndex {
+    location: SourceLocation,
+    offset: usize,
+    len: usize,
+}
+
+/// Builds the definitions of the nodes of a YAML document.
+struct Indexer {
+    source: Arc<NamedSource<Arc<str>>>,
+    /// The byte offsets of each character of the document (spans are in characters).
+    char_offsets: Vec<usize>,
+}
+
+impl Indexer {
+    fn new(path: &str, yaml: &str) -> Self {
+        let mut char_offsets: Vec<usize> = yaml.char_indices().map(|(i, _)| i).collect();
+        // Spans may reference the position after the last character
+        char_offsets.push(yaml.len());
+        Self {
+            source: Arc::new(NamedSource::new(path, Arc::from(yaml))),
+            char_offsets,
+        }
+    }
+
+    /// The definition of the attribute node, keyed by the name of the attribute.
+    fn attribute(&self, attribute: &MarkedYaml<'_>) -> Option<(String, SourceDefinition)> {
+        let name = ATTRIBUTE_NAME_KEYS
+            .iter()
+            .find_map(|key| attribute.data.as_mapping_get(key))?;
+        let name_str = name.data.as_str()?;
+        Some((name_str.to_owned(), self.definition(attribute, name)))
+    }
+
+    /// The definition located at the start of `node` and labelling its `name` node.
+    fn definition(&self, node: &MarkedYaml<'_>, name: &MarkedYaml<'_>) -> SourceDefinition {
+        let offset = |index: usize| {
+            self.char_offsets
+                .get(index)
+                .copied()
+                .unwrap_or(self.char_offsets[self.char_offsets.len() - 1])
+        };
+        let start = offset(name.span.start.index());
+        let end = offset(name.span.end.index()).max(start);
+        SourceDefinition {
+            location: SourceLocation {
+                line: node.span.start.line(),
+                column: node.span.start.col() + 1,
+            },
+            source: self.source.clone(),
+            span: (start, end - start).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_locations() {
+        let yaml = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    attributes:
+      - id: http.request.method
+        type: string
+        brief: The method.
+      - ref: url.full
+  - id: span.http.client
+    type: span
+    brief: HTTP client span.
+"#;
+        let locations = SourceLocations::from_yaml_str("http.yaml", yaml);
+        let group = locations.group("registry.http").unwrap();
+        assert_eq!(group.location(), SourceLocation { line: 2, column: 5 });
+        assert_eq!(
+            locations.attribute("registry.http", "http.request.method"),
+            Some(SourceLocation { line: 6, column: 9 })
+        );
+        assert_eq!(
+            locations.attribute("registry.http", "url.full"),
+            Some(SourceLocation { line: 9, column: 9 })
+        );
+        // Unknown attributes fall back to the group location.
+        assert_eq!(
+            locations.attribute("span.http.client", "server.address"),
+            Some(SourceLocation {
+                line: 10,
+                column: 5
+            })
+        );
+        assert_eq!(locations.attribute("unknown", "url.full"), None);
+        assert_eq!(locations.groups().count(), 2);
+
+        // The definitions label the id (or ref) of the group or of the attribute.
+        let definition = locations
+            .attribute_definition("registry.http", "url.full")
+            .unwrap();
+        assert_eq!(
+            &yaml[definition.span.offset()..][..definition.span.len()],
+            "url.full"
+        );
+        let definition = &group.definition;
+        assert_eq!(
+            &yaml[definition.span.offset()..][..definition.span.len()],
+            "registry.http"
+        );
+
+        assert_eq!(
+            SourceLocations::from_yaml_str("invalid.yaml", "groups: [")
+                .groups()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_source_locations_v2() {
+        let yaml = r#"file_format: definition/2
+attributes:
+  - key: é.attr
+    type: string
+    brief: A non-ASCII attribute.
+metrics:
+  - name: my.metric
+    instrument: counter
+    unit: "1"
+    attributes:
+      - ref: é.attr
+entities:
+  - type: my.entity
+    identity:
+      - ref: é.attr
+metric_refinements:
+  - id: metric.my.refined.metric
+    ref: my.metric
+"#;
+        let locations = SourceLocations::from_yaml_str("model/my_file.yaml", yaml);
+        assert_eq!(locations.groups().count(), 4);
+        assert_eq!(
+            locations.attribute("registry.model.my_file", "é.attr"),
+            Some(SourceLocation { line: 3, column: 5 })
+        );
+        assert_eq!(
+            locations.attribute("metric.my.metric", "é.attr"),
+            Some(SourceLocation {
+                line: 11,
+                column: 9
+            })
+        );
+        assert_eq!(
+            locations.attribute("entity.my.entity", "é.attr"),
+            Some(SourceLocation {
+                line: 15,
+                column: 9
+            })
+        );
+        assert_eq!(
+            locations
+                .group("metric.my.refined.metric")
+                .unwrap()
+                .location(),
+            SourceLocation {
+                line: 17,
+                column: 5
+            }
+        );
+
+        // Spans are byte ranges, even after non-ASCII characters.
+        let definition = locations
+            .attribute_definition("metric.my.metric", "é.attr")
+            .unwrap();
+        assert_eq!(
+            &yaml[definition.span.offset()..][..definition.span.len()],
+            "é.attr"
+        );
+
+        // The index restores the same locations.
+        let index = serde_json::to_string(&locations.index()).unwrap();
+        let restored = SourceLocations::from_index(
+            "model/my_file.yaml",
+            yaml,
+            serde_json::from_str(&index).unwrap(),
+        );
+        assert_eq!(restored, locations);
+    }
+}
@@ -9,7 +9,7 @@ use weaver_common::{
 };
 use weaver_forge::{registry::ResolvedGroup, OutputProcessor};
 use weaver_resolved_schema::{catalog::Catalog, registry::Group, ResolvedTelemetrySchema};
-use weaver_resolver::SchemaResolver;
+use weaver_resolver::{ResolutionCache, SchemaResolver};
 use weaver_semconv::registry_repo::RegistryRepo;
 
 use crate::{parser::GenerateMarkdownArgs, Error, MarkdownSnippetGenerator};
@@ -65,18 +65,22 @@ impl ResolvedSemconvRegistry {
         follow_symlinks: bool,
         include_unreferenced: bool,
     ) -> Result<ResolvedSemconvRegistry, Error> {
-        let loaded =
-            match SchemaResolver::load_semconv_repository(registry_repo.clone(), follow_symlinks) {
-                WResult::Ok(semconv_specs) => semconv_specs,
-                WResult::OkWithNFEs(semconv_specs, errs) => {
-                    diag_msgs
-                        .extend_from_vec(errs.into_iter().map(DiagnosticMessage::new).collect());
-                    semconv_specs
-                }
-                WResult::FatalErr(err) => return Err(err.into()),
-            };
-
-        let schema = match SchemaResolver::resolve(loaded, include_unreferenced) {
+        // The resolution cache is the one configured for the registry, if enabled.
+        let cache = ResolutionCache::from_settings(registry_repo.cache_settings());
+        let loaded = match SchemaResolver::load_semconv_repository(
+            registry_repo.clone(),
+            follow_symlinks,
+            cache.as_ref(),
+        ) {
+            WResult::Ok(semconv_specs) => semconv_specs,
+            WResult::OkWithNFEs(semconv_specs, errs) => {
+                diag_msgs.extend_from_vec(errs.into_iter().map(DiagnosticMessage::new).collect());
+                semconv_specs
+            }
+            WResult::FatalErr(err) => return Err(err.into()),
+        };
+
+        let schema = match SchemaResolver::resolve(loaded, include_unreferenced, cache.as_ref()) {
             WResult::Ok(schema) => schema,
             WResult::OkWithNFEs(schema, errs) => {
                 diag_msgs.extend_from_vec(errs.into_iter().map(DiagnosticMessage::new).collect());
@@ -166,6 +170,7 @@ mod tests {
     use crate::v1::SnippetGenerator;
     use crate::{Error, MarkdownSnippetGenerator};
     use serde_yaml::Value;
+    use weaver_common::cache::CacheSettings;
     use weaver_common::diagnostic::DiagnosticMessages;
     use weaver_common::vdir::VirtualDirectoryPath;
     use weaver_forge::config::{Params, WeaverConfig};
@@ -197,7 +202,8 @@ mod tests {
             path: "data".to_owned(),
         };
         let mut diag_msgs = DiagnosticMessages::empty();
-        let registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
+        let registry_repo =
+            RegistryRepo::try_new(None, &registry_path, &CacheSettings::default(), &mut vec![])?;
         let generator = SnippetGenerator::try_from_registry_repo(
             &registry_repo,
             output,
@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built-in rules classifying schema changes as breaking or non-breaking.
+//!
+//! The rules are based on the stability of the telemetry objects in the baseline registry.
+//! Only changes to stable objects can be breaking:
+//!
+//! - removing a stable object (this includes renaming it without a `renamed` deprecation),
+//! - changing the type of a stable attribute,
+//! - removing members from the enum of a stable attribute,
+//! - changing the unit or the instrument of a stable metric.
+//!
+//! Additions and deprecations are always non-breaking.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::schema_changes::{SchemaItemChange, SchemaItemType};
+
+/// The compatibility of a change with the consumers of the baseline registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    /// The change breaks the consumers of the baseline registry.
+    Breaking,
+    /// The change is backward compatible.
+    NonBreaking,
+}
+
+/// The built-in rule used to classify a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityRule {
+    /// A telemetry object was added.
+    Added,
+    /// A telemetry object was deprecated (renamed, obsoleted or uncategorized).
+    Deprecated,
+    /// A stable telemetry object was removed, or renamed without a `renamed` deprecation.
+    StableRemoved,
+    /// A telemetry object that is not stable was removed.
+    UnstableRemoved,
+    /// The type of a stable attribute changed.
+    TypeChanged,
+    /// Members were removed from the enum of a stable attribute.
+    EnumNarrowed,
+    /// The unit of a stable metric changed.
+    UnitChanged,
+    /// The instrument of a stable metric changed.
+    InstrumentChanged,
+}
+
+/// A change classified by the built-in compatibility rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CompatibilityFinding {
+    /// The type of the changed telemetry object.
+    pub item_type: SchemaItemType,
+    /// The name of the changed telemetry object (in the baseline registry when it exists).
+    pub name: String,
+    /// The rule used to classify the change.
+    pub rule: CompatibilityRule,
+    /// The compatibility of the change.
+    pub compatibility: Compatibility,
+    /// A human-readable description of the change.
+    pub message: String,
+}
+
+impl CompatibilityFinding {
+    /// Returns true if the change is breaking.
+    #[must_use]
+    pub fn is_breaking(&self) -> bool {
+        self.compatibility == Compatibility::Breaking
+    }
+}
+
+/// The fields of a telemetry object inspected by the compatibility rules.
+#[derive(Debug, Default, Clone)]
+pub struct ItemSnapshot {
+    /// True if the telemetry object is stable.
+    pub stable: bool,
+    /// The type of an attribute, `enum` for enums.
+    pub r#type: Option<String>,
+    /// The values of the members of an enum attribute.
+    pub enum_members: Option<BTreeSet<String>>,
+    /// The unit of a metric.
+    pub unit: Option<String>,
+    /// The instrument of a metric.
+    pub instrument: Option<String>,
+}
+
+/// Classifies the changes of a type of telemetry object.
+///
+/// `changes` are the changes detected between the baseline and the head registry,
+/// `head` and `baseline` are the telemetry objects of this type indexed by name.
+/// Field-level changes of objects present in both registries are also classified.
+#[must_use]
+pub fn classify_changes(
+    item_type: SchemaItemType,
+    changes: &[SchemaItemChange],
+    head: &HashMap<&str, ItemSnapshot>,
+    baseline: &HashMap<&str, ItemSnapshot>,
+) -> Vec<CompatibilityFinding> {
+    let kind = item_kind(item_type);
+    let mut findings = Vec::new();
+    let mut finding = |name: &str, rule, compatibility, message| {
+        findings.push(CompatibilityFinding {
+            item_type,
+            name: name.to_owned(),
+            rule,
+            compatibility,
+            message,
+        });
+    };
+
+    for change in changes {
+        match change {
+            SchemaItemChange::Added { name } => finding(
+                name,
+                CompatibilityRule::Added,
+                Compatibility::NonBreaking,
+                format!("The {kind} '{name}' was added."),
+            ),
+            SchemaItemChange::Renamed {
+                old_name, new_name, ..
+            } => finding(
+                old_name,
+                CompatibilityRule::Deprecated,
+                Compatibility::NonBreaking,
+                format!("The {kind} '{old_name}' was deprecated and renamed to '{new_name}'."),
+            ),
+            SchemaItemChange::Obsoleted { name, .. }
+            | SchemaItemChange::Uncategorized { name, .. } => finding(
+                name,
+                CompatibilityRule::Deprecated,
+                Compatibility::NonBreaking,
+                format!("The {kind} '{name}' was deprecated."),
+            ),
+            SchemaItemChange::Removed { name } => {
+                if baseline.get(name.as_str()).is_some_and(|item| item.stable) {
+                    finding(
+                        name,
+                        CompatibilityRule::StableRemoved,
+                        Compatibility::Breaking,
+                        format!(
+                            "The stable {kind} '{name}' was removed. Stable definitions must be deprecated (e.g. with `reason: renamed` and `renamed_to`) instead of being removed or renamed."
+                        ),
+                    );
+                } else {
+                    finding(
+                        name,
+                        CompatibilityRule::UnstableRemoved,
+                        Compatibility::NonBreaking,
+                        format!("The {kind} '{name}' was removed."),
+                    );
+                }
+            }
+            // Field-level changes are classified below.
+            SchemaItemChange::Updated { .. } => {}
+        }
+    }
+
+    // Field-level changes of the stable telemetry objects.
+    let mut names: Vec<_> = baseline.keys().copied().collect();
+    names.sort_unstable();
+    for name in names {
+        let (Some(baseline_item), Some(head_item)) = (baseline.get(name), head.get(name)) else {
+            continue;
+        };
+        if !baseline_item.stable {
+            continue;
+        }
+        if baseline_item.r#type != head_item.r#type {
+            finding(
+                name,
+                CompatibilityRule::TypeChanged,
+                Compatibility::Breaking,
+                format!(
+                    "The type of the stable {kind} '{name}' changed from '{}' to '{}'.",
+                    baseline_item.r#type.as_deref().unwrap_or_default(),
+                    head_item.r#type.as_deref().unwrap_or_default()
+                ),
+            );
+        } else if let (Some(baseline_members), Some(head_members)) =
+            (&baseline_item.enum_members, &head_item.enum_members)
+        {
+            let removed: Vec<_> = baseline_members
+                .difference(head_members)
+                .map(|member| format!("'{member}'"))
+                .collect();
+            if !removed.is_empty() {
+                finding(
+                    name,
+                    CompatibilityRule::EnumNarrowed,
+                    Compatibility::Breaking,
+                    format!(
+                        "The enum member(s) {} were removed from the stable {kind} '{name}'.",
+                        removed.join(", ")
+                    ),
+                );
+            }
+        }
+        if baseline_item.unit != head_item.unit {
+            finding(
+                name,
+                CompatibilityRule::UnitChanged,
+                Compatibility::Breaking,
+                format!(
+                    "The unit of the stable {kind} '{name}' changed from '{}' to '{}'.",
+                    baseline_item.unit.as_deref().unwrap_or_default(),
+                    head_item.unit.as_deref().unwrap_or_default()
+                ),
+            );
+        }
+        if baseline_item.instrument != head_item.instrument {
+            finding(
+                name,
+                CompatibilityRule::InstrumentChanged,
+                Compatibility::Breaking,
+                format!(
+                    "The instrument of the stable {kind} '{name}' changed from '{}' to '{}'.",
+                    baseline_item.instrument.as_deref().unwrap_or_default(),
+                    head_item.instrument.as_deref().unwrap_or_default()
+                ),
+            );
+        }
+    }
+
+    findings
+}
+
+/// Returns the singular name of a type of telemetry object.
+fn item_kind(item_type: SchemaItemType) -> &'static str {
+    match item_type {
+        SchemaItemType::RegistryAttributes => "attribute",
+        SchemaItemType::Metrics => "metric",
+        SchemaItemType::Events => "event",
+        SchemaItemType::Spans => "span",
+        SchemaItemType::Entities => "entity",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stable_metric(unit: &str, instrument: &str) -> ItemSnapshot {
+        ItemSnapshot {
+            stable: true,
+            unit: Some(unit.to_owned()),
+            instrument: Some(instrument.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn enum_attribute(stable: bool, members: &[&str]) -> ItemSnapshot {
+        ItemSnapshot {
+            stable,
+            r#type: Some("enum".to_owned()),
+            enum_members: Some(members.iter().map(|m| (*m).to_owned()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_removed_items() {
+        let baseline = HashMap::from([
+            ("stable.attr", enum_attribute(true, &["a"])),
+            ("dev.attr", enum_attribute(false, &["a"])),
+        ]);
+        let changes = vec![
+            SchemaItemChange::Removed {
+                name: "stable.attr".to_owned(),
+            },
+            SchemaItemChange::Removed {
+                name: "dev.attr".to_owned(),
+            },
+            SchemaItemChange::Added {
+                name: "new.attr".to_owned(),
+            },
+        ];
+        let findings = classify_changes(
+            SchemaItemType::RegistryAttributes,
+            &changes,
+            &HashMap::new(),
+            &baseline,
+        );
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0].rule, CompatibilityRule::StableRemoved);
+        assert!(findings[0].is_breaking());
+        assert_eq!(findings[1].rule, CompatibilityRule::UnstableRemoved);
+        assert!(!findings[1].is_breaking());
+        assert_eq!(findings[2].rule, CompatibilityRule::Added);
+        assert!(!findings[2].is_breaking());
+    }
+
+    #[test]
+    fn test_enum_narrowing() {
+        let baseline = HashMap::from([
+            ("stable.attr", enum_attribute(true, &["a", "b"])),
+            ("dev.attr", enum_attribute(false, &["a", "b"])),
+            ("widened.attr", enum_attribute(true, &["a"])),
+        ]);
+        let head = HashMap::from([
+            ("stable.attr", enum_attribute(true, &["a"])),
+            ("dev.attr", enum_attribute(false, &["a"])),
+            ("widened.attr", enum_attribute(true, &["a", "b"])),
+        ]);
+        let findings = classify_changes(SchemaItemType::RegistryAttributes, &[], &head, &baseline);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].name, "stable.attr");
+        assert_eq!(findings[0].rule, CompatibilityRule::EnumNarrowed);
+        assert!(findings[0].is_breaking());
+    }
+
+    #[test]
+    fn test_metric_changes() {
+        let baseline = HashMap::from([
+            ("metric.unit", stable_metric("s", "histogram")),
+            ("metric.instrument", stable_metric("s", "histogram")),
+            ("metric.same", stable_metric("s", "histogram")),
+        ]);
+        let head = HashMap::from([
+            ("metric.unit", stable_metric("ms", "histogram")),
+            ("metric.instrument", stable_metric("s", "counter")),
+            ("metric.same", stable_metric("s", "histogram")),
+        ]);
+        let changes = vec![SchemaItemChange::Renamed {
+            old_name: "metric.same".to_owned(),
+            new_name: "metric.other".to_owned(),
+            note: "".to_owned(),
+        }];
+        let findings = classify_changes(SchemaItemType::Metrics, &changes, &head, &baseline);
+        let rules: Vec<_> = findings.iter().map(|f| (f.name.as_str(), f.rule)).collect();
+        assert_eq!(
+            rules,
+            vec![
+                ("metric.same", CompatibilityRule::Deprecated),
+                ("metric.instrument", CompatibilityRule::InstrumentChanged),
+                ("metric.unit", CompatibilityRule::UnitChanged),
+            ]
+        );
+        assert_eq!(findings.iter().filter(|f| f.is_breaking()).count(), 2);
+    }
+}
@@ -4,9 +4,11 @@
 
 use schemars::JsonSchema;
 use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use std::str::FromStr;
 
 use crate::all_changes::AllChanges;
 use crate::logs_changes::LogsChanges;
@@ -19,12 +21,14 @@ use resource_changes::ResourceChange;
 use serde::{Deserialize, Serialize};
 use spans_changes::SpansChange;
 
-mod all_changes;
+pub mod all_changes;
+pub mod compatibility;
 pub mod logs_changes;
 pub mod metrics_changes;
 pub mod resource_changes;
 pub mod schema_changes;
 pub mod spans_changes;
+pub mod transform;
 pub mod v2;
 
 /// An error that can occur while loading or resolving version changes.
@@ -52,12 +56,47 @@ pub enum Error {
         /// The error that occurred.
         error: String,
     },
+
+    /// The version is not defined in the `versions` file.
+    #[error("Version {version:?} not found in the versions")]
+    UnknownVersion {
+        /// The unknown version.
+        version: String,
+    },
+
+    /// The version is not a valid semver version.
+    #[error("Invalid version {version:?}\n{error:?}")]
+    InvalidVersion {
+        /// The invalid version.
+        version: String,
+        /// The error that occurred.
+        error: String,
+    },
 }
 
 /// A version of the schema.
 #[derive(PartialOrd, PartialEq)]
 pub struct Version(semver::Version);
 
+impl FromStr for Version {
+    type Err = Error;
+
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        semver::Version::parse(version)
+            .map(Version)
+            .map_err(|e| Error::InvalidVersion {
+                version: version.to_owned(),
+                error: e.to_string(),
+            })
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 /// List of versions with their changes.
 #[derive(Serialize, Deserialize, Debug, Default, Clone, JsonSchema)]
 #[serde(transparent)]
@@ -66,19 +105,24 @@ pub struct Versions {
 }
 
 /// An history of changes to apply to the schema for different versions.
-#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct VersionSpec {
     /// The changes to apply to the following telemetry data: resource attributes,
     /// span attributes, span event attributes, log attributes, metric attributes.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub all: Option<AllChanges>,
     /// The changes to apply to the metrics specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<MetricsChanges>,
     /// The changes to apply to the logs specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub logs: Option<LogsChanges>,
     /// The changes to apply to the spans specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub spans: Option<SpansChanges>,
     /// The changes to apply to the resource specification for a specific version.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub resources: Option<ResourceChanges>,
 }
 
@@ -290,6 +334,18 @@ impl Versions {
         }
     }
 
+    /// Returns true if the given version is defined.
+    #[must_use]
+    pub fn contains(&self, version: &Version) -> bool {
+        self.versions.contains_key(&version.0)
+    }
+
+    /// Inserts the changes of a version, returning the changes previously defined for
+    /// this version if any.
+    pub fn insert(&mut self, version: Version, spec: VersionSpec) -> Option<VersionSpec> {
+        self.versions.insert(version.0, spec)
+    }
+
     /// Returns true if the `Versions` is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -304,6 +360,28 @@ impl Versions {
 }
 
 impl VersionSpec {
+    /// Returns true if the `VersionSpec` doesn't define any change.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.all.as_ref().map_or(true, |all| all.changes.is_empty())
+            && self
+                .metrics
+                .as_ref()
+                .map_or(true, |metrics| metrics.changes.is_empty())
+            && self
+                .logs
+                .as_ref()
+                .map_or(true, |logs| logs.changes.is_empty())
+            && self
+                .spans
+                .as_ref()
+                .map_or(true, |spans| spans.changes.is_empty())
+            && self
+                .resources
+                .as_ref()
+                .map_or(true, |resources| resources.changes.is_empty())
+    }
+
     /// Update the current `VersionSpec` to include the transformations of the parent `VersionSpec`.
     /// Transformations of the current `VersionSpec` take precedence over the parent `VersionSpec`.
     pub fn extend(&mut self, parent_spec: VersionSpec) {
@@ -4,7 +4,7 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 /// Changes to apply to the metrics for a specific version.
 #[derive(Serialize, Deserialize, Debug, Default, Clone, JsonSchema)]
@@ -20,10 +20,12 @@ pub struct MetricsChanges {
 pub struct MetricsChange {
     /// A collection of rename operations to apply to the metric attributes.
     #[serde(default)]
+    #[serde(skip_serializing_if = "RenameAttributes::is_empty")]
     pub rename_attributes: RenameAttributes,
     /// A collection of rename operations to apply to the metric names.
     #[serde(default)]
-    pub rename_metrics: HashMap<String, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rename_metrics: BTreeMap<String, String>,
 }
 
 /// A collection of rename operations to apply to the metric attributes.
@@ -31,9 +33,17 @@ pub struct MetricsChange {
 #[serde(deny_unknown_fields)]
 pub struct RenameAttributes {
     /// A collection of rename operations to apply to the metric attributes.
-    pub attribute_map: HashMap<String, String>,
+    pub attribute_map: BTreeMap<String, String>,
     /// A collection of metric references.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub apply_to_metrics: Vec<String>,
 }
+
+impl RenameAttributes {
+    /// Returns true if no attribute is renamed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attribute_map.is_empty()
+    }
+}
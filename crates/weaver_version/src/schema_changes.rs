@@ -4,7 +4,15 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::all_changes::{AllChange, AllChanges};
+use crate::compatibility::CompatibilityFinding;
+use crate::logs_changes::{LogsChange, LogsChanges};
+use crate::metrics_changes::{MetricsChange, MetricsChanges};
+use crate::resource_changes::{ResourceChange, ResourceChanges};
+use crate::spans_changes::{SpansChange, SpansChanges};
+use crate::VersionSpec;
 
 /// The type of schema item.
 #[derive(Debug, Serialize, Deserialize, Hash, Eq, PartialEq, Copy, Clone, JsonSchema)]
@@ -36,6 +44,10 @@ pub struct SchemaChanges {
     /// "events, "spans", "resources"), and the value is a list of changes associated
     /// with that item type.
     changes: HashMap<SchemaItemType, Vec<SchemaItemChange>>,
+
+    /// The changes classified as breaking or non-breaking by the built-in compatibility rules.
+    #[serde(default)]
+    compatibility: Vec<CompatibilityFinding>,
 }
 
 /// Represents the information of a semantic convention registry manifest.
@@ -68,8 +80,12 @@ pub enum SchemaItemChange {
         note: String,
     },
     /// One or more fields in a top-level telemetry object have been updated in the head registry.
-    /// Note: This is a placeholder for future use.
-    Updated {},
+    Updated {
+        /// The name of the updated telemetry object.
+        name: String,
+        /// The updated fields, sorted by path.
+        fields: Vec<FieldChange>,
+    },
     /// A top-level telemetry object that is now discontinued without a valid replacement in the
     /// head registry.
     Obsoleted {
@@ -94,6 +110,46 @@ pub enum SchemaItemChange {
     },
 }
 
+/// A field of a top-level telemetry object that has been added, removed or modified between
+/// the baseline and the head registry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct FieldChange {
+    /// The path of the field, e.g. `brief`, `type`, `members[get]` for an enum member or
+    /// `attributes[http.route]` for an attribute of a signal.
+    pub path: String,
+    /// The value of the field in the baseline registry, `None` if the field was added.
+    pub old_value: Option<String>,
+    /// The value of the field in the head registry, `None` if the field was removed.
+    pub new_value: Option<String>,
+}
+
+impl FieldChange {
+    /// Compares the fields of a telemetry object in the baseline and the head registry.
+    /// Both maps are indexed by field path.
+    #[must_use]
+    pub fn diff(
+        baseline: &BTreeMap<String, String>,
+        head: &BTreeMap<String, String>,
+    ) -> Vec<FieldChange> {
+        let mut paths: Vec<&String> = baseline.keys().chain(head.keys()).collect();
+        paths.sort_unstable();
+        paths.dedup();
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let old_value = baseline.get(path);
+                let new_value = head.get(path);
+                (old_value != new_value).then(|| FieldChange {
+                    path: path.clone(),
+                    old_value: old_value.cloned(),
+                    new_value: new_value.cloned(),
+                })
+            })
+            .collect()
+    }
+}
+
 impl SchemaChanges {
     /// Create a new instance of `SchemaChanges`.
     #[must_use]
@@ -102,6 +158,7 @@ impl SchemaChanges {
             head: RegistryManifest::default(),
             baseline: RegistryManifest::default(),
             changes: HashMap::new(),
+            compatibility: Vec::new(),
         };
         let _ = schema_changes
             .changes
@@ -196,6 +253,19 @@ impl SchemaChanges {
             .unwrap_or(0)
     }
 
+    /// Counts the number of updated registry attributes in the schema.
+    #[must_use]
+    pub fn count_updated_registry_attributes(&self) -> usize {
+        self.changes
+            .get(&SchemaItemType::RegistryAttributes)
+            .map(|v| {
+                v.iter()
+                    .filter(|c| matches!(c, SchemaItemChange::Updated { .. }))
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
     /// Counts the number of removed registry attributes in the schema.
     #[must_use]
     pub fn count_removed_registry_attributes(&self) -> usize {
@@ -258,6 +328,148 @@ impl SchemaChanges {
             .unwrap_or_default()
     }
 
+    /// Converts the renames of this summary into the changes of a version of an OTel
+    /// telemetry schema file (OTEP 0152).
+    ///
+    /// A renamed registry attribute is scoped to the signals switching from the old to the new
+    /// attribute when they are all of the same kind: metrics (`apply_to_metrics`), spans,
+    /// events (`logs`) or entities (`resources`). Otherwise, the rename applies to `all`.
+    /// Renamed metrics are reported in `metrics.rename_metrics`.
+    #[must_use]
+    pub fn version_spec(&self) -> VersionSpec {
+        let mut all = BTreeMap::new();
+        let mut metric_attributes: BTreeMap<Vec<String>, BTreeMap<String, String>> =
+            BTreeMap::new();
+        let mut span_attributes = BTreeMap::new();
+        let mut log_attributes = BTreeMap::new();
+        let mut resource_attributes = BTreeMap::new();
+
+        for change in self.renamed_registry_attributes() {
+            let SchemaItemChange::Renamed {
+                old_name, new_name, ..
+            } = change
+            else {
+                continue;
+            };
+            let rename = (old_name.as_str(), new_name.as_str());
+            let metrics = self.signals_switching_attribute(SchemaItemType::Metrics, rename);
+            let spans = self.signals_switching_attribute(SchemaItemType::Spans, rename);
+            let events = self.signals_switching_attribute(SchemaItemType::Events, rename);
+            let entities = self.signals_switching_attribute(SchemaItemType::Entities, rename);
+            let kinds = [&metrics, &spans, &events, &entities]
+                .iter()
+                .filter(|signals| !signals.is_empty())
+                .count();
+
+            let attribute_map = if kinds != 1 {
+                &mut all
+            } else if !metrics.is_empty() {
+                metric_attributes.entry(metrics).or_default()
+            } else if !spans.is_empty() {
+                &mut span_attributes
+            } else if !events.is_empty() {
+                &mut log_attributes
+            } else {
+                &mut resource_attributes
+            };
+            _ = attribute_map.insert(old_name.clone(), new_name.clone());
+        }
+
+        let rename_metrics: BTreeMap<_, _> = self
+            .changes
+            .get(&SchemaItemType::Metrics)
+            .into_iter()
+            .flatten()
+            .filter_map(|change| match change {
+                SchemaItemChange::Renamed {
+                    old_name, new_name, ..
+                } => Some((old_name.clone(), new_name.clone())),
+                _ => None,
+            })
+            .collect();
+
+        let mut metrics_changes: Vec<_> = metric_attributes
+            .into_iter()
+            .map(|(apply_to_metrics, attribute_map)| MetricsChange {
+                rename_attributes: crate::metrics_changes::RenameAttributes {
+                    attribute_map,
+                    apply_to_metrics,
+                },
+                rename_metrics: BTreeMap::new(),
+            })
+            .collect();
+        if !rename_metrics.is_empty() {
+            metrics_changes.push(MetricsChange {
+                rename_attributes: Default::default(),
+                rename_metrics,
+            });
+        }
+
+        VersionSpec {
+            all: (!all.is_empty()).then(|| AllChanges {
+                changes: vec![AllChange {
+                    rename_attributes: crate::all_changes::RenameAttributes { attribute_map: all },
+                }],
+            }),
+            metrics: (!metrics_changes.is_empty()).then_some(MetricsChanges {
+                changes: metrics_changes,
+            }),
+            logs: (!log_attributes.is_empty()).then(|| LogsChanges {
+                changes: vec![LogsChange {
+                    rename_attributes: crate::logs_changes::RenameAttributes {
+                        attribute_map: log_attributes,
+                    },
+                }],
+            }),
+            spans: (!span_attributes.is_empty()).then(|| SpansChanges {
+                changes: vec![SpansChange {
+                    rename_attributes: crate::spans_changes::RenameAttributes {
+                        attribute_map: span_attributes,
+                    },
+                }],
+            }),
+            resources: (!resource_attributes.is_empty()).then(|| ResourceChanges {
+                changes: vec![ResourceChange {
+                    rename_attributes: crate::resource_changes::RenameAttributes {
+                        attribute_map: resource_attributes,
+                    },
+                }],
+            }),
+        }
+    }
+
+    /// Returns the sorted names of the signals of the given type whose attribute `old_name`
+    /// was replaced by the attribute `new_name`.
+    fn signals_switching_attribute(
+        &self,
+        item_type: SchemaItemType,
+        (old_name, new_name): (&str, &str),
+    ) -> Vec<String> {
+        let old_path = format!("attributes[{old_name}]");
+        let new_path = format!("attributes[{new_name}]");
+        let mut signals: Vec<_> = self
+            .changes
+            .get(&item_type)
+            .into_iter()
+            .flatten()
+            .filter_map(|change| match change {
+                SchemaItemChange::Updated { name, fields }
+                    if fields
+                        .iter()
+                        .any(|f| f.path == old_path && f.new_value.is_none())
+                        && fields
+                            .iter()
+                            .any(|f| f.path == new_path && f.old_value.is_none()) =>
+                {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        signals.sort();
+        signals
+    }
+
     /// Add a `SchemaChange` to the list of changes for the specified schema item type.
     pub fn add_change(&mut self, item_type: SchemaItemType, change: SchemaItemChange) {
         self.changes
@@ -266,6 +478,26 @@ impl SchemaChanges {
             .push(change);
     }
 
+    /// Add the findings of the compatibility rules.
+    pub fn add_compatibility_findings(&mut self, findings: Vec<CompatibilityFinding>) {
+        self.compatibility.extend(findings);
+    }
+
+    /// Return the findings of the compatibility rules.
+    #[must_use]
+    pub fn compatibility_findings(&self) -> &[CompatibilityFinding] {
+        &self.compatibility
+    }
+
+    /// Counts the number of breaking changes in the schema.
+    #[must_use]
+    pub fn count_breaking_changes(&self) -> usize {
+        self.compatibility
+            .iter()
+            .filter(|f| f.is_breaking())
+            .count()
+    }
+
     /// Set the baseline manifest for the schema changes.
     pub fn set_head_manifest(&mut self, head: RegistryManifest) {
         self.head = head;
@@ -320,6 +552,13 @@ impl SchemaChanges {
                         .filter(|c| matches!(c, SchemaItemChange::Obsoleted { .. }))
                         .count()
                 ));
+                result.push_str(&format!(
+                    "  Updated: {}\n",
+                    changes
+                        .iter()
+                        .filter(|c| matches!(c, SchemaItemChange::Updated { .. }))
+                        .count()
+                ));
                 result.push_str(&format!(
                     "  Removed: {}\n",
                     changes
@@ -363,3 +602,140 @@ impl SchemaChanges {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_change_diff() {
+        let baseline = BTreeMap::from([
+            ("brief".to_owned(), "old brief".to_owned()),
+            ("members[a]".to_owned(), "a".to_owned()),
+            ("type".to_owned(), "enum".to_owned()),
+        ]);
+        let head = BTreeMap::from([
+            ("brief".to_owned(), "new brief".to_owned()),
+            ("members[b]".to_owned(), "b".to_owned()),
+            ("type".to_owned(), "enum".to_owned()),
+        ]);
+        let changes = FieldChange::diff(&baseline, &head);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    path: "brief".to_owned(),
+                    old_value: Some("old brief".to_owned()),
+                    new_value: Some("new brief".to_owned()),
+                },
+                FieldChange {
+                    path: "members[a]".to_owned(),
+                    old_value: Some("a".to_owned()),
+                    new_value: None,
+                },
+                FieldChange {
+                    path: "members[b]".to_owned(),
+                    old_value: None,
+                    new_value: Some("b".to_owned()),
+                },
+            ]
+        );
+        assert!(FieldChange::diff(&baseline, &baseline).is_empty());
+    }
+
+    #[test]
+    fn test_version_spec() {
+        fn renamed(old_name: &str, new_name: &str) -> SchemaItemChange {
+            SchemaItemChange::Renamed {
+                old_name: old_name.to_owned(),
+                new_name: new_name.to_owned(),
+                note: "".to_owned(),
+            }
+        }
+        fn switched(name: &str, old_name: &str, new_name: &str) -> SchemaItemChange {
+            SchemaItemChange::Updated {
+                name: name.to_owned(),
+                fields: vec![
+                    FieldChange {
+                        path: format!("attributes[{new_name}]"),
+                        old_value: None,
+                        new_value: Some("required".to_owned()),
+                    },
+                    FieldChange {
+                        path: format!("attributes[{old_name}]"),
+                        old_value: Some("required".to_owned()),
+                        new_value: None,
+                    },
+                ],
+            }
+        }
+
+        let mut changes = SchemaChanges::new();
+        for (old_name, new_name) in [
+            ("global.old", "global.new"),
+            ("metric.old", "metric.new"),
+            ("span.old", "span.new"),
+            ("mixed.old", "mixed.new"),
+        ] {
+            changes.add_change(
+                SchemaItemType::RegistryAttributes,
+                renamed(old_name, new_name),
+            );
+        }
+        changes.add_change(
+            SchemaItemType::Metrics,
+            switched("metric.b", "metric.old", "metric.new"),
+        );
+        changes.add_change(
+            SchemaItemType::Metrics,
+            switched("metric.a", "metric.old", "metric.new"),
+        );
+        changes.add_change(
+            SchemaItemType::Metrics,
+            switched("metric.a", "mixed.old", "mixed.new"),
+        );
+        changes.add_change(
+            SchemaItemType::Spans,
+            switched("span", "span.old", "span.new"),
+        );
+        changes.add_change(
+            SchemaItemType::Spans,
+            switched("span", "mixed.old", "mixed.new"),
+        );
+        changes.add_change(SchemaItemType::Metrics, renamed("metric.c", "metric.d"));
+
+        let spec = changes.version_spec();
+        let all = &spec.all.as_ref().unwrap().changes;
+        assert_eq!(all.len(), 1);
+        assert_eq!(
+            all[0].rename_attributes.attribute_map,
+            BTreeMap::from([
+                ("global.old".to_owned(), "global.new".to_owned()),
+                ("mixed.old".to_owned(), "mixed.new".to_owned()),
+            ])
+        );
+        let metrics = &spec.metrics.as_ref().unwrap().changes;
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics[0].rename_attributes.apply_to_metrics,
+            vec!["metric.a".to_owned(), "metric.b".to_owned()]
+        );
+        assert_eq!(
+            metrics[0].rename_attributes.attribute_map,
+            BTreeMap::from([("metric.old".to_owned(), "metric.new".to_owned())])
+        );
+        assert_eq!(
+            metrics[1].rename_metrics,
+            BTreeMap::from([("metric.c".to_owned(), "metric.d".to_owned())])
+        );
+        let spans = &spec.spans.as_ref().unwrap().changes;
+        assert_eq!(
+            spans[0].rename_attributes.attribute_map,
+            BTreeMap::from([("span.old".to_owned(), "span.new".to_owned())])
+        );
+        assert!(spec.logs.is_none());
+        assert!(spec.resources.is_none());
+        assert!(!spec.is_empty());
+        assert!(SchemaChanges::new().version_spec().is_empty());
+    }
+}
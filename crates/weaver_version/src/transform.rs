@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of telemetry between two versions of a telemetry schema.
+//!
+//! A [`Transformation`] applies the renames defined in the `versions` section of a telemetry
+//! schema (OTEP 0152) to convert the names of telemetry produced with a version of the schema
+//! into the names of another version. Upgrades apply the changes of the versions in ascending
+//! order, downgrades revert them in descending order.
+//!
+//! Within a version, the `all` changes are applied first, followed by the resource, span, log
+//! and metric changes, in the order in which they are declared.
+
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+use crate::{Error, Version, VersionSpec, Versions};
+
+/// The signals targeted by an attribute rename.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scope {
+    All,
+    Resources,
+    Spans,
+    Logs,
+    Metrics,
+}
+
+/// A rename operation of a version of the schema.
+#[derive(Debug, Clone)]
+enum Operation {
+    RenameAttributes {
+        scope: Scope,
+        attribute_map: BTreeMap<String, String>,
+        /// The metrics targeted by the rename, all metrics if empty.
+        apply_to_metrics: Vec<String>,
+    },
+    RenameMetrics(BTreeMap<String, String>),
+}
+
+impl Operation {
+    /// Returns the operation reverting this operation.
+    /// When several names were renamed to the same name, the first one in lexicographic
+    /// order is restored.
+    fn inverse(self) -> Self {
+        fn invert(map: BTreeMap<String, String>) -> BTreeMap<String, String> {
+            let mut inverted = BTreeMap::new();
+            for (old, new) in map {
+                _ = inverted.entry(new).or_insert(old);
+            }
+            inverted
+        }
+
+        match self {
+            Operation::RenameAttributes {
+                scope,
+                attribute_map,
+                apply_to_metrics,
+            } => Operation::RenameAttributes {
+                scope,
+                attribute_map: invert(attribute_map),
+                apply_to_metrics,
+            },
+            Operation::RenameMetrics(map) => Operation::RenameMetrics(invert(map)),
+        }
+    }
+}
+
+/// The rename operations converting telemetry from a version of a telemetry schema
+/// to another one.
+#[derive(Debug, Clone, Default)]
+pub struct Transformation {
+    operations: Vec<Operation>,
+}
+
+impl Versions {
+    /// Returns the transformation converting telemetry produced with the version `from`
+    /// of the schema into telemetry of the version `to`. The transformation is an upgrade
+    /// if `from` is older than `to` and a downgrade otherwise.
+    ///
+    /// Returns an error if one of the versions is not defined.
+    pub fn transformation(&self, from: &Version, to: &Version) -> Result<Transformation, Error> {
+        for version in [from, to] {
+            if !self.contains(version) {
+                return Err(Error::UnknownVersion {
+                    version: version.to_string(),
+                });
+            }
+        }
+
+        let mut operations = Vec::new();
+        if from.0 < to.0 {
+            for (_, spec) in self
+                .versions
+                .range((Bound::Excluded(&from.0), Bound::Included(&to.0)))
+            {
+                operations.extend(spec_operations(spec));
+            }
+        } else if from.0 > to.0 {
+            for (_, spec) in self
+                .versions
+                .range((Bound::Excluded(&to.0), Bound::Included(&from.0)))
+                .rev()
+            {
+                operations.extend(
+                    spec_operations(spec)
+                        .into_iter()
+                        .rev()
+                        .map(Operation::inverse),
+                );
+            }
+        }
+        Ok(Transformation { operations })
+    }
+}
+
+impl Transformation {
+    /// Returns true if the transformation doesn't rename anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Returns the new name of an attribute that is not attached to a known signal.
+    /// Only the `all` changes are applied.
+    #[must_use]
+    pub fn attribute_name(&self, name: &str) -> String {
+        self.rename_attribute(&[Scope::All], name)
+    }
+
+    /// Returns the new name of a resource attribute.
+    #[must_use]
+    pub fn resource_attribute_name(&self, name: &str) -> String {
+        self.rename_attribute(&[Scope::All, Scope::Resources], name)
+    }
+
+    /// Returns the new name of an attribute of a span, a span event or a span link.
+    #[must_use]
+    pub fn span_attribute_name(&self, name: &str) -> String {
+        self.rename_attribute(&[Scope::All, Scope::Spans], name)
+    }
+
+    /// Returns the new name of a log attribute.
+    #[must_use]
+    pub fn log_attribute_name(&self, name: &str) -> String {
+        self.rename_attribute(&[Scope::All, Scope::Logs], name)
+    }
+
+    /// Returns the new name of a metric.
+    #[must_use]
+    pub fn metric_name(&self, name: &str) -> String {
+        let mut name = name.to_owned();
+        for operation in &self.operations {
+            if let Operation::RenameMetrics(map) = operation {
+                if let Some(new_name) = map.get(&name) {
+                    name.clone_from(new_name);
+                }
+            }
+        }
+        name
+    }
+
+    /// Returns the new name of an attribute of the metric `metric_name` (the name of the
+    /// metric before the transformation).
+    #[must_use]
+    pub fn metric_attribute_name(&self, metric_name: &str, name: &str) -> String {
+        let mut metric_name = metric_name.to_owned();
+        let mut name = name.to_owned();
+        for operation in &self.operations {
+            match operation {
+                Operation::RenameAttributes {
+                    scope,
+                    attribute_map,
+                    apply_to_metrics,
+                } => {
+                    let applies = match scope {
+                        Scope::All => true,
+                        Scope::Metrics => {
+                            apply_to_metrics.is_empty() || apply_to_metrics.contains(&metric_name)
+                        }
+                        _ => false,
+                    };
+                    if applies {
+                        if let Some(new_name) = attribute_map.get(&name) {
+                            name.clone_from(new_name);
+                        }
+                    }
+                }
+                Operation::RenameMetrics(map) => {
+                    if let Some(new_name) = map.get(&metric_name) {
+                        metric_name.clone_from(new_name);
+                    }
+                }
+            }
+        }
+        name
+    }
+
+    fn rename_attribute(&self, scopes: &[Scope], name: &str) -> String {
+        let mut name = name.to_owned();
+        for operation in &self.operations {
+            if let Operation::RenameAttributes {
+                scope,
+                attribute_map,
+                ..
+            } = operation
+            {
+                if scopes.contains(scope) {
+                    if let Some(new_name) = attribute_map.get(&name) {
+                        name.clone_from(new_name);
+                    }
+                }
+            }
+        }
+        name
+    }
+}
+
+/// Returns the rename operations of a version in the order in which they are applied
+/// during an upgrade.
+fn spec_operations(spec: &VersionSpec) -> Vec<Operation> {
+    let mut operations = Vec::new();
+    let mut rename_attributes = |scope, attribute_map: &BTreeMap<String, String>| {
+        if !attribute_map.is_empty() {
+            operations.push(Operation::RenameAttributes {
+                scope,
+                attribute_map: attribute_map.clone(),
+                apply_to_metrics: vec![],
+            });
+        }
+    };
+
+    for change in spec.all.iter().flat_map(|all| &all.changes) {
+        rename_attributes(Scope::All, &change.rename_attributes.attribute_map);
+    }
+    for change in spec
+        .resources
+        .iter()
+        .flat_map(|resources| &resources.changes)
+    {
+        rename_attributes(Scope::Resources, &change.rename_attributes.attribute_map);
+    }
+    for change in spec.spans.iter().flat_map(|spans| &spans.changes) {
+        rename_attributes(Scope::Spans, &change.rename_attributes.attribute_map);
+    }
+    for change in spec.logs.iter().flat_map(|logs| &logs.changes) {
+        rename_attributes(Scope::Logs, &change.rename_attributes.attribute_map);
+    }
+    for change in spec.metrics.iter().flat_map(|metrics| &metrics.changes) {
+        if !change.rename_attributes.is_empty() {
+            operations.push(Operation::RenameAttributes {
+                scope: Scope::Metrics,
+                attribute_map: change.rename_attributes.attribute_map.clone(),
+                apply_to_metrics: change.rename_attributes.apply_to_metrics.clone(),
+            });
+        }
+        if !change.rename_metrics.is_empty() {
+            operations.push(Operation::RenameMetrics(change.rename_metrics.clone()));
+        }
+    }
+    operations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> Versions {
+        serde_yaml::from_str(
+            r#"
+1.20.0:
+1.21.0:
+  all:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            http.method: http.request.method
+  metrics:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            state: db.client.connections.state
+          apply_to_metrics:
+            - db.client.connections.usage
+      - rename_metrics:
+          db.client.connections.usage: db.client.connection.count
+1.22.0:
+  spans:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            http.request.method: http.method.name
+  logs:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            log.kind: log.type
+  resources:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            host.os: os.type
+  metrics:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            db.client.connections.state: db.client.connection.state
+          apply_to_metrics:
+            - db.client.connection.count
+"#,
+        )
+        .unwrap()
+    }
+
+    fn version(version: &str) -> Version {
+        version.parse().unwrap()
+    }
+
+    #[test]
+    fn test_upgrade() {
+        let transformation = versions()
+            .transformation(&version("1.20.0"), &version("1.22.0"))
+            .unwrap();
+        assert_eq!(
+            transformation.span_attribute_name("http.method"),
+            "http.method.name"
+        );
+        assert_eq!(
+            transformation.log_attribute_name("http.method"),
+            "http.request.method"
+        );
+        assert_eq!(transformation.log_attribute_name("log.kind"), "log.type");
+        assert_eq!(transformation.resource_attribute_name("host.os"), "os.type");
+        assert_eq!(transformation.attribute_name("host.os"), "host.os");
+        assert_eq!(
+            transformation.metric_name("db.client.connections.usage"),
+            "db.client.connection.count"
+        );
+        assert_eq!(
+            transformation.metric_attribute_name("db.client.connections.usage", "state"),
+            "db.client.connection.state"
+        );
+        assert_eq!(
+            transformation.metric_attribute_name("other.metric", "state"),
+            "state"
+        );
+        assert_eq!(
+            transformation.metric_attribute_name("other.metric", "http.method"),
+            "http.request.method"
+        );
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let transformation = versions()
+            .transformation(&version("1.22.0"), &version("1.20.0"))
+            .unwrap();
+        assert_eq!(
+            transformation.span_attribute_name("http.method.name"),
+            "http.method"
+        );
+        assert_eq!(transformation.resource_attribute_name("os.type"), "host.os");
+        assert_eq!(
+            transformation.metric_name("db.client.connection.count"),
+            "db.client.connections.usage"
+        );
+        assert_eq!(
+            transformation
+                .metric_attribute_name("db.client.connection.count", "db.client.connection.state"),
+            "state"
+        );
+
+        // Partial downgrade.
+        let transformation = versions()
+            .transformation(&version("1.22.0"), &version("1.21.0"))
+            .unwrap();
+        assert_eq!(
+            transformation.span_attribute_name("http.method.name"),
+            "http.request.method"
+        );
+        assert_eq!(
+            transformation.metric_name("db.client.connection.count"),
+            "db.client.connection.count"
+        );
+    }
+
+    #[test]
+    fn test_same_and_unknown_versions() {
+        let versions = versions();
+        let transformation = versions
+            .transformation(&version("1.21.0"), &version("1.21.0"))
+            .unwrap();
+        assert!(transformation.is_empty());
+        assert!(matches!(
+            versions.transformation(&version("1.20.0"), &version("2.0.0")),
+            Err(Error::UnknownVersion { .. })
+        ));
+    }
+}
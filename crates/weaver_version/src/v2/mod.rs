@@ -3,6 +3,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::compatibility::CompatibilityFinding;
+
 // V2 Leverages the same nomenclature for diff as V1.
 pub use crate::schema_changes::SchemaItemChange;
 
@@ -12,6 +14,9 @@ pub use crate::schema_changes::SchemaItemChange;
 pub struct SchemaChanges {
     /// Changes to the registry.
     pub registry: RegistryChanges,
+    /// The changes classified as breaking or non-breaking by the built-in compatibility rules.
+    #[serde(default)]
+    pub compatibility: Vec<CompatibilityFinding>,
 }
 
 /// A summary of changes to the registry of signals and attributes.
@@ -52,4 +57,13 @@ impl SchemaChanges {
     pub fn is_empty(&self) -> bool {
         self.registry.is_empty()
     }
+
+    /// Counts the number of breaking changes in the schema.
+    #[must_use]
+    pub fn count_breaking_changes(&self) -> usize {
+        self.compatibility
+            .iter()
+            .filter(|f| f.is_breaking())
+            .count()
+    }
 }
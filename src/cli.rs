@@ -6,6 +6,7 @@ use crate::diagnostic::DiagnosticCommand;
 use crate::registry::RegistryCommand;
 use crate::serve::ServeCommand;
 use clap::{Args, Parser, Subcommand};
+use weaver_common::cache::CacheSettings;
 
 /// Command line arguments.
 #[derive(Parser)]
@@ -32,11 +33,58 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub future: bool,
 
+    /// Parameters to specify the cache of remote registries.
+    #[command(flatten)]
+    pub cache: CacheArgs,
+
     /// List of supported commands
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// Parameters to specify the cache of remote registries (Git repositories and remote archives).
+#[derive(Args, Debug, Clone, Default)]
+pub struct CacheArgs {
+    /// Resolve remote registries only from the local cache, without network access.
+    /// Use `weaver registry fetch` to populate the cache.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Path to the lock file pinning remote registries to a commit or an archive digest.
+    /// Defaults to `weaver.lock` in the current directory.
+    #[arg(long, global = true)]
+    pub lock_file: Option<std::path::PathBuf>,
+
+    /// Path to the local cache of remote registries. Defaults to `~/.weaver/cache`.
+    /// Cached registries are never evicted, delete this directory to clean the cache.
+    #[arg(long, global = true)]
+    pub cache_dir: Option<std::path::PathBuf>,
+
+    /// Clone remote registries into temporary directories deleted at the end of the command,
+    /// instead of the local cache.
+    #[arg(long, global = true, conflicts_with = "offline")]
+    pub no_cache: bool,
+
+    /// Cache the parsed semantic convention files (by content hash) and the resolved
+    /// dependencies (by schema URL) in the `resolution` folder of the cache, so that
+    /// unchanged files and dependencies are not parsed and resolved again.
+    #[arg(long, global = true)]
+    pub resolution_cache: bool,
+}
+
+impl From<&CacheArgs> for CacheSettings {
+    fn from(args: &CacheArgs) -> Self {
+        CacheSettings {
+            cache_dir: args.cache_dir.clone(),
+            offline: args.offline,
+            lock_file: args.lock_file.clone(),
+            resolution_cache: args.resolution_cache,
+            disabled: args.no_cache,
+            refresh_lock: false,
+        }
+    }
+}
+
 /// Supported commands.
 #[derive(Subcommand)]
 #[allow(clippy::large_enum_variant)]
@@ -94,6 +94,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Diagnostic(DiagnosticCommand {
                 command: DiagnosticSubCommand::Init(DiagnosticInitArgs {
                     target: "".to_owned(),
@@ -107,9 +108,9 @@ mod tests {
         // The command should succeed.
         assert_eq!(exit_directive.exit_code, 0);
 
-        // Check the presence of 3 subdirectories in the temp_output directory
+        // Check the presence of 4 subdirectories in the temp_output directory
         let subdirs = fs::read_dir(&temp_output).unwrap().count();
-        assert_eq!(subdirs, 3);
+        assert_eq!(subdirs, 4);
 
         // Let's init for a specific target
         let temp_output = TempDir::new()
@@ -119,6 +120,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Diagnostic(DiagnosticCommand {
                 command: DiagnosticSubCommand::Init(DiagnosticInitArgs {
                     target: "json".to_owned(),
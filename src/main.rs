@@ -12,6 +12,7 @@ use std::io;
 use std::io::Write;
 
 use registry::semconv_registry;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::{enable_future_mode, DiagnosticMessages};
 use weaver_common::log_error;
 use weaver_forge::{OutputProcessor, OutputTarget};
@@ -29,7 +30,7 @@ mod weaver;
 #[derive(Args, Debug, Clone)]
 pub(crate) struct DiagnosticArgs {
     /// Format used to render the diagnostic messages. Predefined formats are: ansi, json,
-    /// gh_workflow_command.
+    /// gh_workflow_command, sarif.
     #[arg(long, default_value = "ansi")]
     pub(crate) diagnostic_format: String,
 
@@ -129,10 +130,11 @@ fn run_command(cli: &Cli) -> ExitDirectives {
     if cli.future {
         enable_future_mode();
     }
+    let cache_settings = CacheSettings::from(&cli.cache);
     let cmd_result = match &cli.command {
-        Some(Commands::Registry(params)) => semconv_registry(params),
+        Some(Commands::Registry(params)) => semconv_registry(params, &cache_settings),
         Some(Commands::Diagnostic(params)) => diagnostic::diagnostic(params),
-        Some(Commands::Serve(params)) => serve::command(params),
+        Some(Commands::Serve(params)) => serve::command(params, &cache_settings),
         Some(Commands::Completion(completions)) => {
             if let Err(e) = generate_completion(&completions.shell, &completions.completion_file) {
                 log_error(&e);
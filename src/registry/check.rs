@@ -2,11 +2,13 @@
 
 //! Check a semantic convention registry.
 
+use crate::registry::watch::{self, WatchArgs};
 use crate::registry::{PolicyArgs, RegistryArgs};
 use crate::weaver::{ResolvedV2, WeaverEngine};
 use crate::{DiagnosticArgs, ExitDirectives};
 use clap::Args;
 use log::info;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::vdir::VirtualDirectoryPath;
 use weaver_semconv::registry_repo::RegistryRepo;
@@ -16,27 +18,53 @@ use weaver_semconv::registry_repo::RegistryRepo;
 pub struct RegistryCheckArgs {
     /// Parameters to specify the semantic convention registry
     #[command(flatten)]
-    registry: RegistryArgs,
+    pub(crate) registry: RegistryArgs,
 
     /// Parameters to specify the baseline semantic convention registry
     #[arg(long)]
-    baseline_registry: Option<VirtualDirectoryPath>,
+    pub(crate) baseline_registry: Option<VirtualDirectoryPath>,
 
     /// Policy parameters
     #[command(flatten)]
-    policy: PolicyArgs,
+    pub(crate) policy: PolicyArgs,
 
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
+
+    /// Watch mode parameters.
+    #[command(flatten)]
+    pub(crate) watch: WatchArgs,
 }
 
 /// Check a semantic convention registry.
-pub(crate) fn command(args: &RegistryCheckArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryCheckArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.watch.watch {
+        let paths = [&args.registry.registry]
+            .into_iter()
+            .chain(&args.baseline_registry)
+            .chain(&args.policy.policies)
+            .filter_map(watch::local_path)
+            .collect();
+        return watch::run(&args.watch, paths, &[], &args.diagnostic, || {
+            check(args, cache_settings)
+        });
+    }
+    check(args, cache_settings)
+}
+
+/// Checks the registry once.
+fn check(
+    args: &RegistryCheckArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     let mut diag_msgs = DiagnosticMessages::empty();
     info!("Weaver Registry Check");
     info!("Checking registry `{}`", args.registry.registry);
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
 
     // Initialize the main registry.
     let main_resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
@@ -45,7 +73,7 @@ pub(crate) fn command(args: &RegistryCheckArgs) -> Result<ExitDirectives, Diagno
     let baseline = if let Some(br) = args.baseline_registry.as_ref() {
         // ignore warnings.
         let mut ignored = DiagnosticMessages::empty();
-        let registry_repo = RegistryRepo::try_new(None, br, &mut vec![])?;
+        let registry_repo = RegistryRepo::try_new(None, br, cache_settings, &mut vec![])?;
         let loaded = weaver.load_definitions(registry_repo, &mut ignored)?;
         // TODO - do we need to keep any loading diagnostic messages?
         Some(weaver.resolve(loaded, &mut diag_msgs)?)
@@ -81,14 +109,17 @@ pub(crate) fn command(args: &RegistryCheckArgs) -> Result<ExitDirectives, Diagno
 
 #[cfg(test)]
 mod tests {
-    use crate::cli::{Cli, Commands};
+    use crate::cli::{CacheArgs, Cli, Commands};
+    use crate::diagnostic::DEFAULT_DIAGNOSTIC_TEMPLATES;
     use crate::registry::check::RegistryCheckArgs;
     use crate::registry::{
         semconv_registry, PolicyArgs, RegistryArgs, RegistryCommand, RegistrySubCommand,
     };
     use crate::run_command;
 
+    use weaver_common::cache::CacheSettings;
     use weaver_common::vdir::VirtualDirectoryPath;
+    use weaver_forge::{OutputProcessor, OutputTarget};
 
     #[test]
     fn test_registry_check_exit_code() {
@@ -96,6 +127,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Check(RegistryCheckArgs {
                     registry: RegistryArgs {
@@ -113,6 +145,7 @@ mod tests {
                         display_policy_coverage: false,
                     },
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -126,6 +159,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Check(RegistryCheckArgs {
                     registry: RegistryArgs {
@@ -143,6 +177,7 @@ mod tests {
                         display_policy_coverage: false,
                     },
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -152,6 +187,52 @@ mod tests {
         assert_eq!(exit_directive.exit_code, 1);
     }
 
+    #[test]
+    fn test_registry_check_resolution_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            future: false,
+            cache: CacheArgs {
+                cache_dir: Some(cache_dir.path().to_path_buf()),
+                lock_file: Some(cache_dir.path().join("weaver.lock")),
+                resolution_cache: true,
+                ..Default::default()
+            },
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Check(RegistryCheckArgs {
+                    registry: RegistryArgs {
+                        registry: VirtualDirectoryPath::LocalFolder {
+                            path: "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                        },
+                        follow_symlinks: false,
+                        include_unreferenced: false,
+                        v2: false,
+                    },
+                    baseline_registry: None,
+                    policy: PolicyArgs {
+                        policies: vec![],
+                        skip_policies: true,
+                        display_policy_coverage: false,
+                    },
+                    diagnostic: Default::default(),
+                    watch: Default::default(),
+                }),
+            })),
+        };
+
+        // The parsed files are stored in the resolution cache given on the command line.
+        assert_eq!(run_command(&cli).exit_code, 0);
+        let specs = std::fs::read_dir(cache_dir.path().join("resolution").join("specs"))
+            .unwrap()
+            .count();
+        assert!(specs > 0);
+
+        // The second run reads them back.
+        assert_eq!(run_command(&cli).exit_code, 0);
+    }
+
     #[test]
     fn test_semconv_registry() {
         let registry_cmd = RegistryCommand {
@@ -171,10 +252,11 @@ mod tests {
                     display_policy_coverage: false,
                 },
                 diagnostic: Default::default(),
+                watch: Default::default(),
             }),
         };
 
-        let cmd_result = semconv_registry(&registry_cmd);
+        let cmd_result = semconv_registry(&registry_cmd, &CacheSettings::default());
         // Violations should be observed.
         assert!(cmd_result.command_result.is_err());
         if let Err(diag_msgs) = cmd_result.command_result {
@@ -191,6 +273,74 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sarif_diagnostics_locate_policy_violations() {
+        let registry_cmd = RegistryCommand {
+            command: RegistrySubCommand::Check(RegistryCheckArgs {
+                registry: RegistryArgs {
+                    registry: VirtualDirectoryPath::LocalFolder {
+                        path: "crates/weaver_codegen_test/semconv_registry/".to_owned(),
+                    },
+                    follow_symlinks: false,
+                    include_unreferenced: false,
+                    v2: false,
+                },
+                baseline_registry: None,
+                policy: PolicyArgs {
+                    policies: vec![],
+                    skip_policies: false,
+                    display_policy_coverage: false,
+                },
+                diagnostic: Default::default(),
+                watch: Default::default(),
+            }),
+        };
+
+        let Err(diag_msgs) =
+            semconv_registry(&registry_cmd, &CacheSettings::default()).command_result
+        else {
+            panic!("Policy violations should be observed");
+        };
+        let output = OutputProcessor::new(
+            "sarif",
+            "errors",
+            Some(&DEFAULT_DIAGNOSTIC_TEMPLATES),
+            None,
+            OutputTarget::Stdout,
+        )
+        .unwrap();
+        let sarif: serde_json::Value =
+            serde_json::from_str(&output.generate_to_string(&diag_msgs).unwrap()).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), diag_msgs.len());
+
+        // Before and after resolution violations point at the line defining or
+        // referencing the attribute.
+        for (group, attribute) in [
+            ("registry.network.deprecated", "net.host.name"),
+            ("registry.http", "http.request.method"),
+            ("metric.system.cpu.time", "system.cpu.logical_number"),
+        ] {
+            let result = results
+                .iter()
+                .find(|result| {
+                    let context = &result["properties"]["context"];
+                    context["group"] == group && context["attr"] == attribute
+                })
+                .unwrap_or_else(|| panic!("No result for `{group}`/`{attribute}`"));
+            let location = &result["locations"][0]["physicalLocation"];
+            let uri = location["artifactLocation"]["uri"].as_str().unwrap();
+            let line = location["region"]["startLine"].as_u64().unwrap();
+            let content = std::fs::read_to_string(uri).unwrap();
+            let line_content = content.lines().nth(line as usize - 1).unwrap();
+            assert!(
+                line_content.contains(attribute),
+                "`{group}`/`{attribute}` points at `{uri}:{line}`: {line_content}"
+            );
+        }
+    }
+
     #[test]
     fn test_v2_policies() {
         let registry_cmd = RegistryCommand {
@@ -210,9 +360,10 @@ mod tests {
                     display_policy_coverage: true,
                 },
                 diagnostic: Default::default(),
+                watch: Default::default(),
             }),
         };
-        let cmd_result = semconv_registry(&registry_cmd);
+        let cmd_result = semconv_registry(&registry_cmd, &CacheSettings::default());
         // V2 Violations should be observed.
         assert!(cmd_result.command_result.is_err());
         if let Err(diag_msgs) = cmd_result.command_result {
@@ -251,9 +402,10 @@ mod tests {
                     display_policy_coverage: false,
                 },
                 diagnostic: Default::default(),
+                watch: Default::default(),
             }),
         };
-        let cmd_result = semconv_registry(&registry_cmd);
+        let cmd_result = semconv_registry(&registry_cmd, &CacheSettings::default());
         // V2 Violations should be observed.
         assert!(cmd_result.command_result.is_err());
         if let Err(diag_msgs) = cmd_result.command_result {
@@ -291,9 +443,10 @@ mod tests {
                     display_policy_coverage: false,
                 },
                 diagnostic: Default::default(),
+                watch: Default::default(),
             }),
         };
-        let cmd_result = semconv_registry(&registry_cmd);
+        let cmd_result = semconv_registry(&registry_cmd, &CacheSettings::default());
         // V2 should warn about before_resolution.
         assert!(cmd_result.command_result.is_err());
         if let Err(diag_msgs) = cmd_result.command_result {
@@ -3,14 +3,16 @@
 //! Generate a diff between two versions of a semantic convention registry.
 
 use crate::registry::{PolicyArgs, RegistryArgs};
-use crate::weaver::WeaverEngine;
+use crate::weaver::{ResolvedV2, WeaverEngine};
 use crate::{DiagnosticArgs, ExitDirectives};
-use clap::Args;
+use clap::{Args, ValueEnum};
 use include_dir::{include_dir, Dir};
 use log::info;
 use std::path::PathBuf;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::vdir::VirtualDirectoryPath;
+use weaver_common::{log_error, log_warn};
 use weaver_forge::{OutputProcessor, OutputTarget};
 use weaver_semconv::registry_repo::RegistryRepo;
 
@@ -42,46 +44,76 @@ pub struct RegistryDiffArgs {
     #[arg(short, long)]
     output: Option<PathBuf>,
 
+    /// Exit with a non-zero code when changes of the given kind are found.
+    /// Changes are classified as breaking based on the stability of the telemetry objects
+    /// in the baseline registry (e.g. removal of a stable attribute, change of the unit or
+    /// instrument of a stable metric, narrowing of the enum of a stable attribute).
+    #[arg(long, value_enum, default_value_t = FailOn::None)]
+    fail_on: FailOn,
+
+    /// Policy parameters. Policies are only evaluated when `-p/--policy` is given, and only
+    /// the `comparison_after_resolution` ones.
+    #[command(flatten)]
+    policy: PolicyArgs,
+
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub(crate) diagnostic: DiagnosticArgs,
 }
 
+/// The kind of changes making the `registry diff` command fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// Never fail on schema changes.
+    None,
+    /// Fail if at least one breaking change is found.
+    Breaking,
+}
+
 enum VersionedDiff {
     V1(crate::weaver::Diff),
     V2(crate::weaver::DiffV2),
 }
 
+/// The policy parameters of the command: the default policies are not evaluated, only the
+/// ones given with `-p/--policy`.
+fn policy_args(args: &RegistryDiffArgs) -> PolicyArgs {
+    PolicyArgs {
+        skip_policies: args.policy.skip_policies || args.policy.policies.is_empty(),
+        ..args.policy.clone()
+    }
+}
+
 /// Generate a diff between two versions of a semantic convention registry.
-pub(crate) fn command(args: &RegistryDiffArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryDiffArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     let mut diag_msgs = DiagnosticMessages::empty();
-    // TODO - make sure policy is disabled.
-    let policy_config = PolicyArgs {
-        policies: vec![],
-        skip_policies: true,
-        display_policy_coverage: false,
-    };
-    let weaver = WeaverEngine::new(&args.registry, &policy_config);
+    let policy = policy_args(args);
+    let weaver = WeaverEngine::new(&args.registry, &policy, cache_settings);
 
     info!("Weaver Registry Diff");
     info!("Checking registry `{}`", args.registry.registry);
 
     let registry_path = args.registry.registry.clone();
-    let main_registry_repo = RegistryRepo::try_new(None, &registry_path, &mut vec![])?;
-    let baseline_registry_repo = RegistryRepo::try_new(None, &args.baseline_registry, &mut vec![])?;
+    let main_registry_repo =
+        RegistryRepo::try_new(None, &registry_path, cache_settings, &mut vec![])?;
+    let baseline_registry_repo =
+        RegistryRepo::try_new(None, &args.baseline_registry, cache_settings, &mut vec![])?;
 
     let main = weaver.load_definitions(main_registry_repo, &mut diag_msgs)?;
     let baseline = weaver.load_definitions(baseline_registry_repo, &mut diag_msgs)?;
     let main_resolved = weaver.resolve(main, &mut diag_msgs)?;
     let baseline_resolved = weaver.resolve(baseline, &mut diag_msgs)?;
-    // Generate diff.
+    // Check comparison policies and generate diff.
     let diff = if args.registry.v2 {
-        VersionedDiff::V2(
-            main_resolved
-                .try_into_v2()?
-                .diff(&baseline_resolved.try_into_v2()?),
-        )
+        let main_resolved: ResolvedV2 = main_resolved.try_into_v2()?;
+        let baseline_resolved: ResolvedV2 = baseline_resolved.try_into_v2()?;
+        main_resolved.check_comparison_after_resolution(&baseline_resolved, &mut diag_msgs)?;
+        VersionedDiff::V2(main_resolved.diff(&baseline_resolved))
     } else {
+        main_resolved.check_comparison_after_resolution(&baseline_resolved, &mut diag_msgs)?;
         VersionedDiff::V1(main_resolved.diff(&baseline_resolved))
     };
 
@@ -98,14 +130,28 @@ pub(crate) fn command(args: &RegistryDiffArgs) -> Result<ExitDirectives, Diagnos
         target,
     )?;
 
+    let breaking_changes = match &diff {
+        VersionedDiff::V1(d) => d.count_breaking_changes(),
+        VersionedDiff::V2(d) => d.count_breaking_changes(),
+    };
     match diff {
         VersionedDiff::V1(d) => output.generate(d.as_template_context()),
         VersionedDiff::V2(d) => output.generate(d.as_template_context()),
     }
     .map_err(DiagnosticMessages::from)?;
 
+    let mut exit_code = 0;
+    if breaking_changes > 0 {
+        if args.fail_on == FailOn::Breaking {
+            log_error(format!("{breaking_changes} breaking change(s) detected"));
+            exit_code = 1;
+        } else {
+            log_warn(format!("{breaking_changes} breaking change(s) detected"));
+        }
+    }
+
     Ok(ExitDirectives {
-        exit_code: 0,
+        exit_code,
         warnings: None,
     })
 }
@@ -113,12 +159,14 @@ pub(crate) fn command(args: &RegistryDiffArgs) -> Result<ExitDirectives, Diagnos
 #[cfg(test)]
 mod tests {
     use crate::cli::{Cli, Commands};
-    use crate::registry::diff::RegistryDiffArgs;
+    use crate::registry::diff::{policy_args, FailOn, RegistryDiffArgs};
     use crate::registry::{
-        semconv_registry, RegistryArgs, RegistryCommand, RegistrySubCommand, VirtualDirectoryPath,
+        semconv_registry, PolicyArgs, RegistryArgs, RegistryCommand, RegistrySubCommand,
+        VirtualDirectoryPath,
     };
     use crate::run_command;
     use std::fs::OpenOptions;
+    use weaver_common::cache::CacheSettings;
     use weaver_version::schema_changes::SchemaChanges;
 
     #[test]
@@ -127,6 +175,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Diff(RegistryDiffArgs {
                     registry: RegistryArgs {
@@ -143,6 +192,12 @@ mod tests {
                     format: "json".to_owned(),
                     templates: Default::default(),
                     output: None,
+                    fail_on: FailOn::Breaking,
+                    policy: PolicyArgs {
+                        policies: vec![],
+                        skip_policies: false,
+                        display_policy_coverage: false,
+                    },
                     diagnostic: Default::default(),
                 }),
             })),
@@ -153,6 +208,43 @@ mod tests {
         assert_eq!(exit_directive.exit_code, 0);
     }
 
+    #[test]
+    fn test_registry_diff_policy_args() {
+        let mut args = RegistryDiffArgs {
+            registry: RegistryArgs {
+                registry: VirtualDirectoryPath::LocalFolder {
+                    path: "tests/diff/registry_head/".to_owned(),
+                },
+                follow_symlinks: false,
+                include_unreferenced: false,
+                v2: false,
+            },
+            baseline_registry: VirtualDirectoryPath::LocalFolder {
+                path: "tests/diff/registry_baseline/".to_owned(),
+            },
+            format: "json".to_owned(),
+            templates: Default::default(),
+            output: None,
+            fail_on: FailOn::None,
+            policy: PolicyArgs {
+                policies: vec![],
+                skip_policies: false,
+                display_policy_coverage: false,
+            },
+            diagnostic: Default::default(),
+        };
+        // Without `-p/--policy`, no policy is evaluated.
+        assert!(policy_args(&args).skip_policies);
+
+        args.policy.policies = vec![VirtualDirectoryPath::LocalFolder {
+            path: "tests/v2_check_baseline/next".to_owned(),
+        }];
+        assert!(!policy_args(&args).skip_policies);
+
+        args.policy.skip_policies = true;
+        assert!(policy_args(&args).skip_policies);
+    }
+
     #[test]
     fn test_registry_diff_cmd() {
         let temp_dir = tempfile::Builder::new()
@@ -176,11 +268,17 @@ mod tests {
                 format: "json".to_owned(),
                 templates: Default::default(),
                 output: Some(temp_dir.path().to_path_buf()),
+                fail_on: FailOn::None,
+                policy: PolicyArgs {
+                    policies: vec![],
+                    skip_policies: false,
+                    display_policy_coverage: false,
+                },
                 diagnostic: Default::default(),
             }),
         };
 
-        let cmd_result = semconv_registry(&registry_cmd);
+        let cmd_result = semconv_registry(&registry_cmd, &CacheSettings::default());
         assert_eq!(
             cmd_result
                 .command_result
@@ -201,19 +299,20 @@ mod tests {
         // Note: span differences have disappeared.
         assert_eq!(
             schema_changes.count_changes(),
-            25,
-            "Expected 25 total changes in {:?}",
+            40,
+            "Expected 40 total changes in {:?}",
             &schema_changes
         );
-        assert_eq!(schema_changes.count_registry_attribute_changes(), 5);
+        assert_eq!(schema_changes.count_registry_attribute_changes(), 8);
         assert_eq!(schema_changes.count_added_registry_attributes(), 1);
         assert_eq!(schema_changes.count_removed_registry_attributes(), 1);
         assert_eq!(schema_changes.count_obsoleted_registry_attributes(), 1);
         assert_eq!(schema_changes.count_uncategorized_registry_attributes(), 1);
         assert_eq!(schema_changes.count_renamed_registry_attributes(), 1);
-        assert_eq!(schema_changes.count_metric_changes(), 5);
-        assert_eq!(schema_changes.count_span_changes(), 5);
-        assert_eq!(schema_changes.count_event_changes(), 5);
-        assert_eq!(schema_changes.count_resource_changes(), 5);
+        assert_eq!(schema_changes.count_updated_registry_attributes(), 3);
+        assert_eq!(schema_changes.count_metric_changes(), 8);
+        assert_eq!(schema_changes.count_span_changes(), 8);
+        assert_eq!(schema_changes.count_event_changes(), 8);
+        assert_eq!(schema_changes.count_resource_changes(), 8);
     }
 }
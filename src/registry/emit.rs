@@ -5,6 +5,7 @@
 use clap::Args;
 
 use log::info;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::{DiagnosticMessages, ResultExt};
 use weaver_common::log_success;
 use weaver_emit::{emit, ExporterConfig, RegistryVersion};
@@ -38,7 +39,10 @@ pub struct RegistryEmitArgs {
 }
 
 /// Emit all spans in the resolved registry.
-pub(crate) fn command(args: &RegistryEmitArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryEmitArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     info!("Weaver Registry Emit");
     info!("Resolving registry `{}`", args.registry.registry);
 
@@ -51,7 +55,7 @@ pub(crate) fn command(args: &RegistryEmitArgs) -> Result<ExitDirectives, Diagnos
             endpoint: args.endpoint.clone(),
         }
     };
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
     if args.registry.v2 {
         let resolved_v2: ResolvedV2 = resolved.try_into()?;
@@ -97,6 +101,7 @@ mod tests {
             debug: 1,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Emit(RegistryEmitArgs {
                     registry: RegistryArgs {
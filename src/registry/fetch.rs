@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fetch a semantic convention registry and its dependencies into the local cache, and pin
+//! the remote registries in the lock file.
+
+use crate::registry::RegistryArgs;
+use crate::{DiagnosticArgs, ExitDirectives};
+use clap::Args;
+use log::info;
+use std::collections::HashSet;
+use weaver_common::cache::{CacheSettings, LockFile};
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::vdir::VirtualDirectory;
+use weaver_semconv::registry_repo::RegistryRepo;
+
+/// Parameters for the `registry fetch` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryFetchArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub(crate) diagnostic: DiagnosticArgs,
+}
+
+/// Fetch a semantic convention registry and its dependencies into the local cache.
+/// The pins of the existing lock file are ignored, so that they are updated.
+pub(crate) fn command(
+    args: &RegistryFetchArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    info!("Weaver Registry Fetch");
+    info!("Fetching registry `{}`", args.registry.registry);
+
+    let settings = CacheSettings {
+        refresh_lock: true,
+        ..cache_settings.clone()
+    };
+    let mut lock = LockFile::default();
+    let registry_repo =
+        RegistryRepo::try_new(None, &args.registry.registry, &settings, &mut vec![])?;
+    fetch_registry(registry_repo, &settings, &mut lock, &mut HashSet::new())?;
+
+    let lock_file = settings.lock_file_path();
+    lock.write_to_file(&lock_file)?;
+    info!(
+        "{} remote registr{} pinned in `{}`",
+        lock.sources.len(),
+        if lock.sources.len() == 1 { "y" } else { "ies" },
+        lock_file.display()
+    );
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        warnings: None,
+    })
+}
+
+/// Fetches a registry, its published resolved schema and its dependencies (recursively),
+/// and pins the remote ones in the lock file.
+fn fetch_registry(
+    registry_repo: RegistryRepo,
+    settings: &CacheSettings,
+    lock: &mut LockFile,
+    visited: &mut HashSet<String>,
+) -> Result<(), DiagnosticMessages> {
+    if !visited.insert(registry_repo.registry_path_repr().to_owned()) {
+        return Ok(());
+    }
+    lock_source(registry_repo.virtual_directory(), lock);
+
+    if let Some(resolved_schema_uri) = registry_repo.resolved_schema_uri() {
+        lock_source(
+            &VirtualDirectory::try_new(&resolved_schema_uri, settings)?,
+            lock,
+        );
+    }
+    if let Some(manifest) = registry_repo.manifest() {
+        for dependency in manifest.dependencies() {
+            info!("Fetching dependency `{}`", dependency.schema_url);
+            let dependency_repo =
+                RegistryRepo::try_new_dependency(dependency, settings, &mut vec![])?;
+            fetch_registry(dependency_repo, settings, lock, visited)?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds the commit or digest of a remote virtual directory to the lock file.
+fn lock_source(vdir: &VirtualDirectory, lock: &mut LockFile) {
+    if let (Some(lock_key), Some(locked)) = (vdir.vdir_path().lock_key(), vdir.locked_source()) {
+        _ = lock.sources.insert(lock_key, locked.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{CacheArgs, Cli, Commands};
+    use crate::registry::check::RegistryCheckArgs;
+    use crate::registry::fetch::RegistryFetchArgs;
+    use crate::registry::{PolicyArgs, RegistryArgs, RegistryCommand, RegistrySubCommand};
+    use crate::run_command;
+    use std::path::Path;
+    use std::process::Command;
+    use weaver_common::cache::{LockFile, LockedSource};
+    use weaver_common::vdir::VirtualDirectoryPath;
+
+    fn fetch_cli(registry: &Path, cache_dir: &Path, lock_file: &Path, offline: bool) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            future: false,
+            cache: CacheArgs {
+                offline,
+                lock_file: Some(lock_file.to_path_buf()),
+                cache_dir: Some(cache_dir.to_path_buf()),
+                no_cache: false,
+                resolution_cache: false,
+            },
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Fetch(RegistryFetchArgs {
+                    registry: RegistryArgs {
+                        registry: VirtualDirectoryPath::LocalFolder {
+                            path: registry.display().to_string(),
+                        },
+                        follow_symlinks: false,
+                        include_unreferenced: false,
+                        v2: false,
+                    },
+                    diagnostic: Default::default(),
+                }),
+            })),
+        }
+    }
+
+    fn check_cli(registry: &Path, cache_dir: &Path, lock_file: &Path, offline: bool) -> Cli {
+        let mut cli = fetch_cli(registry, cache_dir, lock_file, offline);
+        cli.command = Some(Commands::Registry(RegistryCommand {
+            command: RegistrySubCommand::Check(RegistryCheckArgs {
+                registry: RegistryArgs {
+                    registry: VirtualDirectoryPath::LocalFolder {
+                        path: registry.display().to_string(),
+                    },
+                    follow_symlinks: false,
+                    include_unreferenced: false,
+                    v2: false,
+                },
+                baseline_registry: None,
+                policy: PolicyArgs {
+                    policies: vec![],
+                    skip_policies: true,
+                    display_policy_coverage: false,
+                },
+                diagnostic: Default::default(),
+                watch: Default::default(),
+            }),
+        }));
+        cli
+    }
+
+    fn git(repo_path: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args([
+                "-c",
+                "user.name=weaver",
+                "-c",
+                "user.email=weaver@example.com",
+            ])
+            .args(args)
+            .current_dir(repo_path)
+            .output()
+            .expect("Failed to run git");
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+        String::from_utf8(output.stdout)
+            .expect("Invalid git output")
+            .trim()
+            .to_owned()
+    }
+
+    #[test]
+    fn test_registry_fetch() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let temp_path = temp_dir.path();
+        let cache_dir = temp_path.join("cache");
+        let lock_file = temp_path.join("weaver.lock");
+
+        // A Git repository containing the dependency registry.
+        let repo_dir = temp_path.join("repo");
+        std::fs::create_dir_all(repo_dir.join("model")).expect("Failed to create repo");
+        _ = std::fs::copy(
+            "crates/weaver_resolver/data/multi-registry/otel_registry/otel_registry.yaml",
+            repo_dir.join("model/otel_registry.yaml"),
+        )
+        .expect("Failed to copy the registry");
+        _ = git(&repo_dir, &["init", "--quiet"]);
+        _ = git(&repo_dir, &["add", "."]);
+        _ = git(&repo_dir, &["commit", "--quiet", "-m", "otel registry"]);
+        _ = git(&repo_dir, &["tag", "v1.30.0"]);
+        let commit = git(&repo_dir, &["rev-parse", "HEAD"]);
+        let git_source = format!("file://{}@v1.30.0", repo_dir.display());
+
+        // A local archive served through a `file://` URL.
+        let archive = std::fs::canonicalize(
+            "crates/weaver_common/tests/test_data/semconv_registry_v1.26.0.tar.gz",
+        )
+        .expect("Archive not found");
+        let archive_source = format!("file://{}", archive.display());
+
+        // The main registry depends on both.
+        let registry_dir = temp_path.join("registry");
+        std::fs::create_dir_all(&registry_dir).expect("Failed to create registry");
+        std::fs::write(
+            registry_dir.join("manifest.yaml"),
+            format!(
+                "schema_url: https://app.example.com/schemas/1.0.0\n\
+                 dependencies:\n  \
+                   - schema_url: https://opentelemetry.io/schemas/1.30.0\n    \
+                     registry_path: {git_source}[model]\n  \
+                   - schema_url: https://opentelemetry.io/schemas/1.26.0\n    \
+                     registry_path: {archive_source}[model]\n"
+            ),
+        )
+        .expect("Failed to write the manifest");
+
+        let exit_directive = run_command(&fetch_cli(&registry_dir, &cache_dir, &lock_file, false));
+        assert_eq!(exit_directive.exit_code, 0);
+        let lock = LockFile::try_from_file(&lock_file).expect("Invalid lock file");
+        assert_eq!(lock.sources.len(), 2);
+        assert_eq!(
+            lock.sources.get(&git_source),
+            Some(&LockedSource::Commit(commit))
+        );
+        assert!(matches!(
+            lock.sources.get(&archive_source),
+            Some(LockedSource::Digest(_))
+        ));
+
+        // Offline, the pinned registries are resolved from the cache.
+        std::fs::remove_dir_all(&repo_dir).expect("Failed to remove repo");
+        let exit_directive = run_command(&fetch_cli(&registry_dir, &cache_dir, &lock_file, true));
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(
+            LockFile::try_from_file(&lock_file).expect("Invalid lock file"),
+            lock
+        );
+
+        // The other commands resolve the registries with the same cache settings.
+        let app_registry_dir = temp_path.join("app_registry");
+        std::fs::create_dir_all(&app_registry_dir).expect("Failed to create registry");
+        std::fs::write(
+            app_registry_dir.join("manifest.yaml"),
+            format!(
+                "schema_url: https://app.example.com/schemas/1.0.0\n\
+                 dependencies:\n  \
+                   - schema_url: https://opentelemetry.io/schemas/1.30.0\n    \
+                     registry_path: {git_source}[model]\n"
+            ),
+        )
+        .expect("Failed to write the manifest");
+        let exit_directive =
+            run_command(&check_cli(&app_registry_dir, &cache_dir, &lock_file, true));
+        assert_eq!(exit_directive.exit_code, 0);
+
+        // Offline with an empty cache.
+        let empty_cache = temp_path.join("empty_cache");
+        let exit_directive = run_command(&fetch_cli(&registry_dir, &empty_cache, &lock_file, true));
+        assert_eq!(exit_directive.exit_code, 1);
+        let exit_directive = run_command(&check_cli(
+            &app_registry_dir,
+            &empty_cache,
+            &lock_file,
+            true,
+        ));
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+
+    #[test]
+    fn test_registry_fetch_moved_branch() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let temp_path = temp_dir.path();
+        let cache_dir = temp_path.join("cache");
+        let lock_file = temp_path.join("weaver.lock");
+
+        // A Git repository whose `main` branch moves after the first fetch.
+        let repo_dir = temp_path.join("repo");
+        std::fs::create_dir_all(repo_dir.join("model")).expect("Failed to create repo");
+        _ = std::fs::copy(
+            "crates/weaver_resolver/data/multi-registry/otel_registry/otel_registry.yaml",
+            repo_dir.join("model/otel_registry.yaml"),
+        )
+        .expect("Failed to copy the registry");
+        _ = git(&repo_dir, &["init", "--quiet", "--initial-branch=main"]);
+        _ = git(&repo_dir, &["add", "."]);
+        _ = git(&repo_dir, &["commit", "--quiet", "-m", "otel registry"]);
+        let first_commit = git(&repo_dir, &["rev-parse", "HEAD"]);
+        let git_source = format!("file://{}@main", repo_dir.display());
+
+        let registry_dir = temp_path.join("registry");
+        std::fs::create_dir_all(&registry_dir).expect("Failed to create registry");
+        std::fs::write(
+            registry_dir.join("manifest.yaml"),
+            format!(
+                "schema_url: https://app.example.com/schemas/1.0.0\n\
+                 dependencies:\n  \
+                   - schema_url: https://opentelemetry.io/schemas/1.30.0\n    \
+                     registry_path: {git_source}[model]\n"
+            ),
+        )
+        .expect("Failed to write the manifest");
+
+        let exit_directive = run_command(&fetch_cli(&registry_dir, &cache_dir, &lock_file, false));
+        assert_eq!(exit_directive.exit_code, 0);
+        let lock = LockFile::try_from_file(&lock_file).expect("Invalid lock file");
+        assert_eq!(
+            lock.sources.get(&git_source),
+            Some(&LockedSource::Commit(first_commit.clone()))
+        );
+
+        // The pinned branch moves, fetching again updates the lock file.
+        _ = git(
+            &repo_dir,
+            &["commit", "--quiet", "--allow-empty", "-m", "new commit"],
+        );
+        let second_commit = git(&repo_dir, &["rev-parse", "HEAD"]);
+        assert_ne!(first_commit, second_commit);
+        let exit_directive = run_command(&fetch_cli(&registry_dir, &cache_dir, &lock_file, false));
+        assert_eq!(exit_directive.exit_code, 0);
+        let lock = LockFile::try_from_file(&lock_file).expect("Invalid lock file");
+        assert_eq!(
+            lock.sources.get(&git_source),
+            Some(&LockedSource::Commit(second_commit))
+        );
+    }
+}
@@ -2,18 +2,21 @@
 
 //! Generate artifacts for a semantic convention registry.
 
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 use clap::Args;
 use log::info;
 use serde_yaml::Value;
 
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::log_success;
 use weaver_forge::config::{Params, WeaverConfig};
 use weaver_forge::file_loader::{FileLoader, FileSystemFileLoader};
 use weaver_forge::{OutputProcessor, OutputTarget};
 
+use crate::registry::watch::{self, WatchArgs};
 use crate::registry::{Error, PolicyArgs, RegistryArgs};
 use crate::weaver::{ResolvedV2, WeaverEngine};
 use crate::{DiagnosticArgs, ExitDirectives};
@@ -67,6 +70,10 @@ pub struct RegistryGenerateArgs {
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
+
+    /// Watch mode parameters.
+    #[command(flatten)]
+    pub watch: WatchArgs,
 }
 
 /// Utility function to parse key-value pairs from the command line.
@@ -83,20 +90,76 @@ pub(crate) fn parse_key_val(s: &str) -> Result<(String, Value), Error> {
 }
 
 /// Generate artifacts from a semantic convention registry.
-pub(crate) fn command(args: &RegistryGenerateArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryGenerateArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if !args.watch.watch {
+        return generate(args, &args.output, cache_settings);
+    }
+
+    // Each run renders into a staging directory, published only when the run succeeds so
+    // that a failed run keeps the previous output. The files published by the previous run
+    // and no longer generated are removed.
+    let staging = std::env::temp_dir().join(format!("weaver-generate-{}", std::process::id()));
+    let mut published = BTreeSet::new();
+    // The output directory may lie inside a watched directory, publishing must not trigger
+    // the next run.
+    let excluded = [args.output.clone()];
+    let result = watch::run(
+        &args.watch,
+        watched_paths(args),
+        &excluded,
+        &args.diagnostic,
+        || {
+            _ = std::fs::remove_dir_all(&staging);
+            let exit_directives = generate(args, &staging, cache_settings)?;
+            published = watch::publish(&staging, &args.output, &published).map_err(|e| {
+                Error::OutputWrite {
+                    path: args.output.clone(),
+                    error: e.to_string(),
+                }
+            })?;
+            Ok(exit_directives)
+        },
+    );
+    _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+/// The local files and directories the command depends on.
+fn watched_paths(args: &RegistryGenerateArgs) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = [&args.registry.registry, &args.templates]
+        .into_iter()
+        .chain(&args.policy.policies)
+        .filter_map(watch::local_path)
+        .collect();
+    paths.extend(args.config.iter().flatten().cloned());
+    paths.extend(args.params.iter().cloned());
+    paths
+}
+
+/// Generates the artifacts into the `output` directory.
+fn generate(
+    args: &RegistryGenerateArgs,
+    output: &Path,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     info!(
         "Generating artifacts for the registry `{}`",
         args.registry.registry
     );
 
     let mut diag_msgs = DiagnosticMessages::empty();
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
     let params = generate_params(args)?;
     let templates_dir =
-        VirtualDirectory::try_new(&args.templates).map_err(|e| Error::InvalidParams {
-            params_file: PathBuf::from(args.templates.to_string()),
-            error: e.to_string(),
+        VirtualDirectory::try_new(&args.templates, cache_settings).map_err(|e| {
+            Error::InvalidParams {
+                params_file: PathBuf::from(args.templates.to_string()),
+                error: e.to_string(),
+            }
         })?;
     let loader =
         FileSystemFileLoader::try_new(resolve_templates_root(&templates_dir), &args.target)?;
@@ -109,7 +172,7 @@ pub(crate) fn command(args: &RegistryGenerateArgs) -> Result<ExitDirectives, Dia
         config,
         loader,
         params,
-        OutputTarget::Directory(args.output.clone()),
+        OutputTarget::Directory(output.to_path_buf()),
     )?;
     // Resolve v1 and v2 schema, based on user request.
     if args.registry.v2 {
@@ -204,6 +267,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "rust".to_owned(),
@@ -229,6 +293,7 @@ mod tests {
                     },
                     future: false,
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -283,6 +348,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "rust".to_owned(),
@@ -308,6 +374,7 @@ mod tests {
                     },
                     future: false,
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -326,6 +393,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "rust".to_owned(),
@@ -358,6 +426,7 @@ mod tests {
                     },
                     future: false,
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -442,6 +511,7 @@ mod tests {
                 debug: 1,
                 quiet: false,
                 future: false,
+                cache: Default::default(),
                 command: Some(Commands::Registry(RegistryCommand {
                     command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                         target: "rust".to_owned(),
@@ -467,6 +537,7 @@ mod tests {
                         },
                         future: false,
                         diagnostic: Default::default(),
+                        watch: Default::default(),
                     }),
                 })),
             };
@@ -519,6 +590,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Generate(RegistryGenerateArgs {
                     target: "markdown".to_owned(),
@@ -544,6 +616,7 @@ mod tests {
                     },
                     future: false,
                     diagnostic: Default::default(),
+                    watch: Default::default(),
                 }),
             })),
         };
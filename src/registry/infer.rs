@@ -2,25 +2,40 @@
 
 //! Generates a semantic convention registry file by inferring the schema from OTLP messages.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
 use clap::Args;
+use globset::Glob;
 use log::info;
 use serde::Serialize;
 use serde_json::Value;
+use weaver_live_check::live_checker::LiveChecker;
 use weaver_live_check::sample_attribute::SampleAttribute;
 use weaver_live_check::sample_metric::{SampleInstrument, SampleMetric};
 use weaver_live_check::sample_resource::SampleResource;
 use weaver_live_check::sample_span::{SampleSpan, SampleSpanEvent};
-use weaver_live_check::Sample;
+use weaver_live_check::{Sample, VersionedRegistry, VersionedSignal};
 use weaver_semconv::attribute::{
-    AttributeSpec, AttributeType, Examples, PrimitiveOrArrayTypeSpec, RequirementLevel,
+    AttributeSpec, AttributeType, EnumEntriesSpec, Examples, PrimitiveOrArrayTypeSpec,
+    RequirementLevel, TemplateTypeSpec, ValueSpec,
 };
-use weaver_semconv::group::{GroupSpec, GroupType, InstrumentSpec, SpanKindSpec};
+use weaver_semconv::group::{GroupSpec, GroupType, GroupWildcard, InstrumentSpec, SpanKindSpec};
+use weaver_semconv::manifest::{DefinitionRegistryManifest, Dependency};
+use weaver_semconv::registry_repo::REGISTRY_MANIFEST;
+use weaver_semconv::schema_url::SchemaUrl;
+use weaver_semconv::semconv::Imports;
 use weaver_semconv::stability::Stability;
+use weaver_semconv::v2::attribute::{AttributeDef, AttributeOrGroupRef, AttributeRef};
+use weaver_semconv::v2::entity::Entity;
+use weaver_semconv::v2::event::Event;
+use weaver_semconv::v2::metric::Metric;
+use weaver_semconv::v2::span::{Span, SpanAttributeOrGroupRef, SpanAttributeRef, SpanName};
+use weaver_semconv::v2::CommonFields;
 
 use super::otlp::conversion::{
     otlp_log_record_to_sample_log, otlp_metric_to_sample, sample_attribute_from_key_value,
@@ -28,11 +43,49 @@ use super::otlp::conversion::{
 };
 use super::otlp::grpc_stubs::proto::resource::v1::Resource;
 use super::otlp::{listen_otlp_requests, OtlpRequest};
+use crate::registry::{PolicyArgs, RegistryArgs};
+use crate::weaver::WeaverEngine;
 use crate::{DiagnosticArgs, ExitDirectives};
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_common::log_success;
+use weaver_common::vdir::VirtualDirectoryPath;
 
 const MAX_EXAMPLES: usize = 5;
+const DEFAULT_ENUM_MAX_VALUES: usize = 10;
+const DEFAULT_ENUM_MIN_SAMPLES: usize = 20;
+const DEFAULT_TEMPLATE_MIN_KEYS: usize = 3;
+
+/// Default explicit bucket boundaries of the OpenTelemetry HTTP duration histograms, in
+/// seconds.
+const SECONDS_BUCKET_BOUNDARIES: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// Default explicit bucket boundaries of the OpenTelemetry SDK histograms, commonly used for
+/// durations in milliseconds.
+const MILLISECONDS_BUCKET_BOUNDARIES: [f64; 15] = [
+    0.0, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1000.0, 2500.0, 5000.0, 7500.0,
+    10000.0,
+];
+
+/// The side report listing the conflicts with the base registry. It is a JSON file so that
+/// the loader does not read it as a semantic convention file.
+const CONFLICTS_REPORT: &str = "conflicts.json";
+
+/// The UCUM units commonly used by the semantic conventions, left unchanged when observed.
+/// UCUM units are case sensitive, e.g. `MBy` (megabyte) and `mBy` (millibyte) differ.
+const KNOWN_UCUM_UNITS: [&str; 41] = [
+    "s", "ms", "us", "ns", "min", "h", "d", "By", "kBy", "MBy", "GBy", "TBy", "KiBy", "MiBy",
+    "GiBy", "TiBy", "bit", "kbit", "Mbit", "Gbit", "Kibit", "Mibit", "Gibit", "Hz", "kHz", "MHz",
+    "GHz", "Cel", "K", "m", "mm", "km", "g", "kg", "W", "J", "V", "A", "Pa", "kPa", "mmHg",
+];
+
+/// The file format of the semantic convention files generated with `--v2`.
+const DEFINITION_V2_FILE_FORMAT: &str = "definition/2";
+
+/// The `--v2` file holding the `imports` of the base signals refined by the inferred registry.
+const IMPORTS_FILE: &str = "imports";
 
 /// Parameters for the `registry infer` sub-command
 #[derive(Debug, Args)]
@@ -53,6 +106,12 @@ pub struct RegistryInferArgs {
     #[arg(long, default_value = "4317")]
     grpc_port: u16,
 
+    /// Port used by the OTLP/HTTP listener (protobuf and JSON payloads), usually 4318.
+    /// The listener shares the address of the gRPC OTLP listener.
+    /// The OTLP/HTTP listener is only started when this port is set.
+    #[arg(long)]
+    http_port: Option<u16>,
+
     /// Port used by the HTTP admin server (endpoints: /stop).
     #[arg(long, default_value = "8080")]
     admin_port: u16,
@@ -60,6 +119,55 @@ pub struct RegistryInferArgs {
     /// Seconds of inactivity before auto-stop (0 = never).
     #[arg(long, default_value = "60")]
     inactivity_timeout: u64,
+
+    /// Generate `definition/2` files, one per attribute namespace, and a `manifest.yaml`
+    /// instead of a single v1 `registry.yaml` file.
+    #[arg(long)]
+    v2: bool,
+
+    /// Schema URL of the registry manifest generated with `--v2` or `--registry`.
+    #[arg(long, default_value = "https://example.com/schemas/inferred/0.1.0")]
+    schema_url: String,
+
+    /// Local folder, Git repo URL, or Git archive URL of an existing semantic convention
+    /// registry. Attributes and signals already defined in this registry are left out of the
+    /// inferred registry, which declares it as a dependency. Type, instrument and unit
+    /// mismatches with the existing definitions are reported in `conflicts.json`.
+    #[arg(short = 'r', long)]
+    registry: Option<VirtualDirectoryPath>,
+
+    /// Thresholds used to generalize the observed attribute values.
+    #[command(flatten)]
+    thresholds: InferThresholds,
+}
+
+/// Thresholds used to generalize the observed attribute values into enums and templates.
+#[derive(Debug, Clone, Args)]
+pub struct InferThresholds {
+    /// Maximum number of distinct values of a string attribute inferred as an enum.
+    /// Set to 0 to disable the enum inference.
+    #[arg(long, default_value_t = DEFAULT_ENUM_MAX_VALUES)]
+    enum_max_values: usize,
+
+    /// Minimum number of samples of a string attribute before inferring it as an enum.
+    #[arg(long, default_value_t = DEFAULT_ENUM_MIN_SAMPLES)]
+    enum_min_samples: usize,
+
+    /// Minimum number of attribute keys sharing a prefix, with mostly variable suffixes
+    /// (e.g. `http.request.header.content-type`), inferred as a template attribute.
+    /// Set to 0 to disable the template inference.
+    #[arg(long, default_value_t = DEFAULT_TEMPLATE_MIN_KEYS)]
+    template_min_keys: usize,
+}
+
+impl Default for InferThresholds {
+    fn default() -> Self {
+        Self {
+            enum_max_values: DEFAULT_ENUM_MAX_VALUES,
+            enum_min_samples: DEFAULT_ENUM_MIN_SAMPLES,
+            template_min_keys: DEFAULT_TEMPLATE_MIN_KEYS,
+        }
+    }
 }
 
 struct AccumulatedSpan {
@@ -85,6 +193,8 @@ struct AccumulatedMetric {
     instrument: InstrumentSpec,
     unit: String,
     attributes: HashMap<String, AttributeSpec>,
+    /// Explicit bucket boundaries of the first histogram data point.
+    bucket_boundaries: Vec<f64>,
 }
 
 impl AccumulatedMetric {
@@ -94,6 +204,7 @@ impl AccumulatedMetric {
             instrument,
             unit,
             attributes: HashMap::new(),
+            bucket_boundaries: Vec::new(),
         }
     }
 }
@@ -112,17 +223,230 @@ impl AccumulatedEvent {
     }
 }
 
+/// Values observed for an attribute key, across all signals.
+#[derive(Default)]
+struct ObservedValues {
+    /// Number of samples.
+    samples: usize,
+    /// Type of the observed values, widened to fit all of them.
+    r#type: Option<PrimitiveOrArrayTypeSpec>,
+    /// Distinct string values, cleared once there are too many for an enum.
+    values: BTreeSet<String>,
+    high_cardinality: bool,
+}
+
+/// Observations of the attribute values used to generalize the inferred attributes.
+#[derive(Default)]
+struct AttributeObservations {
+    thresholds: InferThresholds,
+    values: HashMap<String, ObservedValues>,
+}
+
+impl AttributeObservations {
+    fn observe(&mut self, sample: &SampleAttribute) {
+        let observed = self.values.entry(sample.name.clone()).or_default();
+        observed.samples += 1;
+        let r#type = sample
+            .r#type
+            .clone()
+            .unwrap_or(PrimitiveOrArrayTypeSpec::String);
+        observed.r#type = Some(match &observed.r#type {
+            Some(current) => widen_type(current, &r#type),
+            None => r#type,
+        });
+        if let Some(Value::String(value)) = &sample.value {
+            if !observed.high_cardinality && !observed.values.contains(value) {
+                if observed.values.len() < self.thresholds.enum_max_values {
+                    _ = observed.values.insert(value.clone());
+                } else {
+                    observed.high_cardinality = true;
+                    observed.values.clear();
+                }
+            }
+        }
+    }
+
+    /// Computes the generalized type of every observed attribute key, and the keys grouped
+    /// into template attributes.
+    fn generalization(&self) -> Generalization {
+        let mut generalization = Generalization::default();
+        for (key, observed) in &self.values {
+            let Some(r#type) = &observed.r#type else {
+                continue;
+            };
+            let r#type = match enum_members(observed, &self.thresholds) {
+                Some(members) => AttributeType::Enum { members },
+                None => AttributeType::PrimitiveOrArray(r#type.clone()),
+            };
+            _ = generalization.types.insert(key.clone(), r#type);
+        }
+
+        if self.thresholds.template_min_keys == 0 {
+            return generalization;
+        }
+        let mut prefixes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for key in self.values.keys() {
+            if let Some((prefix, suffix)) = key.rsplit_once('.') {
+                prefixes.entry(prefix).or_default().push(suffix);
+            }
+        }
+        for (prefix, suffixes) in prefixes {
+            // Most suffixes must look like data, so that a few sibling attributes with an
+            // unusual name (e.g. `http.request.method` and `http.request.X-Id`) are kept.
+            let variable_suffixes = suffixes
+                .iter()
+                .filter(|suffix| is_variable_suffix(suffix))
+                .count();
+            if suffixes.len() < self.thresholds.template_min_keys
+                || variable_suffixes * 2 <= suffixes.len()
+                || self.values.contains_key(prefix)
+            {
+                continue;
+            }
+            let mut template_type: Option<PrimitiveOrArrayTypeSpec> = None;
+            for suffix in suffixes {
+                let key = format!("{prefix}.{suffix}");
+                if let Some(r#type) = self.values.get(&key).and_then(|o| o.r#type.as_ref()) {
+                    template_type = Some(match &template_type {
+                        Some(current) => widen_type(current, r#type),
+                        None => r#type.clone(),
+                    });
+                }
+                _ = generalization.templates.insert(key, prefix.to_owned());
+            }
+            _ = generalization.types.insert(
+                prefix.to_owned(),
+                AttributeType::Template(template_type_spec(
+                    &template_type.unwrap_or(PrimitiveOrArrayTypeSpec::String),
+                )),
+            );
+        }
+        generalization
+    }
+}
+
+/// The generalized types of the observed attribute keys.
+#[derive(Default)]
+struct Generalization {
+    /// Generalized type by attribute key, template prefixes included.
+    types: HashMap<String, AttributeType>,
+    /// Template prefix by attribute key, for the keys grouped into a template attribute.
+    templates: HashMap<String, String>,
+}
+
+impl Generalization {
+    /// Applies the generalized types to a set of inferred attributes, merging the keys of a
+    /// template into a single template attribute.
+    fn apply(&self, attributes: HashMap<String, AttributeSpec>) -> HashMap<String, AttributeSpec> {
+        let mut keys: Vec<String> = attributes.keys().cloned().collect();
+        keys.sort();
+        let mut attributes = attributes;
+        let mut generalized = HashMap::new();
+        for key in keys {
+            let Some(mut attribute) = attributes.remove(&key) else {
+                continue;
+            };
+            let key = self.templates.get(&key).cloned().unwrap_or(key);
+            if let (
+                AttributeSpec::Id {
+                    id,
+                    r#type,
+                    examples,
+                    ..
+                },
+                Some(generalized_type),
+            ) = (&mut attribute, self.types.get(&key))
+            {
+                id.clone_from(&key);
+                *r#type = generalized_type.clone();
+                *examples = convert_examples(examples.take(), generalized_type);
+            }
+            _ = generalized.entry(key).or_insert(attribute);
+        }
+        generalized
+    }
+}
+
 #[derive(Default)]
 struct AccumulatedSamples {
     resources: HashMap<String, AttributeSpec>,
     spans: HashMap<String, AccumulatedSpan>,
     metrics: HashMap<String, AccumulatedMetric>,
     events: HashMap<String, AccumulatedEvent>,
+    /// New attributes observed on signals already defined in the base registry.
+    attributes: HashMap<String, AttributeSpec>,
+    /// The signals of the base registry these new attributes were observed on.
+    refined: RefinedSignals,
+    observations: AttributeObservations,
+}
+
+/// The signals of the base registry refined by the inferred registry, imported from it.
+#[derive(Default)]
+struct RefinedSignals {
+    /// Metric names.
+    metrics: BTreeSet<String>,
+    /// Event names.
+    events: BTreeSet<String>,
+    /// Span group ids.
+    spans: BTreeSet<String>,
+}
+
+impl RefinedSignals {
+    /// Returns the `imports` section importing the refined signals, if any.
+    fn to_imports(&self) -> Option<Imports> {
+        let wildcards = |names: &BTreeSet<String>| {
+            let wildcards: Vec<GroupWildcard> = names
+                .iter()
+                .filter_map(|name| Glob::new(&globset::escape(name)).ok())
+                .map(GroupWildcard)
+                .collect();
+            (!wildcards.is_empty()).then_some(wildcards)
+        };
+        let imports = Imports {
+            metrics: wildcards(&self.metrics),
+            events: wildcards(&self.events),
+            entities: None,
+            spans: wildcards(&self.spans),
+            attribute_groups: None,
+        };
+        (imports.metrics.is_some() || imports.events.is_some() || imports.spans.is_some())
+            .then_some(imports)
+    }
 }
 
 impl AccumulatedSamples {
-    fn new() -> Self {
-        Self::default()
+    fn new(thresholds: InferThresholds) -> Self {
+        Self {
+            observations: AttributeObservations {
+                thresholds,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Generalizes the inferred attributes and metric units from the observed values:
+    /// - types are widened to fit all the observed values (e.g. int and double to double),
+    /// - string attributes with few distinct values across many samples become enums,
+    /// - keys sharing a prefix with variable suffixes become template attributes,
+    /// - metric units are normalized to UCUM, or guessed from histogram bucket boundaries.
+    fn generalize(&mut self) {
+        let generalization = self.observations.generalization();
+        self.resources = generalization.apply(std::mem::take(&mut self.resources));
+        self.attributes = generalization.apply(std::mem::take(&mut self.attributes));
+        for span in self.spans.values_mut() {
+            span.attributes = generalization.apply(std::mem::take(&mut span.attributes));
+            for event in span.events.values_mut() {
+                event.attributes = generalization.apply(std::mem::take(&mut event.attributes));
+            }
+        }
+        for metric in self.metrics.values_mut() {
+            metric.attributes = generalization.apply(std::mem::take(&mut metric.attributes));
+            metric.unit = infer_unit(&metric.unit, &metric.bucket_boundaries);
+        }
+        for event in self.events.values_mut() {
+            event.attributes = generalization.apply(std::mem::take(&mut event.attributes));
+        }
     }
 
     fn add_sample(&mut self, sample: Sample) {
@@ -131,7 +455,10 @@ impl AccumulatedSamples {
             Sample::Span(span) => self.add_span(span),
             Sample::Metric(metric) => self.add_metric(metric),
             Sample::Log(log) => self.add_event(log.event_name, log.attributes),
-            Sample::Attribute(attr) => accumulate_attribute(&mut self.resources, attr),
+            Sample::Attribute(attr) => {
+                self.observations.observe(&attr);
+                accumulate_attribute(&mut self.resources, attr);
+            }
             other => {
                 // This shouldn't happen since we control when add_sample is called.
                 // Adding anyway just in case.
@@ -142,6 +469,7 @@ impl AccumulatedSamples {
 
     fn add_resource(&mut self, resource: SampleResource) {
         for attr in resource.attributes {
+            self.observations.observe(&attr);
             accumulate_attribute(&mut self.resources, attr);
         }
     }
@@ -153,6 +481,7 @@ impl AccumulatedSamples {
             .or_insert_with(|| AccumulatedSpan::new(span.name.clone(), span.kind.clone()));
 
         for attr in span.attributes {
+            self.observations.observe(&attr);
             accumulate_attribute(&mut entry.attributes, attr);
         }
 
@@ -164,6 +493,7 @@ impl AccumulatedSamples {
                 .or_insert_with(|| AccumulatedEvent::new(event.name.clone()));
 
             for attr in event.attributes {
+                self.observations.observe(&attr);
                 accumulate_attribute(&mut event_entry.attributes, attr);
             }
         }
@@ -186,13 +516,18 @@ impl AccumulatedSamples {
                 DataPoints::Number(points) => {
                     for point in points {
                         for attr in point.attributes {
+                            self.observations.observe(&attr);
                             accumulate_attribute(&mut entry.attributes, attr);
                         }
                     }
                 }
                 DataPoints::Histogram(points) => {
                     for point in points {
+                        if entry.bucket_boundaries.is_empty() {
+                            entry.bucket_boundaries = point.explicit_bounds;
+                        }
                         for attr in point.attributes {
+                            self.observations.observe(&attr);
                             accumulate_attribute(&mut entry.attributes, attr);
                         }
                     }
@@ -200,6 +535,7 @@ impl AccumulatedSamples {
                 DataPoints::ExponentialHistogram(points) => {
                     for point in points {
                         for attr in point.attributes {
+                            self.observations.observe(&attr);
                             accumulate_attribute(&mut entry.attributes, attr);
                         }
                     }
@@ -219,6 +555,7 @@ impl AccumulatedSamples {
             .or_insert_with(|| AccumulatedEvent::new(event_name));
 
         for attr in attributes {
+            self.observations.observe(&attr);
             accumulate_attribute(&mut entry.attributes, attr);
         }
     }
@@ -228,6 +565,7 @@ impl AccumulatedSamples {
             && self.spans.is_empty()
             && self.metrics.is_empty()
             && self.events.is_empty()
+            && self.attributes.is_empty()
     }
 
     fn stats(&self) -> (usize, usize, usize, usize) {
@@ -239,6 +577,131 @@ impl AccumulatedSamples {
         )
     }
 
+    /// Removes the attributes and signals already defined in the base registry, and returns
+    /// the conflicts between the observed telemetry and the base definitions.
+    ///
+    /// Known attributes are kept as references on the new signals. New attributes observed on
+    /// known signals are kept as standalone attribute definitions.
+    fn retain_unknown(&mut self, base: &LiveChecker) -> BTreeSet<InferConflict> {
+        let mut conflicts = BTreeSet::new();
+
+        self.resources =
+            classify_attributes(std::mem::take(&mut self.resources), base, &mut conflicts);
+        self.resources
+            .retain(|_, attribute| matches!(attribute, AttributeSpec::Id { .. }));
+
+        let spans = std::mem::take(&mut self.spans);
+        for (name, mut span) in spans {
+            span.attributes =
+                classify_attributes(std::mem::take(&mut span.attributes), base, &mut conflicts);
+            for event in std::mem::take(&mut span.events).into_values() {
+                if let Some(event) = self.retain_unknown_event(event, base, &mut conflicts) {
+                    _ = span.events.insert(event.name.clone(), event);
+                }
+            }
+            let sample_span = SampleSpan {
+                name: span.name.clone(),
+                kind: span.kind.clone(),
+                status: None,
+                attributes: span
+                    .attributes
+                    .keys()
+                    .map(|key| SampleAttribute {
+                        name: key.clone(),
+                        value: None,
+                        r#type: None,
+                        live_check_result: None,
+                    })
+                    .collect(),
+                span_events: Vec::new(),
+                span_links: Vec::new(),
+                live_check_result: None,
+                resource: None,
+                span_type: None,
+            };
+            if let Some((_, definition)) = base.find_span(&sample_span) {
+                if has_unknown_attributes(&span.attributes) {
+                    let group_id = match &*definition {
+                        VersionedSignal::Group(group) => Some(group.id.clone()),
+                        VersionedSignal::Span(span) => Some(format!("span.{}", span.r#type)),
+                        VersionedSignal::Metric(_) | VersionedSignal::Event(_) => None,
+                    };
+                    self.refined.spans.extend(group_id);
+                }
+                take_unknown_attributes(span.attributes, &mut self.attributes);
+                // Span events with a new name are kept as standalone events.
+                self.events.extend(span.events);
+            } else {
+                _ = self.spans.insert(name, span);
+            }
+        }
+
+        let metrics = std::mem::take(&mut self.metrics);
+        for (name, mut metric) in metrics {
+            metric.attributes =
+                classify_attributes(std::mem::take(&mut metric.attributes), base, &mut conflicts);
+            if let Some(definition) = base.find_metric(&name) {
+                if let Some(instrument) = definition.instrument() {
+                    if *instrument != metric.instrument {
+                        _ = conflicts.insert(InferConflict::new(
+                            ConflictKind::Metric,
+                            &name,
+                            "instrument",
+                            &metric.instrument,
+                            instrument,
+                        ));
+                    }
+                }
+                if let Some(unit) = definition.unit() {
+                    if *unit != metric.unit {
+                        _ = conflicts.insert(InferConflict::new(
+                            ConflictKind::Metric,
+                            &name,
+                            "unit",
+                            &metric.unit,
+                            unit,
+                        ));
+                    }
+                }
+                if has_unknown_attributes(&metric.attributes) {
+                    _ = self.refined.metrics.insert(name);
+                }
+                take_unknown_attributes(metric.attributes, &mut self.attributes);
+            } else {
+                _ = self.metrics.insert(name, metric);
+            }
+        }
+
+        for event in std::mem::take(&mut self.events).into_values() {
+            if let Some(event) = self.retain_unknown_event(event, base, &mut conflicts) {
+                _ = self.events.insert(event.name.clone(), event);
+            }
+        }
+
+        conflicts
+    }
+
+    /// Returns the event if it is not defined in the base registry, otherwise keeps its new
+    /// attributes as standalone attribute definitions.
+    fn retain_unknown_event(
+        &mut self,
+        mut event: AccumulatedEvent,
+        base: &LiveChecker,
+        conflicts: &mut BTreeSet<InferConflict>,
+    ) -> Option<AccumulatedEvent> {
+        event.attributes =
+            classify_attributes(std::mem::take(&mut event.attributes), base, conflicts);
+        if base.find_event(&event.name).is_some() {
+            if has_unknown_attributes(&event.attributes) {
+                _ = self.refined.events.insert(event.name);
+            }
+            take_unknown_attributes(event.attributes, &mut self.attributes);
+            None
+        } else {
+            Some(event)
+        }
+    }
+
     /// Convert accumulated samples to a semconv-compatible registry file.
     ///
     /// This method produces `GroupSpec` instances from `weaver_semconv` which
@@ -265,6 +728,21 @@ impl AccumulatedSamples {
             });
         }
 
+        // Attribute group for the new attributes of signals defined in the base registry
+        if !self.attributes.is_empty() {
+            let mut attributes: Vec<AttributeSpec> = self.attributes.values().cloned().collect();
+            attributes.sort_by_key(|a| a.id());
+
+            groups.push(GroupSpec {
+                id: "registry.inferred".to_owned(),
+                r#type: GroupType::AttributeGroup,
+                brief: String::new(),
+                stability: Some(Stability::Development),
+                attributes,
+                ..Default::default()
+            });
+        }
+
         // Span groups
         for span in self.spans.values() {
             let mut attributes: Vec<AttributeSpec> = span.attributes.values().cloned().collect();
@@ -332,7 +810,148 @@ impl AccumulatedSamples {
             });
         }
 
-        InferredRegistry { groups }
+        InferredRegistry {
+            groups,
+            imports: self.refined.to_imports(),
+        }
+    }
+
+    /// Convert accumulated samples to `definition/2` semantic convention files, indexed by
+    /// namespace (i.e. the first segment of the attribute key or signal name).
+    ///
+    /// Attributes are defined once in the file of their namespace and referenced by the
+    /// signals. Resource attributes are grouped into one entity per namespace.
+    fn to_semconv_spec_v2(&self) -> BTreeMap<String, InferredRegistryV2> {
+        let mut files: BTreeMap<String, InferredRegistryV2> = BTreeMap::new();
+
+        // Attribute definitions, the first observation of an attribute wins.
+        let mut definitions: BTreeMap<String, AttributeDef> = BTreeMap::new();
+        let mut define = |attributes: &HashMap<String, AttributeSpec>| -> Vec<String> {
+            let mut keys: Vec<String> = attributes.keys().cloned().collect();
+            keys.sort();
+            for key in &keys {
+                if let Some(definition) = attribute_def_from_spec(&attributes[key]) {
+                    _ = definitions.entry(key.clone()).or_insert(definition);
+                }
+            }
+            keys
+        };
+
+        _ = define(&self.attributes);
+
+        // Entities, one per namespace of resource attributes.
+        let mut entities: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for key in define(&self.resources) {
+            entities.entry(namespace(&key)).or_default().push(key);
+        }
+        for (entity_type, keys) in entities {
+            // Names and ids identify the entity, other attributes describe it.
+            let (mut identity, mut description): (Vec<String>, Vec<String>) = keys
+                .into_iter()
+                .partition(|key| key.ends_with(".name") || key.ends_with(".id"));
+            if identity.is_empty() {
+                std::mem::swap(&mut identity, &mut description);
+            }
+            files
+                .entry(namespace(&entity_type))
+                .or_default()
+                .entities
+                .push(Entity {
+                    r#type: entity_type.clone().into(),
+                    identity: identity.iter().map(|key| attribute_ref(key)).collect(),
+                    description: description.iter().map(|key| attribute_ref(key)).collect(),
+                    common: common_fields(),
+                });
+        }
+
+        // Events from logs and span events, merged by name.
+        let mut events: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let span_events = self.spans.values().flat_map(|span| span.events.values());
+        for event in self.events.values().chain(span_events) {
+            events
+                .entry(event.name.clone())
+                .or_default()
+                .extend(define(&event.attributes));
+        }
+        for (name, keys) in events {
+            files
+                .entry(namespace(&name))
+                .or_default()
+                .events
+                .push(Event {
+                    name: name.clone().into(),
+                    attributes: keys
+                        .iter()
+                        .map(|key| AttributeOrGroupRef::Attribute(attribute_ref(key)))
+                        .collect(),
+                    entity_associations: vec![],
+                    common: common_fields(),
+                });
+        }
+
+        let mut spans: Vec<&AccumulatedSpan> = self.spans.values().collect();
+        spans.sort_by(|a, b| a.name.cmp(&b.name));
+        for span in spans {
+            let r#type = sanitize_id(&span.name);
+            let attributes = define(&span.attributes)
+                .iter()
+                .map(|key| {
+                    SpanAttributeOrGroupRef::Attribute(SpanAttributeRef {
+                        base: attribute_ref(key),
+                        sampling_relevant: None,
+                    })
+                })
+                .collect();
+            files
+                .entry(namespace(&r#type))
+                .or_default()
+                .spans
+                .push(Span {
+                    r#type: r#type.clone().into(),
+                    kind: span.kind.clone(),
+                    name: SpanName {
+                        note: span.name.clone(),
+                    },
+                    attributes,
+                    entity_associations: vec![],
+                    common: common_fields(),
+                });
+        }
+
+        let mut metrics: Vec<&AccumulatedMetric> = self.metrics.values().collect();
+        metrics.sort_by(|a, b| a.name.cmp(&b.name));
+        for metric in metrics {
+            let attributes = define(&metric.attributes)
+                .iter()
+                .map(|key| AttributeOrGroupRef::Attribute(attribute_ref(key)))
+                .collect();
+            files
+                .entry(namespace(&metric.name))
+                .or_default()
+                .metrics
+                .push(Metric {
+                    name: metric.name.clone().into(),
+                    instrument: metric.instrument.clone(),
+                    unit: metric.unit.clone(),
+                    attributes,
+                    entity_associations: vec![],
+                    common: common_fields(),
+                });
+        }
+
+        for (key, definition) in definitions {
+            files
+                .entry(namespace(&key))
+                .or_default()
+                .attributes
+                .push(definition);
+        }
+
+        // The refined signals of the base registry are imported once, in a dedicated file.
+        if let Some(imports) = self.refined.to_imports() {
+            files.entry(IMPORTS_FILE.to_owned()).or_default().imports = Some(imports);
+        }
+        files
     }
 }
 
@@ -344,6 +963,242 @@ impl AccumulatedSamples {
 #[derive(Serialize)]
 struct InferredRegistry {
     groups: Vec<GroupSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    imports: Option<Imports>,
+}
+
+/// A `definition/2` semantic convention file.
+///
+/// Note: We use this wrapper instead of `SemConvSpecV2` directly because its
+/// fields are `pub(crate)` in weaver_semconv and the `file_format` field is not
+/// part of it.
+#[derive(Serialize)]
+struct InferredRegistryV2 {
+    file_format: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<AttributeDef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    entities: Vec<Entity>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    events: Vec<Event>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    metrics: Vec<Metric>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    spans: Vec<Span>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    imports: Option<Imports>,
+}
+
+impl Default for InferredRegistryV2 {
+    fn default() -> Self {
+        Self {
+            file_format: DEFINITION_V2_FILE_FORMAT,
+            attributes: vec![],
+            entities: vec![],
+            events: vec![],
+            metrics: vec![],
+            spans: vec![],
+            imports: None,
+        }
+    }
+}
+
+/// The kind of item in conflict with the base registry.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+enum ConflictKind {
+    Attribute,
+    Metric,
+}
+
+/// A mismatch between the observed telemetry and a definition of the base registry.
+#[derive(Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct InferConflict {
+    kind: ConflictKind,
+    name: String,
+    property: &'static str,
+    observed: String,
+    expected: String,
+}
+
+impl InferConflict {
+    fn new(
+        kind: ConflictKind,
+        name: &str,
+        property: &'static str,
+        observed: &impl Display,
+        expected: &impl Display,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.to_owned(),
+            property,
+            observed: observed.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+}
+
+/// The side report listing the conflicts with the base registry.
+#[derive(Serialize)]
+struct ConflictReport {
+    registry: String,
+    conflicts: Vec<InferConflict>,
+}
+
+/// Returns the namespace of an attribute key or a signal name, used to name the generated
+/// `definition/2` file.
+fn namespace(name: &str) -> String {
+    let namespace = sanitize_id(name.split('.').next().unwrap_or_default());
+    if namespace.is_empty() {
+        "other".to_owned()
+    } else {
+        namespace
+    }
+}
+
+/// Common fields of the inferred attributes and signals.
+fn common_fields() -> CommonFields {
+    CommonFields {
+        brief: String::new(),
+        note: String::new(),
+        stability: Stability::Development,
+        deprecated: None,
+        annotations: BTreeMap::new(),
+    }
+}
+
+/// Create a reference to an attribute defined in the inferred registry.
+fn attribute_ref(key: &str) -> AttributeRef {
+    AttributeRef {
+        r#ref: key.to_owned(),
+        brief: None,
+        examples: None,
+        requirement_level: None,
+        note: None,
+        stability: None,
+        deprecated: None,
+        annotations: BTreeMap::new(),
+    }
+}
+
+/// Create a `definition/2` attribute definition from an inferred AttributeSpec.
+fn attribute_def_from_spec(spec: &AttributeSpec) -> Option<AttributeDef> {
+    match spec {
+        AttributeSpec::Id {
+            id,
+            r#type,
+            examples,
+            ..
+        } => Some(AttributeDef {
+            key: id.clone(),
+            r#type: r#type.clone(),
+            examples: examples.clone(),
+            common: common_fields(),
+        }),
+        AttributeSpec::Ref { .. } => None,
+    }
+}
+
+/// Replaces the attributes defined in the base registry with references to their
+/// definitions, recording the type conflicts. Attributes matching a template are referenced
+/// through the template.
+fn classify_attributes(
+    attributes: HashMap<String, AttributeSpec>,
+    base: &LiveChecker,
+    conflicts: &mut BTreeSet<InferConflict>,
+) -> HashMap<String, AttributeSpec> {
+    let mut classified = HashMap::new();
+    for (key, attribute) in attributes {
+        let AttributeSpec::Id { r#type, .. } = &attribute else {
+            _ = classified.insert(key, attribute);
+            continue;
+        };
+        let Some(definition) = base
+            .find_attribute(&key)
+            .or_else(|| base.find_template(&key))
+        else {
+            _ = classified.insert(key, attribute);
+            continue;
+        };
+        if let AttributeType::PrimitiveOrArray(observed) = r#type {
+            if !is_compatible_type(observed, definition.r#type()) {
+                _ = conflicts.insert(InferConflict::new(
+                    ConflictKind::Attribute,
+                    &key,
+                    "type",
+                    observed,
+                    definition.r#type(),
+                ));
+            }
+        }
+        let name = definition.name().to_owned();
+        _ = classified.insert(name.clone(), attribute_spec_ref(name));
+    }
+    classified
+}
+
+/// Returns true if some attributes are not defined in the base registry.
+fn has_unknown_attributes(attributes: &HashMap<String, AttributeSpec>) -> bool {
+    attributes
+        .values()
+        .any(|attribute| matches!(attribute, AttributeSpec::Id { .. }))
+}
+
+/// Moves the attributes not defined in the base registry into `into`.
+fn take_unknown_attributes(
+    attributes: HashMap<String, AttributeSpec>,
+    into: &mut HashMap<String, AttributeSpec>,
+) {
+    for (key, attribute) in attributes {
+        if matches!(attribute, AttributeSpec::Id { .. }) {
+            _ = into.entry(key).or_insert(attribute);
+        }
+    }
+}
+
+/// Returns true if an observed attribute type is compatible with the type of its definition.
+/// Enum values can be either strings or ints.
+fn is_compatible_type(observed: &PrimitiveOrArrayTypeSpec, expected: &AttributeType) -> bool {
+    match expected {
+        AttributeType::PrimitiveOrArray(expected) => observed.is_compatible(expected),
+        AttributeType::Template(expected) => {
+            let expected = match expected {
+                TemplateTypeSpec::Boolean => PrimitiveOrArrayTypeSpec::Boolean,
+                TemplateTypeSpec::Int => PrimitiveOrArrayTypeSpec::Int,
+                TemplateTypeSpec::Double => PrimitiveOrArrayTypeSpec::Double,
+                TemplateTypeSpec::String => PrimitiveOrArrayTypeSpec::String,
+                TemplateTypeSpec::Any => PrimitiveOrArrayTypeSpec::Any,
+                TemplateTypeSpec::Strings => PrimitiveOrArrayTypeSpec::Strings,
+                TemplateTypeSpec::Ints => PrimitiveOrArrayTypeSpec::Ints,
+                TemplateTypeSpec::Doubles => PrimitiveOrArrayTypeSpec::Doubles,
+                TemplateTypeSpec::Booleans => PrimitiveOrArrayTypeSpec::Booleans,
+            };
+            observed.is_compatible(&expected)
+        }
+        AttributeType::Enum { .. } => matches!(
+            observed,
+            PrimitiveOrArrayTypeSpec::String | PrimitiveOrArrayTypeSpec::Int
+        ),
+    }
+}
+
+/// Create a reference to an attribute defined in the base registry.
+fn attribute_spec_ref(name: String) -> AttributeSpec {
+    AttributeSpec::Ref {
+        r#ref: name,
+        brief: None,
+        examples: None,
+        tag: None,
+        requirement_level: None,
+        sampling_relevant: None,
+        note: None,
+        stability: None,
+        deprecated: None,
+        prefix: false,
+        annotations: None,
+        role: None,
+    }
 }
 
 /// Create a new AttributeSpec from a SampleAttribute.
@@ -500,6 +1355,207 @@ fn add_to_existing_examples(examples: Examples, value: &Value) -> Examples {
     }
 }
 
+/// Returns the type fitting the values of two observed types: ints and doubles widen to
+/// doubles, scalars and arrays widen to arrays, and other mixes fall back to strings.
+fn widen_type(
+    current: &PrimitiveOrArrayTypeSpec,
+    observed: &PrimitiveOrArrayTypeSpec,
+) -> PrimitiveOrArrayTypeSpec {
+    use PrimitiveOrArrayTypeSpec::{
+        Any, Boolean, Booleans, Double, Doubles, Int, Ints, String, Strings,
+    };
+
+    /// Splits a type into its scalar type and whether it is an array.
+    fn split(r#type: &PrimitiveOrArrayTypeSpec) -> (PrimitiveOrArrayTypeSpec, bool) {
+        match r#type {
+            Booleans => (Boolean, true),
+            Ints => (Int, true),
+            Doubles => (Double, true),
+            Strings => (String, true),
+            scalar => (scalar.clone(), false),
+        }
+    }
+
+    if current == observed {
+        return current.clone();
+    }
+    let ((current_scalar, current_array), (observed_scalar, observed_array)) =
+        (split(current), split(observed));
+    let scalar = match (current_scalar, observed_scalar) {
+        (Any, _) | (_, Any) => return Any,
+        (a, b) if a == b => a,
+        (Int, Double) | (Double, Int) => Double,
+        _ => String,
+    };
+    match (scalar, current_array || observed_array) {
+        (Boolean, true) => Booleans,
+        (Int, true) => Ints,
+        (Double, true) => Doubles,
+        (String, true) => Strings,
+        (scalar, _) => scalar,
+    }
+}
+
+/// Returns the enum members of a string attribute with few distinct values across enough
+/// samples.
+fn enum_members(
+    observed: &ObservedValues,
+    thresholds: &InferThresholds,
+) -> Option<Vec<EnumEntriesSpec>> {
+    if observed.r#type != Some(PrimitiveOrArrayTypeSpec::String)
+        || observed.high_cardinality
+        || observed.values.is_empty()
+        || observed.samples < thresholds.enum_min_samples
+    {
+        return None;
+    }
+    let mut ids = HashSet::new();
+    let mut members = Vec::new();
+    for value in &observed.values {
+        let id = sanitize_id(value).replace('.', "_");
+        // Values without a distinct member id are not inferred as an enum.
+        if id.is_empty() || !ids.insert(id.clone()) {
+            return None;
+        }
+        members.push(EnumEntriesSpec {
+            id,
+            value: ValueSpec::String(value.clone()),
+            brief: None,
+            note: None,
+            stability: Some(Stability::Development),
+            deprecated: None,
+            annotations: None,
+        });
+    }
+    Some(members)
+}
+
+/// Returns true if the last segment of an attribute key looks like data (e.g. an HTTP header
+/// name or an index) rather than a snake_case name.
+fn is_variable_suffix(suffix: &str) -> bool {
+    suffix.is_empty()
+        || suffix.starts_with(|c: char| c.is_ascii_digit())
+        || suffix
+            .chars()
+            .any(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+}
+
+/// Returns the template type of a template attribute whose values have the given type.
+fn template_type_spec(r#type: &PrimitiveOrArrayTypeSpec) -> TemplateTypeSpec {
+    match r#type {
+        PrimitiveOrArrayTypeSpec::Boolean => TemplateTypeSpec::Boolean,
+        PrimitiveOrArrayTypeSpec::Int => TemplateTypeSpec::Int,
+        PrimitiveOrArrayTypeSpec::Double => TemplateTypeSpec::Double,
+        PrimitiveOrArrayTypeSpec::String => TemplateTypeSpec::String,
+        PrimitiveOrArrayTypeSpec::Any => TemplateTypeSpec::Any,
+        PrimitiveOrArrayTypeSpec::Booleans => TemplateTypeSpec::Booleans,
+        PrimitiveOrArrayTypeSpec::Ints => TemplateTypeSpec::Ints,
+        PrimitiveOrArrayTypeSpec::Doubles => TemplateTypeSpec::Doubles,
+        PrimitiveOrArrayTypeSpec::Strings => TemplateTypeSpec::Strings,
+    }
+}
+
+/// Converts the examples of an attribute to its generalized type. Int examples become double
+/// examples when the type is widened, examples not matching the type are dropped.
+fn convert_examples(examples: Option<Examples>, r#type: &AttributeType) -> Option<Examples> {
+    use weaver_common::ordered_float::OrderedF64;
+
+    #[allow(clippy::cast_precision_loss)]
+    let to_double = |value: i64| OrderedF64(value as f64);
+    let scalar = match r#type {
+        AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Any)
+        | AttributeType::Template(TemplateTypeSpec::Any) => return examples,
+        AttributeType::PrimitiveOrArray(r#type) => r#type.clone(),
+        AttributeType::Template(r#type) => match r#type {
+            TemplateTypeSpec::Boolean => PrimitiveOrArrayTypeSpec::Boolean,
+            TemplateTypeSpec::Int => PrimitiveOrArrayTypeSpec::Int,
+            TemplateTypeSpec::Double => PrimitiveOrArrayTypeSpec::Double,
+            TemplateTypeSpec::String => PrimitiveOrArrayTypeSpec::String,
+            // Array values are not collected as examples.
+            _ => return None,
+        },
+        AttributeType::Enum { .. } => PrimitiveOrArrayTypeSpec::String,
+    };
+    match (examples?, scalar) {
+        (Examples::Int(value), PrimitiveOrArrayTypeSpec::Double) => {
+            Some(Examples::Double(to_double(value)))
+        }
+        (Examples::Ints(values), PrimitiveOrArrayTypeSpec::Double) => Some(Examples::Doubles(
+            values.into_iter().map(to_double).collect(),
+        )),
+        (
+            examples @ (Examples::Bool(_) | Examples::Bools(_)),
+            PrimitiveOrArrayTypeSpec::Boolean,
+        )
+        | (examples @ (Examples::Int(_) | Examples::Ints(_)), PrimitiveOrArrayTypeSpec::Int)
+        | (
+            examples @ (Examples::Double(_) | Examples::Doubles(_)),
+            PrimitiveOrArrayTypeSpec::Double,
+        )
+        | (
+            examples @ (Examples::String(_) | Examples::Strings(_)),
+            PrimitiveOrArrayTypeSpec::String,
+        ) => Some(examples),
+        _ => None,
+    }
+}
+
+/// Returns the UCUM unit of a metric: the common spellings of the observed unit are
+/// normalized (e.g. `seconds` to `s`, `bytes/sec` to `By/s`, `requests` to `{requests}`), and
+/// a missing unit is guessed from the default histogram bucket boundaries.
+fn infer_unit(unit: &str, bucket_boundaries: &[f64]) -> String {
+    let same_boundaries = |expected: &[f64]| {
+        bucket_boundaries.len() == expected.len()
+            && bucket_boundaries
+                .iter()
+                .zip(expected)
+                .all(|(a, b)| (a - b).abs() <= f64::EPSILON * b.abs().max(1.0))
+    };
+    if unit.is_empty() {
+        return if same_boundaries(&SECONDS_BUCKET_BOUNDARIES) {
+            "s".to_owned()
+        } else if same_boundaries(&MILLISECONDS_BUCKET_BOUNDARIES) {
+            "ms".to_owned()
+        } else {
+            String::new()
+        };
+    }
+    unit.split('/')
+        .map(|atom| {
+            if KNOWN_UCUM_UNITS.contains(&atom) {
+                return atom.to_owned();
+            }
+            match atom.to_lowercase().as_str() {
+                "sec" | "secs" | "second" | "seconds" => "s".to_owned(),
+                "msec" | "msecs" | "millisecond" | "milliseconds" => "ms".to_owned(),
+                "usec" | "usecs" | "μs" | "µs" | "microsecond" | "microseconds" => {
+                    "us".to_owned()
+                }
+                "nsec" | "nsecs" | "nanosecond" | "nanoseconds" => "ns".to_owned(),
+                "minute" | "minutes" => "min".to_owned(),
+                "hour" | "hours" => "h".to_owned(),
+                "day" | "days" => "d".to_owned(),
+                "b" | "byte" | "bytes" => "By".to_owned(),
+                "kb" | "kilobyte" | "kilobytes" => "kBy".to_owned(),
+                "mb" | "megabyte" | "megabytes" => "MBy".to_owned(),
+                "gb" | "gigabyte" | "gigabytes" => "GBy".to_owned(),
+                "kib" | "kiby" | "kibibyte" | "kibibytes" => "KiBy".to_owned(),
+                "mib" | "miby" | "mebibyte" | "mebibytes" => "MiBy".to_owned(),
+                "gib" | "giby" | "gibibyte" | "gibibytes" => "GiBy".to_owned(),
+                "bits" => "bit".to_owned(),
+                "mmhg" => "mmHg".to_owned(),
+                "percent" => "%".to_owned(),
+                // Annotations describing a count, e.g. `{request}`.
+                word if word.len() > 3 && word.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    format!("{{{atom}}}")
+                }
+                _ => atom.to_owned(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 fn sanitize_id(name: &str) -> String {
     use convert_case::{Case, Casing};
     // Split by dots first (namespace separator), then apply snake_case to each segment
@@ -573,6 +1629,7 @@ fn process_otlp_request(request: OtlpRequest, accumulator: &mut AccumulatedSampl
                             span_links: Vec::new(),
                             live_check_result: None,
                             resource: None,
+                            span_type: None,
                         };
                         for attribute in span.attributes {
                             sample_span
@@ -609,36 +1666,251 @@ fn process_otlp_request(request: OtlpRequest, accumulator: &mut AccumulatedSampl
     }
 }
 
+/// Serializes a value into a YAML file.
+fn write_yaml<T: Serialize>(output_path: &Path, value: &T) -> Result<(), DiagnosticMessages> {
+    let yaml = serde_yaml::to_string(value).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Failed to serialize YAML: {}", e),
+        })
+    })?;
+
+    fs::write(output_path, yaml).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Failed to write file: {}", e),
+        })
+    })?;
+
+    info!("Generated registry file: {:?}", output_path);
+    Ok(())
+}
+
+/// Serializes a value into a JSON file.
+fn write_json<T: Serialize>(output_path: &Path, value: &T) -> Result<(), DiagnosticMessages> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Failed to serialize JSON: {}", e),
+        })
+    })?;
+
+    fs::write(output_path, json).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Failed to write file: {}", e),
+        })
+    })?;
+
+    info!("Generated report file: {:?}", output_path);
+    Ok(())
+}
+
+/// Writes the `definition/2` files of the inferred registry, one per namespace, into the
+/// output directory.
+fn write_registry_v2(
+    accumulator: &AccumulatedSamples,
+    output: &Path,
+) -> Result<(), DiagnosticMessages> {
+    for (namespace, registry) in accumulator.to_semconv_spec_v2() {
+        write_yaml(&output.join(format!("{namespace}.yaml")), &registry)?;
+    }
+    Ok(())
+}
+
+/// Writes the manifest of the inferred registry into the output directory.
+fn write_manifest(
+    output: &Path,
+    schema_url: &str,
+    dependency: Option<Dependency>,
+) -> Result<(), DiagnosticMessages> {
+    let schema_url = SchemaUrl::try_from(schema_url.to_owned()).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Invalid schema URL: {}", e),
+        })
+    })?;
+    let mut manifest = DefinitionRegistryManifest::from_schema_url(schema_url);
+    manifest.description = Some("Registry inferred from OTLP telemetry.".to_owned());
+    manifest.dependencies.extend(dependency);
+    write_yaml(&output.join(REGISTRY_MANIFEST), &manifest)
+}
+
+/// The existing registry the observed telemetry is compared to.
+struct BaseRegistry {
+    live_checker: LiveChecker,
+    dependency: Dependency,
+}
+
+/// Resolves the base registry and builds the lookups used to classify the observed
+/// telemetry.
+fn load_base_registry(
+    registry: &VirtualDirectoryPath,
+    v2: bool,
+    cache_settings: &CacheSettings,
+) -> Result<BaseRegistry, DiagnosticMessages> {
+    info!("Resolving registry `{}`", registry);
+    // Local paths are made absolute so that the dependency resolves from the output directory.
+    let registry = match registry {
+        VirtualDirectoryPath::LocalFolder { path } => VirtualDirectoryPath::LocalFolder {
+            path: fs::canonicalize(path)
+                .map_err(|e| {
+                    DiagnosticMessages::from(super::otlp::Error::OtlpError {
+                        error: format!("Invalid registry path `{}`: {}", path, e),
+                    })
+                })?
+                .display()
+                .to_string(),
+        },
+        registry => registry.clone(),
+    };
+    let registry_args = RegistryArgs {
+        registry,
+        follow_symlinks: false,
+        include_unreferenced: true,
+        v2,
+    };
+    let policy_args = PolicyArgs {
+        policies: vec![],
+        skip_policies: true,
+        display_policy_coverage: false,
+    };
+
+    let mut diag_msgs = DiagnosticMessages::empty();
+    let weaver = WeaverEngine::new(&registry_args, &policy_args, cache_settings);
+    let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
+    let schema_url =
+        SchemaUrl::try_from(resolved.resolved_schema().schema_url.clone()).map_err(|e| {
+            DiagnosticMessages::from(super::otlp::Error::OtlpError {
+                error: format!(
+                    "Invalid schema URL of registry `{}`: {}",
+                    registry_args.registry, e
+                ),
+            })
+        })?;
+    let versioned_registry = if v2 {
+        VersionedRegistry::V2(Box::new(resolved.try_into_v2()?.into_template_schema()))
+    } else {
+        VersionedRegistry::V1(Box::new(resolved.into_template_schema()))
+    };
+    Ok(BaseRegistry {
+        live_checker: LiveChecker::new(Arc::new(versioned_registry), vec![]),
+        dependency: Dependency {
+            schema_url,
+            registry_path: Some(registry_args.registry),
+        },
+    })
+}
+
+/// Generalizes the inferred registry and writes it into the output directory. With a base
+/// registry, only the delta is written, along with the conflicts with the base definitions.
+fn write_inferred_registry(
+    accumulator: &mut AccumulatedSamples,
+    args: &RegistryInferArgs,
+    base: Option<BaseRegistry>,
+) -> Result<(), DiagnosticMessages> {
+    accumulator.generalize();
+    let conflicts = base
+        .as_ref()
+        .map(|base| accumulator.retain_unknown(&base.live_checker))
+        .unwrap_or_default();
+
+    if accumulator.is_empty() && conflicts.is_empty() {
+        if base.is_some() {
+            info!("No new attributes or signals observed. No YAML file generated.");
+        } else {
+            info!("No telemetry data received. No YAML file generated.");
+        }
+        return Ok(());
+    }
+
+    // Create output directory
+    fs::create_dir_all(&args.output).map_err(|e| {
+        DiagnosticMessages::from(super::otlp::Error::OtlpError {
+            error: format!("Failed to create output directory: {}", e),
+        })
+    })?;
+
+    if !accumulator.is_empty() {
+        if args.v2 {
+            write_registry_v2(accumulator, &args.output)?;
+        } else {
+            write_yaml(
+                &args.output.join("registry.yaml"),
+                &accumulator.to_semconv_spec(),
+            )?;
+        }
+        if args.v2 || base.is_some() {
+            write_manifest(
+                &args.output,
+                &args.schema_url,
+                base.map(|base| base.dependency),
+            )?;
+        }
+    }
+
+    if !conflicts.is_empty() {
+        log::warn!(
+            "{} conflict(s) with the existing registry definitions, see `{}`",
+            conflicts.len(),
+            CONFLICTS_REPORT
+        );
+        let report = ConflictReport {
+            registry: args
+                .registry
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            conflicts: conflicts.into_iter().collect(),
+        };
+        write_json(&args.output.join(CONFLICTS_REPORT), &report)?;
+    }
+    Ok(())
+}
+
 /// Infer a semantic convention registry from OTLP telemetry.
-pub(crate) fn command(args: &RegistryInferArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryInferArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     log::warn!(
         "The `registry infer` command is experimental and not yet stable. \
         The generated schema format, command options, and output may change in future versions."
     );
 
     info!("Weaver Registry Infer");
+
+    // Resolve the base registry first to fail before listening for telemetry.
+    let base = args
+        .registry
+        .as_ref()
+        .map(|registry| load_base_registry(registry, args.v2, cache_settings))
+        .transpose()?;
+
     info!(
         "Starting OTLP gRPC server on {}:{}",
         args.grpc_address, args.grpc_port
     );
+    if let Some(http_port) = args.http_port {
+        info!(
+            "Starting OTLP/HTTP server on {}:{}",
+            args.grpc_address, http_port
+        );
+    }
 
     // Start the OTLP gRPC server and get an iterator of requests
     let (requests, _report_sender) = listen_otlp_requests(
         &args.grpc_address,
         args.grpc_port,
+        args.http_port,
         args.admin_port,
         Duration::from_secs(args.inactivity_timeout),
     )
     .map_err(DiagnosticMessages::from)?;
 
-    info!("OTLP gRPC server started. Waiting for telemetry...");
+    info!("OTLP server started. Waiting for telemetry...");
     info!(
         "To stop: press CTRL+C, send SIGHUP, or POST to http://localhost:{}/stop",
         args.admin_port
     );
 
     // Accumulate samples
-    let mut accumulator = AccumulatedSamples::new();
+    let mut accumulator = AccumulatedSamples::new(args.thresholds.clone());
 
     for request in requests {
         if !process_otlp_request(request, &mut accumulator) {
@@ -652,34 +1924,7 @@ pub(crate) fn command(args: &RegistryInferArgs) -> Result<ExitDirectives, Diagno
         resources, spans, metrics, events
     );
 
-    if accumulator.is_empty() {
-        info!("No telemetry data received. No YAML file generated.");
-    } else {
-        // Create output directory
-        fs::create_dir_all(&args.output).map_err(|e| {
-            DiagnosticMessages::from(super::otlp::Error::OtlpError {
-                error: format!("Failed to create output directory: {}", e),
-            })
-        })?;
-
-        // Generate YAML
-        let registry = accumulator.to_semconv_spec();
-        let yaml = serde_yaml::to_string(&registry).map_err(|e| {
-            DiagnosticMessages::from(super::otlp::Error::OtlpError {
-                error: format!("Failed to serialize YAML: {}", e),
-            })
-        })?;
-
-        // Write to file
-        let output_path = args.output.join("registry.yaml");
-        fs::write(&output_path, yaml).map_err(|e| {
-            DiagnosticMessages::from(super::otlp::Error::OtlpError {
-                error: format!("Failed to write file: {}", e),
-            })
-        })?;
-
-        info!("Generated registry file: {:?}", output_path);
-    }
+    write_inferred_registry(&mut accumulator, args, base)?;
 
     log_success("Registry infer completed");
 
@@ -945,14 +2190,14 @@ mod tests {
 
     #[test]
     fn test_accumulated_samples_new_is_empty() {
-        let acc = AccumulatedSamples::new();
+        let acc = AccumulatedSamples::default();
         assert!(acc.is_empty());
         assert_eq!(acc.stats(), (0, 0, 0, 0));
     }
 
     #[test]
     fn test_accumulated_samples_add_resource() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         let resource = SampleResource {
             attributes: vec![SampleAttribute {
@@ -981,7 +2226,7 @@ mod tests {
 
     #[test]
     fn test_accumulated_samples_add_span() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         let span = SampleSpan {
             name: "GET /api/users".to_owned(),
@@ -997,6 +2242,7 @@ mod tests {
             span_links: vec![],
             live_check_result: None,
             resource: None,
+            span_type: None,
         };
 
         acc.add_span(span);
@@ -1011,7 +2257,7 @@ mod tests {
 
     #[test]
     fn test_accumulated_samples_add_span_with_events() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         let span = SampleSpan {
             name: "process".to_owned(),
@@ -1031,6 +2277,7 @@ mod tests {
             span_links: vec![],
             live_check_result: None,
             resource: None,
+            span_type: None,
         };
 
         acc.add_span(span);
@@ -1044,7 +2291,7 @@ mod tests {
 
     #[test]
     fn test_accumulated_samples_add_event_ignores_empty_name() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         acc.add_event(
             String::new(),
@@ -1061,7 +2308,7 @@ mod tests {
 
     #[test]
     fn test_accumulated_samples_add_event_with_name() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         acc.add_event(
             "user.login".to_owned(),
@@ -1083,7 +2330,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_empty_accumulator() {
-        let acc = AccumulatedSamples::new();
+        let acc = AccumulatedSamples::default();
         let registry = acc.to_semconv_spec();
 
         assert!(registry.groups.is_empty());
@@ -1091,7 +2338,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_with_resources() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         accumulate_attribute(
             &mut acc.resources,
@@ -1115,7 +2362,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_with_span() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         acc.add_span(SampleSpan {
             name: "HTTP GET".to_owned(),
@@ -1131,6 +2378,7 @@ mod tests {
             span_links: vec![],
             live_check_result: None,
             resource: None,
+            span_type: None,
         });
 
         let registry = acc.to_semconv_spec();
@@ -1145,7 +2393,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_with_metric() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         let metric = SampleMetric {
             name: "http.server.duration".to_owned(),
@@ -1171,7 +2419,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_metric_empty_unit_is_empty_string() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         let metric = SampleMetric {
             name: "custom.counter".to_owned(),
@@ -1192,7 +2440,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_with_event() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         acc.add_event(
             "user.signup".to_owned(),
@@ -1215,7 +2463,7 @@ mod tests {
 
     #[test]
     fn test_to_semconv_spec_attributes_are_sorted() {
-        let mut acc = AccumulatedSamples::new();
+        let mut acc = AccumulatedSamples::default();
 
         // Add attributes in non-alphabetical order
         accumulate_attribute(
@@ -1252,4 +2500,549 @@ mod tests {
         let attr_ids: Vec<_> = group.attributes.iter().map(|a| a.id()).collect();
         assert_eq!(attr_ids, vec!["a.attr", "m.attr", "z.attr"]);
     }
+
+    // ============================================
+    // Tests for to_semconv_spec_v2()
+    // ============================================
+
+    fn string_attribute(name: &str, value: &str) -> SampleAttribute {
+        SampleAttribute {
+            name: name.to_owned(),
+            r#type: Some(PrimitiveOrArrayTypeSpec::String),
+            value: Some(json!(value)),
+            live_check_result: None,
+        }
+    }
+
+    fn v2_samples() -> AccumulatedSamples {
+        let mut acc = AccumulatedSamples::default();
+        acc.add_resource(SampleResource {
+            attributes: vec![
+                string_attribute("service.name", "checkout"),
+                string_attribute("service.version", "1.0.0"),
+                string_attribute("host.arch", "amd64"),
+            ],
+            live_check_result: None,
+        });
+        acc.add_span(SampleSpan {
+            name: "ProcessOrder".to_owned(),
+            kind: SpanKindSpec::Server,
+            status: None,
+            attributes: vec![string_attribute("http.request.method", "GET")],
+            span_events: vec![SampleSpanEvent {
+                name: "exception".to_owned(),
+                attributes: vec![string_attribute("exception.type", "IOException")],
+                live_check_result: None,
+            }],
+            span_links: vec![],
+            live_check_result: None,
+            resource: None,
+            span_type: None,
+        });
+        acc.add_metric(SampleMetric {
+            name: "http.server.request.duration".to_owned(),
+            instrument: SampleInstrument::Supported(InstrumentSpec::Histogram),
+            unit: "s".to_owned(),
+            data_points: None,
+            live_check_result: None,
+            resource: None,
+        });
+        acc.add_event(
+            "user.login".to_owned(),
+            vec![string_attribute("user.id", "user-123")],
+        );
+        acc
+    }
+
+    #[test]
+    fn test_to_semconv_spec_v2_splits_by_namespace() {
+        let files = v2_samples().to_semconv_spec_v2();
+        let namespaces: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(
+            namespaces,
+            vec![
+                "exception",
+                "host",
+                "http",
+                "process_order",
+                "service",
+                "user"
+            ]
+        );
+
+        let service = &files["service"];
+        assert_eq!(service.entities.len(), 1);
+        let entity = &service.entities[0];
+        assert_eq!(&*entity.r#type, "service");
+        assert_eq!(entity.identity[0].r#ref, "service.name");
+        assert_eq!(entity.description[0].r#ref, "service.version");
+        let keys: Vec<&str> = service.attributes.iter().map(|a| a.key.as_str()).collect();
+        assert_eq!(keys, vec!["service.name", "service.version"]);
+
+        // An entity without name or id is identified by all its attributes.
+        assert_eq!(files["host"].entities[0].identity[0].r#ref, "host.arch");
+
+        let http = &files["http"];
+        assert_eq!(http.metrics[0].unit, "s");
+        assert_eq!(http.metrics[0].instrument, InstrumentSpec::Histogram);
+        assert_eq!(http.attributes[0].key, "http.request.method");
+
+        let span = &files["process_order"].spans[0];
+        assert_eq!(span.kind, SpanKindSpec::Server);
+        assert_eq!(span.name.note, "ProcessOrder");
+        assert_eq!(files["exception"].events.len(), 1);
+        assert_eq!(files["user"].events[0].attributes.len(), 1);
+    }
+
+    fn infer_args(output: &Path, v2: bool, registry: Option<&Path>) -> RegistryInferArgs {
+        RegistryInferArgs {
+            diagnostic: Default::default(),
+            output: output.to_path_buf(),
+            grpc_address: "0.0.0.0".to_owned(),
+            grpc_port: 4317,
+            http_port: None,
+            admin_port: 8080,
+            inactivity_timeout: 60,
+            v2,
+            schema_url: "https://example.com/schemas/inferred/0.1.0".to_owned(),
+            registry: registry.map(|path| VirtualDirectoryPath::LocalFolder {
+                path: path.display().to_string(),
+            }),
+            thresholds: InferThresholds::default(),
+        }
+    }
+
+    /// Runs `weaver registry check` on a registry and returns the exit code.
+    fn check_registry(path: &Path, v2: bool) -> i32 {
+        use crate::cli::{Cli, Commands};
+        use crate::registry::check::RegistryCheckArgs;
+        use crate::registry::{RegistryCommand, RegistrySubCommand};
+        use crate::run_command;
+
+        let cli = Cli {
+            debug: 0,
+            quiet: false,
+            future: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Check(RegistryCheckArgs {
+                    registry: RegistryArgs {
+                        registry: VirtualDirectoryPath::LocalFolder {
+                            path: path.display().to_string(),
+                        },
+                        follow_symlinks: false,
+                        include_unreferenced: false,
+                        v2,
+                    },
+                    baseline_registry: None,
+                    policy: PolicyArgs {
+                        policies: vec![],
+                        skip_policies: true,
+                        display_policy_coverage: false,
+                    },
+                    diagnostic: Default::default(),
+                    watch: Default::default(),
+                }),
+            })),
+        };
+        run_command(&cli).exit_code
+    }
+
+    #[test]
+    fn test_write_registry_v2_passes_registry_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("inferred");
+        let args = infer_args(&output, true, None);
+        write_inferred_registry(&mut v2_samples(), &args, None).unwrap();
+        let content = fs::read_to_string(output.join("http.yaml")).unwrap();
+        assert!(content.starts_with("file_format: definition/2"));
+        assert!(output.join(REGISTRY_MANIFEST).exists());
+
+        assert_eq!(check_registry(&output, true), 0);
+    }
+
+    const BASE_REGISTRY: &str = r#"
+groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    attributes:
+      - id: http.request.method
+        type: string
+        stability: stable
+        brief: HTTP request method.
+        examples: [GET]
+      - id: http.response.status_code
+        type: int
+        stability: stable
+        brief: HTTP response status code.
+        examples: [200]
+      - id: http.request.header
+        type: template[string]
+        stability: stable
+        brief: HTTP request headers.
+        examples: [application/json]
+  - id: metric.http.server.request.duration
+    type: metric
+    metric_name: http.server.request.duration
+    brief: Duration of HTTP server requests.
+    instrument: histogram
+    unit: s
+    stability: stable
+    attributes:
+      - ref: http.request.method
+"#;
+
+    /// Telemetry partially covered by the base registry.
+    fn delta_samples() -> AccumulatedSamples {
+        let mut acc = AccumulatedSamples::default();
+        acc.add_resource(SampleResource {
+            attributes: vec![
+                string_attribute("service.name", "checkout"),
+                string_attribute("http.request.method", "GET"),
+            ],
+            live_check_result: None,
+        });
+        acc.add_span(SampleSpan {
+            name: "ProcessOrder".to_owned(),
+            kind: SpanKindSpec::Server,
+            status: None,
+            attributes: vec![
+                string_attribute("order.id", "42"),
+                string_attribute("http.response.status_code", "200"),
+                string_attribute("http.request.header.content_type", "text/plain"),
+            ],
+            span_events: vec![],
+            span_links: vec![],
+            live_check_result: None,
+            resource: None,
+            span_type: None,
+        });
+        acc.add_metric(SampleMetric {
+            name: "http.server.request.duration".to_owned(),
+            instrument: SampleInstrument::Supported(InstrumentSpec::Histogram),
+            unit: "ms".to_owned(),
+            data_points: None,
+            live_check_result: None,
+            resource: None,
+        });
+        let metric = acc.metrics.get_mut("http.server.request.duration").unwrap();
+        accumulate_attribute(
+            &mut metric.attributes,
+            string_attribute("http.request.method", "GET"),
+        );
+        accumulate_attribute(&mut metric.attributes, string_attribute("http.route", "/"));
+        acc
+    }
+
+    /// Creates the base registry in a `base` sub-folder, temporary folders are hidden folders
+    /// ignored by the loader.
+    fn base_registry(temp_path: &Path) -> BaseRegistry {
+        let path = temp_path.join("base");
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("http.yaml"), BASE_REGISTRY).unwrap();
+        let registry = VirtualDirectoryPath::LocalFolder {
+            path: path.display().to_string(),
+        };
+        load_base_registry(&registry, false, &CacheSettings::default()).unwrap()
+    }
+
+    #[test]
+    fn test_retain_unknown_keeps_only_the_delta() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let base = base_registry(base_dir.path());
+        let mut acc = delta_samples();
+        let conflicts: Vec<InferConflict> =
+            acc.retain_unknown(&base.live_checker).into_iter().collect();
+
+        // Known resource attributes and metrics are left out.
+        let resources: Vec<&String> = acc.resources.keys().collect();
+        assert_eq!(resources, vec!["service.name"]);
+        assert!(acc.metrics.is_empty());
+
+        // New attributes of known signals are kept as standalone attributes.
+        let attributes: Vec<&String> = acc.attributes.keys().collect();
+        assert_eq!(attributes, vec!["http.route"]);
+        // The known signals they were observed on are imported from the base registry.
+        let refined: Vec<&String> = acc.refined.metrics.iter().collect();
+        assert_eq!(refined, vec!["http.server.request.duration"]);
+        assert!(acc.refined.spans.is_empty());
+        assert!(acc.refined.events.is_empty());
+
+        // Known attributes of new signals are references, templates included.
+        let span = &acc.spans["ProcessOrder"];
+        assert!(matches!(
+            span.attributes["order.id"],
+            AttributeSpec::Id { .. }
+        ));
+        assert!(matches!(
+            span.attributes["http.response.status_code"],
+            AttributeSpec::Ref { .. }
+        ));
+        assert!(matches!(
+            span.attributes["http.request.header"],
+            AttributeSpec::Ref { .. }
+        ));
+
+        assert_eq!(
+            conflicts,
+            vec![
+                InferConflict {
+                    kind: ConflictKind::Attribute,
+                    name: "http.response.status_code".to_owned(),
+                    property: "type",
+                    observed: "string".to_owned(),
+                    expected: "int".to_owned(),
+                },
+                InferConflict {
+                    kind: ConflictKind::Metric,
+                    name: "http.server.request.duration".to_owned(),
+                    property: "unit",
+                    observed: "ms".to_owned(),
+                    expected: "s".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_inferred_registry_delta_passes_registry_check() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let base = base_registry(base_dir.path());
+        let output = base_dir.path().join("inferred");
+        let args = infer_args(&output, false, Some(&base_dir.path().join("base")));
+        write_inferred_registry(&mut delta_samples(), &args, Some(base)).unwrap();
+
+        let manifest = fs::read_to_string(output.join(REGISTRY_MANIFEST)).unwrap();
+        assert!(manifest.contains("dependencies:"));
+        let registry = fs::read_to_string(output.join("registry.yaml")).unwrap();
+        assert!(registry.contains("ref: http.response.status_code"));
+        assert!(registry.contains("imports:\n  metrics:\n  - http.server.request.duration"));
+        let conflicts = fs::read_to_string(output.join(CONFLICTS_REPORT)).unwrap();
+        assert!(conflicts.contains("\"name\": \"http.response.status_code\""));
+
+        assert_eq!(check_registry(&output, false), 0);
+    }
+
+    #[test]
+    fn test_write_inferred_registry_delta_v2_imports_refined_signals() {
+        let base_dir = tempfile::tempdir().unwrap();
+        // Writes the base registry files, then loads them again as a v2 registry.
+        let _ = base_registry(base_dir.path());
+        let registry = VirtualDirectoryPath::LocalFolder {
+            path: base_dir.path().join("base").display().to_string(),
+        };
+        let base = load_base_registry(&registry, true, &CacheSettings::default()).unwrap();
+        let output = base_dir.path().join("inferred");
+        let args = infer_args(&output, true, Some(&base_dir.path().join("base")));
+        write_inferred_registry(&mut delta_samples(), &args, Some(base)).unwrap();
+
+        let imports = fs::read_to_string(output.join(format!("{IMPORTS_FILE}.yaml"))).unwrap();
+        assert!(imports.contains("imports:\n  metrics:\n  - http.server.request.duration"));
+        assert_eq!(check_registry(&output, true), 0);
+    }
+
+    // ============================================
+    // Tests for the generalization of the samples
+    // ============================================
+
+    fn typed_attribute(
+        name: &str,
+        value: Value,
+        r#type: PrimitiveOrArrayTypeSpec,
+    ) -> SampleAttribute {
+        SampleAttribute {
+            name: name.to_owned(),
+            r#type: Some(r#type),
+            value: Some(value),
+            live_check_result: None,
+        }
+    }
+
+    fn span_with(attributes: Vec<SampleAttribute>) -> SampleSpan {
+        SampleSpan {
+            name: "GET".to_owned(),
+            kind: SpanKindSpec::Server,
+            status: None,
+            attributes,
+            span_events: vec![],
+            span_links: vec![],
+            live_check_result: None,
+            resource: None,
+            span_type: None,
+        }
+    }
+
+    /// Telemetry with low cardinality methods, numeric values of mixed types and HTTP headers.
+    fn generalization_samples() -> AccumulatedSamples {
+        let mut acc = AccumulatedSamples::default();
+        for i in 0..DEFAULT_ENUM_MIN_SAMPLES {
+            let method = if i % 2 == 0 { "GET" } else { "POST" };
+            acc.add_span(span_with(vec![
+                string_attribute("http.request.method", method),
+                string_attribute("url.path", &format!("/orders/{i}")),
+            ]));
+        }
+        acc.add_span(span_with(vec![
+            typed_attribute("app.ratio", json!(1), PrimitiveOrArrayTypeSpec::Int),
+            typed_attribute("app.tags", json!("a"), PrimitiveOrArrayTypeSpec::String),
+            string_attribute("http.request.header.content-type", "application/json"),
+            string_attribute("http.request.header.accept", "*/*"),
+        ]));
+        acc.add_span(span_with(vec![
+            typed_attribute("app.ratio", json!(0.5), PrimitiveOrArrayTypeSpec::Double),
+            typed_attribute(
+                "app.tags",
+                json!(["a", "b"]),
+                PrimitiveOrArrayTypeSpec::Strings,
+            ),
+            string_attribute("http.request.header.user-agent", "curl"),
+        ]));
+        acc
+    }
+
+    fn attribute_type(acc: &AccumulatedSamples, key: &str) -> AttributeType {
+        match &acc.spans["GET"].attributes[key] {
+            AttributeSpec::Id { r#type, .. } => r#type.clone(),
+            AttributeSpec::Ref { .. } => panic!("Expected AttributeSpec::Id"),
+        }
+    }
+
+    #[test]
+    fn test_widen_type() {
+        use PrimitiveOrArrayTypeSpec::{Any, Boolean, Double, Doubles, Int, Ints, String, Strings};
+
+        assert_eq!(widen_type(&Int, &Int), Int);
+        assert_eq!(widen_type(&Int, &Double), Double);
+        assert_eq!(widen_type(&Int, &Ints), Ints);
+        assert_eq!(widen_type(&Ints, &Double), Doubles);
+        assert_eq!(widen_type(&String, &Strings), Strings);
+        assert_eq!(widen_type(&Boolean, &Int), String);
+        assert_eq!(widen_type(&Ints, &String), Strings);
+        assert_eq!(widen_type(&Any, &Int), Any);
+    }
+
+    #[test]
+    fn test_generalize_infers_enums() {
+        let mut acc = generalization_samples();
+        acc.generalize();
+
+        let AttributeType::Enum { members } = attribute_type(&acc, "http.request.method") else {
+            panic!("Expected an enum");
+        };
+        let ids: Vec<&str> = members.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["get", "post"]);
+        assert_eq!(members[0].value, ValueSpec::String("GET".to_owned()));
+        assert_eq!(members[0].stability, Some(Stability::Development));
+
+        // Too many distinct values.
+        assert_eq!(
+            attribute_type(&acc, "url.path"),
+            AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String)
+        );
+
+        // Not enough samples.
+        let mut acc = generalization_samples();
+        acc.observations.thresholds.enum_min_samples = DEFAULT_ENUM_MIN_SAMPLES + 1;
+        acc.generalize();
+        assert_eq!(
+            attribute_type(&acc, "http.request.method"),
+            AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String)
+        );
+    }
+
+    #[test]
+    fn test_generalize_widens_types() {
+        let mut acc = generalization_samples();
+        acc.generalize();
+
+        assert_eq!(
+            attribute_type(&acc, "app.ratio"),
+            AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Double)
+        );
+        let AttributeSpec::Id { examples, .. } = &acc.spans["GET"].attributes["app.ratio"] else {
+            panic!("Expected AttributeSpec::Id");
+        };
+        assert!(matches!(examples, Some(Examples::Double(_))));
+        assert_eq!(
+            attribute_type(&acc, "app.tags"),
+            AttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Strings)
+        );
+    }
+
+    #[test]
+    fn test_generalize_detects_templates() {
+        let mut acc = generalization_samples();
+        acc.generalize();
+
+        let attributes = &acc.spans["GET"].attributes;
+        assert!(!attributes.contains_key("http.request.header.accept"));
+        assert_eq!(
+            attribute_type(&acc, "http.request.header"),
+            AttributeType::Template(TemplateTypeSpec::String)
+        );
+        // Regular names sharing a prefix are not a template.
+        assert!(attributes.contains_key("app.ratio"));
+
+        let mut acc = generalization_samples();
+        acc.observations.thresholds.template_min_keys = 4;
+        acc.generalize();
+        assert!(acc.spans["GET"]
+            .attributes
+            .contains_key("http.request.header.accept"));
+    }
+
+    #[test]
+    fn test_generalize_keeps_fixed_siblings() {
+        let mut acc = AccumulatedSamples::default();
+        acc.add_span(span_with(vec![
+            string_attribute("http.request.method", "GET"),
+            string_attribute("http.request.method_original", "get"),
+            typed_attribute(
+                "http.request.resend_count",
+                json!(1),
+                PrimitiveOrArrayTypeSpec::Int,
+            ),
+            string_attribute("http.request.X-Request-Id", "42"),
+        ]));
+        acc.generalize();
+
+        let attributes = &acc.spans["GET"].attributes;
+        assert!(attributes.contains_key("http.request.method"));
+        assert!(attributes.contains_key("http.request.resend_count"));
+        assert!(attributes.contains_key("http.request.X-Request-Id"));
+        assert!(!attributes.contains_key("http.request"));
+    }
+
+    #[test]
+    fn test_infer_unit() {
+        assert_eq!(infer_unit("s", &[]), "s");
+        assert_eq!(infer_unit("seconds", &[]), "s");
+        assert_eq!(infer_unit("Milliseconds", &[]), "ms");
+        assert_eq!(infer_unit("bytes/sec", &[]), "By/s");
+        assert_eq!(infer_unit("requests", &[]), "{requests}");
+        assert_eq!(infer_unit("{request}", &[]), "{request}");
+        assert_eq!(infer_unit("1", &[]), "1");
+        assert_eq!(infer_unit("KiBy", &[]), "KiBy");
+        assert_eq!(infer_unit("MiBy/s", &[]), "MiBy/s");
+        assert_eq!(infer_unit("mmHg", &[]), "mmHg");
+        assert_eq!(infer_unit("bits", &[]), "bit");
+        assert_eq!(infer_unit("kibibytes/s", &[]), "KiBy/s");
+        assert_eq!(infer_unit("", &SECONDS_BUCKET_BOUNDARIES), "s");
+        assert_eq!(infer_unit("", &MILLISECONDS_BUCKET_BOUNDARIES), "ms");
+        assert_eq!(infer_unit("", &[1.0, 2.0]), "");
+    }
+
+    #[test]
+    fn test_generalized_registry_passes_registry_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let output = temp_dir.path().join("inferred");
+        let args = infer_args(&output, false, None);
+        write_inferred_registry(&mut generalization_samples(), &args, None).unwrap();
+        let registry = fs::read_to_string(output.join("registry.yaml")).unwrap();
+        assert!(registry.contains("type: template[string]"));
+        assert!(registry.contains("members:"));
+
+        assert_eq!(check_registry(&output, false), 0);
+    }
 }
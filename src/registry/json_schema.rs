@@ -117,6 +117,7 @@ mod tests {
                 debug: 0,
                 quiet: false,
                 future: false,
+                cache: Default::default(),
                 command: Some(Commands::Registry(RegistryCommand {
                     command: RegistrySubCommand::JsonSchema(RegistryJsonSchemaArgs {
                         json_schema: json_schema_type.clone(),
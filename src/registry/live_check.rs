@@ -7,16 +7,20 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use clap::Args;
+use clap::{Args, ValueEnum};
 use include_dir::{include_dir, Dir};
 
 use log::info;
+use weaver_checker::FindingLevel;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
-use weaver_common::log_success;
+use weaver_common::{log_error, log_success};
 use weaver_forge::{OutputProcessor, OutputTarget};
 use weaver_live_check::advice::{
-    Advisor, DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, StabilityAdvisor, TypeAdvisor,
+    Advisor, DeprecatedAdvisor, EnumAdvisor, RegoAdvisor, RenameHistory, RequiredAttributeAdvisor,
+    StabilityAdvisor, TypeAdvisor, UpgradeAdvisor,
 };
+use weaver_live_check::baseline::Baseline;
 use weaver_live_check::json_file_ingester::JsonFileIngester;
 use weaver_live_check::json_stdin_ingester::JsonStdinIngester;
 use weaver_live_check::live_checker::LiveChecker;
@@ -31,6 +35,7 @@ use crate::registry::{PolicyArgs, RegistryArgs};
 use crate::weaver::WeaverEngine;
 use crate::{DiagnosticArgs, ExitDirectives};
 
+use super::otlp::otlp_file_ingester::{OtlpEncoding, OtlpFileIngester};
 use super::otlp::otlp_ingester::OtlpIngester;
 use super::otlp::AdminReportSender;
 
@@ -61,17 +66,66 @@ impl From<String> for InputSource {
 enum InputFormat {
     Text,
     Json,
+    OtlpProto,
+    OtlpJson,
 }
 
 impl From<String> for InputFormat {
     fn from(s: String) -> Self {
         match s.to_lowercase().as_str() {
             "json" | "js" => InputFormat::Json,
+            "otlp-proto" => InputFormat::OtlpProto,
+            "otlp-json" => InputFormat::OtlpJson,
             _ => InputFormat::Text,
         }
     }
 }
 
+/// Formats rendering a complete report, which cannot be streamed sample by sample.
+const REPORT_ONLY_FORMATS: [&str; 2] = ["junit", "sarif"];
+
+/// The level of the findings making the `registry live-check` command fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    /// Never fail on findings.
+    None,
+    /// Fail if at least one finding is found.
+    Information,
+    /// Fail if at least one improvement or violation is found.
+    Improvement,
+    /// Fail if at least one violation is found.
+    Violation,
+}
+
+impl FailOn {
+    fn level(self) -> Option<FindingLevel> {
+        match self {
+            FailOn::None => None,
+            FailOn::Information => Some(FindingLevel::Information),
+            FailOn::Improvement => Some(FindingLevel::Improvement),
+            FailOn::Violation => Some(FindingLevel::Violation),
+        }
+    }
+}
+
+/// The level of the new findings making the `registry live-check` command fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOnNew {
+    /// Fail if at least one new violation is found.
+    Violation,
+    /// Fail if at least one new improvement or violation is found.
+    Improvement,
+}
+
+impl FailOnNew {
+    fn level(self) -> FindingLevel {
+        match self {
+            FailOnNew::Violation => FindingLevel::Violation,
+            FailOnNew::Improvement => FindingLevel::Improvement,
+        }
+    }
+}
+
 /// Parameters for the `registry live-check` sub-command
 #[derive(Debug, Args)]
 pub struct RegistryLiveCheckArgs {
@@ -91,13 +145,20 @@ pub struct RegistryLiveCheckArgs {
     #[arg(long, default_value = "otlp")]
     input_source: InputSource,
 
-    /// The format of the input telemetry. (Not required for OTLP). text | json
+    /// The format of the input telemetry. (Not required for OTLP).
+    /// text | json | otlp-proto | otlp-json
+    ///
+    /// `otlp-proto` reads length-delimited OTLP `Export*ServiceRequest` protobuf messages
+    /// and `otlp-json` reads OTLP-JSON lines (as written by the OpenTelemetry Collector
+    /// `file` exporter), to replay captured OTLP traffic.
     #[arg(long, default_value = "json")]
     input_format: InputFormat,
 
     /// Format used to render the report.
     /// Builtin formats: json, yaml, jsonl (uses serde directly).
     /// Other values are treated as template names (e.g., "ansi" uses ansi templates).
+    /// Embedded templates: ansi, junit (JUnit XML), sarif (SARIF 2.1.0). The junit and sarif
+    /// formats always render a complete report (no streaming).
     #[arg(long, default_value = "ansi")]
     format: String,
 
@@ -132,6 +193,12 @@ pub struct RegistryLiveCheckArgs {
     #[clap(long, default_value = "4317")]
     otlp_grpc_port: u16,
 
+    /// Port used by the OTLP/HTTP listener (protobuf and JSON payloads), usually 4318.
+    /// The listener shares the address of the gRPC OTLP listener.
+    /// The OTLP/HTTP listener is only started when this port is set.
+    #[clap(long)]
+    otlp_http_port: Option<u16>,
+
     /// Enable OTLP log emission for live check policy findings
     #[arg(long, default_value = "false")]
     emit_otlp_logs: bool,
@@ -163,17 +230,88 @@ pub struct RegistryLiveCheckArgs {
     /// versus processing the data for every sample.
     #[arg(long)]
     advice_preprocessor: Option<PathBuf>,
+
+    /// Path to the telemetry schema file (OTEP 0152) of the registry.
+    ///
+    /// The rename history of the schema is used to report the schema version targeted by
+    /// renamed attributes and metrics, and to suggest their name at the current version.
+    /// If not specified, the rename history is built from the `renamed` deprecations of
+    /// the registry.
+    #[arg(long)]
+    schema_file: Option<PathBuf>,
+
+    /// Path to a baseline file of known findings (JSON).
+    ///
+    /// If the file does not exist, it is created with the findings of this session.
+    /// Otherwise, the findings recorded in the baseline are suppressed from the report
+    /// and counted separately in the statistics. Findings are matched on their id, signal
+    /// type, signal name and identifying context (e.g. attribute or metric name).
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Exit with a non-zero code when findings (not suppressed by the baseline) of the given
+    /// level or higher are found. Requires statistics (ignored with --no-stats).
+    #[arg(long, value_enum, default_value_t = FailOn::Violation)]
+    fail_on: FailOn,
+
+    /// Exit with a non-zero code when the registry coverage is below the given percentage
+    /// (0-100).
+    #[arg(long, conflicts_with = "no_stats")]
+    min_coverage: Option<f32>,
+
+    /// Exit with a non-zero code when new findings (not suppressed by the baseline) of the
+    /// given level or higher are found. Unlike --fail-on, this also applies with --no-stats.
+    #[arg(long, value_enum, requires = "baseline")]
+    fail_on_new: Option<FailOnNew>,
 }
 
 fn default_advisors() -> Vec<Box<dyn Advisor>> {
     vec![
         Box::new(DeprecatedAdvisor),
         Box::new(StabilityAdvisor),
-        Box::new(TypeAdvisor),
+        Box::new(TypeAdvisor::without_missing_attributes()),
+        Box::new(RequiredAttributeAdvisor),
         Box::new(EnumAdvisor),
     ]
 }
 
+/// Runs live-check on a set of samples with the default advisors and policies, and returns
+/// the samples with their findings and the statistics.
+pub(crate) fn check_samples(
+    registry: Arc<VersionedRegistry>,
+    samples: Vec<Sample>,
+) -> Result<LiveCheckReport, Error> {
+    let mut live_checker = LiveChecker::new(registry, default_advisors());
+    let rego_advisor = RegoAdvisor::new(&live_checker, &None, &None)?;
+    live_checker.add_advisor(Box::new(rego_advisor));
+    let rename_history = RenameHistory::from_registry(&live_checker.registry);
+    live_checker.add_advisor(Box::new(UpgradeAdvisor::new(rename_history)));
+
+    let mut stats =
+        LiveCheckStatistics::Cumulative(CumulativeStatistics::new(&live_checker.registry));
+    let samples = samples
+        .into_iter()
+        .map(|sample| check_sample(&mut live_checker, &mut stats, sample))
+        .collect::<Result<Vec<_>, _>>()?;
+    stats.finalize();
+    Ok(LiveCheckReport {
+        samples,
+        statistics: stats,
+    })
+}
+
+/// Runs live-check on a top-level sample and returns it with its findings. The advisors
+/// receive the sample as it was received as their parent signal.
+fn check_sample(
+    live_checker: &mut LiveChecker,
+    stats: &mut LiveCheckStatistics,
+    mut sample: Sample,
+) -> Result<Sample, Error> {
+    let parent_signal = sample.clone();
+    sample.run_live_check(live_checker, stats, None, &parent_signal)?;
+    Ok(sample)
+}
+
 /// Generate output for a complete report - handles line-oriented special case
 fn generate_report(
     output: &mut OutputProcessor,
@@ -199,7 +337,10 @@ fn generate_report(
 }
 
 /// Perform a live check on sample data by comparing it to a semantic convention registry.
-pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryLiveCheckArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     let mut exit_code = 0;
 
     // Detect --output http mode
@@ -234,7 +375,7 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     info!("Resolving registry `{}`", args.registry.registry);
 
     let mut diag_msgs = DiagnosticMessages::empty();
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
     let registry = if args.registry.v2 {
         let resolved_v2 = resolved.try_into_v2()?;
@@ -254,6 +395,29 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     )?;
     live_checker.add_advisor(Box::new(rego_advisor));
 
+    let rename_history = match &args.schema_file {
+        Some(path) => {
+            let schema = weaver_otel_schema::TelemetrySchema::try_from_file(path)?;
+            RenameHistory::from_versions(&schema.versions.unwrap_or_default())
+        }
+        None => RenameHistory::from_registry(&live_checker.registry),
+    };
+    live_checker.add_advisor(Box::new(UpgradeAdvisor::new(rename_history)));
+
+    if let Some(path) = &args.baseline {
+        let baseline = Baseline::load_or_record(path)?;
+        if baseline.is_recording() {
+            info!("Recording the baseline `{}`", path.display());
+        } else {
+            info!(
+                "Suppressing the {} finding(s) of the baseline `{}`",
+                baseline.len(),
+                path.display()
+            );
+        }
+        live_checker.baseline = Some(baseline);
+    }
+
     // Prepare the ingester
     let mut admin_report_sender: Option<AdminReportSender> = None;
     let ingester = match (&args.input_source, &args.input_format) {
@@ -265,10 +429,27 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
 
         (InputSource::Stdin, InputFormat::Json) => JsonStdinIngester::new().ingest()?,
 
+        (InputSource::File(path), InputFormat::OtlpProto) => {
+            OtlpFileIngester::new(path, OtlpEncoding::Proto).ingest()?
+        }
+
+        (InputSource::Stdin, InputFormat::OtlpProto) => {
+            OtlpFileIngester::stdin(OtlpEncoding::Proto).ingest()?
+        }
+
+        (InputSource::File(path), InputFormat::OtlpJson) => {
+            OtlpFileIngester::new(path, OtlpEncoding::Json).ingest()?
+        }
+
+        (InputSource::Stdin, InputFormat::OtlpJson) => {
+            OtlpFileIngester::stdin(OtlpEncoding::Json).ingest()?
+        }
+
         (InputSource::Otlp, _) => {
             let otlp = OtlpIngester {
                 otlp_grpc_address: args.otlp_grpc_address.clone(),
                 otlp_grpc_port: args.otlp_grpc_port,
+                otlp_http_port: args.otlp_http_port,
                 admin_port: args.admin_port,
                 inactivity_timeout: args.inactivity_timeout,
             };
@@ -321,7 +502,7 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     } else {
         // This flag is not set by default. The user can set it to disable streaming output
         // and force report mode.
-        args.no_stream
+        args.no_stream || REPORT_ONLY_FORMATS.contains(&args.format.to_lowercase().as_str())
     };
 
     let mut stats = if args.no_stats {
@@ -331,8 +512,8 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     };
 
     let mut samples = Vec::new();
-    for mut sample in ingester {
-        sample.run_live_check(&mut live_checker, &mut stats, None, &sample.clone())?;
+    for sample in ingester {
+        let sample = check_sample(&mut live_checker, &mut stats, sample)?;
         //TODO: Check for violations and set exit_code here for no-stats mode
         if report_mode {
             samples.push(sample);
@@ -343,9 +524,39 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
     }
 
     stats.finalize();
-    // Set the exit_code to a non-zero code if there are any violations
-    if stats.has_violations() {
-        exit_code = 1;
+    let baseline = live_checker.baseline.take();
+    if let Some(baseline) = &baseline {
+        baseline.save()?;
+    }
+    // Set the exit_code to a non-zero code if there are findings at or above the level
+    if let Some(level) = args.fail_on.level() {
+        if stats.has_findings(&level) {
+            log_error(format!("Finding(s) of level `{level}` or higher detected"));
+            exit_code = 1;
+        }
+    }
+    if let Some(fail_on_new) = args.fail_on_new {
+        // Set the exit_code to a non-zero code if there are new findings at or above the level
+        let level = fail_on_new.level();
+        if baseline
+            .as_ref()
+            .and_then(Baseline::highest_new_level)
+            .is_some_and(|highest_new_level| *highest_new_level >= level)
+        {
+            log_error(format!(
+                "New finding(s) of level `{level}` or higher detected (not in the baseline)"
+            ));
+            exit_code = 1;
+        }
+    }
+    if let (Some(min_coverage), Some(coverage)) = (args.min_coverage, stats.registry_coverage()) {
+        let coverage = coverage * 100.0;
+        if coverage < min_coverage {
+            log_error(format!(
+                "Registry coverage {coverage:.2}% is below the minimum of {min_coverage}%"
+            ));
+            exit_code = 1;
+        }
     }
 
     if is_http_output {
@@ -429,3 +640,102 @@ pub(crate) fn command(args: &RegistryLiveCheckArgs) -> Result<ExitDirectives, Di
         warnings: Some(diag_msgs),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+
+    use crate::cli::Cli;
+    use crate::run_command;
+
+    fn live_check(args: &[&str]) -> i32 {
+        let cli = Cli::parse_from(
+            [
+                "weaver",
+                "--quiet",
+                "registry",
+                "live-check",
+                "-r",
+                "crates/weaver_live_check/data/model",
+                "--input-source",
+                "crates/weaver_live_check/data/span.json",
+            ]
+            .iter()
+            .chain(args),
+        );
+        run_command(&cli).exit_code
+    }
+
+    fn report(output: &Path, format: &str) -> i32 {
+        live_check(&[
+            "--format",
+            format,
+            "--fail-on",
+            "none",
+            "-o",
+            output.to_str().expect("Invalid path"),
+        ])
+    }
+
+    #[test]
+    fn test_live_check_exit_code_policy() {
+        let out = ["-o", "none"];
+        // The sample contains violations and improvements
+        assert_eq!(live_check(&out), 1);
+        assert_eq!(
+            live_check(&[&out[..], &["--fail-on", "violation"]].concat()),
+            1
+        );
+        assert_eq!(live_check(&[&out[..], &["--fail-on", "none"]].concat()), 0);
+        assert_eq!(
+            live_check(&[&out[..], &["--fail-on", "none", "--min-coverage", "0"]].concat()),
+            0
+        );
+        assert_eq!(
+            live_check(&[&out[..], &["--fail-on", "none", "--min-coverage", "50"]].concat()),
+            1
+        );
+    }
+
+    #[test]
+    fn test_live_check_junit_and_sarif_reports() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let junit_dir = temp_dir.path().join("junit");
+        assert_eq!(report(&junit_dir, "junit"), 0);
+        let junit = std::fs::read_to_string(junit_dir.join("live_check.xml"))
+            .expect("Missing JUnit report");
+        assert!(junit.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(junit.contains(r#"<testsuites name="weaver live-check" tests="14" failures="10">"#));
+        assert!(junit
+            .contains(r#"<testcase classname="span" name="test &gt; http.response.status_code">"#));
+        assert!(junit.contains(r#"type="missing_attribute">"#));
+        assert!(junit.contains("[improvement] [missing_namespace]"));
+
+        let sarif_dir = temp_dir.path().join("sarif");
+        assert_eq!(report(&sarif_dir, "sarif"), 0);
+        let sarif: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(sarif_dir.join("live_check.sarif"))
+                .expect("Missing SARIF report"),
+        )
+        .expect("Invalid SARIF report");
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "weaver");
+        let results = run["results"].as_array().expect("Missing results");
+        assert_eq!(results.len(), 14);
+        let result = results
+            .iter()
+            .find(|r| {
+                r["locations"][0]["logicalLocations"][0]["name"]
+                    == "test > http.response.status_code"
+            })
+            .expect("Missing result");
+        assert_eq!(result["ruleId"], "missing_attribute");
+        assert_eq!(result["level"], "error");
+        assert_eq!(result["properties"]["signal_type"], "span");
+        assert_eq!(run["properties"]["total_advisories"], 14);
+    }
+}
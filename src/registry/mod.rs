@@ -10,6 +10,7 @@ use miette::Diagnostic;
 use serde::Serialize;
 
 use crate::registry::diff::RegistryDiffArgs;
+use crate::registry::fetch::RegistryFetchArgs;
 use crate::registry::generate::RegistryGenerateArgs;
 use crate::registry::infer::RegistryInferArgs;
 use crate::registry::json_schema::RegistryJsonSchemaArgs;
@@ -17,28 +18,35 @@ use crate::registry::live_check::RegistryLiveCheckArgs;
 use crate::registry::mcp::RegistryMcpArgs;
 use crate::registry::package::RegistryPackageArgs;
 use crate::registry::resolve::RegistryResolveArgs;
+use crate::registry::schema_file::RegistrySchemaFileArgs;
 use crate::registry::search::RegistrySearchArgs;
 use crate::registry::stats::RegistryStatsArgs;
+use crate::registry::transform::RegistryTransformArgs;
 use crate::registry::update_markdown::RegistryUpdateMarkdownArgs;
 use crate::CmdResult;
 use check::RegistryCheckArgs;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
 use weaver_common::vdir::VirtualDirectoryPath;
 
 mod check;
 mod diff;
 mod emit;
+mod fetch;
 mod generate;
 mod infer;
 mod json_schema;
-mod live_check;
+pub(crate) mod live_check;
 mod mcp;
-mod otlp;
+pub(crate) mod otlp;
 mod package;
 mod resolve;
+mod schema_file;
 mod search;
 mod stats;
+mod transform;
 mod update_markdown;
+pub(crate) mod watch;
 
 /// Errors emitted by the `registry` sub-commands
 #[derive(thiserror::Error, Debug, Serialize, Diagnostic)]
@@ -66,6 +74,18 @@ pub enum Error {
     /// Failed to write an output file during packaging
     #[error("Failed to write output file `{path}`: {error}")]
     OutputWrite { path: PathBuf, error: String },
+
+    /// The samples cannot be converted between the schema versions
+    #[error("Cannot convert the samples between the schema versions. {error}")]
+    Transform { error: String },
+
+    /// The version cannot be added to the telemetry schema file
+    #[error("Cannot add the version `{version}` to the telemetry schema file. {error}")]
+    SchemaFileVersion { version: String, error: String },
+
+    /// Telemetry schema files are only generated from v1 registries
+    #[error("Telemetry schema files are only generated from v1 registries. Remove `--v2`.")]
+    SchemaFileRequiresV1,
 }
 
 impl From<Error> for DiagnosticMessages {
@@ -133,6 +153,21 @@ pub enum RegistrySubCommand {
     #[clap(verbatim_doc_comment)]
     Diff(RegistryDiffArgs),
 
+    /// Generate an OTel telemetry schema file (OTEP 0152) from two versions of a semantic convention registry.
+    ///
+    /// The renames detected between the baseline and the current registry (attributes, metrics and
+    /// attributes of the signals) are added as a new version of the telemetry schema file.
+    /// An existing telemetry schema file can be extended with `--schema-file`.
+    #[clap(verbatim_doc_comment)]
+    SchemaFile(RegistrySchemaFileArgs),
+
+    /// Convert telemetry samples between two versions of an OTel telemetry schema (OTEP 0152).
+    ///
+    /// The renames of the telemetry schema file are applied to the attribute names and metric names
+    /// of the samples (JSON format of `registry live-check`), to upgrade or downgrade them.
+    #[clap(verbatim_doc_comment)]
+    Transform(RegistryTransformArgs),
+
     /// Emits a semantic convention registry as example signals to your OTLP receiver.
     ///
     /// This uses the standard OpenTelemetry SDK, defaulting to OTLP gRPC on localhost:4317.
@@ -161,10 +196,20 @@ pub enum RegistrySubCommand {
     /// Packages a semantic convention registry into a self-contained artifact.
     #[clap(verbatim_doc_comment)]
     Package(RegistryPackageArgs),
+
+    /// Fetch a semantic convention registry and its dependencies into the local cache.
+    ///
+    /// Git repositories and remote archives are stored in the cache (`~/.weaver/cache` by default)
+    /// and pinned to a commit or an archive digest in the lock file (`weaver.lock` by default).
+    /// Subsequent commands resolve pinned registries from the cache, also with `--offline`.
+    /// The existing pins are ignored, so that the lock file is updated to the latest version
+    /// of the registries (except with `--offline`).
+    #[clap(verbatim_doc_comment)]
+    Fetch(RegistryFetchArgs),
 }
 
 /// Set of parameters used to specify a semantic convention registry.
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct RegistryArgs {
     /// Local folder, Git repo URL, or Git archive URL of the semantic
     /// convention registry. For Git URLs, a reference can be specified
@@ -194,7 +239,7 @@ pub struct RegistryArgs {
 }
 
 /// Set of common parameters used for policy checks.
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Clone)]
 pub struct PolicyArgs {
     /// Optional list of policy files or directories to check against the files of the semantic
     /// convention registry.  If a directory is provided all `.rego` files in the directory will be
@@ -212,47 +257,69 @@ pub struct PolicyArgs {
 }
 
 /// Manage a semantic convention registry and return the exit code.
-pub fn semconv_registry(command: &RegistryCommand) -> CmdResult {
+pub fn semconv_registry(command: &RegistryCommand, cache_settings: &CacheSettings) -> CmdResult {
     match &command.command {
-        RegistrySubCommand::Check(args) => {
-            CmdResult::new(check::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Generate(args) => {
-            CmdResult::new(generate::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Stats(args) => {
-            CmdResult::new(stats::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Resolve(args) => {
-            CmdResult::new(resolve::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Search(args) => {
-            CmdResult::new(search::command(args), Some(args.diagnostic.clone()))
-        }
+        RegistrySubCommand::Check(args) => CmdResult::new(
+            check::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Generate(args) => CmdResult::new(
+            generate::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Stats(args) => CmdResult::new(
+            stats::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Resolve(args) => CmdResult::new(
+            resolve::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Search(args) => CmdResult::new(
+            search::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
         RegistrySubCommand::UpdateMarkdown(args) => CmdResult::new(
-            update_markdown::command(args),
+            update_markdown::command(args, cache_settings),
             Some(args.diagnostic.clone()),
         ),
         RegistrySubCommand::JsonSchema(args) => {
             CmdResult::new(json_schema::command(args), Some(args.diagnostic.clone()))
         }
-        RegistrySubCommand::Diff(args) => {
-            CmdResult::new(diff::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::LiveCheck(args) => {
-            CmdResult::new(live_check::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Emit(args) => {
-            CmdResult::new(emit::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Mcp(args) => {
-            CmdResult::new(mcp::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Infer(args) => {
-            CmdResult::new(infer::command(args), Some(args.diagnostic.clone()))
-        }
-        RegistrySubCommand::Package(args) => {
-            CmdResult::new(package::command(args), Some(args.diagnostic.clone()))
+        RegistrySubCommand::Diff(args) => CmdResult::new(
+            diff::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::SchemaFile(args) => CmdResult::new(
+            schema_file::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Transform(args) => {
+            CmdResult::new(transform::command(args), Some(args.diagnostic.clone()))
         }
+        RegistrySubCommand::LiveCheck(args) => CmdResult::new(
+            live_check::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Emit(args) => CmdResult::new(
+            emit::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Mcp(args) => CmdResult::new(
+            mcp::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Infer(args) => CmdResult::new(
+            infer::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Package(args) => CmdResult::new(
+            package::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
+        RegistrySubCommand::Fetch(args) => CmdResult::new(
+            fetch::command(args, cache_settings),
+            Some(args.diagnostic.clone()),
+        ),
     }
 }
@@ -3,13 +3,17 @@
 //! A basic OTLP receiver integrated into Weaver.
 
 pub mod conversion;
+pub mod otlp_file_ingester;
 pub mod otlp_ingester;
+pub mod otlp_json;
 
-use axum::extract::State;
-use axum::http::{header, StatusCode};
-use axum::response::IntoResponse;
+use axum::body::Bytes;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response as HttpResponse};
 use axum::routing::{get, post};
 use axum::{Json, Router};
+use flate2::read::GzDecoder;
 use grpc_stubs::proto::collector::logs::v1::logs_service_server::{LogsService, LogsServiceServer};
 use grpc_stubs::proto::collector::logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse};
 use grpc_stubs::proto::collector::metrics::v1::metrics_service_server::{
@@ -25,8 +29,10 @@ use grpc_stubs::proto::collector::trace::v1::{
     ExportTraceServiceRequest, ExportTraceServiceResponse,
 };
 use miette::Diagnostic;
+use prost::Message;
 use serde::Serialize;
 use std::fmt::{Display, Formatter};
+use std::io::Read;
 use std::net::{AddrParseError, SocketAddr};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -184,9 +190,16 @@ impl Display for StopSignal {
     }
 }
 
+/// Maximum size of an OTLP/HTTP request body (after decompression).
+const MAX_OTLP_HTTP_BODY_SIZE: usize = 64 * 1024 * 1024;
+
 /// Start an OTLP receiver listening to a specific port on all IPv4 interfaces
 /// and return an iterator of received OTLP requests and an admin report sender.
 ///
+/// The gRPC services are always started. When `http_port` is set, an OTLP/HTTP
+/// listener (`/v1/traces`, `/v1/metrics` and `/v1/logs`) is also started on the
+/// same address. Both listeners feed the same request channel.
+///
 /// The `AdminReportSender` allows the caller to send a formatted report back
 /// through the `/stop` HTTP endpoint. When `/stop` is called, the HTTP handler
 /// stores a oneshot sender in the slot and waits for the report.
@@ -196,6 +209,7 @@ impl Display for StopSignal {
 pub fn listen_otlp_requests(
     grpc_addr: &str,
     grpc_port: u16,
+    http_port: Option<u16>,
     admin_port: u16,
     inactivity_timeout: Duration,
 ) -> Result<(impl Iterator<Item = OtlpRequest>, AdminReportSender), Error> {
@@ -215,6 +229,10 @@ pub fn listen_otlp_requests(
             error: e.to_string(),
         })?;
 
+    let http_listener = http_port
+        .map(|http_port| bind_otlp_http_listener(grpc_addr, http_port))
+        .transpose()?;
+
     let (tx, rx) = mpsc::channel(100);
     let stop_tx = tx.clone();
     // Create a watch channel for the last activity timestamp
@@ -236,6 +254,10 @@ pub fn listen_otlp_requests(
         tx: tx.clone(),
         activity_tx: activity_tx.clone(),
     };
+    let http_state = OtlpHttpState {
+        tx: tx.clone(),
+        activity_tx: activity_tx.clone(),
+    };
 
     let (ready_tx, ready_rx) = oneshot::channel();
 
@@ -269,6 +291,9 @@ pub fn listen_otlp_requests(
                         &mut tasks,
                     );
                 }
+                if let Some(http_listener) = http_listener {
+                    spawn_otlp_http_handler(http_listener, http_state, &mut tasks);
+                }
 
                 let tokio_listener = TcpListener::from_std(listener)
                     .expect("Failed to convert std listener to tokio listener");
@@ -514,6 +539,218 @@ fn spawn_inactivity_monitor(
     });
 }
 
+/// Bind the OTLP/HTTP listener synchronously so that binding errors are
+/// reported before the receiver is considered started.
+fn bind_otlp_http_listener(addr: &str, port: u16) -> Result<std::net::TcpListener, Error> {
+    let addr: SocketAddr = format!("{addr}:{port}")
+        .parse()
+        .map_err(|e: AddrParseError| Error::OtlpError {
+            error: e.to_string(),
+        })?;
+    let listener = std::net::TcpListener::bind(addr).map_err(|e| Error::OtlpError {
+        error: format!("Failed to bind OTLP/HTTP port {port}: {e}"),
+    })?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| Error::OtlpError {
+            error: e.to_string(),
+        })?;
+    Ok(listener)
+}
+
+/// Shared state for the OTLP/HTTP handlers.
+#[derive(Clone)]
+struct OtlpHttpState {
+    tx: mpsc::Sender<OtlpRequest>,
+    activity_tx: watch::Sender<Instant>,
+}
+
+/// The encoding of an OTLP/HTTP payload, derived from the `Content-Type` header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OtlpHttpEncoding {
+    Protobuf,
+    Json,
+}
+
+/// Spawn the OTLP/HTTP server exposing `/v1/traces`, `/v1/metrics` and `/v1/logs`.
+///
+/// Note: All the tasks created in this function are recorded into a
+/// JoinSet. `JoinSet::spawn` returns a `AbortHandle` that we can
+/// ignore as we don't need to abort these tasks.
+fn spawn_otlp_http_handler(
+    listener: std::net::TcpListener,
+    state: OtlpHttpState,
+    tasks: &mut JoinSet<()>,
+) {
+    let tx = state.tx.clone();
+    let app = Router::new()
+        .route("/v1/traces", post(http_traces_handler))
+        .route("/v1/metrics", post(http_metrics_handler))
+        .route("/v1/logs", post(http_logs_handler))
+        .layer(DefaultBodyLimit::max(MAX_OTLP_HTTP_BODY_SIZE))
+        .with_state(state);
+
+    let _ = tasks.spawn(async move {
+        let result = match TcpListener::from_std(listener) {
+            Ok(listener) => axum::serve(listener, app).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            let _ = tx
+                .send(OtlpRequest::Error(Error::OtlpError {
+                    error: format!("The OTLP/HTTP listener encountered an error: {e}"),
+                }))
+                .await;
+        }
+    });
+}
+
+/// POST /v1/traces — OTLP/HTTP trace export.
+async fn http_traces_handler(
+    State(state): State<OtlpHttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResponse {
+    handle_otlp_http_export(
+        &state,
+        &headers,
+        &body,
+        otlp_json::traces_request_from_json,
+        OtlpRequest::Traces,
+        ExportTraceServiceResponse::default(),
+    )
+    .await
+}
+
+/// POST /v1/metrics — OTLP/HTTP metrics export.
+async fn http_metrics_handler(
+    State(state): State<OtlpHttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResponse {
+    handle_otlp_http_export(
+        &state,
+        &headers,
+        &body,
+        otlp_json::metrics_request_from_json,
+        OtlpRequest::Metrics,
+        ExportMetricsServiceResponse::default(),
+    )
+    .await
+}
+
+/// POST /v1/logs — OTLP/HTTP logs export.
+async fn http_logs_handler(
+    State(state): State<OtlpHttpState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResponse {
+    handle_otlp_http_export(
+        &state,
+        &headers,
+        &body,
+        otlp_json::logs_request_from_json,
+        OtlpRequest::Logs,
+        ExportLogsServiceResponse::default(),
+    )
+    .await
+}
+
+/// Decode an OTLP/HTTP export request (protobuf or JSON, optionally gzipped),
+/// forward it to the request channel and build the export response encoded
+/// like the request.
+async fn handle_otlp_http_export<T: Message + Default, R: Message>(
+    state: &OtlpHttpState,
+    headers: &HeaderMap,
+    body: &[u8],
+    from_json: fn(&[u8]) -> Result<T, Error>,
+    wrapper: fn(T) -> OtlpRequest,
+    response: R,
+) -> HttpResponse {
+    let Some(encoding) = otlp_http_encoding(headers) else {
+        return otlp_http_error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Unsupported content type, expected `application/x-protobuf` or `application/json`",
+        );
+    };
+
+    let body = match decompress_otlp_http_body(headers, body) {
+        Ok(body) => body,
+        Err(error) => return otlp_http_error(StatusCode::BAD_REQUEST, &error),
+    };
+
+    let request = match encoding {
+        OtlpHttpEncoding::Protobuf => T::decode(body.as_slice()).map_err(|e| e.to_string()),
+        OtlpHttpEncoding::Json => from_json(&body).map_err(|e| e.to_string()),
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(error) => return otlp_http_error(StatusCode::BAD_REQUEST, &error),
+    };
+
+    // Update last activity time
+    let _ = state.activity_tx.send(Instant::now());
+
+    if let Err(e) = state.tx.send(wrapper(request)).await {
+        return otlp_http_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            &format!("Channel closed: {e}"),
+        );
+    }
+
+    match encoding {
+        OtlpHttpEncoding::Protobuf => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "application/x-protobuf")],
+            response.encode_to_vec(),
+        )
+            .into_response(),
+        // An export response without partial success is an empty JSON object.
+        OtlpHttpEncoding::Json => (StatusCode::OK, Json(serde_json::json!({}))).into_response(),
+    }
+}
+
+/// Determine the payload encoding from the `Content-Type` header.
+fn otlp_http_encoding(headers: &HeaderMap) -> Option<OtlpHttpEncoding> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())?;
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    match mime.to_lowercase().as_str() {
+        "application/x-protobuf" | "application/protobuf" => Some(OtlpHttpEncoding::Protobuf),
+        "application/json" => Some(OtlpHttpEncoding::Json),
+        _ => None,
+    }
+}
+
+/// Decompress the body according to the `Content-Encoding` header.
+fn decompress_otlp_http_body(headers: &HeaderMap, body: &[u8]) -> Result<Vec<u8>, String> {
+    let content_encoding = headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_lowercase());
+    match content_encoding.as_deref() {
+        None | Some("") | Some("identity") => Ok(body.to_vec()),
+        Some("gzip") => {
+            let mut decompressed = Vec::new();
+            let _ = GzDecoder::new(body)
+                .take(MAX_OTLP_HTTP_BODY_SIZE as u64 + 1)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| format!("Invalid gzip payload: {e}"))?;
+            if decompressed.len() > MAX_OTLP_HTTP_BODY_SIZE {
+                return Err("Decompressed payload is too large".to_owned());
+            }
+            Ok(decompressed)
+        }
+        Some(other) => Err(format!("Unsupported content encoding `{other}`")),
+    }
+}
+
+/// Build an OTLP/HTTP error response.
+fn otlp_http_error(status: StatusCode, message: &str) -> HttpResponse {
+    (status, Json(serde_json::json!({"message": message}))).into_response()
+}
+
 // Synchronous iterator wrapping a Tokio mpsc::Receiver.
 pub struct SyncReceiver<T> {
     receiver: mpsc::Receiver<T>,
@@ -620,7 +857,8 @@ mod tests {
         let inactivity_timeout = Duration::from_secs(1);
 
         let (mut receiver, _report_sender) =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, None, admin_port, inactivity_timeout)
+                .unwrap();
         let grpc_endpoint = format!("http://127.0.0.1:{grpc_port}");
         let expected_metrics_count = 3;
         let expected_logs_count = 4;
@@ -713,7 +951,8 @@ mod tests {
         let inactivity_timeout = Duration::from_secs(5);
 
         let (mut receiver, report_sender) =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, None, admin_port, inactivity_timeout)
+                .unwrap();
 
         // Enable report-via-HTTP mode (simulates --output http)
         report_sender.expect_report.store(true, Ordering::Relaxed);
@@ -760,7 +999,8 @@ mod tests {
         let inactivity_timeout = Duration::from_secs(5);
 
         let (mut receiver, _report_sender) =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, None, admin_port, inactivity_timeout)
+                .unwrap();
 
         // expect_report defaults to false — /stop should return 200 immediately
 
@@ -788,6 +1028,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_otlp_http_protobuf_and_json() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let grpc_port = portpicker::pick_unused_port().expect("No free ports");
+        let http_port = portpicker::pick_unused_port().expect("No free ports");
+        let admin_port = portpicker::pick_unused_port().expect("No free ports");
+        let inactivity_timeout = Duration::from_secs(1);
+
+        let (mut receiver, _report_sender) = listen_otlp_requests(
+            "127.0.0.1",
+            grpc_port,
+            Some(http_port),
+            admin_port,
+            inactivity_timeout,
+        )
+        .unwrap();
+
+        // Give the server a little time to finish binding the port.
+        thread::sleep(Duration::from_millis(200));
+
+        // Binary protobuf
+        let url = format!("http://127.0.0.1:{http_port}/v1/metrics");
+        let response = ureq::post(&url)
+            .header("Content-Type", "application/x-protobuf")
+            .send(ExportMetricsServiceRequest::default().encode_to_vec())
+            .expect("POST /v1/metrics failed");
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "application/x-protobuf"
+        );
+
+        // Gzipped protobuf
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&ExportLogsServiceRequest::default().encode_to_vec())
+            .unwrap();
+        let url = format!("http://127.0.0.1:{http_port}/v1/logs");
+        let response = ureq::post(&url)
+            .header("Content-Type", "application/x-protobuf")
+            .header("Content-Encoding", "gzip")
+            .send(encoder.finish().unwrap())
+            .expect("POST /v1/logs failed");
+        assert_eq!(response.status(), 200);
+
+        // OTLP-JSON
+        let url = format!("http://127.0.0.1:{http_port}/v1/traces");
+        let response = ureq::post(&url)
+            .header("Content-Type", "application/json")
+            .send(r#"{"resourceSpans":[{"scopeSpans":[{"spans":[{"name":"test","kind":2}]}]}]}"#)
+            .expect("POST /v1/traces failed");
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.into_body().read_to_string().unwrap(), "{}");
+
+        // Unsupported content type
+        let result = ureq::post(&url)
+            .header("Content-Type", "text/plain")
+            .send("hello");
+        assert!(matches!(result, Err(ureq::Error::StatusCode(415))));
+
+        // Invalid payload
+        let result = ureq::post(&url)
+            .header("Content-Type", "application/json")
+            .send("[1, 2, 3]");
+        assert!(matches!(result, Err(ureq::Error::StatusCode(400))));
+
+        let mut metrics_count = 0;
+        let mut logs_count = 0;
+        let mut span_names = vec![];
+        loop {
+            match receiver.next().unwrap() {
+                OtlpRequest::Metrics(_) => metrics_count += 1,
+                OtlpRequest::Logs(_) => logs_count += 1,
+                OtlpRequest::Traces(traces) => {
+                    for resource_spans in traces.resource_spans {
+                        for scope_spans in resource_spans.scope_spans {
+                            span_names.extend(scope_spans.spans.into_iter().map(|s| s.name));
+                        }
+                    }
+                }
+                OtlpRequest::Stop(StopSignal::Inactivity) => break,
+                other => panic!("Unexpected request: {other:?}"),
+            }
+        }
+        assert_eq!(metrics_count, 1);
+        assert_eq!(logs_count, 1);
+        assert_eq!(span_names, vec!["test".to_owned()]);
+    }
+
     #[test]
     fn test_health_endpoint() {
         let grpc_port = portpicker::pick_unused_port().expect("No free ports");
@@ -795,7 +1127,8 @@ mod tests {
         let inactivity_timeout = Duration::from_secs(5);
 
         let (_receiver, _report_sender) =
-            listen_otlp_requests("127.0.0.1", grpc_port, admin_port, inactivity_timeout).unwrap();
+            listen_otlp_requests("127.0.0.1", grpc_port, None, admin_port, inactivity_timeout)
+                .unwrap();
 
         // Give the server a little time to finish binding the port.
         thread::sleep(Duration::from_millis(200));
@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! An ingester replaying OTLP requests recorded in a capture file (or piped via
+//! standard input).
+//!
+//! Two encodings are supported:
+//! - `proto`: a sequence of length-delimited (varint prefix) `Export*ServiceRequest`
+//!   protobuf messages,
+//! - `json`: OTLP-JSON lines, one `Export*ServiceRequest` per line, as written by the
+//!   OpenTelemetry Collector `file` exporter.
+//!
+//! The decoded requests go through the same conversion as the OTLP listener, so
+//! replaying a capture produces exactly the same findings as receiving it live.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use prost::Message;
+use weaver_live_check::{Error, Ingester, Sample};
+
+use super::grpc_stubs::proto::collector::logs::v1::ExportLogsServiceRequest;
+use super::grpc_stubs::proto::collector::metrics::v1::ExportMetricsServiceRequest;
+use super::grpc_stubs::proto::collector::trace::v1::ExportTraceServiceRequest;
+use super::otlp_ingester::OtlpIterator;
+use super::otlp_json::{
+    logs_request_from_json, metrics_request_from_json, traces_request_from_json,
+};
+use super::OtlpRequest;
+
+/// The encoding of an OTLP capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpEncoding {
+    /// Length-delimited protobuf `Export*ServiceRequest` messages.
+    Proto,
+    /// OTLP-JSON lines (OpenTelemetry Collector `file` exporter).
+    Json,
+}
+
+/// An ingester for OTLP capture files.
+pub struct OtlpFileIngester {
+    /// The capture file, or `None` to read from standard input.
+    path: Option<PathBuf>,
+    encoding: OtlpEncoding,
+}
+
+impl OtlpFileIngester {
+    /// Create a new ingester reading the given capture file.
+    #[must_use]
+    pub fn new(path: &Path, encoding: OtlpEncoding) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+            encoding,
+        }
+    }
+
+    /// Create a new ingester reading a capture from standard input.
+    #[must_use]
+    pub fn stdin(encoding: OtlpEncoding) -> Self {
+        Self {
+            path: None,
+            encoding,
+        }
+    }
+
+    fn read_input(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = Vec::new();
+        match &self.path {
+            Some(path) => {
+                buffer = std::fs::read(path).map_err(|e| Error::IngestError {
+                    error: format!("Failed to read file {}: {e}", path.display()),
+                })?;
+            }
+            None => {
+                _ = io::stdin().lock().read_to_end(&mut buffer).map_err(|e| {
+                    Error::IngestError {
+                        error: format!("Failed to read from stdin: {e}"),
+                    }
+                })?;
+            }
+        }
+        Ok(buffer)
+    }
+}
+
+impl Ingester for OtlpFileIngester {
+    fn ingest(&self) -> Result<Box<dyn Iterator<Item = Sample>>, Error> {
+        let input = self.read_input()?;
+        let requests = match self.encoding {
+            OtlpEncoding::Proto => decode_proto_requests(&input)?,
+            OtlpEncoding::Json => decode_json_requests(&input)?,
+        };
+        Ok(Box::new(OtlpIterator::new(Box::new(requests.into_iter()))))
+    }
+}
+
+/// Decodes a sequence of length-delimited `Export*ServiceRequest` messages.
+///
+/// The messages do not carry their signal type, so each one is decoded as traces,
+/// metrics and logs in turn. The first decoding consuming the whole message is
+/// preferred, falling back to the first one that succeeds.
+fn decode_proto_requests(mut input: &[u8]) -> Result<Vec<OtlpRequest>, Error> {
+    let mut requests = Vec::new();
+    while !input.is_empty() {
+        let index = requests.len();
+        let len = prost::encoding::decode_varint(&mut input).map_err(|e| Error::IngestError {
+            error: format!("Invalid length prefix for OTLP message #{index}: {e}"),
+        })?;
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|len| *len <= input.len())
+            .ok_or_else(|| Error::IngestError {
+                error: format!(
+                    "Truncated OTLP message #{index}: expected {len} bytes, {} available",
+                    input.len()
+                ),
+            })?;
+        let (message, rest) = input.split_at(len);
+        requests.push(
+            decode_proto_request(message).ok_or_else(|| Error::IngestError {
+                error: format!("OTLP message #{index} is not a valid Export*ServiceRequest"),
+            })?,
+        );
+        input = rest;
+    }
+    Ok(requests)
+}
+
+fn decode_proto_request(message: &[u8]) -> Option<OtlpRequest> {
+    let candidates = [
+        ExportTraceServiceRequest::decode(message)
+            .ok()
+            .map(|r| (r.encoded_len(), OtlpRequest::Traces(r))),
+        ExportMetricsServiceRequest::decode(message)
+            .ok()
+            .map(|r| (r.encoded_len(), OtlpRequest::Metrics(r))),
+        ExportLogsServiceRequest::decode(message)
+            .ok()
+            .map(|r| (r.encoded_len(), OtlpRequest::Logs(r))),
+    ];
+    let mut fallback = None;
+    for (encoded_len, request) in candidates.into_iter().flatten() {
+        if encoded_len == message.len() {
+            return Some(request);
+        }
+        if fallback.is_none() {
+            fallback = Some(request);
+        }
+    }
+    fallback
+}
+
+/// Decodes OTLP-JSON lines. Empty lines are skipped.
+fn decode_json_requests(input: &[u8]) -> Result<Vec<OtlpRequest>, Error> {
+    let mut requests = Vec::new();
+    for (index, line) in input.split(|b| *b == b'\n').enumerate() {
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        requests.push(
+            decode_json_request(line).map_err(|error| Error::IngestError {
+                error: format!("Invalid OTLP-JSON on line {}: {error}", index + 1),
+            })?,
+        );
+    }
+    Ok(requests)
+}
+
+/// Decodes an OTLP-JSON `Export*ServiceRequest`, the signal being detected from its root
+/// field.
+fn decode_json_request(json: &[u8]) -> Result<OtlpRequest, String> {
+    let root: serde_json::Value = serde_json::from_slice(json).map_err(|e| e.to_string())?;
+    let has_field =
+        |camel: &str, snake: &str| root.get(camel).is_some() || root.get(snake).is_some();
+    let request = if has_field("resourceSpans", "resource_spans") {
+        traces_request_from_json(json).map(OtlpRequest::Traces)
+    } else if has_field("resourceMetrics", "resource_metrics") {
+        metrics_request_from_json(json).map(OtlpRequest::Metrics)
+    } else if has_field("resourceLogs", "resource_logs") {
+        logs_request_from_json(json).map(OtlpRequest::Logs)
+    } else {
+        return Err(
+            "expected one of `resourceSpans`, `resourceMetrics` or `resourceLogs`".to_owned(),
+        );
+    };
+    request.map_err(|e| e.to_string())
+}
+
+/// Converts an OTLP-JSON `Export*ServiceRequest` to samples, the same way as the OTLP
+/// listener.
+pub(crate) fn samples_from_json_request(json: &[u8]) -> Result<Vec<Sample>, Error> {
+    let request = decode_json_request(json).map_err(|error| Error::IngestError {
+        error: format!("Invalid OTLP-JSON: {error}"),
+    })?;
+    Ok(OtlpIterator::new(Box::new(std::iter::once(request))).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::otlp::grpc_stubs::proto::common::v1::{any_value, AnyValue, KeyValue};
+    use crate::registry::otlp::grpc_stubs::proto::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+    use crate::registry::otlp::grpc_stubs::proto::trace::v1::{ResourceSpans, ScopeSpans, Span};
+
+    const TRACES_JSON: &str = r#"{"resourceSpans":[{"scopeSpans":[{"spans":[{"name":"GET /cart","kind":2,"attributes":[{"key":"http.request.method","value":{"stringValue":"GET"}}]}]}]}]}"#;
+    const LOGS_JSON: &str = r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"eventName":"session.start","attributes":[{"key":"session.id","value":{"stringValue":"abc"}}]}]}]}]}"#;
+
+    fn traces_request() -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![Span {
+                        name: "GET /cart".to_owned(),
+                        kind: 2,
+                        attributes: vec![KeyValue {
+                            key: "http.request.method".to_owned(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue("GET".to_owned())),
+                            }),
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn logs_request() -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord {
+                        event_name: "session.start".to_owned(),
+                        attributes: vec![KeyValue {
+                            key: "session.id".to_owned(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::StringValue("abc".to_owned())),
+                            }),
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// The samples produced by the live OTLP listener for the same requests.
+    fn live_samples(requests: Vec<OtlpRequest>) -> Vec<Sample> {
+        OtlpIterator::new(Box::new(requests.into_iter())).collect()
+    }
+
+    fn replay(input: &[u8], encoding: OtlpEncoding) -> Result<Vec<Sample>, Error> {
+        let requests = match encoding {
+            OtlpEncoding::Proto => decode_proto_requests(input)?,
+            OtlpEncoding::Json => decode_json_requests(input)?,
+        };
+        Ok(live_samples(requests))
+    }
+
+    #[test]
+    fn test_replay_proto_capture() {
+        let mut capture = traces_request().encode_length_delimited_to_vec();
+        capture.extend(logs_request().encode_length_delimited_to_vec());
+
+        let samples = replay(&capture, OtlpEncoding::Proto).unwrap();
+        assert!(!samples.is_empty());
+        assert_eq!(
+            samples,
+            live_samples(vec![
+                OtlpRequest::Traces(traces_request()),
+                OtlpRequest::Logs(logs_request()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_replay_json_capture() {
+        let capture = format!("{TRACES_JSON}\n\n{LOGS_JSON}\n");
+
+        let samples = replay(capture.as_bytes(), OtlpEncoding::Json).unwrap();
+        assert_eq!(
+            samples,
+            live_samples(vec![
+                OtlpRequest::Traces(traces_request()),
+                OtlpRequest::Logs(logs_request()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_replay_capture_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("capture.jsonl");
+        std::fs::write(&path, TRACES_JSON).unwrap();
+
+        let samples: Vec<Sample> = OtlpFileIngester::new(&path, OtlpEncoding::Json)
+            .ingest()
+            .unwrap()
+            .collect();
+        assert_eq!(
+            samples,
+            live_samples(vec![OtlpRequest::Traces(traces_request())])
+        );
+
+        let missing = OtlpFileIngester::new(&temp_dir.path().join("missing"), OtlpEncoding::Proto);
+        assert!(missing.ingest().is_err());
+    }
+
+    #[test]
+    fn test_replay_invalid_capture() {
+        let capture = traces_request().encode_length_delimited_to_vec();
+        let error = replay(&capture[..capture.len() - 1], OtlpEncoding::Proto).unwrap_err();
+        assert!(error.to_string().contains("Truncated OTLP message #0"));
+
+        let capture = format!("{TRACES_JSON}\n{{\"spans\":[]}}\n");
+        let error = replay(capture.as_bytes(), OtlpEncoding::Json).unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+
+        let error = replay(b"not json", OtlpEncoding::Json).unwrap_err();
+        assert!(error.to_string().contains("line 1"));
+    }
+}
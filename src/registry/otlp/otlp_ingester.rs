@@ -26,6 +26,8 @@ pub struct OtlpIngester {
     pub otlp_grpc_address: String,
     /// The port of the OTLP gRPC server
     pub otlp_grpc_port: u16,
+    /// The port of the OTLP/HTTP server, if enabled
+    pub otlp_http_port: Option<u16>,
     /// The port of the admin server
     pub admin_port: u16,
     /// The inactivity timeout
@@ -33,13 +35,13 @@ pub struct OtlpIngester {
 }
 
 /// Iterator for OTLP samples
-struct OtlpIterator {
+pub(crate) struct OtlpIterator {
     otlp_requests: Box<dyn Iterator<Item = OtlpRequest>>,
     buffer: Vec<Sample>,
 }
 
 impl OtlpIterator {
-    fn new(otlp_requests: Box<dyn Iterator<Item = OtlpRequest>>) -> Self {
+    pub(crate) fn new(otlp_requests: Box<dyn Iterator<Item = OtlpRequest>>) -> Self {
         Self {
             otlp_requests,
             buffer: Vec::new(),
@@ -163,6 +165,7 @@ impl OtlpIterator {
                                 span_links: Vec::new(),
                                 live_check_result: None,
                                 resource: rc_resource.clone(),
+                                span_type: None,
                             };
                             for attribute in span.attributes {
                                 sample_span
@@ -234,6 +237,7 @@ impl OtlpIngester {
         let (otlp_requests, report_sender) = listen_otlp_requests(
             self.otlp_grpc_address.as_str(),
             self.otlp_grpc_port,
+            self.otlp_http_port,
             self.admin_port,
             Duration::from_secs(self.inactivity_timeout),
         )
@@ -0,0 +1,829 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decoding of OTLP-JSON payloads into the OTLP protobuf messages.
+//!
+//! The OTLP/HTTP JSON encoding follows the proto3 JSON mapping with a few
+//! OTLP specific rules:
+//! - field names are lowerCamelCase (snake_case is also accepted),
+//! - `traceId` and `spanId` are hex encoded instead of base64,
+//! - enums are encoded as integers (enum names are also accepted),
+//! - 64-bit integers may be encoded as JSON numbers or strings.
+//!
+//! Decoding into the protobuf messages allows the same conversion routines
+//! (see `conversion.rs`) to be used for both the gRPC and HTTP listeners.
+
+use base64::Engine;
+use serde_json::{Map, Value};
+
+use super::grpc_stubs::proto::collector::logs::v1::ExportLogsServiceRequest;
+use super::grpc_stubs::proto::collector::metrics::v1::ExportMetricsServiceRequest;
+use super::grpc_stubs::proto::collector::trace::v1::ExportTraceServiceRequest;
+use super::grpc_stubs::proto::common::v1::{
+    any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+};
+use super::grpc_stubs::proto::logs::v1::{LogRecord, ResourceLogs, ScopeLogs, SeverityNumber};
+use super::grpc_stubs::proto::metrics::v1::{
+    exemplar, exponential_histogram_data_point, metric, number_data_point, summary_data_point,
+    AggregationTemporality, Exemplar, ExponentialHistogram, ExponentialHistogramDataPoint, Gauge,
+    Histogram, HistogramDataPoint, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+    Summary, SummaryDataPoint,
+};
+use super::grpc_stubs::proto::resource::v1::Resource;
+use super::grpc_stubs::proto::trace::v1::{span, status, ResourceSpans, ScopeSpans, Span, Status};
+use super::Error;
+
+type JsonObject = Map<String, Value>;
+
+/// Decodes an OTLP-JSON `ExportTraceServiceRequest`.
+pub fn traces_request_from_json(bytes: &[u8]) -> Result<ExportTraceServiceRequest, Error> {
+    let root = parse_root(bytes)?;
+    Ok(ExportTraceServiceRequest {
+        resource_spans: objects(&root, "resourceSpans", "resource_spans")?
+            .into_iter()
+            .map(resource_spans)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Decodes an OTLP-JSON `ExportMetricsServiceRequest`.
+pub fn metrics_request_from_json(bytes: &[u8]) -> Result<ExportMetricsServiceRequest, Error> {
+    let root = parse_root(bytes)?;
+    Ok(ExportMetricsServiceRequest {
+        resource_metrics: objects(&root, "resourceMetrics", "resource_metrics")?
+            .into_iter()
+            .map(resource_metrics)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Decodes an OTLP-JSON `ExportLogsServiceRequest`.
+pub fn logs_request_from_json(bytes: &[u8]) -> Result<ExportLogsServiceRequest, Error> {
+    let root = parse_root(bytes)?;
+    Ok(ExportLogsServiceRequest {
+        resource_logs: objects(&root, "resourceLogs", "resource_logs")?
+            .into_iter()
+            .map(resource_logs)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn parse_root(bytes: &[u8]) -> Result<JsonObject, Error> {
+    match serde_json::from_slice::<Value>(bytes) {
+        Ok(Value::Object(object)) => Ok(object),
+        Ok(_) => Err(invalid("the OTLP-JSON payload must be a JSON object")),
+        Err(e) => Err(invalid(&format!("invalid OTLP-JSON payload: {e}"))),
+    }
+}
+
+fn invalid(error: &str) -> Error {
+    Error::OtlpError {
+        error: error.to_owned(),
+    }
+}
+
+// Generic field accessors ====================================================
+
+/// Returns the value of a field, accepting both the lowerCamelCase and the
+/// original snake_case field names. `null` is treated as an absent field.
+fn field<'a>(object: &'a JsonObject, camel: &str, snake: &str) -> Option<&'a Value> {
+    object
+        .get(camel)
+        .or_else(|| object.get(snake))
+        .filter(|value| !value.is_null())
+}
+
+fn nested<'a>(
+    object: &'a JsonObject,
+    camel: &str,
+    snake: &str,
+) -> Result<Option<&'a JsonObject>, Error> {
+    match field(object, camel, snake) {
+        None => Ok(None),
+        Some(Value::Object(inner)) => Ok(Some(inner)),
+        Some(_) => Err(invalid(&format!("field `{camel}` must be an object"))),
+    }
+}
+
+fn array<'a>(object: &'a JsonObject, camel: &str, snake: &str) -> Result<&'a [Value], Error> {
+    match field(object, camel, snake) {
+        None => Ok(&[]),
+        Some(Value::Array(values)) => Ok(values),
+        Some(_) => Err(invalid(&format!("field `{camel}` must be an array"))),
+    }
+}
+
+fn objects<'a>(
+    object: &'a JsonObject,
+    camel: &str,
+    snake: &str,
+) -> Result<Vec<&'a JsonObject>, Error> {
+    array(object, camel, snake)?
+        .iter()
+        .map(|value| {
+            value
+                .as_object()
+                .ok_or_else(|| invalid(&format!("items of `{camel}` must be objects")))
+        })
+        .collect()
+}
+
+fn string(object: &JsonObject, camel: &str, snake: &str) -> Result<String, Error> {
+    match field(object, camel, snake) {
+        None => Ok(String::new()),
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(_) => Err(invalid(&format!("field `{camel}` must be a string"))),
+    }
+}
+
+fn boolean(object: &JsonObject, camel: &str, snake: &str) -> Result<bool, Error> {
+    match field(object, camel, snake) {
+        None => Ok(false),
+        Some(Value::Bool(b)) => Ok(*b),
+        Some(_) => Err(invalid(&format!("field `{camel}` must be a boolean"))),
+    }
+}
+
+fn u64_value(value: &Value, name: &str) -> Result<u64, Error> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(&format!("field `{name}` must be an unsigned integer")))
+}
+
+fn i64_value(value: &Value, name: &str) -> Result<i64, Error> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(&format!("field `{name}` must be an integer")))
+}
+
+fn f64_value(value: &Value, name: &str) -> Result<f64, Error> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Some(f64::NAN),
+            "Infinity" => Some(f64::INFINITY),
+            "-Infinity" => Some(f64::NEG_INFINITY),
+            _ => s.parse().ok(),
+        },
+        _ => None,
+    }
+    .ok_or_else(|| invalid(&format!("field `{name}` must be a number")))
+}
+
+fn u64_field(object: &JsonObject, camel: &str, snake: &str) -> Result<u64, Error> {
+    field(object, camel, snake).map_or(Ok(0), |v| u64_value(v, camel))
+}
+
+fn u32_field(object: &JsonObject, camel: &str, snake: &str) -> Result<u32, Error> {
+    u32::try_from(u64_field(object, camel, snake)?)
+        .map_err(|_| invalid(&format!("field `{camel}` is out of range")))
+}
+
+fn i32_field(object: &JsonObject, camel: &str, snake: &str) -> Result<i32, Error> {
+    let value = field(object, camel, snake).map_or(Ok(0), |v| i64_value(v, camel))?;
+    i32::try_from(value).map_err(|_| invalid(&format!("field `{camel}` is out of range")))
+}
+
+fn f64_field(object: &JsonObject, camel: &str, snake: &str) -> Result<f64, Error> {
+    field(object, camel, snake).map_or(Ok(0.0), |v| f64_value(v, camel))
+}
+
+fn optional_f64_field(object: &JsonObject, camel: &str, snake: &str) -> Result<Option<f64>, Error> {
+    field(object, camel, snake)
+        .map(|v| f64_value(v, camel))
+        .transpose()
+}
+
+fn u64_array(object: &JsonObject, camel: &str, snake: &str) -> Result<Vec<u64>, Error> {
+    array(object, camel, snake)?
+        .iter()
+        .map(|v| u64_value(v, camel))
+        .collect()
+}
+
+fn f64_array(object: &JsonObject, camel: &str, snake: &str) -> Result<Vec<f64>, Error> {
+    array(object, camel, snake)?
+        .iter()
+        .map(|v| f64_value(v, camel))
+        .collect()
+}
+
+/// Decodes a proto enum encoded either as an integer or as its name.
+fn enum_field(
+    object: &JsonObject,
+    camel: &str,
+    snake: &str,
+    from_name: fn(&str) -> Option<i32>,
+) -> Result<i32, Error> {
+    match field(object, camel, snake) {
+        None => Ok(0),
+        Some(Value::String(name)) => {
+            from_name(name).ok_or_else(|| invalid(&format!("unknown `{camel}` value `{name}`")))
+        }
+        Some(value) => i32::try_from(i64_value(value, camel)?)
+            .map_err(|_| invalid(&format!("field `{camel}` is out of range"))),
+    }
+}
+
+/// Decodes a trace or span id. OTLP-JSON encodes them as hex strings.
+fn id_field(object: &JsonObject, camel: &str, snake: &str) -> Result<Vec<u8>, Error> {
+    let hex = string(object, camel, snake)?;
+    if hex.len() % 2 != 0 {
+        return Err(invalid(&format!("field `{camel}` must be a hex string")));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| invalid(&format!("field `{camel}` must be a hex string")))
+        })
+        .collect()
+}
+
+// Common messages ============================================================
+
+fn any_value(object: &JsonObject) -> Result<AnyValue, Error> {
+    let value = if let Some(v) = field(object, "stringValue", "string_value") {
+        Some(any_value::Value::StringValue(
+            v.as_str()
+                .ok_or_else(|| invalid("`stringValue` must be a string"))?
+                .to_owned(),
+        ))
+    } else if let Some(v) = field(object, "boolValue", "bool_value") {
+        Some(any_value::Value::BoolValue(
+            v.as_bool()
+                .ok_or_else(|| invalid("`boolValue` must be a boolean"))?,
+        ))
+    } else if let Some(v) = field(object, "intValue", "int_value") {
+        Some(any_value::Value::IntValue(i64_value(v, "intValue")?))
+    } else if let Some(v) = field(object, "doubleValue", "double_value") {
+        Some(any_value::Value::DoubleValue(f64_value(v, "doubleValue")?))
+    } else if let Some(array_value) = nested(object, "arrayValue", "array_value")? {
+        Some(any_value::Value::ArrayValue(ArrayValue {
+            values: objects(array_value, "values", "values")?
+                .into_iter()
+                .map(any_value)
+                .collect::<Result<_, _>>()?,
+        }))
+    } else if let Some(kvlist_value) = nested(object, "kvlistValue", "kvlist_value")? {
+        Some(any_value::Value::KvlistValue(KeyValueList {
+            values: key_values(kvlist_value, "values", "values")?,
+        }))
+    } else if let Some(v) = field(object, "bytesValue", "bytes_value") {
+        let encoded = v
+            .as_str()
+            .ok_or_else(|| invalid("`bytesValue` must be a base64 string"))?;
+        Some(any_value::Value::BytesValue(
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| invalid(&format!("`bytesValue` is not valid base64: {e}")))?,
+        ))
+    } else {
+        None
+    };
+    Ok(AnyValue { value })
+}
+
+fn key_values(object: &JsonObject, camel: &str, snake: &str) -> Result<Vec<KeyValue>, Error> {
+    objects(object, camel, snake)?
+        .into_iter()
+        .map(|kv| {
+            Ok(KeyValue {
+                key: string(kv, "key", "key")?,
+                value: nested(kv, "value", "value")?.map(any_value).transpose()?,
+            })
+        })
+        .collect()
+}
+
+fn attributes(object: &JsonObject) -> Result<Vec<KeyValue>, Error> {
+    key_values(object, "attributes", "attributes")
+}
+
+fn resource(object: &JsonObject) -> Result<Resource, Error> {
+    Ok(Resource {
+        attributes: attributes(object)?,
+        dropped_attributes_count: u32_field(
+            object,
+            "droppedAttributesCount",
+            "dropped_attributes_count",
+        )?,
+        entity_refs: Vec::new(),
+    })
+}
+
+fn scope(object: &JsonObject) -> Result<InstrumentationScope, Error> {
+    Ok(InstrumentationScope {
+        name: string(object, "name", "name")?,
+        version: string(object, "version", "version")?,
+        attributes: attributes(object)?,
+        dropped_attributes_count: u32_field(
+            object,
+            "droppedAttributesCount",
+            "dropped_attributes_count",
+        )?,
+    })
+}
+
+// Traces =====================================================================
+
+fn resource_spans(object: &JsonObject) -> Result<ResourceSpans, Error> {
+    Ok(ResourceSpans {
+        resource: nested(object, "resource", "resource")?
+            .map(resource)
+            .transpose()?,
+        scope_spans: objects(object, "scopeSpans", "scope_spans")?
+            .into_iter()
+            .map(|scope_spans| {
+                Ok(ScopeSpans {
+                    scope: nested(scope_spans, "scope", "scope")?
+                        .map(scope)
+                        .transpose()?,
+                    spans: objects(scope_spans, "spans", "spans")?
+                        .into_iter()
+                        .map(span)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: string(scope_spans, "schemaUrl", "schema_url")?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        schema_url: string(object, "schemaUrl", "schema_url")?,
+    })
+}
+
+fn span(object: &JsonObject) -> Result<Span, Error> {
+    Ok(Span {
+        trace_id: id_field(object, "traceId", "trace_id")?,
+        span_id: id_field(object, "spanId", "span_id")?,
+        trace_state: string(object, "traceState", "trace_state")?,
+        parent_span_id: id_field(object, "parentSpanId", "parent_span_id")?,
+        flags: u32_field(object, "flags", "flags")?,
+        name: string(object, "name", "name")?,
+        kind: enum_field(object, "kind", "kind", |name| {
+            span::SpanKind::from_str_name(name).map(|kind| kind as i32)
+        })?,
+        start_time_unix_nano: u64_field(object, "startTimeUnixNano", "start_time_unix_nano")?,
+        end_time_unix_nano: u64_field(object, "endTimeUnixNano", "end_time_unix_nano")?,
+        attributes: attributes(object)?,
+        dropped_attributes_count: u32_field(
+            object,
+            "droppedAttributesCount",
+            "dropped_attributes_count",
+        )?,
+        events: objects(object, "events", "events")?
+            .into_iter()
+            .map(|event| {
+                Ok(span::Event {
+                    time_unix_nano: u64_field(event, "timeUnixNano", "time_unix_nano")?,
+                    name: string(event, "name", "name")?,
+                    attributes: attributes(event)?,
+                    dropped_attributes_count: u32_field(
+                        event,
+                        "droppedAttributesCount",
+                        "dropped_attributes_count",
+                    )?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        dropped_events_count: u32_field(object, "droppedEventsCount", "dropped_events_count")?,
+        links: objects(object, "links", "links")?
+            .into_iter()
+            .map(|link| {
+                Ok(span::Link {
+                    trace_id: id_field(link, "traceId", "trace_id")?,
+                    span_id: id_field(link, "spanId", "span_id")?,
+                    trace_state: string(link, "traceState", "trace_state")?,
+                    attributes: attributes(link)?,
+                    dropped_attributes_count: u32_field(
+                        link,
+                        "droppedAttributesCount",
+                        "dropped_attributes_count",
+                    )?,
+                    flags: u32_field(link, "flags", "flags")?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        dropped_links_count: u32_field(object, "droppedLinksCount", "dropped_links_count")?,
+        status: nested(object, "status", "status")?
+            .map(|status| {
+                Ok::<_, Error>(Status {
+                    message: string(status, "message", "message")?,
+                    code: enum_field(status, "code", "code", |name| {
+                        status::StatusCode::from_str_name(name).map(|code| code as i32)
+                    })?,
+                })
+            })
+            .transpose()?,
+    })
+}
+
+// Metrics ====================================================================
+
+fn resource_metrics(object: &JsonObject) -> Result<ResourceMetrics, Error> {
+    Ok(ResourceMetrics {
+        resource: nested(object, "resource", "resource")?
+            .map(resource)
+            .transpose()?,
+        scope_metrics: objects(object, "scopeMetrics", "scope_metrics")?
+            .into_iter()
+            .map(|scope_metrics| {
+                Ok(ScopeMetrics {
+                    scope: nested(scope_metrics, "scope", "scope")?
+                        .map(scope)
+                        .transpose()?,
+                    metrics: objects(scope_metrics, "metrics", "metrics")?
+                        .into_iter()
+                        .map(metric)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: string(scope_metrics, "schemaUrl", "schema_url")?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        schema_url: string(object, "schemaUrl", "schema_url")?,
+    })
+}
+
+fn aggregation_temporality(object: &JsonObject) -> Result<i32, Error> {
+    enum_field(
+        object,
+        "aggregationTemporality",
+        "aggregation_temporality",
+        |name| AggregationTemporality::from_str_name(name).map(|t| t as i32),
+    )
+}
+
+fn metric(object: &JsonObject) -> Result<Metric, Error> {
+    let data = if let Some(gauge) = nested(object, "gauge", "gauge")? {
+        Some(metric::Data::Gauge(Gauge {
+            data_points: number_data_points(gauge)?,
+        }))
+    } else if let Some(sum) = nested(object, "sum", "sum")? {
+        Some(metric::Data::Sum(Sum {
+            data_points: number_data_points(sum)?,
+            aggregation_temporality: aggregation_temporality(sum)?,
+            is_monotonic: boolean(sum, "isMonotonic", "is_monotonic")?,
+        }))
+    } else if let Some(histogram) = nested(object, "histogram", "histogram")? {
+        Some(metric::Data::Histogram(Histogram {
+            data_points: objects(histogram, "dataPoints", "data_points")?
+                .into_iter()
+                .map(histogram_data_point)
+                .collect::<Result<_, _>>()?,
+            aggregation_temporality: aggregation_temporality(histogram)?,
+        }))
+    } else if let Some(histogram) = nested(object, "exponentialHistogram", "exponential_histogram")?
+    {
+        Some(metric::Data::ExponentialHistogram(ExponentialHistogram {
+            data_points: objects(histogram, "dataPoints", "data_points")?
+                .into_iter()
+                .map(exponential_histogram_data_point)
+                .collect::<Result<_, _>>()?,
+            aggregation_temporality: aggregation_temporality(histogram)?,
+        }))
+    } else if let Some(summary) = nested(object, "summary", "summary")? {
+        Some(metric::Data::Summary(Summary {
+            data_points: objects(summary, "dataPoints", "data_points")?
+                .into_iter()
+                .map(summary_data_point)
+                .collect::<Result<_, _>>()?,
+        }))
+    } else {
+        None
+    };
+
+    Ok(Metric {
+        name: string(object, "name", "name")?,
+        description: string(object, "description", "description")?,
+        unit: string(object, "unit", "unit")?,
+        metadata: key_values(object, "metadata", "metadata")?,
+        data,
+    })
+}
+
+fn number_data_points(object: &JsonObject) -> Result<Vec<NumberDataPoint>, Error> {
+    objects(object, "dataPoints", "data_points")?
+        .into_iter()
+        .map(|point| {
+            let value = if let Some(v) = field(point, "asDouble", "as_double") {
+                Some(number_data_point::Value::AsDouble(f64_value(
+                    v, "asDouble",
+                )?))
+            } else if let Some(v) = field(point, "asInt", "as_int") {
+                Some(number_data_point::Value::AsInt(i64_value(v, "asInt")?))
+            } else {
+                None
+            };
+            Ok(NumberDataPoint {
+                attributes: attributes(point)?,
+                start_time_unix_nano: u64_field(
+                    point,
+                    "startTimeUnixNano",
+                    "start_time_unix_nano",
+                )?,
+                time_unix_nano: u64_field(point, "timeUnixNano", "time_unix_nano")?,
+                exemplars: exemplars(point)?,
+                flags: u32_field(point, "flags", "flags")?,
+                value,
+            })
+        })
+        .collect()
+}
+
+fn histogram_data_point(point: &JsonObject) -> Result<HistogramDataPoint, Error> {
+    Ok(HistogramDataPoint {
+        attributes: attributes(point)?,
+        start_time_unix_nano: u64_field(point, "startTimeUnixNano", "start_time_unix_nano")?,
+        time_unix_nano: u64_field(point, "timeUnixNano", "time_unix_nano")?,
+        count: u64_field(point, "count", "count")?,
+        sum: optional_f64_field(point, "sum", "sum")?,
+        bucket_counts: u64_array(point, "bucketCounts", "bucket_counts")?,
+        explicit_bounds: f64_array(point, "explicitBounds", "explicit_bounds")?,
+        exemplars: exemplars(point)?,
+        flags: u32_field(point, "flags", "flags")?,
+        min: optional_f64_field(point, "min", "min")?,
+        max: optional_f64_field(point, "max", "max")?,
+    })
+}
+
+fn exponential_histogram_data_point(
+    point: &JsonObject,
+) -> Result<ExponentialHistogramDataPoint, Error> {
+    let buckets = |camel: &str| {
+        nested(point, camel, camel)?
+            .map(|buckets| {
+                Ok::<_, Error>(exponential_histogram_data_point::Buckets {
+                    offset: i32_field(buckets, "offset", "offset")?,
+                    bucket_counts: u64_array(buckets, "bucketCounts", "bucket_counts")?,
+                })
+            })
+            .transpose()
+    };
+    Ok(ExponentialHistogramDataPoint {
+        attributes: attributes(point)?,
+        start_time_unix_nano: u64_field(point, "startTimeUnixNano", "start_time_unix_nano")?,
+        time_unix_nano: u64_field(point, "timeUnixNano", "time_unix_nano")?,
+        count: u64_field(point, "count", "count")?,
+        sum: optional_f64_field(point, "sum", "sum")?,
+        scale: i32_field(point, "scale", "scale")?,
+        zero_count: u64_field(point, "zeroCount", "zero_count")?,
+        positive: buckets("positive")?,
+        negative: buckets("negative")?,
+        flags: u32_field(point, "flags", "flags")?,
+        exemplars: exemplars(point)?,
+        min: optional_f64_field(point, "min", "min")?,
+        max: optional_f64_field(point, "max", "max")?,
+        zero_threshold: f64_field(point, "zeroThreshold", "zero_threshold")?,
+    })
+}
+
+fn summary_data_point(point: &JsonObject) -> Result<SummaryDataPoint, Error> {
+    Ok(SummaryDataPoint {
+        attributes: attributes(point)?,
+        start_time_unix_nano: u64_field(point, "startTimeUnixNano", "start_time_unix_nano")?,
+        time_unix_nano: u64_field(point, "timeUnixNano", "time_unix_nano")?,
+        count: u64_field(point, "count", "count")?,
+        sum: f64_field(point, "sum", "sum")?,
+        quantile_values: objects(point, "quantileValues", "quantile_values")?
+            .into_iter()
+            .map(|quantile| {
+                Ok(summary_data_point::ValueAtQuantile {
+                    quantile: f64_field(quantile, "quantile", "quantile")?,
+                    value: f64_field(quantile, "value", "value")?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        flags: u32_field(point, "flags", "flags")?,
+    })
+}
+
+fn exemplars(object: &JsonObject) -> Result<Vec<Exemplar>, Error> {
+    objects(object, "exemplars", "exemplars")?
+        .into_iter()
+        .map(|ex| {
+            let value = if let Some(v) = field(ex, "asDouble", "as_double") {
+                Some(exemplar::Value::AsDouble(f64_value(v, "asDouble")?))
+            } else if let Some(v) = field(ex, "asInt", "as_int") {
+                Some(exemplar::Value::AsInt(i64_value(v, "asInt")?))
+            } else {
+                None
+            };
+            Ok(Exemplar {
+                filtered_attributes: key_values(ex, "filteredAttributes", "filtered_attributes")?,
+                time_unix_nano: u64_field(ex, "timeUnixNano", "time_unix_nano")?,
+                span_id: id_field(ex, "spanId", "span_id")?,
+                trace_id: id_field(ex, "traceId", "trace_id")?,
+                value,
+            })
+        })
+        .collect()
+}
+
+// Logs =======================================================================
+
+fn resource_logs(object: &JsonObject) -> Result<ResourceLogs, Error> {
+    Ok(ResourceLogs {
+        resource: nested(object, "resource", "resource")?
+            .map(resource)
+            .transpose()?,
+        scope_logs: objects(object, "scopeLogs", "scope_logs")?
+            .into_iter()
+            .map(|scope_logs| {
+                Ok(ScopeLogs {
+                    scope: nested(scope_logs, "scope", "scope")?
+                        .map(scope)
+                        .transpose()?,
+                    log_records: objects(scope_logs, "logRecords", "log_records")?
+                        .into_iter()
+                        .map(log_record)
+                        .collect::<Result<_, _>>()?,
+                    schema_url: string(scope_logs, "schemaUrl", "schema_url")?,
+                })
+            })
+            .collect::<Result<_, Error>>()?,
+        schema_url: string(object, "schemaUrl", "schema_url")?,
+    })
+}
+
+fn log_record(object: &JsonObject) -> Result<LogRecord, Error> {
+    Ok(LogRecord {
+        time_unix_nano: u64_field(object, "timeUnixNano", "time_unix_nano")?,
+        observed_time_unix_nano: u64_field(
+            object,
+            "observedTimeUnixNano",
+            "observed_time_unix_nano",
+        )?,
+        severity_number: enum_field(object, "severityNumber", "severity_number", |name| {
+            SeverityNumber::from_str_name(name).map(|severity| severity as i32)
+        })?,
+        severity_text: string(object, "severityText", "severity_text")?,
+        body: nested(object, "body", "body")?.map(any_value).transpose()?,
+        attributes: attributes(object)?,
+        dropped_attributes_count: u32_field(
+            object,
+            "droppedAttributesCount",
+            "dropped_attributes_count",
+        )?,
+        flags: u32_field(object, "flags", "flags")?,
+        trace_id: id_field(object, "traceId", "trace_id")?,
+        span_id: id_field(object, "spanId", "span_id")?,
+        event_name: string(object, "eventName", "event_name")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_from_json() {
+        let payload = r#"{
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [{"key": "service.name", "value": {"stringValue": "shop"}}]
+                },
+                "scopeSpans": [{
+                    "scope": {"name": "my.library", "version": "1.0.0"},
+                    "spans": [{
+                        "traceId": "5b8efff798038103d269b633813fc60c",
+                        "spanId": "eee19b7ec3c1b174",
+                        "name": "GET /cart",
+                        "kind": 2,
+                        "startTimeUnixNano": "1544712660000000000",
+                        "endTimeUnixNano": 1544712661000000000,
+                        "attributes": [
+                            {"key": "http.request.method", "value": {"stringValue": "GET"}},
+                            {"key": "http.response.status_code", "value": {"intValue": "200"}},
+                            {"key": "tags", "value": {"arrayValue": {"values": [{"stringValue": "a"}]}}}
+                        ],
+                        "events": [{"name": "exception", "timeUnixNano": "1544712660500000000"}],
+                        "status": {"code": "STATUS_CODE_ERROR", "message": "boom"}
+                    }]
+                }]
+            }]
+        }"#;
+        let request = traces_request_from_json(payload.as_bytes()).unwrap();
+        assert_eq!(request.resource_spans.len(), 1);
+        let resource_spans = &request.resource_spans[0];
+        assert_eq!(
+            resource_spans.resource.as_ref().unwrap().attributes[0].key,
+            "service.name"
+        );
+        let span = &resource_spans.scope_spans[0].spans[0];
+        assert_eq!(span.name, "GET /cart");
+        assert_eq!(span.kind(), span::SpanKind::Server);
+        assert_eq!(span.trace_id.len(), 16);
+        assert_eq!(
+            span.span_id,
+            vec![0xee, 0xe1, 0x9b, 0x7e, 0xc3, 0xc1, 0xb1, 0x74]
+        );
+        assert_eq!(span.start_time_unix_nano, 1_544_712_660_000_000_000);
+        assert_eq!(span.end_time_unix_nano, 1_544_712_661_000_000_000);
+        assert_eq!(span.attributes.len(), 3);
+        assert_eq!(
+            span.attributes[1].value.as_ref().unwrap().value,
+            Some(any_value::Value::IntValue(200))
+        );
+        assert_eq!(span.events[0].name, "exception");
+        assert_eq!(
+            span.status.as_ref().unwrap().code(),
+            status::StatusCode::Error
+        );
+    }
+
+    #[test]
+    fn test_metrics_from_json() {
+        let payload = r#"{
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [
+                        {
+                            "name": "http.server.request.duration",
+                            "unit": "s",
+                            "histogram": {
+                                "aggregationTemporality": 2,
+                                "dataPoints": [{
+                                    "count": "2",
+                                    "sum": 0.5,
+                                    "bucketCounts": ["1", "1"],
+                                    "explicitBounds": [0.1],
+                                    "attributes": [{"key": "http.request.method", "value": {"stringValue": "GET"}}]
+                                }]
+                            }
+                        },
+                        {
+                            "name": "queue.size",
+                            "unit": "{item}",
+                            "sum": {"isMonotonic": false, "dataPoints": [{"asInt": "3"}]}
+                        }
+                    ]
+                }]
+            }]
+        }"#;
+        let request = metrics_request_from_json(payload.as_bytes()).unwrap();
+        let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 2);
+        match &metrics[0].data {
+            Some(metric::Data::Histogram(histogram)) => {
+                assert_eq!(histogram.data_points[0].count, 2);
+                assert_eq!(histogram.data_points[0].bucket_counts, vec![1, 1]);
+                assert_eq!(histogram.data_points[0].sum, Some(0.5));
+            }
+            other => panic!("Expected a histogram, got {other:?}"),
+        }
+        match &metrics[1].data {
+            Some(metric::Data::Sum(sum)) => {
+                assert!(!sum.is_monotonic);
+                assert_eq!(
+                    sum.data_points[0].value,
+                    Some(number_data_point::Value::AsInt(3))
+                );
+            }
+            other => panic!("Expected a sum, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_logs_from_json() {
+        let payload = r#"{
+            "resource_logs": [{
+                "scope_logs": [{
+                    "log_records": [{
+                        "event_name": "session.start",
+                        "severity_number": "SEVERITY_NUMBER_INFO",
+                        "body": {"kvlistValue": {"values": [{"key": "k", "value": {"boolValue": true}}]}},
+                        "attributes": [{"key": "bytes", "value": {"bytesValue": "aGVsbG8="}}]
+                    }]
+                }]
+            }]
+        }"#;
+        let request = logs_request_from_json(payload.as_bytes()).unwrap();
+        let record = &request.resource_logs[0].scope_logs[0].log_records[0];
+        assert_eq!(record.event_name, "session.start");
+        assert_eq!(record.severity_number(), SeverityNumber::Info);
+        assert_eq!(
+            record.attributes[0].value.as_ref().unwrap().value,
+            Some(any_value::Value::BytesValue(b"hello".to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(traces_request_from_json(b"[]").is_err());
+        assert!(traces_request_from_json(b"{not json").is_err());
+        assert!(traces_request_from_json(
+            br#"{"resourceSpans": [{"scopeSpans": [{"spans": [{"traceId": "xyz"}]}]}]}"#
+        )
+        .is_err());
+        // An empty object is a valid (empty) request.
+        assert!(traces_request_from_json(b"{}")
+            .unwrap()
+            .resource_spans
+            .is_empty());
+    }
+}
@@ -8,6 +8,7 @@ use std::path::{Path, PathBuf};
 
 use clap::Args;
 use log::info;
+use weaver_common::cache::CacheSettings;
 use weaver_common::log_success;
 
 use weaver_common::diagnostic::{DiagnosticMessage, DiagnosticMessages};
@@ -56,7 +57,10 @@ fn write_yaml(path: &Path, data: &impl serde::Serialize) -> Result<(), Diagnosti
 }
 
 /// Package a semantic convention registry.
-pub(crate) fn command(args: &RegistryPackageArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryPackageArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     info!("Packaging registry `{}`", args.registry.registry);
 
     // we only support packaging v2 registries
@@ -65,11 +69,11 @@ pub(crate) fn command(args: &RegistryPackageArgs) -> Result<ExitDirectives, Diag
     }
 
     let mut diag_msgs = DiagnosticMessages::empty();
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
     let registry_path = &args.registry.registry;
 
     let mut nfes = vec![];
-    let repo = RegistryRepo::try_new(None, registry_path, &mut nfes)?;
+    let repo = RegistryRepo::try_new(None, registry_path, cache_settings, &mut nfes)?;
     diag_msgs.extend_from_vec(nfes.into_iter().map(DiagnosticMessage::new).collect());
 
     // we require a definition manifest file to be present for packaging
@@ -172,7 +176,7 @@ mod tests {
             "https://test/semconv/1.0.0/resolved.yaml",
         );
 
-        let result = command(&args);
+        let result = command(&args, &CacheSettings::default());
         assert!(result.is_ok(), "Expected success, got: {result:?}");
 
         // resolved.yaml must exist, be valid YAML, and contain the v2 resolved schema format
@@ -222,7 +226,7 @@ mod tests {
             "https://test/semconv/1.0.0/resolved.yaml",
         );
 
-        let result = command(&args);
+        let result = command(&args, &CacheSettings::default());
         assert!(result.is_ok(), "Expected success, got: {result:?}");
         assert!(nested_output.exists(), "output directory was not created");
         assert!(nested_output.join("resolved.yaml").exists());
@@ -240,7 +244,7 @@ mod tests {
             "https://test/semconv/1.0.0/resolved.yaml",
         );
 
-        let result = command(&args);
+        let result = command(&args, &CacheSettings::default());
         assert!(result.is_err());
         let diag_msgs = result.unwrap_err();
         let msg = format!("{diag_msgs:?}");
@@ -261,7 +265,7 @@ mod tests {
             "https://test/semconv/1.0.0/resolved.yaml",
         );
 
-        let result = command(&args);
+        let result = command(&args, &CacheSettings::default());
         assert!(result.is_err());
         let diag_msgs = result.unwrap_err();
         let msg = format!("{diag_msgs:?}");
@@ -282,7 +286,7 @@ mod tests {
             "https://test/semconv/1.0.0/resolved.yaml",
         );
 
-        let result = command(&args);
+        let result = command(&args, &CacheSettings::default());
         assert!(
             result.is_err(),
             "Expected resolution failure for invalid definition"
@@ -8,6 +8,7 @@ use clap::Args;
 
 use log::info;
 use miette::Diagnostic;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::{is_future_mode_enabled, DiagnosticMessage, DiagnosticMessages};
 use weaver_forge::{OutputProcessor, OutputTarget};
 use weaver_semconv::registry_repo::RegistryRepo;
@@ -57,7 +58,10 @@ pub struct RegistryResolveArgs {
 
 /// Resolve a semantic convention registry and write the resolved schema to a
 /// file or print it to stdout.
-pub(crate) fn command(args: &RegistryResolveArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryResolveArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     // Display deprecation warning
     if is_future_mode_enabled() {
         return Err(DiagnosticMessages::from_error(Error::Deprecated));
@@ -68,11 +72,11 @@ pub(crate) fn command(args: &RegistryResolveArgs) -> Result<ExitDirectives, Diag
 
     info!("Resolving registry `{}`", args.registry.registry);
     let mut diag_msgs = DiagnosticMessages::empty();
-    let weaver = WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = WeaverEngine::new(&args.registry, &args.policy, cache_settings);
     let registry_path = &args.registry.registry;
 
     let mut nfes = vec![];
-    let main_registry_repo = RegistryRepo::try_new(None, registry_path, &mut nfes)?;
+    let main_registry_repo = RegistryRepo::try_new(None, registry_path, cache_settings, &mut nfes)?;
 
     diag_msgs.extend_from_vec(nfes.into_iter().map(DiagnosticMessage::new).collect());
 
@@ -129,6 +133,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Resolve(RegistryResolveArgs {
                     registry: RegistryArgs {
@@ -161,6 +166,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::Resolve(RegistryResolveArgs {
                     registry: RegistryArgs {
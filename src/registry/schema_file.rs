@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generate an OTel telemetry schema file (OTEP 0152) from two versions of a semantic
+//! convention registry.
+
+use crate::registry::{Error, PolicyArgs, RegistryArgs};
+use crate::weaver::WeaverEngine;
+use crate::{DiagnosticArgs, ExitDirectives};
+use clap::Args;
+use log::info;
+use std::path::PathBuf;
+use weaver_common::cache::CacheSettings;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::vdir::VirtualDirectoryPath;
+use weaver_otel_schema::TelemetrySchema;
+use weaver_semconv::registry_repo::RegistryRepo;
+use weaver_version::{Version, Versions};
+
+/// Parameters for the `registry schema-file` sub-command
+#[derive(Debug, Args)]
+pub struct RegistrySchemaFileArgs {
+    /// Parameters to specify the semantic convention registry
+    #[command(flatten)]
+    registry: RegistryArgs,
+
+    /// Parameters to specify the baseline semantic convention registry
+    #[arg(long)]
+    baseline_registry: VirtualDirectoryPath,
+
+    /// Path to an existing telemetry schema file to extend with the new version.
+    /// If not specified, a new telemetry schema file is created.
+    #[arg(long)]
+    schema_file: Option<PathBuf>,
+
+    /// Version of the changes to add to the telemetry schema file.
+    /// Defaults to the version of the registry manifest.
+    #[arg(long)]
+    version: Option<String>,
+
+    /// URL at which the telemetry schema file is published.
+    /// Defaults to the schema URL of the registry manifest.
+    #[arg(long)]
+    schema_url: Option<String>,
+
+    /// Path to the telemetry schema file to write.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub(crate) diagnostic: DiagnosticArgs,
+}
+
+/// Generate an OTel telemetry schema file from two versions of a semantic convention registry.
+pub(crate) fn command(
+    args: &RegistrySchemaFileArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.registry.v2 {
+        return Err(Error::SchemaFileRequiresV1.into());
+    }
+
+    let mut diag_msgs = DiagnosticMessages::empty();
+    let policy_config = PolicyArgs {
+        policies: vec![],
+        skip_policies: true,
+        display_policy_coverage: false,
+    };
+    let weaver = WeaverEngine::new(&args.registry, &policy_config, cache_settings);
+
+    info!("Weaver Registry Schema File");
+    info!(
+        "Comparing registry `{}` with baseline registry `{}`",
+        args.registry.registry, args.baseline_registry
+    );
+
+    let main_registry_repo =
+        RegistryRepo::try_new(None, &args.registry.registry, cache_settings, &mut vec![])?;
+    let baseline_registry_repo =
+        RegistryRepo::try_new(None, &args.baseline_registry, cache_settings, &mut vec![])?;
+    let main = weaver.load_definitions(main_registry_repo, &mut diag_msgs)?;
+    let baseline = weaver.load_definitions(baseline_registry_repo, &mut diag_msgs)?;
+    let main_resolved = weaver.resolve(main, &mut diag_msgs)?;
+    let baseline_resolved = weaver.resolve(baseline, &mut diag_msgs)?;
+
+    if diag_msgs.has_error() {
+        return Err(diag_msgs);
+    }
+
+    let main_schema = main_resolved.resolved_schema();
+    let changes = main_schema.diff(baseline_resolved.resolved_schema());
+    let manifest = main_schema.registry_manifest.as_ref();
+
+    let version = args
+        .version
+        .as_deref()
+        .or(manifest.map(|manifest| manifest.version()))
+        .ok_or_else(|| Error::SchemaFileVersion {
+            version: String::new(),
+            error: "The registry manifest doesn't define a version, use `--version`.".to_owned(),
+        })?;
+    let version: Version =
+        version
+            .parse()
+            .map_err(|e: weaver_version::Error| Error::SchemaFileVersion {
+                version: version.to_owned(),
+                error: e.to_string(),
+            })?;
+
+    let schema_url = args
+        .schema_url
+        .clone()
+        .or(manifest.map(|manifest| manifest.schema_url().as_str().to_owned()));
+    let mut schema = match &args.schema_file {
+        Some(path) => TelemetrySchema::try_from_file(path)?,
+        None => TelemetrySchema::new(schema_url.clone().ok_or_else(|| {
+            Error::SchemaFileVersion {
+                version: version.to_string(),
+                error: "The registry manifest doesn't define a schema URL, use `--schema-url`."
+                    .to_owned(),
+            }
+        })?),
+    };
+    if let Some(schema_url) = schema_url {
+        schema.schema_url = schema_url;
+    }
+
+    let versions = schema.versions.get_or_insert_with(Versions::default);
+    if let Some(latest) = versions.latest_version() {
+        if latest >= version {
+            return Err(Error::SchemaFileVersion {
+                version: version.to_string(),
+                error: format!(
+                    "The telemetry schema file already defines the version `{latest}`, the new version must be greater."
+                ),
+            }
+            .into());
+        }
+    }
+    let spec = changes.version_spec();
+    if spec.is_empty() {
+        info!("No renames found, the version `{version}` doesn't define any change");
+    }
+    _ = versions.insert(version, spec);
+
+    schema.write_to_file(&args.output)?;
+    info!(
+        "Telemetry schema file written to `{}`",
+        args.output.display()
+    );
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        warnings: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Cli, Commands};
+    use crate::registry::schema_file::RegistrySchemaFileArgs;
+    use crate::registry::{RegistryArgs, RegistryCommand, RegistrySubCommand};
+    use crate::run_command;
+    use weaver_common::vdir::VirtualDirectoryPath;
+    use weaver_otel_schema::TelemetrySchema;
+
+    fn schema_file_cli(schema_file: Option<&str>, output: &std::path::Path, v2: bool) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            future: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::SchemaFile(RegistrySchemaFileArgs {
+                    registry: RegistryArgs {
+                        registry: VirtualDirectoryPath::LocalFolder {
+                            path: "tests/schema_file/registry_head/".to_owned(),
+                        },
+                        follow_symlinks: false,
+                        include_unreferenced: false,
+                        v2,
+                    },
+                    baseline_registry: VirtualDirectoryPath::LocalFolder {
+                        path: "tests/schema_file/registry_baseline/".to_owned(),
+                    },
+                    schema_file: schema_file.map(Into::into),
+                    version: None,
+                    schema_url: None,
+                    output: output.to_path_buf(),
+                    diagnostic: Default::default(),
+                }),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_registry_schema_file() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let output = temp_dir.path().join("1.1.0");
+
+        let exit_directive = run_command(&schema_file_cli(
+            Some("tests/schema_file/schemas/1.0.0"),
+            &output,
+            false,
+        ));
+        assert_eq!(exit_directive.exit_code, 0);
+
+        let schema = TelemetrySchema::try_from_file(&output).expect("Invalid schema file");
+        assert_eq!(schema.schema_url, "https://example.com/schemas/1.1.0");
+        let versions = schema.versions.expect("Missing versions");
+        assert_eq!(versions.len(), 2);
+        let (version, spec) = versions.versions_desc().remove(0);
+        assert_eq!(version.to_string(), "1.1.0");
+        let all = &spec.all.as_ref().expect("Missing `all` changes").changes;
+        assert_eq!(
+            all[0].rename_attributes.attribute_map["test.old_name"],
+            "test.new_name"
+        );
+        let metrics = &spec
+            .metrics
+            .as_ref()
+            .expect("Missing metric changes")
+            .changes;
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics[0].rename_attributes.apply_to_metrics,
+            vec!["test.requests".to_owned()]
+        );
+        assert_eq!(
+            metrics[0].rename_attributes.attribute_map["test.old_state"],
+            "test.state"
+        );
+        assert_eq!(
+            metrics[1].rename_metrics["test.old_duration"],
+            "test.duration"
+        );
+
+        // The version is already defined in the produced schema file.
+        let exit_directive = run_command(&schema_file_cli(
+            Some(output.to_str().expect("Invalid path")),
+            &temp_dir.path().join("1.1.0-bis"),
+            false,
+        ));
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+
+    #[test]
+    fn test_registry_schema_file_rejects_v2() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let output = temp_dir.path().join("1.1.0");
+
+        let exit_directive = run_command(&schema_file_cli(None, &output, true));
+        assert_eq!(exit_directive.exit_code, 1);
+        assert!(!output.exists());
+    }
+}
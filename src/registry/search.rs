@@ -6,6 +6,7 @@ use clap::Args;
 use itertools::Itertools;
 use log::info;
 use miette::Diagnostic;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_resolved_schema::{attribute::Attribute, ResolvedTelemetrySchema};
 
@@ -373,7 +374,10 @@ fn run_command_line_search(schema: &ResolvedTelemetrySchema, pattern: &str) {
     println!("{results}");
 }
 
-pub(crate) fn command(args: &RegistrySearchArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistrySearchArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     info!("Resolving registry `{}`", args.registry.registry);
 
     // Check for V2 schema incompatibility
@@ -387,7 +391,7 @@ pub(crate) fn command(args: &RegistrySearchArgs) -> Result<ExitDirectives, Diagn
         skip_policies: true,
         display_policy_coverage: false,
     };
-    let weaver = WeaverEngine::new(&args.registry, &policy_config);
+    let weaver = WeaverEngine::new(&args.registry, &policy_config, cache_settings);
     // Load the semantic convention registry into a local cache.
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
 
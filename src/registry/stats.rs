@@ -10,6 +10,7 @@ use include_dir::{include_dir, Dir};
 use log::info;
 use serde::Serialize;
 use std::path::PathBuf;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_forge::{OutputProcessor, OutputTarget};
 
@@ -52,16 +53,19 @@ struct StatsContext<T> {
 }
 
 /// Compute stats on a semantic convention registry.
-pub(crate) fn command(args: &RegistryStatsArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+pub(crate) fn command(
+    args: &RegistryStatsArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
     info!(
         "Compute statistics on the registry `{}`",
         args.registry.registry
     );
 
     if args.registry.v2 {
-        compute_v2(args)?;
+        compute_v2(args, cache_settings)?;
     } else {
-        compute_v1(args)?;
+        compute_v1(args, cache_settings)?;
     }
 
     Ok(ExitDirectives {
@@ -70,14 +74,17 @@ pub(crate) fn command(args: &RegistryStatsArgs) -> Result<ExitDirectives, Diagno
     })
 }
 
-fn compute_v2(args: &RegistryStatsArgs) -> Result<(), DiagnosticMessages> {
+fn compute_v2(
+    args: &RegistryStatsArgs,
+    cache_settings: &CacheSettings,
+) -> Result<(), DiagnosticMessages> {
     let mut diag_msgs = DiagnosticMessages::empty();
     let policy_config = PolicyArgs {
         policies: vec![],
         skip_policies: true,
         display_policy_coverage: false,
     };
-    let weaver = WeaverEngine::new(&args.registry, &policy_config);
+    let weaver = WeaverEngine::new(&args.registry, &policy_config, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
     let resolved_v2: ResolvedV2 = resolved.try_into()?;
 
@@ -101,14 +108,17 @@ fn compute_v2(args: &RegistryStatsArgs) -> Result<(), DiagnosticMessages> {
     Ok(())
 }
 
-fn compute_v1(args: &RegistryStatsArgs) -> Result<(), DiagnosticMessages> {
+fn compute_v1(
+    args: &RegistryStatsArgs,
+    cache_settings: &CacheSettings,
+) -> Result<(), DiagnosticMessages> {
     let mut diag_msgs = DiagnosticMessages::empty();
     let policy_config = PolicyArgs {
         policies: vec![],
         skip_policies: true,
         display_policy_coverage: false,
     };
-    let weaver = WeaverEngine::new(&args.registry, &policy_config);
+    let weaver = WeaverEngine::new(&args.registry, &policy_config, cache_settings);
     let loaded = weaver.load_main_definitions(&mut diag_msgs)?;
 
     let resolved = weaver.resolve(loaded, &mut diag_msgs)?;
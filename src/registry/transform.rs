@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Convert telemetry samples between two versions of a telemetry schema.
+
+use crate::registry::Error;
+use crate::{DiagnosticArgs, ExitDirectives};
+use clap::Args;
+use log::info;
+use std::path::PathBuf;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_live_check::json_file_ingester::JsonFileIngester;
+use weaver_live_check::json_stdin_ingester::JsonStdinIngester;
+use weaver_live_check::{Ingester, Sample};
+use weaver_otel_schema::TelemetrySchema;
+use weaver_version::Version;
+
+/// Parameters for the `registry transform` sub-command
+#[derive(Debug, Args)]
+pub struct RegistryTransformArgs {
+    /// Path to the telemetry schema file (OTEP 0152) defining the changes between versions.
+    #[arg(long)]
+    schema_file: PathBuf,
+
+    /// Version of the schema the samples were produced with.
+    #[arg(long)]
+    from: String,
+
+    /// Version of the schema to convert the samples to.
+    /// Defaults to the latest version of the telemetry schema file.
+    #[arg(long)]
+    to: Option<String>,
+
+    /// Path to the JSON file containing the samples (same format as `registry live-check`).
+    /// If not specified, the samples are read from stdin.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+
+    /// Path to the file where the converted samples will be saved.
+    /// If not specified, the samples are printed to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Parameters to specify the diagnostic format.
+    #[command(flatten)]
+    pub(crate) diagnostic: DiagnosticArgs,
+}
+
+/// Convert telemetry samples between two versions of a telemetry schema.
+pub(crate) fn command(args: &RegistryTransformArgs) -> Result<ExitDirectives, DiagnosticMessages> {
+    let transform_error = |error: String| Error::Transform { error };
+
+    let schema = TelemetrySchema::try_from_file(&args.schema_file)?;
+    let versions = schema.versions.unwrap_or_default();
+    let from: Version = args
+        .from
+        .parse()
+        .map_err(|e: weaver_version::Error| transform_error(e.to_string()))?;
+    let to: Version = match &args.to {
+        Some(to) => to
+            .parse()
+            .map_err(|e: weaver_version::Error| transform_error(e.to_string()))?,
+        None => versions.latest_version().ok_or_else(|| {
+            transform_error("The telemetry schema file doesn't define any version.".to_owned())
+        })?,
+    };
+    let transformation = versions
+        .transformation(&from, &to)
+        .map_err(|e| transform_error(e.to_string()))?;
+    info!("Converting samples from version `{from}` to version `{to}`");
+
+    let samples = match &args.input {
+        Some(path) => JsonFileIngester::new(path).ingest()?,
+        None => JsonStdinIngester::new().ingest()?,
+    };
+    let samples: Vec<Sample> = samples
+        .map(|mut sample| {
+            sample.transform(&transformation);
+            sample
+        })
+        .collect();
+
+    let json = serde_json::to_string_pretty(&samples)
+        .map_err(|e| transform_error(format!("Failed to serialize the samples: {e}")))?;
+    match &args.output {
+        Some(path) => std::fs::write(path, json).map_err(|e| Error::OutputWrite {
+            path: path.clone(),
+            error: e.to_string(),
+        })?,
+        None => println!("{json}"),
+    }
+    info!("{} sample(s) converted", samples.len());
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        warnings: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{Cli, Commands};
+    use crate::registry::transform::RegistryTransformArgs;
+    use crate::registry::{RegistryCommand, RegistrySubCommand};
+    use crate::run_command;
+    use weaver_live_check::Sample;
+
+    fn transform_cli(from: &str, to: Option<&str>, input: &str, output: &std::path::Path) -> Cli {
+        Cli {
+            debug: 0,
+            quiet: false,
+            future: false,
+            cache: Default::default(),
+            command: Some(Commands::Registry(RegistryCommand {
+                command: RegistrySubCommand::Transform(RegistryTransformArgs {
+                    schema_file: "tests/transform/schema.yaml".into(),
+                    from: from.to_owned(),
+                    to: to.map(ToOwned::to_owned),
+                    input: Some(input.into()),
+                    output: Some(output.to_path_buf()),
+                    diagnostic: Default::default(),
+                }),
+            })),
+        }
+    }
+
+    fn read_samples(path: &std::path::Path) -> Vec<Sample> {
+        serde_json::from_str(&std::fs::read_to_string(path).expect("Failed to read samples"))
+            .expect("Invalid samples")
+    }
+
+    #[test]
+    fn test_registry_transform() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let upgraded = temp_dir.path().join("upgraded.json");
+        let exit_directive = run_command(&transform_cli(
+            "1.20.0",
+            None,
+            "tests/transform/samples_1.20.0.json",
+            &upgraded,
+        ));
+        assert_eq!(exit_directive.exit_code, 0);
+        let samples = read_samples(&upgraded);
+        let Sample::Span(span) = &samples[0] else {
+            panic!("Expected a span");
+        };
+        assert_eq!(span.attributes[0].name, "http.request.method");
+        let Sample::Metric(metric) = &samples[1] else {
+            panic!("Expected a metric");
+        };
+        assert_eq!(metric.name, "db.client.connection.count");
+
+        let downgraded = temp_dir.path().join("downgraded.json");
+        let exit_directive = run_command(&transform_cli(
+            "1.26.0",
+            Some("1.20.0"),
+            upgraded.to_str().expect("Invalid path"),
+            &downgraded,
+        ));
+        assert_eq!(exit_directive.exit_code, 0);
+        assert_eq!(
+            read_samples(&downgraded),
+            read_samples("tests/transform/samples_1.20.0.json".as_ref())
+        );
+
+        // Unknown version
+        let exit_directive = run_command(&transform_cli(
+            "1.19.0",
+            None,
+            "tests/transform/samples_1.20.0.json",
+            &downgraded,
+        ));
+        assert_eq!(exit_directive.exit_code, 1);
+    }
+}
@@ -4,6 +4,7 @@
 //! update the specified sections.
 
 use crate::registry::generate::generate_params_shared;
+use crate::registry::watch::{self, WatchArgs};
 use crate::registry::{PolicyArgs, RegistryArgs};
 use crate::weaver::WeaverEngine;
 use crate::{DiagnosticArgs, ExitDirectives};
@@ -11,6 +12,7 @@ use clap::Args;
 use miette::Diagnostic;
 use serde_yaml::Value;
 use std::path::PathBuf;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::{is_future_mode_enabled, DiagnosticMessage, DiagnosticMessages};
 use weaver_common::vdir::VirtualDirectory;
 use weaver_common::vdir::VirtualDirectoryPath;
@@ -75,11 +77,35 @@ pub struct RegistryUpdateMarkdownArgs {
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
+
+    /// Watch mode parameters. The markdown files are not watched as the command updates them.
+    #[command(flatten)]
+    pub watch: WatchArgs,
 }
 
 /// Update markdown files.
 pub(crate) fn command(
     args: &RegistryUpdateMarkdownArgs,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    if args.watch.watch {
+        let mut paths: Vec<PathBuf> = [&args.registry.registry, &args.templates]
+            .into_iter()
+            .filter_map(watch::local_path)
+            .collect();
+        paths.extend(args.params.iter().cloned());
+        let excluded = [PathBuf::from(&args.markdown_dir)];
+        return watch::run(&args.watch, paths, &excluded, &args.diagnostic, || {
+            update_markdown(args, cache_settings)
+        });
+    }
+    update_markdown(args, cache_settings)
+}
+
+/// Updates (or validates) the markdown files once.
+fn update_markdown(
+    args: &RegistryUpdateMarkdownArgs,
+    cache_settings: &CacheSettings,
 ) -> Result<ExitDirectives, DiagnosticMessages> {
     fn is_markdown(entry: &walkdir::DirEntry) -> bool {
         let path = entry.path();
@@ -91,24 +117,24 @@ pub(crate) fn command(
     let params = generate_params_shared(&args.param, &args.params)?;
 
     // Construct a generator if we were given a `--target` argument.
-    let output = {
-        let templates_dir = VirtualDirectory::try_new(&args.templates).map_err(|e| {
-            Error::InvalidVirtualDirectory {
-                path: args.templates.to_string(),
-                error: e.to_string(),
-            }
-        })?;
-        let loader =
-            FileSystemFileLoader::try_new(templates_dir.path().join("registry"), &args.target)?;
-        let config = WeaverConfig::try_from_loader(&loader)?;
-        OutputProcessor::from_template_config(config, loader, params, OutputTarget::Stdout)?
-    };
+    let output =
+        {
+            let templates_dir = VirtualDirectory::try_new(&args.templates, cache_settings)
+                .map_err(|e| Error::InvalidVirtualDirectory {
+                    path: args.templates.to_string(),
+                    error: e.to_string(),
+                })?;
+            let loader =
+                FileSystemFileLoader::try_new(templates_dir.path().join("registry"), &args.target)?;
+            let config = WeaverConfig::try_from_loader(&loader)?;
+            OutputProcessor::from_template_config(config, loader, params, OutputTarget::Stdout)?
+        };
     let policy_config = PolicyArgs {
         policies: vec![],
         skip_policies: true,
         display_policy_coverage: false,
     };
-    let weaver = WeaverEngine::new(&args.registry, &policy_config);
+    let weaver = WeaverEngine::new(&args.registry, &policy_config, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
     let generator: Box<dyn MarkdownSnippetGenerator> = if args.registry.v2 {
         let resolved_v2 = resolved.try_into_v2()?;
@@ -196,6 +222,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::UpdateMarkdown(RegistryUpdateMarkdownArgs {
                     markdown_dir: "data/update_markdown/markdown".to_owned(),
@@ -216,6 +243,7 @@ mod tests {
                     target: "markdown".to_owned(),
                     param: None,
                     params: None,
+                    watch: Default::default(),
                 }),
             })),
         };
@@ -235,6 +263,7 @@ mod tests {
             debug: 0,
             quiet: false,
             future: false,
+            cache: Default::default(),
             command: Some(Commands::Registry(RegistryCommand {
                 command: RegistrySubCommand::UpdateMarkdown(RegistryUpdateMarkdownArgs {
                     markdown_dir: markdown_dir.to_owned(),
@@ -255,6 +284,7 @@ mod tests {
                     target: "markdown".to_owned(),
                     param: None,
                     params: None,
+                    watch: Default::default(),
                 }),
             })),
         };
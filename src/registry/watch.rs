@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Watch mode shared by the `registry generate`, `registry check` and
+//! `registry update-markdown` sub-commands.
+//!
+//! The watched files and directories are polled: each scan records the modification time
+//! and the size of every file, and the command is re-run when any of them is added,
+//! modified or removed.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use clap::Args;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::vdir::VirtualDirectoryPath;
+use weaver_common::{log_info, log_warn};
+
+use crate::{process_diagnostics, CmdResult, DiagnosticArgs, ExitDirectives};
+
+/// Parameters of the watch mode.
+#[derive(Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// Watch the local registry, templates, policies and configuration files, and re-run the
+    /// command each time one of them changes.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Interval (in milliseconds) between two scans of the watched files.
+    #[arg(long, default_value = "500")]
+    pub watch_interval: u64,
+}
+
+impl Default for WatchArgs {
+    fn default() -> Self {
+        Self {
+            watch: false,
+            watch_interval: 500,
+        }
+    }
+}
+
+/// The modification time and the size of a file.
+type FileStamp = (Option<SystemTime>, u64);
+
+/// Detects the files added, modified or removed in a set of files and directories.
+pub(crate) struct FileWatcher {
+    paths: Vec<PathBuf>,
+    /// The directories not watched, as absolute paths, e.g. the output of the command.
+    excluded: Vec<PathBuf>,
+    snapshot: BTreeMap<PathBuf, FileStamp>,
+}
+
+impl FileWatcher {
+    /// Creates a watcher for the given files and directories (watched recursively).
+    /// Paths that do not exist yet are watched as well.
+    pub(crate) fn new(paths: Vec<PathBuf>) -> Self {
+        Self::with_excluded(paths, &[])
+    }
+
+    /// Creates a watcher for the given files and directories, ignoring the files under the
+    /// `excluded` directories. A command writing into a watched directory must exclude its
+    /// output, otherwise each run would trigger the next one.
+    pub(crate) fn with_excluded(paths: Vec<PathBuf>, excluded: &[PathBuf]) -> Self {
+        let excluded: Vec<PathBuf> = excluded.iter().map(|path| absolute_path(path)).collect();
+        let snapshot = scan(&paths, &excluded);
+        Self {
+            paths,
+            excluded,
+            snapshot,
+        }
+    }
+
+    /// Returns the watched files and directories.
+    pub(crate) fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Returns the files added, modified or removed since the previous call (or since the
+    /// creation of the watcher).
+    pub(crate) fn changes(&mut self) -> Vec<PathBuf> {
+        let snapshot = scan(&self.paths, &self.excluded);
+        let mut changes: Vec<PathBuf> = snapshot
+            .iter()
+            .filter(|(path, stamp)| self.snapshot.get(*path) != Some(*stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changes.extend(
+            self.snapshot
+                .keys()
+                .filter(|path| !snapshot.contains_key(*path))
+                .cloned(),
+        );
+        changes.sort();
+        self.snapshot = snapshot;
+        changes
+    }
+
+    /// Blocks until at least one file changes and returns the changed files. Changes made
+    /// within one `interval` of each other (e.g. an editor saving several files) are
+    /// reported together.
+    pub(crate) fn wait_for_changes(&mut self, interval: Duration) -> Vec<PathBuf> {
+        loop {
+            std::thread::sleep(interval);
+            let mut changes = self.changes();
+            if !changes.is_empty() {
+                std::thread::sleep(interval);
+                changes.extend(self.changes());
+                changes.sort();
+                changes.dedup();
+                return changes;
+            }
+        }
+    }
+}
+
+/// Records the stamp of every file under the given paths. Hidden files and directories
+/// (e.g. `.git`) and the `excluded` directories are ignored.
+fn scan(paths: &[PathBuf], excluded: &[PathBuf]) -> BTreeMap<PathBuf, FileStamp> {
+    let is_excluded = |path: &Path| {
+        !excluded.is_empty() && {
+            let path = absolute_path(path);
+            excluded.iter().any(|excluded| path.starts_with(excluded))
+        }
+    };
+    let mut snapshot = BTreeMap::new();
+    for path in paths {
+        let entries = walkdir::WalkDir::new(path)
+            .into_iter()
+            .filter_entry(|entry| {
+                (entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.'))
+                    && !(entry.file_type().is_dir() && is_excluded(entry.path()))
+            })
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file());
+        for entry in entries {
+            if let Ok(metadata) = entry.metadata() {
+                _ = snapshot.insert(
+                    entry.path().to_path_buf(),
+                    (metadata.modified().ok(), metadata.len()),
+                );
+            }
+        }
+    }
+    snapshot
+}
+
+/// Returns the absolute path of a file or directory, with its symbolic links and `..`
+/// components resolved as far as it exists.
+fn absolute_path(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => {
+            absolute_path(parent).join(name)
+        }
+        _ => std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()),
+    }
+}
+
+/// Returns the local path of a virtual directory, if any. Remote registries and templates
+/// are not watched.
+pub(crate) fn local_path(path: &VirtualDirectoryPath) -> Option<PathBuf> {
+    match path {
+        VirtualDirectoryPath::LocalFolder { path } => Some(PathBuf::from(path)),
+        VirtualDirectoryPath::LocalArchive { path, .. } => Some(PathBuf::from(path)),
+        _ => None,
+    }
+}
+
+/// Runs `run` once, then again each time one of the watched files changes, until `run`
+/// breaks. `run` receives the files changed since the previous run (empty for the first
+/// run).
+pub(crate) fn watch_loop<F>(watcher: &mut FileWatcher, interval: Duration, mut run: F)
+where
+    F: FnMut(&[PathBuf]) -> ControlFlow<()>,
+{
+    let mut changes = vec![];
+    loop {
+        if run(&changes).is_break() {
+            return;
+        }
+        log_info(format!(
+            "Watching {} for changes (press Ctrl+C to stop)",
+            watcher
+                .paths()
+                .iter()
+                .map(|path| format!("`{}`", path.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        changes = watcher.wait_for_changes(interval);
+    }
+}
+
+/// Runs a command in watch mode: its diagnostics are printed after each run and a failed
+/// run does not stop the watch. The files under the `excluded` directories (the output of
+/// the command) are not watched.
+pub(crate) fn run<F>(
+    args: &WatchArgs,
+    paths: Vec<PathBuf>,
+    excluded: &[PathBuf],
+    diagnostic_args: &DiagnosticArgs,
+    mut command: F,
+) -> Result<ExitDirectives, DiagnosticMessages>
+where
+    F: FnMut() -> Result<ExitDirectives, DiagnosticMessages>,
+{
+    if paths.is_empty() {
+        log_warn("None of the inputs of the command is a local path, nothing to watch.");
+        return command();
+    }
+    let mut watcher = FileWatcher::with_excluded(paths, excluded);
+    watch_loop(
+        &mut watcher,
+        Duration::from_millis(args.watch_interval),
+        |changes| {
+            if !changes.is_empty() {
+                log_info(format!(
+                    "Changes detected in {}, re-running",
+                    changes
+                        .iter()
+                        .map(|path| format!("`{}`", path.display()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            let exit_directives =
+                process_diagnostics(CmdResult::new(command(), Some(diagnostic_args.clone())));
+            if exit_directives.exit_code != 0 {
+                log_warn("The run failed, the previous output is kept.");
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    Ok(ExitDirectives {
+        exit_code: 0,
+        warnings: None,
+    })
+}
+
+/// Mirrors the files of the `staging` directory into the `output` directory: the staged
+/// files replace the existing ones, and the files published by the previous run
+/// (`previous`, relative to `output`) that are no longer staged are removed, with their
+/// directories left empty. Other files of the `output` directory are kept.
+/// Returns the files published by this run, relative to `output`.
+pub(crate) fn publish(
+    staging: &Path,
+    output: &Path,
+    previous: &BTreeSet<PathBuf>,
+) -> std::io::Result<BTreeSet<PathBuf>> {
+    let mut published = BTreeSet::new();
+    for entry in walkdir::WalkDir::new(staging) {
+        let entry = entry?;
+        let relative = entry
+            .path()
+            .strip_prefix(staging)
+            .map_err(std::io::Error::other)?;
+        let target = output.join(relative);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            _ = std::fs::copy(entry.path(), &target)?;
+            _ = published.insert(relative.to_path_buf());
+        }
+    }
+    for stale in previous.difference(&published) {
+        let path = output.join(stale);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        // Removes the directories left empty, `remove_dir` fails on the first non-empty one.
+        for dir in path.ancestors().skip(1) {
+            if dir == output || std::fs::remove_dir(dir).is_err() {
+                break;
+            }
+        }
+    }
+    Ok(published)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_watcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = dir.path().join("registry");
+        std::fs::create_dir_all(registry.join(".git")).unwrap();
+        std::fs::write(registry.join("a.yaml"), "groups: []").unwrap();
+        let config = dir.path().join("weaver.yaml");
+
+        let mut watcher = FileWatcher::new(vec![registry.clone(), config.clone()]);
+        assert!(watcher.changes().is_empty());
+
+        // Added, modified and removed files are detected, hidden directories are ignored.
+        std::fs::write(registry.join("b.yaml"), "groups: []").unwrap();
+        std::fs::write(registry.join(".git").join("index"), "ignored").unwrap();
+        std::fs::write(&config, "templates: []").unwrap();
+        assert_eq!(
+            watcher.changes(),
+            vec![registry.join("b.yaml"), config.clone()]
+        );
+        assert!(watcher.changes().is_empty());
+
+        std::fs::write(registry.join("a.yaml"), "groups: [] # updated").unwrap();
+        std::fs::remove_file(&config).unwrap();
+        assert_eq!(watcher.changes(), vec![registry.join("a.yaml"), config]);
+    }
+
+    #[test]
+    fn test_file_watcher_excludes_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let templates = dir.path().join("templates");
+        let output = templates.join("out");
+        std::fs::create_dir_all(&templates).unwrap();
+
+        // The output directory does not exist yet, and is given with a relative component.
+        let mut watcher = FileWatcher::with_excluded(
+            vec![templates.clone()],
+            &[templates.join("..").join("templates").join("out")],
+        );
+        std::fs::create_dir_all(output.join("sub")).unwrap();
+        std::fs::write(output.join("sub").join("main.rs"), "main").unwrap();
+        assert!(watcher.changes().is_empty());
+
+        std::fs::write(templates.join("main.rs.j2"), "main").unwrap();
+        assert_eq!(watcher.changes(), vec![templates.join("main.rs.j2")]);
+    }
+
+    #[test]
+    fn test_watch_loop() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("registry.yaml");
+        let mut watcher = FileWatcher::new(vec![dir.path().to_path_buf()]);
+
+        let mut runs: Vec<Vec<PathBuf>> = vec![];
+        watch_loop(&mut watcher, Duration::from_millis(10), |changes| {
+            runs.push(changes.to_vec());
+            match runs.len() {
+                1 => std::fs::write(&file, "groups: []").unwrap(),
+                2 => std::fs::write(&file, "groups: [] # updated").unwrap(),
+                _ => return ControlFlow::Break(()),
+            }
+            ControlFlow::Continue(())
+        });
+        assert_eq!(runs, vec![vec![], vec![file.clone()], vec![file]]);
+    }
+
+    #[test]
+    fn test_publish() {
+        let staging = tempfile::tempdir().unwrap();
+        let output = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(staging.path().join("sub")).unwrap();
+        std::fs::write(staging.path().join("sub").join("old.rs"), "old").unwrap();
+        std::fs::write(staging.path().join("main.rs"), "main").unwrap();
+        std::fs::write(output.path().join("manual.rs"), "manual").unwrap();
+
+        let published = publish(staging.path(), output.path(), &BTreeSet::new()).unwrap();
+        assert_eq!(
+            published,
+            BTreeSet::from([PathBuf::from("main.rs"), Path::new("sub").join("old.rs")])
+        );
+
+        // `sub/old.rs` is renamed to `new.rs` by the next run.
+        std::fs::remove_dir_all(staging.path().join("sub")).unwrap();
+        std::fs::write(staging.path().join("new.rs"), "new").unwrap();
+        std::fs::write(staging.path().join("main.rs"), "main updated").unwrap();
+        let published = publish(staging.path(), output.path(), &published).unwrap();
+        assert_eq!(
+            published,
+            BTreeSet::from([PathBuf::from("main.rs"), PathBuf::from("new.rs")])
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.path().join("main.rs")).unwrap(),
+            "main updated"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output.path().join("new.rs")).unwrap(),
+            "new"
+        );
+        // The stale file and its empty directory are removed, not the files of the output
+        // directory that were never generated.
+        assert!(!output.path().join("sub").exists());
+        assert_eq!(
+            std::fs::read_to_string(output.path().join("manual.rs")).unwrap(),
+            "manual"
+        );
+    }
+}
@@ -2,22 +2,44 @@
 
 //! HTTP request handlers for the serve command.
 
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
+use futures_util::stream;
 use schemars::schema_for;
 use serde_json::json;
-use weaver_forge::run_filter_raw;
+use tokio::sync::broadcast::error::RecvError;
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_forge::config::{Params, WeaverConfig};
+use weaver_forge::error::Error as ForgeError;
+use weaver_forge::file_loader::InMemoryFileLoader;
+use weaver_forge::v2::registry::ForgeResolvedRegistry;
+use weaver_forge::{run_filter_raw, GeneratedFile, OutputProcessor, OutputTarget, WEAVER_YAML};
+use weaver_live_check::{Error as LiveCheckError, LiveCheckReport, Sample, VersionedRegistry};
+use weaver_search::SearchResults;
 
+use crate::registry::live_check::check_samples;
+use crate::registry::otlp::otlp_file_ingester::samples_from_json_request;
 use crate::serve::types::FilterParams;
 
-use super::server::AppState;
-use super::types::{RegistryCounts, RegistryStats, SearchParams, SearchResponse};
+use super::server::{AppState, ServedRegistry};
+use super::types::{
+    DiffParams, LiveCheckParams, RegistryCounts, RegistryDiagnostics, RegistryInfo, RegistryStats,
+    ReloadEvent, RenderParams, RenderRequest, RenderResponse, RenderedFile, SearchParams,
+    SearchResponse,
+};
 
 /// Health check.
 #[utoipa::path(
@@ -49,9 +71,9 @@ pub async fn get_schema(Path(name): Path<String>) -> impl IntoResponse {
     let name = name.trim_start_matches('/');
 
     let schema = match name {
-        "ForgeRegistryV2" => schema_for!(weaver_forge::v2::registry::ForgeResolvedRegistry),
+        "ForgeRegistryV2" => schema_for!(ForgeResolvedRegistry),
         "SemconvDefinitionV2" => schema_for!(weaver_semconv::v2::SemConvSpecV2),
-        "LiveCheckSample" => schema_for!(weaver_live_check::Sample),
+        "LiveCheckSample" => schema_for!(Sample),
         _ => {
             return (
                 StatusCode::NOT_FOUND,
@@ -86,19 +108,13 @@ pub async fn get_schema(Path(name): Path<String>) -> impl IntoResponse {
     ),
     tag = "registry"
 )]
-pub async fn get_registry_stats(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+pub async fn get_registry_stats(State(served): State<Arc<ServedRegistry>>) -> impl IntoResponse {
+    let state = served.current();
     let registry = &state.registry;
 
     let stats = RegistryStats {
         schema_url: registry.schema_url.to_string(),
-        counts: RegistryCounts {
-            attributes: registry.registry.attributes.len(),
-            metrics: registry.registry.metrics.len(),
-            spans: registry.registry.spans.len(),
-            events: registry.registry.events.len(),
-            entities: registry.registry.entities.len(),
-            attribute_groups: registry.registry.attribute_groups.len(),
-        },
+        counts: RegistryCounts::from_registry(registry),
     };
 
     Json(stats)
@@ -118,9 +134,10 @@ pub async fn get_registry_stats(State(state): State<Arc<AppState>>) -> impl Into
     tag = "registry"
 )]
 pub async fn get_registry_attribute(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Path(key): Path<String>,
 ) -> impl IntoResponse {
+    let state = served.current();
     // Remove leading slash if present (from wildcard match)
     let key = key.trim_start_matches('/');
 
@@ -153,9 +170,10 @@ pub async fn get_registry_attribute(
     tag = "registry"
 )]
 pub async fn get_registry_metric(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Path(name): Path<String>,
 ) -> impl IntoResponse {
+    let state = served.current();
     let name = name.trim_start_matches('/');
 
     // O(1) lookup via SearchContext
@@ -183,9 +201,10 @@ pub async fn get_registry_metric(
     tag = "registry"
 )]
 pub async fn get_registry_span(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Path(span_type): Path<String>,
 ) -> impl IntoResponse {
+    let state = served.current();
     let span_type = span_type.trim_start_matches('/');
 
     // O(1) lookup via SearchContext
@@ -213,9 +232,10 @@ pub async fn get_registry_span(
     tag = "registry"
 )]
 pub async fn get_registry_event(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Path(name): Path<String>,
 ) -> impl IntoResponse {
+    let state = served.current();
     let name = name.trim_start_matches('/');
 
     // O(1) lookup via SearchContext
@@ -243,9 +263,10 @@ pub async fn get_registry_event(
     tag = "registry"
 )]
 pub async fn get_registry_entity(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Path(entity_type): Path<String>,
 ) -> impl IntoResponse {
+    let state = served.current();
     let entity_type = entity_type.trim_start_matches('/');
 
     // O(1) lookup via SearchContext
@@ -272,13 +293,18 @@ pub async fn get_registry_entity(
     tag = "registry"
 )]
 pub async fn search_registry(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Query(params): Query<SearchParams>,
 ) -> impl IntoResponse {
+    let state = served.current();
     // Convert Option<String> to Option<&str> for search
     let query = params.q.as_deref();
 
-    let (results, total) = state.search_ctx.search(
+    let SearchResults {
+        results,
+        total,
+        facets,
+    } = state.search_ctx.search_with_facets(
         query,
         params.search_type,
         params.stability,
@@ -292,6 +318,7 @@ pub async fn search_registry(
         count: results.len(),
         offset: params.offset,
         results,
+        facets,
     };
 
     Json(response).into_response()
@@ -310,9 +337,10 @@ pub async fn search_registry(
     tag = "registry"
 )]
 pub async fn filter_registry(
-    State(state): State<Arc<AppState>>,
+    State(served): State<Arc<ServedRegistry>>,
     Query(params): Query<FilterParams>,
 ) -> impl IntoResponse {
+    let state = served.current();
     // TODO - Should filter be required?
     let filter = params.filter.as_deref().unwrap_or(".");
     match run_filter_raw(&state.registry, filter) {
@@ -324,3 +352,367 @@ pub async fn filter_registry(
             .into_response(),
     }
 }
+
+/// List the served registries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/registries",
+    responses(
+        (status = 200, description = "Served registries, the default one first", body = Vec<RegistryInfo>)
+    ),
+    tag = "registries"
+)]
+pub async fn list_registries(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let registries: Vec<RegistryInfo> = state
+        .registries
+        .iter()
+        .enumerate()
+        .map(|(index, registry)| registry.info(index == 0))
+        .collect();
+    Json(registries)
+}
+
+/// Fallback for the endpoints of registries that are not served.
+pub async fn registry_not_found(
+    Path((name, version, _)): Path<(String, String, String)>,
+) -> impl IntoResponse {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({"error": "Registry not found", "name": name, "version": version})),
+    )
+}
+
+/// Get the changes between two served registries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/diff",
+    params(
+        DiffParams
+    ),
+    responses(
+        (status = 200, description = "Changes from the `from` registry to the `to` registry", body = serde_json::Value),
+        (status = 404, description = "Registry not found")
+    ),
+    tag = "registries"
+)]
+pub async fn diff_registries(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<DiffParams>,
+) -> impl IntoResponse {
+    let Some(from) = state.get(&params.from) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Registry not found", "registry": params.from})),
+        )
+            .into_response();
+    };
+    let Some(to) = state.get(&params.to) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Registry not found", "registry": params.to})),
+        )
+            .into_response();
+    };
+
+    Json(
+        to.current()
+            .resolved_schema
+            .diff(&from.current().resolved_schema),
+    )
+    .into_response()
+}
+
+/// Get the diagnostics of the last resolution of each served registry.
+#[utoipa::path(
+    get,
+    path = "/api/v1/diagnostics",
+    responses(
+        (status = 200, description = "Diagnostics of the served registries, the default one first", body = Vec<RegistryDiagnostics>)
+    ),
+    tag = "reload"
+)]
+pub async fn get_diagnostics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let diagnostics: Vec<RegistryDiagnostics> = state
+        .registries
+        .iter()
+        .map(|registry| registry.diagnostics())
+        .collect();
+    Json(diagnostics)
+}
+
+/// Subscribe to the reloads of the served registries.
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    responses(
+        (status = 200, description = "Server-sent events named `reload`, sent each time a served registry is reloaded", body = ReloadEvent, content_type = "text/event-stream")
+    ),
+    tag = "reload"
+)]
+pub async fn events(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let stream = stream::unfold(state.events.subscribe(), |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    return Some((Event::default().event("reload").json_data(event), receiver))
+                }
+                // Only the latest reloads matter to a slow subscriber
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Run live-check on telemetry samples.
+#[utoipa::path(
+    post,
+    path = "/api/v1/live-check",
+    params(
+        LiveCheckParams
+    ),
+    request_body(
+        content = serde_json::Value,
+        description = "A JSON array of samples (see the `LiveCheckSample` schema) or an OTLP-JSON `ExportTraceServiceRequest`, `ExportMetricsServiceRequest` or `ExportLogsServiceRequest`",
+        content_type = "application/json"
+    ),
+    responses(
+        (status = 200, description = "The samples with their findings, and the statistics", body = LiveCheckReport),
+        (status = 400, description = "Invalid samples"),
+        (status = 404, description = "Registry not found")
+    ),
+    tag = "live-check"
+)]
+pub async fn live_check(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<LiveCheckParams>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let served = match &params.registry {
+        Some(id) => state.get(id),
+        None => state.registries.first(),
+    };
+    let Some(served) = served else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Registry not found", "registry": params.registry})),
+        )
+            .into_response();
+    };
+
+    // Samples and the live checker are not `Send`, they live on a blocking thread
+    let registry = served.current().live_check_registry();
+    match tokio::task::spawn_blocking(move || run_live_check(registry, &body)).await {
+        Ok(response) => response,
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("Live check failed: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// Runs live-check on the body of a live-check request.
+fn run_live_check(registry: Arc<VersionedRegistry>, body: &[u8]) -> Response {
+    let samples = match parse_samples(body) {
+        Ok(samples) => samples,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({"error": format!("{e}")})),
+            )
+                .into_response()
+        }
+    };
+    match check_samples(registry, samples) {
+        Ok(report) => Json(report).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": format!("{e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// Parses the body of a live-check request: a JSON array of samples or an OTLP-JSON request.
+fn parse_samples(body: &[u8]) -> Result<Vec<Sample>, LiveCheckError> {
+    let value: serde_json::Value =
+        serde_json::from_slice(body).map_err(|e| LiveCheckError::IngestError {
+            error: format!("Invalid JSON: {e}"),
+        })?;
+    if value.is_array() {
+        serde_json::from_value(value).map_err(|e| LiveCheckError::IngestError {
+            error: format!("Invalid samples: {e}"),
+        })
+    } else {
+        samples_from_json_request(body)
+    }
+}
+
+/// Name of the template rendered by the render endpoint.
+const RENDER_TEMPLATE: &str = "template.j2";
+
+/// Maximum number of template instructions executed by each rendering of the render endpoint.
+const RENDER_FUEL: u64 = 10_000_000;
+
+/// Maximum duration of a request to the render endpoint.
+const RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum size of the files rendered by a request to the render endpoint.
+const MAX_RENDER_OUTPUT_SIZE: usize = 16 * 1024 * 1024;
+
+/// Render a template against a registry.
+#[utoipa::path(
+    post,
+    path = "/api/v1/render",
+    params(
+        RenderParams
+    ),
+    request_body = RenderRequest,
+    responses(
+        (status = 200, description = "The rendered files, or the diagnostics if the template could not be rendered", body = RenderResponse),
+        (status = 404, description = "Registry not found")
+    ),
+    tag = "render"
+)]
+pub async fn render(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<RenderParams>,
+    Json(request): Json<RenderRequest>,
+) -> impl IntoResponse {
+    let served = match &params.registry {
+        Some(id) => state.get(id),
+        None => state.registries.first(),
+    };
+    let Some(served) = served else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "Registry not found", "registry": params.registry})),
+        )
+            .into_response();
+    };
+
+    // Filters and templates can take a while on large registries. The templates are
+    // untrusted, their rendering is limited in fuel, duration and output size.
+    let registry = served.current();
+    let rendering =
+        tokio::task::spawn_blocking(move || render_template(&registry.registry, &request));
+    let result = match tokio::time::timeout(RENDER_TIMEOUT, rendering).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({"error": format!("Rendering failed: {e}")})),
+            )
+                .into_response()
+        }
+        Err(_) => Err(render_error(
+            "render_timeout",
+            format!(
+                "The rendering did not complete within {} seconds",
+                RENDER_TIMEOUT.as_secs()
+            ),
+        )),
+    };
+    match result {
+        Ok(files) => Json(RenderResponse {
+            success: true,
+            files: files
+                .into_iter()
+                .map(|file| RenderedFile {
+                    path: file.path.to_string_lossy().into_owned(),
+                    content: file.content,
+                })
+                .collect(),
+            diagnostics: DiagnosticMessages::empty(),
+        })
+        .into_response(),
+        Err(e) => Json(RenderResponse {
+            success: false,
+            files: vec![],
+            diagnostics: e.into(),
+        })
+        .into_response(),
+    }
+}
+
+/// Renders the template of a render request against a registry.
+fn render_template(
+    registry: &ForgeResolvedRegistry,
+    request: &RenderRequest,
+) -> Result<Vec<GeneratedFile>, ForgeError> {
+    let files = BTreeMap::from([
+        (WEAVER_YAML.to_owned(), render_config(request)?),
+        (RENDER_TEMPLATE.to_owned(), request.template.clone()),
+    ]);
+    let loader = InMemoryFileLoader::new(PathBuf::new(), files);
+    let config = WeaverConfig::try_from_loader(&loader)?;
+    let mut output = OutputProcessor::from_template_config(
+        config,
+        loader,
+        Params::default(),
+        OutputTarget::Stdout,
+    )?;
+    output.set_fuel(Some(RENDER_FUEL));
+    let files = output.generate_to_files(registry)?;
+    if files.iter().map(|file| file.content.len()).sum::<usize>() > MAX_RENDER_OUTPUT_SIZE {
+        return Err(render_error(
+            "render_output_too_large",
+            format!("The rendered files exceed {MAX_RENDER_OUTPUT_SIZE} bytes"),
+        ));
+    }
+    Ok(files)
+}
+
+/// An error of the render endpoint, reported as a template evaluation error.
+fn render_error(error_id: &str, error: String) -> ForgeError {
+    ForgeError::TemplateEvaluationFailed {
+        template: PathBuf::from(RENDER_TEMPLATE),
+        error_id: error_id.to_owned(),
+        error,
+    }
+}
+
+/// Builds the `weaver.yaml` of a render request. Template configurations without a
+/// `template` pattern apply to the rendered template, and the filter of the request
+/// overrides theirs.
+fn render_config(request: &RenderRequest) -> Result<String, ForgeError> {
+    let invalid_config = |error: String| ForgeError::InvalidConfigFile {
+        config_file: PathBuf::from(WEAVER_YAML),
+        error,
+    };
+    let mut config = match &request.config {
+        Some(config) => serde_yaml::from_str(config).map_err(|e| invalid_config(e.to_string()))?,
+        None => serde_yaml::Value::Null,
+    };
+    if config.is_null() {
+        config = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+    }
+    let serde_yaml::Value::Mapping(config_map) = &mut config else {
+        return Err(invalid_config("Expected a mapping".to_owned()));
+    };
+    let templates = config_map
+        .entry("templates".into())
+        .or_insert_with(|| vec![serde_yaml::Value::Mapping(serde_yaml::Mapping::new())].into());
+    let serde_yaml::Value::Sequence(templates) = templates else {
+        return Err(invalid_config(
+            "Expected a sequence of templates".to_owned(),
+        ));
+    };
+    for template in templates {
+        let serde_yaml::Value::Mapping(template) = template else {
+            return Err(invalid_config("Expected a template mapping".to_owned()));
+        };
+        _ = template
+            .entry("template".into())
+            .or_insert_with(|| RENDER_TEMPLATE.into());
+        _ = template
+            .entry("application_mode".into())
+            .or_insert_with(|| "single".into());
+        if let Some(filter) = &request.filter {
+            _ = template.insert("filter".into(), filter.as_str().into());
+        }
+    }
+    serde_yaml::to_string(&config).map_err(|e| invalid_config(e.to_string()))
+}
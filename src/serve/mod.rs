@@ -2,12 +2,21 @@
 
 //! Web API server for registry search, schema browsing, and more.
 
+use std::fmt::{Display, Formatter};
 use std::net::SocketAddr;
+use std::ops::ControlFlow;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use clap::Args;
 use log::info;
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_common::vdir::VirtualDirectoryPath;
+use weaver_common::{log_info, log_warn};
 
+use crate::registry::watch::{self, FileWatcher, WatchArgs};
 use crate::registry::{PolicyArgs, RegistryArgs};
 use crate::{CmdResult, DiagnosticArgs, ExitDirectives};
 
@@ -16,7 +25,7 @@ mod server;
 mod types;
 mod ui;
 
-pub use server::run_server;
+pub use server::{run_server, AppState, RegistryState};
 
 /// Parameters for the `weaver serve` command.
 #[derive(Debug, Args)]
@@ -25,6 +34,19 @@ pub struct ServeCommand {
     #[command(flatten)]
     pub registry: RegistryArgs,
 
+    /// Additional registries to serve next to `--registry`, as `[NAME=]PATH` where PATH is a
+    /// local folder, a Git repo URL (with an optional `@refspec`), an archive URL or the path
+    /// of a published manifest. Each registry is served under
+    /// `/api/v1/registries/{name}/{version}`. NAME defaults to the host of the schema URL of
+    /// the registry, and the version is the one of its schema URL.
+    #[arg(long = "serve-registry", value_name = "[NAME=]PATH")]
+    pub additional_registries: Vec<NamedRegistryPath>,
+
+    /// Name under which the `--registry` registry is served. Defaults to the host of its
+    /// schema URL.
+    #[arg(long)]
+    pub registry_name: Option<String>,
+
     /// Parameters to specify the policy engine.
     #[command(flatten)]
     pub policy: PolicyArgs,
@@ -41,57 +63,253 @@ pub struct ServeCommand {
     /// Parameters to specify the diagnostic format.
     #[command(flatten)]
     pub diagnostic: DiagnosticArgs,
+
+    /// Watch mode parameters. In watch mode, each local registry is resolved again when its
+    /// files or the policies change, and replaces the served registry if the resolution
+    /// succeeds.
+    #[command(flatten)]
+    pub(crate) watch: WatchArgs,
+}
+
+/// The path of a registry to serve, with an optional name.
+#[derive(Debug, Clone)]
+pub struct NamedRegistryPath {
+    /// Name under which the registry is served.
+    pub name: Option<String>,
+    /// Path of the registry.
+    pub path: VirtualDirectoryPath,
+}
+
+impl FromStr for NamedRegistryPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // A `NAME=` prefix is only recognized if it is a valid name, so that URLs containing
+        // a `=` are kept intact.
+        let (name, path) = match s.split_once('=') {
+            Some((name, path)) if is_valid_registry_name(name) => (Some(name.to_owned()), path),
+            _ => (None, s),
+        };
+        Ok(Self {
+            name,
+            path: path.parse().map_err(|e| format!("{e}"))?,
+        })
+    }
+}
+
+impl Display for NamedRegistryPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name}={}", self.path),
+            None => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// Returns true if the name can be used as a path segment of the API.
+fn is_valid_registry_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
 }
 
 /// Execute the `weaver serve` command.
-pub fn command(args: &ServeCommand) -> CmdResult {
-    CmdResult::new(run_serve(args), Some(args.diagnostic.clone()))
+pub fn command(args: &ServeCommand, cache_settings: &CacheSettings) -> CmdResult {
+    CmdResult::new(
+        run_serve(args, cache_settings),
+        Some(args.diagnostic.clone()),
+    )
 }
 
-fn run_serve(args: &ServeCommand) -> Result<ExitDirectives, DiagnosticMessages> {
-    // TODO: Currently the serve command takes a registry on the command line. Really we want to be
+fn run_serve(
+    args: &ServeCommand,
+    cache_settings: &CacheSettings,
+) -> Result<ExitDirectives, DiagnosticMessages> {
+    // TODO: Currently the serve command takes registries on the command line. Really we want to be
     // able to hot load a registry from within the server. This would mean calling an API to load
     // a new registry, and then the server would update its internal state to use the new registry.
     // A UI could be built to allow selecting a registry file, or specifying a git repo/branch.
 
-    info!("Loading registry from `{}`", args.registry.registry);
+    if let Some(name) = args.registry_name.as_deref() {
+        if !is_valid_registry_name(name) {
+            return Err(DiagnosticMessages::from_error(
+                server::Error::InvalidRegistryName {
+                    name: name.to_owned(),
+                },
+            ));
+        }
+    }
+
+    let sources: Vec<NamedRegistryPath> = [NamedRegistryPath {
+        name: args.registry_name.clone(),
+        path: args.registry.registry.clone(),
+    }]
+    .into_iter()
+    .chain(args.additional_registries.iter().cloned())
+    .collect();
+    let mut registries = Vec::with_capacity(sources.len());
+    for source in &sources {
+        let registry_args = RegistryArgs {
+            registry: source.path.clone(),
+            ..args.registry.clone()
+        };
+        registries.push(load_registry(
+            &registry_args,
+            &args.policy,
+            cache_settings,
+            source.name.as_deref(),
+        )?);
+    }
+    let state = Arc::new(AppState::try_new(registries).map_err(DiagnosticMessages::from_error)?);
+
+    if args.watch.watch {
+        for (source, served) in sources.into_iter().zip(&state.registries) {
+            spawn_watcher(
+                args,
+                cache_settings,
+                source.path,
+                Arc::clone(&state),
+                Arc::clone(served),
+            );
+        }
+    }
+
+    info!("Starting server on {}", args.bind);
+
+    // Run the async server using tokio runtime
+    tokio::runtime::Runtime::new()
+        .expect("Failed to create tokio runtime")
+        .block_on(async { run_server(args.bind, state, args.cors_origins.as_deref()).await })
+        .map_err(DiagnosticMessages::from_error)?;
+
+    Ok(ExitDirectives {
+        exit_code: 0,
+        warnings: None,
+    })
+}
+
+/// Starts a thread resolving the registry again each time its files or the policies change,
+/// and replacing the served registry with the result. Remote registries are not watched.
+fn spawn_watcher(
+    args: &ServeCommand,
+    cache_settings: &CacheSettings,
+    path: VirtualDirectoryPath,
+    state: Arc<AppState>,
+    served: Arc<server::ServedRegistry>,
+) {
+    let Some(registry_path) = watch::local_path(&path) else {
+        log_warn(format!(
+            "Registry `{path}` is not a local path, it will not be reloaded."
+        ));
+        return;
+    };
+    let paths = [registry_path]
+        .into_iter()
+        .chain(args.policy.policies.iter().filter_map(watch::local_path))
+        .collect();
+    let registry_args = RegistryArgs {
+        registry: path,
+        ..args.registry.clone()
+    };
+    let policy_args = args.policy.clone();
+    let cache_settings = cache_settings.clone();
+    let interval = Duration::from_millis(args.watch.watch_interval);
+
+    _ = std::thread::spawn(move || {
+        let mut watcher = FileWatcher::new(paths);
+        watch::watch_loop(&mut watcher, interval, |changes| {
+            // The registry is already loaded before the first run
+            if changes.is_empty() {
+                return ControlFlow::Continue(());
+            }
+            log_info(format!(
+                "Changes detected in {}, reloading registry `{}`",
+                changes
+                    .iter()
+                    .map(|path| format!("`{}`", path.display()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                served.id()
+            ));
+            let result = load_registry(
+                &registry_args,
+                &policy_args,
+                &cache_settings,
+                Some(&served.name),
+            );
+            if let Err(diagnostics) = &result {
+                diagnostics.log();
+                log_warn(format!(
+                    "Failed to reload registry `{}`, the previous registry is still served.",
+                    served.id()
+                ));
+            }
+            state.reload(&served, result);
+            ControlFlow::Continue(())
+        });
+    });
+}
+
+/// Loads and resolves a registry to serve. Returns the registry and the warnings of its
+/// resolution.
+fn load_registry(
+    registry_args: &RegistryArgs,
+    policy_args: &PolicyArgs,
+    cache_settings: &CacheSettings,
+    name: Option<&str>,
+) -> Result<(RegistryState, DiagnosticMessages), DiagnosticMessages> {
+    info!("Loading registry from `{}`", registry_args.registry);
 
     let mut diag_msgs = DiagnosticMessages::empty();
 
     // Create a weaver engine and load/resolve the registry using V2 schema
-    let weaver = crate::weaver::WeaverEngine::new(&args.registry, &args.policy);
+    let weaver = crate::weaver::WeaverEngine::new(registry_args, policy_args, cache_settings);
     let resolved = weaver.load_and_resolve_main(&mut diag_msgs)?;
 
     // Convert to V2 ForgeResolvedRegistry
-    let resolved_v2 = resolved.try_into_v2()?;
-    let forge_registry = resolved_v2.into_template_schema();
+    let (resolved_schema, forge_registry) = resolved.try_into_v2()?.into_schemas();
 
     if !diag_msgs.is_empty() {
         // Log warnings but continue
         diag_msgs.log();
     }
 
-    info!("Registry loaded successfully");
+    let state = RegistryState::new(name, resolved_schema, forge_registry);
+    info!(
+        "Registry `{}` version `{}` loaded successfully",
+        state.name, state.version
+    );
     info!(
         "Found {} attributes, {} metrics, {} spans, {} events, {} entities",
-        forge_registry.registry.attributes.len(),
-        forge_registry.registry.metrics.len(),
-        forge_registry.registry.spans.len(),
-        forge_registry.registry.events.len(),
-        forge_registry.registry.entities.len(),
+        state.registry.registry.attributes.len(),
+        state.registry.registry.metrics.len(),
+        state.registry.registry.spans.len(),
+        state.registry.registry.events.len(),
+        state.registry.registry.entities.len(),
     );
-    info!("Starting server on {}", args.bind);
+    Ok((state, diag_msgs))
+}
 
-    // Run the async server using tokio runtime
-    tokio::runtime::Runtime::new()
-        .expect("Failed to create tokio runtime")
-        .block_on(async {
-            run_server(args.bind, forge_registry, args.cors_origins.as_deref()).await
-        })
-        .map_err(DiagnosticMessages::from_error)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(ExitDirectives {
-        exit_code: 0,
-        warnings: None,
-    })
+    #[test]
+    fn test_named_registry_path() {
+        let named: NamedRegistryPath = "otel=crates/weaver_emit/data".parse().unwrap();
+        assert_eq!(named.name.as_deref(), Some("otel"));
+        assert_eq!(named.path.to_string(), "crates/weaver_emit/data");
+
+        let unnamed: NamedRegistryPath = "crates/weaver_emit/data".parse().unwrap();
+        assert_eq!(unnamed.name, None);
+
+        // URLs containing a `=` are not split.
+        let url: NamedRegistryPath = "https://example.com/archive.zip?ref=main".parse().unwrap();
+        assert_eq!(url.name, None);
+        assert_eq!(
+            url.path.to_string(),
+            "https://example.com/archive.zip?ref=main"
+        );
+    }
 }
@@ -5,35 +5,262 @@
 #![allow(clippy::needless_for_each)]
 
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
+use arc_swap::ArcSwap;
 use axum::{
+    extract::DefaultBodyLimit,
     http::{header, StatusCode},
     response::{Html, IntoResponse},
-    routing::get,
+    routing::{get, post},
     Json, Router,
 };
 use miette::Diagnostic;
 use serde::Serialize;
+use tokio::sync::broadcast;
 use tower_http::cors::{Any, CorsLayer};
 use utoipa::OpenApi;
+use weaver_common::diagnostic::DiagnosticMessages;
 use weaver_forge::v2::{
     attribute::Attribute, entity::Entity, event::Event, metric::Metric,
     registry::ForgeResolvedRegistry, span::Span,
 };
+use weaver_live_check::{
+    CumulativeStatistics, DisabledStatistics, LiveCheckReport, LiveCheckStatistics,
+    VersionedRegistry,
+};
+use weaver_resolved_schema::v2::ResolvedTelemetrySchema;
 use weaver_semconv::stability::Stability;
 
 use super::handlers;
-use super::types::{RegistryCounts, RegistryStats, SearchResponse};
+use super::types::{
+    RegistryCounts, RegistryDiagnostics, RegistryInfo, RegistryStats, ReloadEvent, RenderRequest,
+    RenderResponse, RenderedFile, SearchResponse,
+};
 use super::ui::UI_DIST;
-use weaver_search::{ScoredResult, SearchContext, SearchResult, SearchType};
+use weaver_search::{ScoredResult, SearchContext, SearchFacets, SearchResult, SearchType};
 
 /// Shared application state for all request handlers.
 pub struct AppState {
-    /// The resolved registry loaded at startup.
+    /// The served registries. The first one is the default registry, also served under
+    /// `/api/v1/registry`.
+    pub registries: Vec<Arc<ServedRegistry>>,
+    /// Notifies the reloads of the served registries to the subscribers of `/api/v1/events`.
+    pub events: broadcast::Sender<ReloadEvent>,
+}
+
+impl AppState {
+    /// Creates the state of the server for the given registries (the first one being the
+    /// default registry) and the diagnostics of their resolution.
+    ///
+    /// Returns an error if two registries have the same name and version.
+    pub fn try_new(registries: Vec<(RegistryState, DiagnosticMessages)>) -> Result<Self, Error> {
+        let mut served: Vec<Arc<ServedRegistry>> = Vec::with_capacity(registries.len());
+        for (registry, diagnostics) in registries {
+            if served.iter().any(|other| other.id() == registry.id()) {
+                return Err(Error::DuplicateRegistry {
+                    name: registry.name,
+                    version: registry.version,
+                });
+            }
+            served.push(Arc::new(ServedRegistry::new(registry, diagnostics)));
+        }
+        let (events, _) = broadcast::channel(16);
+        Ok(Self {
+            registries: served,
+            events,
+        })
+    }
+
+    /// Returns the registry with the given id (`{name}/{version}`).
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Arc<ServedRegistry>> {
+        self.registries.iter().find(|registry| registry.id() == id)
+    }
+
+    /// Replaces a served registry with the result of its new resolution and notifies the
+    /// subscribers. If the resolution failed, the previous registry is still served and
+    /// only its diagnostics are updated.
+    pub fn reload(
+        &self,
+        served: &ServedRegistry,
+        result: Result<(RegistryState, DiagnosticMessages), DiagnosticMessages>,
+    ) {
+        let state = match result {
+            Ok((registry, diagnostics)) => (
+                Arc::new(registry),
+                Arc::new(ReloadStatus::Loaded(diagnostics)),
+            ),
+            Err(diagnostics) => (
+                served.current(),
+                Arc::new(ReloadStatus::Failed(diagnostics)),
+            ),
+        };
+        let event = ReloadEvent {
+            name: served.name.clone(),
+            version: served.version.clone(),
+            success: matches!(state.1.as_ref(), ReloadStatus::Loaded(_)),
+        };
+        served.state.store(Arc::new(state));
+        // No subscriber is not an error
+        _ = self.events.send(event);
+    }
+}
+
+/// A registry served under `/api/v1/registries/{name}/{version}`. The registry can be
+/// replaced while it is served, e.g. when its definition files change.
+pub struct ServedRegistry {
+    /// Name under which the registry is served.
+    pub name: String,
+    /// Version under which the registry is served. A reload keeps the registry served under
+    /// the same version, even if the version of its schema URL changed.
+    pub version: String,
+    /// The registry currently served and the status of its last resolution, replaced
+    /// together so that a request never sees a registry with the status of another one.
+    state: ArcSwap<(Arc<RegistryState>, Arc<ReloadStatus>)>,
+}
+
+/// The outcome of the last resolution of a served registry.
+pub enum ReloadStatus {
+    /// The registry was resolved and is served, with the given warnings.
+    Loaded(DiagnosticMessages),
+    /// The resolution failed, the previous registry is still served.
+    Failed(DiagnosticMessages),
+}
+
+impl ServedRegistry {
+    fn new(registry: RegistryState, diagnostics: DiagnosticMessages) -> Self {
+        Self {
+            name: registry.name.clone(),
+            version: registry.version.clone(),
+            state: ArcSwap::from_pointee((
+                Arc::new(registry),
+                Arc::new(ReloadStatus::Loaded(diagnostics)),
+            )),
+        }
+    }
+
+    /// Returns the registry currently served.
+    #[must_use]
+    pub fn current(&self) -> Arc<RegistryState> {
+        self.state.load().0.clone()
+    }
+
+    /// Returns the status of the last resolution of the registry.
+    #[must_use]
+    pub fn status(&self) -> Arc<ReloadStatus> {
+        self.state.load().1.clone()
+    }
+
+    /// Returns the id of the registry (`{name}/{version}`).
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.name, self.version)
+    }
+
+    /// Returns the path under which the registry is served.
+    #[must_use]
+    pub fn base_path(&self) -> String {
+        format!("/api/v1/registries/{}/{}", self.name, self.version)
+    }
+
+    /// Returns the description of the registry.
+    #[must_use]
+    pub fn info(&self, default: bool) -> RegistryInfo {
+        let registry = self.current();
+        RegistryInfo {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            schema_url: registry.registry.schema_url.to_string(),
+            path: self.base_path(),
+            default,
+            counts: RegistryCounts::from_registry(&registry.registry),
+        }
+    }
+
+    /// Returns the diagnostics of the last resolution of the registry.
+    #[must_use]
+    pub fn diagnostics(&self) -> RegistryDiagnostics {
+        let (success, diagnostics) = match self.status().as_ref() {
+            ReloadStatus::Loaded(diagnostics) => (true, diagnostics.clone()),
+            ReloadStatus::Failed(diagnostics) => (false, diagnostics.clone()),
+        };
+        RegistryDiagnostics {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            success,
+            diagnostics,
+        }
+    }
+}
+
+/// A registry served by the API, with the state of its request handlers.
+pub struct RegistryState {
+    /// Name under which the registry is served.
+    pub name: String,
+    /// Version of the registry.
+    pub version: String,
+    /// The resolved registry.
     pub registry: ForgeResolvedRegistry,
+    /// The resolved schema, used to compute the changes between registries.
+    pub resolved_schema: ResolvedTelemetrySchema,
     /// Pre-built search context for fast lookups.
     pub search_ctx: SearchContext,
+    /// The registry checked by live-check, built on first use.
+    live_check_registry: OnceLock<Arc<VersionedRegistry>>,
+}
+
+impl RegistryState {
+    /// Creates the state of a registry, named after the host of its schema URL if no name
+    /// is given.
+    #[must_use]
+    pub fn new(
+        name: Option<&str>,
+        resolved_schema: ResolvedTelemetrySchema,
+        registry: ForgeResolvedRegistry,
+    ) -> Self {
+        let schema_url = &registry.schema_url;
+        let name = name.map_or_else(
+            || {
+                schema_url
+                    .name()
+                    .split('/')
+                    .next()
+                    .unwrap_or_default()
+                    .replace(
+                        |c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-',
+                        "_",
+                    )
+            },
+            str::to_owned,
+        );
+        let version = schema_url.version().to_owned();
+        // Build search context once per resolution
+        let search_ctx = SearchContext::from_registry(&registry);
+        Self {
+            name,
+            version,
+            registry,
+            resolved_schema,
+            search_ctx,
+            live_check_registry: OnceLock::new(),
+        }
+    }
+
+    /// Returns the id of the registry (`{name}/{version}`).
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.name, self.version)
+    }
+
+    /// Returns the registry checked by live-check.
+    #[must_use]
+    pub fn live_check_registry(&self) -> Arc<VersionedRegistry> {
+        Arc::clone(
+            self.live_check_registry
+                .get_or_init(|| Arc::new(VersionedRegistry::V2(Box::new(self.registry.clone())))),
+        )
+    }
 }
 
 /// Error type for server operations.
@@ -45,6 +272,24 @@ pub enum Error {
         /// The error message.
         error: String,
     },
+
+    /// Two served registries have the same name and version.
+    #[error("Registry `{name}` version `{version}` is served more than once, use `NAME=PATH` to serve it under another name")]
+    DuplicateRegistry {
+        /// The name of the registry.
+        name: String,
+        /// The version of the registry.
+        version: String,
+    },
+
+    /// A registry name can't be used in the API paths.
+    #[error(
+        "Invalid registry name `{name}`, only ASCII letters, digits, `.`, `-` and `_` are allowed"
+    )]
+    InvalidRegistryName {
+        /// The invalid name.
+        name: String,
+    },
 }
 
 impl From<std::io::Error> for Error {
@@ -82,14 +327,31 @@ impl From<std::io::Error> for Error {
         handlers::get_registry_entity,
         handlers::search_registry,
         handlers::filter_registry,
+        handlers::list_registries,
+        handlers::diff_registries,
+        handlers::get_diagnostics,
+        handlers::events,
+        handlers::live_check,
+        handlers::render,
     ),
     components(
         schemas(
             RegistryStats,
             RegistryCounts,
+            RegistryInfo,
+            RegistryDiagnostics,
+            ReloadEvent,
+            LiveCheckReport,
+            LiveCheckStatistics,
+            CumulativeStatistics,
+            DisabledStatistics,
+            RenderRequest,
+            RenderResponse,
+            RenderedFile,
             SearchType,
             SearchResponse,
             SearchResult,
+            SearchFacets,
             ScoredResult<Attribute>,
             ScoredResult<Metric>,
             ScoredResult<Span>,
@@ -106,7 +368,15 @@ impl From<std::io::Error> for Error {
     tags(
         (name = "health", description = "Health check endpoints"),
         (name = "schemas", description = "JSON schema endpoints"),
-        (name = "registry", description = "Registry stats, search, and resource lookup"),
+        (name = "registry", description = "Registry stats, search, and resource lookup. \
+            These endpoints serve the default registry under `/api/v1/registry` and each served \
+            registry under `/api/v1/registries/{name}/{version}`"),
+        (name = "registries", description = "Served registries and the changes between them"),
+        (name = "live-check", description = "Validation of telemetry samples against the \
+            served registries"),
+        (name = "render", description = "Rendering of templates against the served registries"),
+        (name = "reload", description = "Diagnostics and notifications of the reloads of the \
+            served registries (see `weaver serve --watch`)"),
     )
 )]
 pub struct ApiDoc;
@@ -116,60 +386,57 @@ async fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
     Json(ApiDoc::openapi())
 }
 
+/// Maximum size of the body of a live-check request, the default maximum message size of
+/// the OTLP/gRPC receivers.
+const MAX_LIVE_CHECK_BODY_SIZE: usize = 4 * 1024 * 1024;
+
 /// Run the API server.
 ///
 /// # Arguments
 ///
 /// * `bind_addr` - The address to bind the server to.
-/// * `registry` - The resolved V2 registry to serve.
+/// * `state` - The registries to serve, see [`AppState::try_new`].
 /// * `cors_origins` - Optional CORS origins. Use "*" for any origin, comma-separated for specific origins, or None for no CORS.
 pub async fn run_server(
     bind_addr: SocketAddr,
-    registry: ForgeResolvedRegistry,
+    state: Arc<AppState>,
     cors_origins: Option<&str>,
 ) -> Result<(), Error> {
-    // Build search context once at startup
-    let search_ctx = SearchContext::from_registry(&registry);
-
-    let state = Arc::new(AppState {
-        registry,
-        search_ctx,
-    });
-
     let mut app = Router::new()
         // Health check
         .route("/health", get(handlers::health))
         // Schemas
         .route("/api/v1/schema/{name}", get(handlers::get_schema))
-        // Registry stats
-        .route("/api/v1/registry/stats", get(handlers::get_registry_stats))
-        // Individual resources
+        // Served registries
+        .route("/api/v1/registries", get(handlers::list_registries))
         .route(
-            "/api/v1/registry/attribute/{key}",
-            get(handlers::get_registry_attribute),
+            "/api/v1/registries/{name}/{version}/{*rest}",
+            get(handlers::registry_not_found),
         )
+        // Changes between two served registries
+        .route("/api/v1/diff", get(handlers::diff_registries))
+        // Diagnostics and reload notifications
+        .route("/api/v1/diagnostics", get(handlers::get_diagnostics))
+        .route("/api/v1/events", get(handlers::events))
+        // Live-check
         .route(
-            "/api/v1/registry/metric/{name}",
-            get(handlers::get_registry_metric),
+            "/api/v1/live-check",
+            post(handlers::live_check).layer(DefaultBodyLimit::max(MAX_LIVE_CHECK_BODY_SIZE)),
         )
-        .route(
-            "/api/v1/registry/span/{type}",
-            get(handlers::get_registry_span),
-        )
-        .route(
-            "/api/v1/registry/event/{name}",
-            get(handlers::get_registry_event),
-        )
-        .route(
-            "/api/v1/registry/entity/{type}",
-            get(handlers::get_registry_entity),
-        )
-        // Search
-        .route("/api/v1/registry/search", get(handlers::search_registry))
-        // Filter
-        .route("/api/v1/registry/filter", get(handlers::filter_registry))
+        // Template rendering
+        .route("/api/v1/render", post(handlers::render))
         // OpenAPI specification
-        .route("/api/v1/openapi.json", get(openapi_spec))
+        .route("/api/v1/openapi.json", get(openapi_spec));
+
+    // The default registry, then each registry under its own path
+    if let Some(default) = state.registries.first() {
+        app = app.nest("/api/v1/registry", registry_routes(Arc::clone(default)));
+    }
+    for registry in &state.registries {
+        app = app.nest(&registry.base_path(), registry_routes(Arc::clone(registry)));
+    }
+
+    let mut app = app
         // UI fallback - serves embedded static files
         .fallback(serve_ui)
         .with_state(state);
@@ -207,6 +474,24 @@ pub async fn run_server(
     Ok(())
 }
 
+/// Routes of the endpoints of a registry.
+fn registry_routes(registry: Arc<ServedRegistry>) -> Router<Arc<AppState>> {
+    Router::new()
+        // Registry stats
+        .route("/stats", get(handlers::get_registry_stats))
+        // Individual resources
+        .route("/attribute/{key}", get(handlers::get_registry_attribute))
+        .route("/metric/{name}", get(handlers::get_registry_metric))
+        .route("/span/{type}", get(handlers::get_registry_span))
+        .route("/event/{name}", get(handlers::get_registry_event))
+        .route("/entity/{type}", get(handlers::get_registry_entity))
+        // Search
+        .route("/search", get(handlers::search_registry))
+        // Filter
+        .route("/filter", get(handlers::filter_registry))
+        .with_state(registry)
+}
+
 /// Serve embedded UI files with SPA fallback.
 async fn serve_ui(uri: axum::http::Uri) -> impl IntoResponse {
     let path = uri.path().trim_start_matches('/');
@@ -4,7 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
-use weaver_search::{SearchResult, SearchType};
+use weaver_common::diagnostic::DiagnosticMessages;
+use weaver_forge::v2::registry::ForgeResolvedRegistry;
+use weaver_search::{SearchFacets, SearchResult, SearchType};
 use weaver_semconv::stability::Stability;
 
 /// Registry stats response.
@@ -35,6 +37,133 @@ pub struct RegistryCounts {
     pub attribute_groups: usize,
 }
 
+impl RegistryCounts {
+    /// Counts the definitions of a registry.
+    #[must_use]
+    pub fn from_registry(registry: &ForgeResolvedRegistry) -> Self {
+        Self {
+            attributes: registry.registry.attributes.len(),
+            metrics: registry.registry.metrics.len(),
+            spans: registry.registry.spans.len(),
+            events: registry.registry.events.len(),
+            entities: registry.registry.entities.len(),
+            attribute_groups: registry.registry.attribute_groups.len(),
+        }
+    }
+}
+
+/// A served registry.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegistryInfo {
+    /// Name under which the registry is served.
+    pub name: String,
+    /// Version of the registry.
+    pub version: String,
+    /// The schema URL.
+    pub schema_url: String,
+    /// Path under which the registry endpoints are served.
+    pub path: String,
+    /// Whether this registry is also served under `/api/v1/registry`.
+    pub default: bool,
+    /// Counts of different entity types.
+    pub counts: RegistryCounts,
+}
+
+/// The diagnostics of the last resolution of a served registry.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegistryDiagnostics {
+    /// Name under which the registry is served.
+    pub name: String,
+    /// Version under which the registry is served.
+    pub version: String,
+    /// Whether the last resolution succeeded. If it failed, the previous registry is still
+    /// served.
+    pub success: bool,
+    /// The errors and warnings of the last resolution.
+    #[schema(value_type = Vec<Object>)]
+    pub diagnostics: DiagnosticMessages,
+}
+
+/// Notification sent to the subscribers of `/api/v1/events` when a served registry is
+/// reloaded.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReloadEvent {
+    /// Name under which the registry is served.
+    pub name: String,
+    /// Version under which the registry is served.
+    pub version: String,
+    /// Whether the registry was reloaded. If not, the previous registry is still served and
+    /// the errors are available at `/api/v1/diagnostics`.
+    pub success: bool,
+}
+
+/// Query parameters for the live-check endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct LiveCheckParams {
+    /// The registry to check the samples against, as `{name}/{version}` (default: the
+    /// default registry).
+    #[param(example = "opentelemetry.io/1.34.0")]
+    pub registry: Option<String>,
+}
+
+/// Query parameters for the render endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RenderParams {
+    /// The registry to render the template against, as `{name}/{version}` (default: the
+    /// default registry).
+    #[param(example = "opentelemetry.io/1.34.0")]
+    pub registry: Option<String>,
+}
+
+/// Template to render against a registry.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RenderRequest {
+    /// The Jinja template, named `template.j2`.
+    #[schema(example = "{% for attr in ctx.attributes %}{{ attr.key }}\n{% endfor %}")]
+    pub template: String,
+    /// Content of a `weaver.yaml` file. Its template configurations (filter,
+    /// application_mode, params, file_name) apply to `template.j2` when they have no
+    /// `template` pattern. By default the template is applied once to the whole registry.
+    #[serde(default)]
+    pub config: Option<String>,
+    /// A jq filter overriding the filter of the template configurations.
+    #[serde(default)]
+    #[schema(example = ".registry")]
+    pub filter: Option<String>,
+}
+
+/// A file rendered by a template.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RenderedFile {
+    /// Path of the file, as it would be written in the output directory.
+    pub path: String,
+    /// Rendered content.
+    pub content: String,
+}
+
+/// Result of rendering a template.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RenderResponse {
+    /// Whether the template was rendered without errors.
+    pub success: bool,
+    /// The rendered files.
+    pub files: Vec<RenderedFile>,
+    /// The configuration, filter and template errors.
+    #[schema(value_type = Vec<Object>)]
+    pub diagnostics: DiagnosticMessages,
+}
+
+/// Query parameters for the diff endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DiffParams {
+    /// The baseline registry, as `{name}/{version}`.
+    #[param(example = "opentelemetry.io/1.30.0")]
+    pub from: String,
+    /// The registry compared to the baseline, as `{name}/{version}`.
+    #[param(example = "opentelemetry.io/1.34.0")]
+    pub to: String,
+}
+
 /// Query parameters for search endpoint.
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct SearchParams {
@@ -73,6 +202,8 @@ pub struct SearchResponse {
     pub offset: usize,
     /// The search results.
     pub results: Vec<SearchResult>,
+    /// Number of matches per type and per stability level.
+    pub facets: SearchFacets,
 }
 
 /// Query parameters for filter endpoint.
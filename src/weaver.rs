@@ -1,20 +1,24 @@
 //! A module containing all the "process" of running weaver as components.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use miette::Diagnostic;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::Serialize;
 use weaver_checker::Error::{InvalidPolicyFile, PolicyViolation};
-use weaver_checker::{Engine, PolicyStage, SEMCONV_REGO};
+use weaver_checker::{Engine, PolicyFinding, PolicyStage, SEMCONV_REGO};
+use weaver_common::cache::CacheSettings;
 use weaver_common::diagnostic::DiagnosticMessage;
 use weaver_common::log_success;
 use weaver_common::vdir::VirtualDirectory;
 use weaver_common::{diagnostic::DiagnosticMessages, result::WResult};
 use weaver_forge::registry::ResolvedRegistry;
 use weaver_resolved_schema::ResolvedTelemetrySchema;
-use weaver_resolver::{LoadedSemconvRegistry, SchemaResolver};
+use weaver_resolver::{LoadedSemconvRegistry, ResolutionCache, SchemaResolver};
 use weaver_semconv::semconv::Versioned;
+use weaver_semconv::source_location::{GroupSourceLocations, SourceLocation};
 use weaver_semconv::{registry_repo::RegistryRepo, semconv::SemConvSpecWithProvenance};
 use weaver_version::schema_changes::SchemaChanges;
 
@@ -25,13 +29,22 @@ pub struct WeaverEngine<'a> {
     // TODO - divorce config from args
     registry_config: &'a RegistryArgs,
     policy_config: &'a PolicyArgs,
+    cache_settings: &'a CacheSettings,
+    resolution_cache: Option<ResolutionCache>,
 }
 impl<'a> WeaverEngine<'a> {
-    /// Constructs a new engine.
-    pub fn new(registry: &'a RegistryArgs, policy: &'a PolicyArgs) -> Self {
+    /// Constructs a new engine resolving remote registries and policies with the given cache
+    /// settings.
+    pub fn new(
+        registry: &'a RegistryArgs,
+        policy: &'a PolicyArgs,
+        cache_settings: &'a CacheSettings,
+    ) -> Self {
         Self {
             registry_config: registry,
             policy_config: policy,
+            cache_settings,
+            resolution_cache: ResolutionCache::from_settings(cache_settings),
         }
     }
 
@@ -59,7 +72,8 @@ impl<'a> WeaverEngine<'a> {
     ) -> Result<Loaded, Error> {
         let registry_path = &self.registry_config.registry;
         let mut nfes = vec![];
-        let main_registry_repo = RegistryRepo::try_new(None, registry_path, &mut nfes)?;
+        let main_registry_repo =
+            RegistryRepo::try_new(None, registry_path, self.cache_settings, &mut nfes)?;
 
         diag_msgs.extend_from_vec(nfes.into_iter().map(DiagnosticMessage::new).collect());
 
@@ -76,11 +90,12 @@ impl<'a> WeaverEngine<'a> {
         let loaded = SchemaResolver::load_semconv_repository(
             repo.clone(),
             self.registry_config.follow_symlinks,
+            self.resolution_cache.as_ref(),
         )
         .capture_non_fatal_errors(diag_msgs)?;
 
         // Optionally init policy engine
-        let policy_engine = prepare_policy_engine(self.policy_config, &repo)?;
+        let policy_engine = prepare_policy_engine(self.policy_config, &repo, self.cache_settings)?;
         Ok(Loaded {
             loaded,
             policy_engine,
@@ -96,9 +111,13 @@ impl<'a> WeaverEngine<'a> {
         diag_msgs: &mut DiagnosticMessages,
     ) -> Result<Resolved, Error> {
         let registry_path_repr: String = loaded.loaded.registry_path_repr().to_owned();
-        let resolved =
-            SchemaResolver::resolve(loaded.loaded, self.registry_config.include_unreferenced)
-                .capture_non_fatal_errors(diag_msgs)?;
+        let source_index = SourceIndex::from_loaded(&loaded.loaded);
+        let resolved = SchemaResolver::resolve(
+            loaded.loaded,
+            self.registry_config.include_unreferenced,
+            self.resolution_cache.as_ref(),
+        )
+        .capture_non_fatal_errors(diag_msgs)?;
 
         // This creates the template/json friendly registry.
         let template =
@@ -108,6 +127,7 @@ impl<'a> WeaverEngine<'a> {
             resolved_schema: resolved,
             template_schema: template,
             registry_path_repr,
+            source_index,
             policy_engine: loaded.policy_engine,
         })
     }
@@ -137,7 +157,9 @@ impl Loaded {
         if let Some(policy_engine) = self.policy_engine.as_ref() {
             // Note: We can't check polices on resolved registries.
             if let LoadedSemconvRegistry::Unresolved { specs, .. } = &self.loaded {
-                check_policy(policy_engine, specs).capture_non_fatal_errors(diag_msgs)?;
+                let source_index = SourceIndex::from_loaded(&self.loaded);
+                check_policy(policy_engine, specs, &source_index)
+                    .capture_non_fatal_errors(diag_msgs)?;
             }
         }
         Ok(())
@@ -151,6 +173,7 @@ pub struct Resolved {
     resolved_schema: ResolvedTelemetrySchema,
     template_schema: ResolvedRegistry,
     registry_path_repr: String,
+    source_index: SourceIndex,
     policy_engine: Option<Engine>,
 }
 impl Resolved {
@@ -190,6 +213,7 @@ impl Resolved {
                 &mut e,
                 PolicyStage::AfterResolution,
                 &self.registry_path_repr,
+                &self.source_index,
                 &self.template_schema,
                 &[],
             )
@@ -220,6 +244,7 @@ impl Resolved {
                 &mut policy_engine,
                 PolicyStage::ComparisonAfterResolution,
                 &self.registry_path_repr,
+                &self.source_index,
                 &self.template_schema(),
                 &[baseline.template_schema()],
             )
@@ -249,6 +274,7 @@ pub struct ResolvedV2 {
     resolved_schema: weaver_resolved_schema::v2::ResolvedTelemetrySchema,
     template_schema: weaver_forge::v2::registry::ForgeResolvedRegistry,
     registry_path_repr: String,
+    source_index: SourceIndex,
     policy_engine: Option<Engine>,
 }
 
@@ -273,6 +299,16 @@ impl ResolvedV2 {
         self.template_schema
     }
 
+    /// Gives both the resolved schema and the template schema.
+    pub fn into_schemas(
+        self,
+    ) -> (
+        weaver_resolved_schema::v2::ResolvedTelemetrySchema,
+        weaver_forge::v2::registry::ForgeResolvedRegistry,
+    ) {
+        (self.resolved_schema, self.template_schema)
+    }
+
     /// Checks after resolution policies.
     pub fn check_after_resolution_policy(
         &self,
@@ -284,6 +320,7 @@ impl ResolvedV2 {
                 &mut e,
                 PolicyStage::AfterResolution,
                 &self.registry_path_repr,
+                &self.source_index,
                 &self.template_schema,
                 &[],
             )
@@ -314,6 +351,7 @@ impl ResolvedV2 {
                 &mut policy_engine,
                 PolicyStage::ComparisonAfterResolution,
                 &self.registry_path_repr,
+                &self.source_index,
                 &self.template_schema(),
                 &[baseline.template_schema()],
             )
@@ -353,6 +391,7 @@ impl TryFrom<Resolved> for ResolvedV2 {
             resolved_schema,
             template_schema,
             registry_path_repr: value.registry_path_repr,
+            source_index: value.source_index,
             policy_engine: value.policy_engine,
         })
     }
@@ -368,6 +407,11 @@ impl Diff {
     pub fn as_template_context(&self) -> &SchemaChanges {
         &self.changes
     }
+
+    /// Returns the number of changes classified as breaking.
+    pub fn count_breaking_changes(&self) -> usize {
+        self.changes.count_breaking_changes()
+    }
 }
 
 /// The difference between two resolved repositories.
@@ -380,6 +424,11 @@ impl DiffV2 {
     pub fn as_template_context(&self) -> &weaver_version::v2::SchemaChanges {
         &self.changes
     }
+
+    /// Returns the number of changes classified as breaking.
+    pub fn count_breaking_changes(&self) -> usize {
+        self.changes.count_breaking_changes()
+    }
 }
 
 /// Errors we expect from the weaver engine.
@@ -402,7 +451,72 @@ pub enum Error {
 // TODO - transparently convert to diagnostic messages.
 impl From<Error> for DiagnosticMessages {
     fn from(value: Error) -> Self {
-        DiagnosticMessages::new(vec![DiagnosticMessage::new(value)])
+        match value {
+            // Keep one diagnostic per error, each with its own source snippet.
+            Error::Semconv(error) => error.into(),
+            Error::Resolver(error) => error.into(),
+            _ => DiagnosticMessages::new(vec![DiagnosticMessage::new(value)]),
+        }
+    }
+}
+
+/// The locations of the group definitions of a registry (and of its dependencies), used
+/// to point policy violations at the YAML definition they apply to.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SourceIndex {
+    groups: HashMap<String, (Arc<str>, GroupSourceLocations)>,
+}
+
+impl SourceIndex {
+    /// Context keys identifying the group of a policy finding.
+    const GROUP_CONTEXT_KEYS: [&'static str; 2] = ["group_id", "group"];
+    /// Context keys identifying the attribute of a policy finding.
+    const ATTRIBUTE_CONTEXT_KEYS: [&'static str; 3] = ["attribute_name", "attr", "attribute_key"];
+
+    /// Indexes the groups of a loaded registry. Pre-resolved registries have no source
+    /// locations.
+    pub(crate) fn from_loaded(loaded: &LoadedSemconvRegistry) -> Self {
+        let mut index = Self::default();
+        index.add_loaded(loaded);
+        index
+    }
+
+    fn add_loaded(&mut self, loaded: &LoadedSemconvRegistry) {
+        if let LoadedSemconvRegistry::Unresolved {
+            specs,
+            dependencies,
+            ..
+        } = loaded
+        {
+            for dependency in dependencies {
+                self.add_loaded(dependency);
+            }
+            for spec in specs {
+                let path: Arc<str> = Arc::from(spec.provenance.path.as_str());
+                for (group_id, locations) in spec.source_locations.groups() {
+                    _ = self
+                        .groups
+                        .insert(group_id.clone(), (path.clone(), locations.clone()));
+                }
+            }
+        }
+    }
+
+    /// Locates the definition a policy finding applies to from the group id (and
+    /// attribute) of its context. Returns the path of the file defining the group and
+    /// the location of the attribute (or of the group) in this file.
+    pub(crate) fn locate(&self, finding: &PolicyFinding) -> Option<(&str, SourceLocation)> {
+        let context = finding.context.as_ref()?.as_object()?;
+        let context_value = |keys: &[&str]| {
+            keys.iter()
+                .find_map(|key| context.get(*key).and_then(|value| value.as_str()))
+        };
+        let (path, group) = self.groups.get(context_value(&Self::GROUP_CONTEXT_KEYS)?)?;
+        let location = match context_value(&Self::ATTRIBUTE_CONTEXT_KEYS) {
+            Some(attribute) => group.attribute(attribute).location,
+            None => group.location(),
+        };
+        Some((path, location))
     }
 }
 
@@ -410,13 +524,14 @@ impl From<Error> for DiagnosticMessages {
 fn prepare_policy_engine(
     policy_args: &PolicyArgs,
     registry_repo: &RegistryRepo,
+    cache_settings: &CacheSettings,
 ) -> Result<Option<Engine>, Error> {
     if !policy_args.skip_policies {
         // Create and hold all VirtualDirectory instances to keep them from being dropped
         let policy_vdirs: Vec<VirtualDirectory> = policy_args
             .policies
             .iter()
-            .map(VirtualDirectory::try_new)
+            .map(|policy| VirtualDirectory::try_new(policy, cache_settings))
             .collect::<Result<_, _>>()?;
 
         // Extract paths from VirtualDirectory instances
@@ -482,6 +597,7 @@ fn init_policy_engine(
 ///
 /// * `policy_engine` - The pre-configured policy engine for checking policies.
 /// * `semconv_specs` - The semantic convention specifications to check.
+/// * `source_index` - The locations of the group definitions of the registry.
 ///
 /// # Returns
 ///
@@ -490,6 +606,7 @@ fn init_policy_engine(
 pub(crate) fn check_policy(
     policy_engine: &Engine,
     semconv_specs: &[SemConvSpecWithProvenance],
+    source_index: &SourceIndex,
 ) -> WResult<(), weaver_checker::Error> {
     // Check policies in parallel
     let results = semconv_specs
@@ -502,6 +619,7 @@ pub(crate) fn check_policy(
                 &mut policy_engine,
                 PolicyStage::BeforeResolution,
                 semconv.provenance.path.as_str(),
+                source_index,
                 &semconv.spec,
                 &[],
             )
@@ -529,11 +647,13 @@ pub(crate) fn check_policy(
 /// * `policy_engine` - The policy engine.
 /// * `policy_stage` - The policy stage to check.
 /// * `policy_file` - The policy file to check.
+/// * `source_index` - The locations used to point violations at their definition.
 /// * `input` - The input to check.
 pub(crate) fn check_policy_stage<T: Serialize, U: Serialize>(
     policy_engine: &mut Engine,
     policy_stage: PolicyStage,
     policy_file: &str,
+    source_index: &SourceIndex,
     input: &T,
     data: &[U],
 ) -> WResult<(), weaver_checker::Error> {
@@ -552,9 +672,15 @@ pub(crate) fn check_policy_stage<T: Serialize, U: Serialize>(
         Ok(_) => match policy_engine.check(policy_stage) {
             Ok(violations) => {
                 for violation in violations {
+                    let (provenance, location) = match source_index.locate(&violation) {
+                        Some((path, location)) => (path.to_owned(), Some(location)),
+                        None => (policy_file.to_owned(), None),
+                    };
                     errors.push(PolicyViolation {
-                        provenance: policy_file.to_owned(),
+                        provenance,
                         violation: Box::new(violation),
+                        line: location.map(|l| l.line),
+                        column: location.map(|l| l.column),
                     });
                 }
             }
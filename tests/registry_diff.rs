@@ -29,8 +29,40 @@ fn test_cli_interface() {
     let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
     let schema_changes: SchemaChanges = serde_json::from_str(&stdout)
         .unwrap_or_else(|err| panic!("Invalid JSON: {}\n{}", err, &stdout));
-    assert_eq!(schema_changes.count_registry_attribute_changes(), 5);
-    // We expect 5 types of schema changes and 5 schema changes per telemetry object type.
-    // => 5*5 = 25 schema changes.
-    assert_eq!(schema_changes.count_changes(), 25);
+    assert_eq!(schema_changes.count_registry_attribute_changes(), 8);
+    // We expect 5 types of telemetry objects with 5 schema changes each, plus 3 objects per
+    // type whose brief was updated when they were deprecated.
+    // => 5*(5+3) = 40 schema changes.
+    assert_eq!(schema_changes.count_changes(), 40);
+}
+
+/// Breaking changes (removed stable attribute, narrowed stable enum, new unit of a stable
+/// metric) make the command fail with `--fail-on breaking`.
+#[test]
+fn test_cli_fail_on_breaking() {
+    for fail_on in ["none", "breaking"] {
+        let mut cmd = Command::cargo_bin("weaver").unwrap();
+        let output = cmd
+            .arg("registry")
+            .arg("diff")
+            .arg("--baseline-registry")
+            .arg("tests/diff_breaking/registry_baseline/")
+            .arg("-r")
+            .arg("tests/diff_breaking/registry_head/")
+            .arg("--diff-format")
+            .arg("json")
+            .arg("--fail-on")
+            .arg(fail_on)
+            .timeout(std::time::Duration::from_secs(60))
+            .output()
+            .expect("failed to execute process");
+
+        assert_eq!(output.status.success(), fail_on == "none");
+
+        let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+        let schema_changes: SchemaChanges = serde_json::from_str(&stdout)
+            .unwrap_or_else(|err| panic!("Invalid JSON: {}\n{}", err, &stdout));
+        assert_eq!(schema_changes.count_breaking_changes(), 3);
+        assert_eq!(schema_changes.compatibility_findings().len(), 5);
+    }
 }
@@ -4,6 +4,7 @@
 
 use miette::Diagnostic;
 
+use weaver_common::cache::{CacheSettings, DEFAULT_LOCK_FILE};
 use weaver_common::vdir::VirtualDirectoryPath;
 use weaver_resolver::SchemaResolver;
 use weaver_semconv::{registry_repo::RegistryRepo, schema_url::SchemaUrl};
@@ -39,11 +40,19 @@ fn test_cli_interface() {
             .try_into()
             .unwrap(),
     );
-    let registry_repo = RegistryRepo::try_new(schema_url, &registry_path, &mut vec![])
-        .unwrap_or_else(|e| {
-            panic!("Failed to create the registry repo, error: {e}");
-        });
-    let loaded = SchemaResolver::load_semconv_repository(registry_repo, false)
+    // The registry is cached in a temporary directory.
+    let cache_dir = tempfile::tempdir().unwrap();
+    let cache_settings = CacheSettings {
+        cache_dir: Some(cache_dir.path().join("cache")),
+        lock_file: Some(cache_dir.path().join(DEFAULT_LOCK_FILE)),
+        ..CacheSettings::default()
+    };
+    let registry_repo =
+        RegistryRepo::try_new(schema_url, &registry_path, &cache_settings, &mut vec![])
+            .unwrap_or_else(|e| {
+                panic!("Failed to create the registry repo, error: {e}");
+            });
+    let loaded = SchemaResolver::load_semconv_repository(registry_repo, false, None)
         .ignore(|e| matches!(e.severity(), Some(miette::Severity::Warning)))
         .into_result_failing_non_fatal()
         .unwrap_or_else(|e| {
@@ -54,7 +63,7 @@ fn test_cli_interface() {
     assert_eq!(log.error_count(), 0);
 
     // Resolve the official semantic convention registry.
-    let resolved_registry = SchemaResolver::resolve(loaded, false)
+    let resolved_registry = SchemaResolver::resolve(loaded, false, None)
         .into_result_failing_non_fatal()
         .unwrap_or_else(|e| {
             panic!("Failed to resolve the official semantic convention registry, error: {e}");
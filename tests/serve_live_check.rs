@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test the live-check endpoint of weaver serve.
+
+use std::process::{Child, Command as StdCommand};
+use std::thread::sleep;
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Kills the server when the test ends, even if it fails.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn live_check(port: u16, query: &str, body: &str) -> Result<Value, ureq::Error> {
+    let body = ureq::post(format!("http://127.0.0.1:{port}/api/v1/live-check{query}"))
+        .header("Content-Type", "application/json")
+        .send(body)?
+        .into_body()
+        .read_to_string()?;
+    Ok(serde_json::from_str(&body).expect("A JSON response"))
+}
+
+/// Returns the ids of the findings of a sample.
+fn finding_ids(sample: &Value) -> Vec<&str> {
+    sample["live_check_result"]["all_advice"]
+        .as_array()
+        .expect("An array of findings")
+        .iter()
+        .filter_map(|finding| finding["id"].as_str())
+        .collect()
+}
+
+#[test]
+fn test_serve_live_check() {
+    let port = portpicker::pick_unused_port().expect("No free ports");
+    let _server = Server(
+        StdCommand::new(env!("CARGO_BIN_EXE_weaver"))
+            .args([
+                "serve",
+                "-r",
+                "crates/weaver_emit/data",
+                "--bind",
+                &format!("127.0.0.1:{port}"),
+            ])
+            .spawn()
+            .expect("Failed to start weaver serve process"),
+    );
+
+    // Wait for the registry to be loaded
+    let mut started = false;
+    for _ in 0..60 {
+        if ureq::get(format!("http://127.0.0.1:{port}/health"))
+            .call()
+            .is_ok()
+        {
+            started = true;
+            break;
+        }
+        sleep(Duration::from_millis(500));
+    }
+    assert!(started, "The server did not start");
+
+    // A JSON array of samples
+    let report = live_check(
+        port,
+        "",
+        r#"[
+            {"attribute": {"name": "test.string", "value": "x"}},
+            {"attribute": {"name": "test.enum", "value": "VALUE_3"}},
+            {"attribute": {"name": "unknown.attr", "value": "x"}}
+        ]"#,
+    )
+    .unwrap();
+    let samples = report["samples"].as_array().expect("An array of samples");
+    assert_eq!(samples.len(), 3);
+    assert!(finding_ids(&samples[0]["attribute"]).is_empty());
+    assert!(finding_ids(&samples[1]["attribute"]).contains(&"undefined_enum_variant"));
+    assert!(finding_ids(&samples[2]["attribute"]).contains(&"missing_attribute"));
+    assert_eq!(report["statistics"]["total_entities"], 3);
+    assert!(
+        report["statistics"]["registry_coverage"]
+            .as_f64()
+            .unwrap_or_default()
+            > 0.0
+    );
+
+    // An OTLP-JSON request against the registry served under its own path
+    let report = live_check(
+        port,
+        "?registry=unknown/unknown",
+        r#"{"resourceSpans":[{"scopeSpans":[{"spans":[{"name":"GET","kind":2,"attributes":[{"key":"test.integer","value":{"stringValue":"x"}}]}]}]}]}"#,
+    )
+    .unwrap();
+    let span = &report["samples"][0]["span"];
+    assert_eq!(span["name"], "GET");
+    assert!(finding_ids(&span["attributes"][0]).contains(&"type_mismatch"));
+
+    // Invalid requests
+    assert!(live_check(port, "", "not json").is_err());
+    assert!(live_check(port, "", r#"{"unknown": []}"#).is_err());
+    assert!(live_check(port, "?registry=unknown/1.0", "[]").is_err());
+
+    // Too large requests are rejected
+    let too_large = format!("[{}]", " ".repeat(5 * 1024 * 1024));
+    assert!(matches!(
+        live_check(port, "", &too_large),
+        Err(ureq::Error::StatusCode(413))
+    ));
+}
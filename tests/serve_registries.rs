@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test that weaver serve serves several registries and the changes between them.
+
+use std::process::{Child, Command as StdCommand};
+use std::thread::sleep;
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Kills the server when the test ends, even if it fails.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn get(port: u16, path: &str) -> Result<Value, ureq::Error> {
+    let body = ureq::get(format!("http://127.0.0.1:{port}{path}"))
+        .call()?
+        .into_body()
+        .read_to_string()?;
+    Ok(serde_json::from_str(&body).expect("A JSON response"))
+}
+
+#[test]
+fn test_serve_registries() {
+    let port = portpicker::pick_unused_port().expect("No free ports");
+    let _server = Server(
+        StdCommand::new(env!("CARGO_BIN_EXE_weaver"))
+            .args([
+                "serve",
+                "-r",
+                "tests/diff/registry_head",
+                "--serve-registry",
+                "tests/diff/registry_baseline",
+                "--serve-registry",
+                "emit=crates/weaver_emit/data",
+                "--bind",
+                &format!("127.0.0.1:{port}"),
+            ])
+            .spawn()
+            .expect("Failed to start weaver serve process"),
+    );
+
+    // Wait for the registries to be loaded
+    let mut registries = None;
+    for _ in 0..60 {
+        if let Ok(value) = get(port, "/api/v1/registries") {
+            registries = Some(value);
+            break;
+        }
+        sleep(Duration::from_millis(500));
+    }
+    let registries = registries.expect("The server did not start");
+    let ids: Vec<(&str, &str, bool)> = registries
+        .as_array()
+        .expect("An array of registries")
+        .iter()
+        .map(|registry| {
+            (
+                registry["name"].as_str().unwrap_or_default(),
+                registry["version"].as_str().unwrap_or_default(),
+                registry["default"].as_bool().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            ("opentelemetry.io", "1.1", true),
+            ("opentelemetry.io", "1.0", false),
+            ("emit", "unknown", false),
+        ]
+    );
+
+    // Each registry is served under its own path, the default one also under `/registry`.
+    let head = get(port, "/api/v1/registries/opentelemetry.io/1.1/stats").unwrap();
+    let default = get(port, "/api/v1/registry/stats").unwrap();
+    let baseline = get(port, "/api/v1/registries/opentelemetry.io/1.0/stats").unwrap();
+    assert_eq!(head, default);
+    assert_eq!(head["schema_url"], "https://opentelemetry.io/schemas/1.1");
+    assert_eq!(
+        baseline["schema_url"],
+        "https://opentelemetry.io/schemas/1.0"
+    );
+    let search = get(port, "/api/v1/registries/emit/unknown/search?q=http").unwrap();
+    assert!(search["total"].as_u64().unwrap_or_default() > 0);
+
+    // Unknown registries are not found.
+    assert!(get(port, "/api/v1/registries/opentelemetry.io/9.9/stats").is_err());
+
+    // Changes between two registries.
+    let diff = get(
+        port,
+        "/api/v1/diff?from=opentelemetry.io/1.0&to=opentelemetry.io/1.1",
+    )
+    .unwrap();
+    assert!(diff["registry"].is_object());
+    let no_changes = get(
+        port,
+        "/api/v1/diff?from=opentelemetry.io/1.1&to=opentelemetry.io/1.1",
+    )
+    .unwrap();
+    assert_ne!(diff, no_changes);
+    assert!(get(
+        port,
+        "/api/v1/diff?from=opentelemetry.io/1.0&to=unknown/1.0"
+    )
+    .is_err());
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test that weaver serve reloads a registry when its files change.
+
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Child, Command as StdCommand};
+use std::sync::mpsc;
+use std::thread::sleep;
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// Kills the server when the test ends, even if it fails.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn get(port: u16, path: &str) -> Result<Value, ureq::Error> {
+    let body = ureq::get(format!("http://127.0.0.1:{port}{path}"))
+        .call()?
+        .into_body()
+        .read_to_string()?;
+    Ok(serde_json::from_str(&body).expect("A JSON response"))
+}
+
+fn write_registry(dir: &Path, attributes: &[&str]) {
+    let attributes: String = attributes
+        .iter()
+        .map(|id| {
+            format!(
+                "        - id: {id}\n          type: boolean\n          brief: {id}\n          stability: development\n"
+            )
+        })
+        .collect();
+    std::fs::write(
+        dir.join("registry.yaml"),
+        format!(
+            "groups:\n    - id: registry.test\n      type: attribute_group\n      brief: Test attributes.\n      attributes:\n{attributes}"
+        ),
+    )
+    .expect("Failed to write the registry");
+}
+
+/// Waits until the stats of the served registry report the given number of attributes.
+fn wait_for_attributes(port: u16, count: u64) -> bool {
+    for _ in 0..60 {
+        if let Ok(stats) = get(port, "/api/v1/registry/stats") {
+            if stats["counts"]["attributes"] == count {
+                return true;
+            }
+        }
+        sleep(Duration::from_millis(250));
+    }
+    false
+}
+
+#[test]
+fn test_serve_reload() {
+    // The default prefix of temp dirs makes them hidden, and hidden files are not loaded
+    let registry = tempfile::Builder::new()
+        .prefix("registry")
+        .tempdir()
+        .expect("Failed to create a temp dir");
+    std::fs::write(
+        registry.path().join("manifest.yaml"),
+        "name: test\nschema_url: https://example.com/schemas/1.0.0\n",
+    )
+    .expect("Failed to write the manifest");
+    write_registry(registry.path(), &["test.a"]);
+
+    let port = portpicker::pick_unused_port().expect("No free ports");
+    let _server = Server(
+        StdCommand::new(env!("CARGO_BIN_EXE_weaver"))
+            .args([
+                "serve",
+                "-r",
+                &registry.path().to_string_lossy(),
+                "--watch",
+                "--watch-interval",
+                "100",
+                "--bind",
+                &format!("127.0.0.1:{port}"),
+            ])
+            .spawn()
+            .expect("Failed to start weaver serve process"),
+    );
+    assert!(wait_for_attributes(port, 1), "The server did not start");
+
+    // Subscribe to the reload notifications
+    let (sender, events) = mpsc::channel();
+    let response = ureq::get(format!("http://127.0.0.1:{port}/api/v1/events"))
+        .call()
+        .expect("Failed to subscribe to the events");
+    let _reader = std::thread::spawn(move || {
+        let reader = BufReader::new(response.into_body().into_reader());
+        for line in reader.lines().map_while(Result::ok) {
+            if let Some(data) = line.strip_prefix("data:") {
+                let event: Value = serde_json::from_str(data.trim()).expect("A JSON event");
+                if sender.send(event).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    // A valid change replaces the served registry
+    write_registry(registry.path(), &["test.a", "test.b"]);
+    assert!(
+        wait_for_attributes(port, 2),
+        "The registry was not reloaded"
+    );
+    let event = events
+        .recv_timeout(Duration::from_secs(15))
+        .expect("No reload event");
+    assert_eq!(event["name"], "example.com");
+    assert_eq!(event["version"], "1.0.0");
+    assert_eq!(event["success"], true);
+    let diagnostics = get(port, "/api/v1/diagnostics").unwrap();
+    assert_eq!(diagnostics[0]["success"], true);
+
+    // An invalid change keeps the previous registry served
+    std::fs::write(registry.path().join("registry.yaml"), "groups: [")
+        .expect("Failed to write the registry");
+    let event = events
+        .recv_timeout(Duration::from_secs(15))
+        .expect("No reload event");
+    assert_eq!(event["success"], false);
+    let diagnostics = get(port, "/api/v1/diagnostics").unwrap();
+    assert_eq!(diagnostics[0]["success"], false);
+    assert!(!diagnostics[0]["diagnostics"]
+        .as_array()
+        .expect("An array of diagnostics")
+        .is_empty());
+    assert!(wait_for_attributes(port, 2));
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test the template rendering endpoint of weaver serve.
+
+use std::process::{Child, Command as StdCommand};
+use std::thread::sleep;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+/// Kills the server when the test ends, even if it fails.
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn render(port: u16, query: &str, request: &Value) -> Result<Value, ureq::Error> {
+    let body = ureq::post(format!("http://127.0.0.1:{port}/api/v1/render{query}"))
+        .header("Content-Type", "application/json")
+        .send(request.to_string())?
+        .into_body()
+        .read_to_string()?;
+    Ok(serde_json::from_str(&body).expect("A JSON response"))
+}
+
+#[test]
+fn test_serve_render() {
+    let port = portpicker::pick_unused_port().expect("No free ports");
+    let _server = Server(
+        StdCommand::new(env!("CARGO_BIN_EXE_weaver"))
+            .args([
+                "serve",
+                "-r",
+                "crates/weaver_emit/data",
+                "--bind",
+                &format!("127.0.0.1:{port}"),
+            ])
+            .spawn()
+            .expect("Failed to start weaver serve process"),
+    );
+
+    // Wait for the registry to be loaded
+    let mut started = false;
+    for _ in 0..60 {
+        if ureq::get(format!("http://127.0.0.1:{port}/health"))
+            .call()
+            .is_ok()
+        {
+            started = true;
+            break;
+        }
+        sleep(Duration::from_millis(500));
+    }
+    assert!(started, "The server did not start");
+
+    // The template is applied once to the whole registry by default
+    let response = render(
+        port,
+        "",
+        &json!({"template": "{{ ctx.schema_url }}: {{ ctx.registry.attributes | length > 0 }}"}),
+    )
+    .unwrap();
+    assert_eq!(response["success"], true);
+    assert_eq!(
+        response["files"],
+        json!([{"path": "template", "content": "https://unknown/unknown: true"}])
+    );
+
+    // A jq filter and a template configuration applying the template to each attribute
+    let response = render(
+        port,
+        "?registry=unknown/unknown",
+        &json!({
+            "template": "{{ ctx.key }} ({{ params.suffix }})",
+            "config": "templates:\n  - application_mode: each\n    file_name: \"{{ ctx.key }}.txt\"\n    params:\n      suffix: attr\n",
+            "filter": ".registry.attributes | map(select(.key == \"test.string\" or .key == \"test.enum\")) | sort_by(.key)",
+        }),
+    )
+    .unwrap();
+    assert_eq!(response["success"], true);
+    assert_eq!(
+        response["files"],
+        json!([
+            {"path": "test.enum.txt", "content": "test.enum (attr)"},
+            {"path": "test.string.txt", "content": "test.string (attr)"},
+        ])
+    );
+
+    // Template errors are returned as diagnostics
+    let response = render(
+        port,
+        "",
+        &json!({"template": "{{ ctx.registry | unknown_filter }}"}),
+    )
+    .unwrap();
+    assert_eq!(response["success"], false);
+    assert!(response["files"].as_array().expect("An array").is_empty());
+    let message = response["diagnostics"][0]["diagnostic"]["message"]
+        .as_str()
+        .unwrap_or_default();
+    assert!(message.contains("unknown_filter"), "{message}");
+
+    // Invalid configurations and filters too
+    let response = render(
+        port,
+        "",
+        &json!({"template": "{{ ctx }}", "config": "templates: 42"}),
+    )
+    .unwrap();
+    assert_eq!(response["success"], false);
+    let response = render(
+        port,
+        "",
+        &json!({"template": "{{ ctx }}", "filter": ".registry |||"}),
+    )
+    .unwrap();
+    assert_eq!(response["success"], false);
+
+    // Templates looping without bound run out of fuel
+    let response = render(
+        port,
+        "",
+        &json!({"template": "{% for i in range(100000) %}{% for j in range(100000) %}{{ j }}{% endfor %}{% endfor %}"}),
+    )
+    .unwrap();
+    assert_eq!(response["success"], false);
+    let message = response["diagnostics"][0]["diagnostic"]["message"]
+        .as_str()
+        .unwrap_or_default();
+    assert!(message.contains("fuel"), "{message}");
+
+    // Unknown registries are not found
+    assert!(render(port, "?registry=unknown/1.0", &json!({"template": ""})).is_err());
+}
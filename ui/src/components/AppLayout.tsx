@@ -1,5 +1,13 @@
-import { useState, useEffect } from 'react'
+import { useState, useEffect, type ChangeEvent } from 'react'
 import { Outlet, useLocation, useNavigate } from '@tanstack/react-router'
+import {
+  getDiagnostics,
+  getSelectedRegistry,
+  listRegistries,
+  selectRegistry,
+  subscribeToReloads,
+} from '../lib/api'
+import type { RegistryDiagnostics, RegistryInfo } from '../lib/api'
 
 export function AppLayout() {
   const location = useLocation()
@@ -11,6 +19,54 @@ export function AppLayout() {
     return savedTheme === 'dark' ? 'dark' : 'light'
   })
 
+  const [registries, setRegistries] = useState<RegistryInfo[]>([])
+  const selectedRegistry = getSelectedRegistry()
+
+  useEffect(() => {
+    listRegistries()
+      .then((served) => {
+        setRegistries(served)
+        // Forget a selected registry that is no longer served
+        const selected = getSelectedRegistry()
+        if (selected && !served.some((r) => `${r.name}/${r.version}` === selected)) {
+          selectRegistry(null)
+          window.location.reload()
+        }
+      })
+      .catch(() => setRegistries([]))
+  }, [])
+
+  const handleRegistryChange = (event: ChangeEvent<HTMLSelectElement>) => {
+    const registry = registries.find((r) => `${r.name}/${r.version}` === event.target.value)
+    const id = registry && !registry.default ? `${registry.name}/${registry.version}` : null
+    selectRegistry(id)
+    // Reload the current page with the data of the selected registry
+    window.location.reload()
+  }
+
+  const defaultRegistry = registries.find((r) => r.default)
+
+  // Notify the reloads of the browsed registry (see `weaver serve --watch`)
+  const [reloaded, setReloaded] = useState<RegistryDiagnostics | null>(null)
+
+  useEffect(() => {
+    return subscribeToReloads((event) => {
+      const id = `${event.name}/${event.version}`
+      const browsed = getSelectedRegistry()
+      listRegistries()
+        .then((served) => {
+          const isBrowsed = browsed
+            ? browsed === id
+            : served.some((r) => r.default && `${r.name}/${r.version}` === id)
+          if (!isBrowsed) return
+          return getDiagnostics().then((diagnostics) => {
+            setReloaded(diagnostics.find((d) => `${d.name}/${d.version}` === id) ?? null)
+          })
+        })
+        .catch(() => setReloaded(null))
+    })
+  }, [])
+
   useEffect(() => {
     document.documentElement.setAttribute('data-theme', theme)
     localStorage.setItem('theme', theme)
@@ -77,6 +133,23 @@ export function AppLayout() {
             </button>
           </div>
           <div className="flex-none gap-2">
+            {registries.length > 1 && (
+              <select
+                className="select select-bordered select-sm"
+                aria-label="Registry"
+                value={
+                  selectedRegistry ??
+                  (defaultRegistry ? `${defaultRegistry.name}/${defaultRegistry.version}` : '')
+                }
+                onChange={handleRegistryChange}
+              >
+                {registries.map((r) => (
+                  <option key={`${r.name}/${r.version}`} value={`${r.name}/${r.version}`}>
+                    {r.name} {r.version}
+                  </option>
+                ))}
+              </select>
+            )}
             <button
               className="btn btn-ghost btn-circle"
               onClick={toggleTheme}
@@ -119,6 +192,42 @@ export function AppLayout() {
         </div>
 
         <main id="main-content" className="flex-1 p-6">
+          {reloaded && (
+            <div
+              className={`alert ${reloaded.success ? 'alert-info' : 'alert-error'} mb-4`}
+              role="alert"
+            >
+              <div className="flex-1">
+                {reloaded.success ? (
+                  <span>
+                    Registry {reloaded.name} {reloaded.version} has been reloaded.
+                  </span>
+                ) : (
+                  <>
+                    <span>
+                      Registry {reloaded.name} {reloaded.version} could not be reloaded, the
+                      previous version is still served.
+                    </span>
+                    <ul className="list-disc ml-6 mt-2 text-sm font-mono">
+                      {reloaded.diagnostics.map((d, i) => (
+                        <li key={i}>{d.diagnostic.message}</li>
+                      ))}
+                    </ul>
+                  </>
+                )}
+              </div>
+              <div className="flex-none gap-2">
+                {reloaded.success && (
+                  <button className="btn btn-sm" onClick={() => window.location.reload()}>
+                    Refresh
+                  </button>
+                )}
+                <button className="btn btn-sm btn-ghost" onClick={() => setReloaded(null)}>
+                  Dismiss
+                </button>
+              </div>
+            </div>
+          )}
           <Outlet />
         </main>
       </div>
@@ -153,6 +262,28 @@ export function AppLayout() {
               Stats
             </button>
           </li>
+          <li>
+            <button
+              onClick={() => {
+                navigate({ to: '/live-check' })
+                closeSidebar()
+              }}
+              className={isActive('/live-check') ? 'active' : ''}
+            >
+              Live Check
+            </button>
+          </li>
+          <li>
+            <button
+              onClick={() => {
+                navigate({ to: '/render' })
+                closeSidebar()
+              }}
+              className={isActive('/render') ? 'active' : ''}
+            >
+              Templates
+            </button>
+          </li>
           <li className="menu-title mt-4">Schema</li>
           <li>
             <button
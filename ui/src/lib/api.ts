@@ -1,4 +1,26 @@
 const BASE_URL = '/api/v1';
+const REGISTRY_STORAGE_KEY = 'registry';
+
+// Returns the id (`{name}/{version}`) of the selected registry, null for the default registry.
+export function getSelectedRegistry(): string | null {
+  if (typeof window === 'undefined') return null;
+  return localStorage.getItem(REGISTRY_STORAGE_KEY);
+}
+
+// Selects the registry browsed by the UI, null for the default registry.
+export function selectRegistry(id: string | null): void {
+  if (id) {
+    localStorage.setItem(REGISTRY_STORAGE_KEY, id);
+  } else {
+    localStorage.removeItem(REGISTRY_STORAGE_KEY);
+  }
+}
+
+// Base URL of the endpoints of the selected registry.
+function registryUrl(): string {
+  const selected = getSelectedRegistry();
+  return selected ? `${BASE_URL}/registries/${selected}` : `${BASE_URL}/registry`;
+}
 
 async function fetchJSON<T>(url: string, options?: RequestInit): Promise<T> {
   const response = await fetch(url, options);
@@ -13,6 +35,68 @@ export interface RegistryStats {
   counts: RegistryCounts;
 }
 
+export interface RegistryInfo {
+  name: string;
+  version: string;
+  schema_url: string;
+  path: string;
+  default: boolean;
+  counts: RegistryCounts;
+}
+
+export interface RegistryDiagnostics {
+  name: string;
+  version: string;
+  success: boolean;
+  diagnostics: Array<{ diagnostic: { message: string; severity?: string } }>;
+}
+
+export interface ReloadEvent {
+  name: string;
+  version: string;
+  success: boolean;
+}
+
+export interface RenderRequest {
+  template: string;
+  config?: string;
+  filter?: string;
+}
+
+export interface RenderResponse {
+  success: boolean;
+  files: Array<{ path: string; content: string }>;
+  diagnostics: Array<{ diagnostic: { message: string; severity?: string } }>;
+}
+
+export interface LiveCheckFinding {
+  id: string;
+  message: string;
+  level: 'violation' | 'improvement' | 'information';
+  context?: Record<string, unknown>;
+  signal_type?: string | null;
+  signal_name?: string | null;
+}
+
+export interface LiveCheckResult {
+  all_advice: LiveCheckFinding[];
+  highest_advice_level?: string | null;
+}
+
+// A sample is an object with a single key, the kind of the sample (e.g. `attribute`, `span`).
+export type LiveCheckSample = Record<string, Record<string, unknown>>;
+
+export interface LiveCheckReport {
+  samples: LiveCheckSample[];
+  statistics: {
+    total_entities: number;
+    total_advisories: number;
+    advice_level_counts: Record<string, number>;
+    no_advice_count: number;
+    registry_coverage: number;
+  };
+}
+
 export interface RegistryCounts {
   attributes: number;
   metrics: number;
@@ -179,6 +263,12 @@ export interface SearchResponse {
   count: number;
   offset: number;
   results: SearchResult[];
+  facets: SearchFacets;
+}
+
+export interface SearchFacets {
+  types: Partial<Record<Exclude<TypeFilter, 'all'>, number>>;
+  stability: Partial<Record<Exclude<StabilityFilter, null>, number>>;
 }
 
 export type StabilityFilter =
@@ -192,29 +282,29 @@ export type StabilityFilter =
 export type TypeFilter = 'all' | 'attribute' | 'metric' | 'span' | 'event' | 'entity';
 
 export async function getRegistryStats(): Promise<RegistryStats> {
-  return fetchJSON<RegistryStats>(`${BASE_URL}/registry/stats`);
+  return fetchJSON<RegistryStats>(`${registryUrl()}/stats`);
 }
 
 export async function getAttribute(key: string): Promise<AttributeResponse> {
   return fetchJSON<AttributeResponse>(
-    `${BASE_URL}/registry/attribute/${encodeURIComponent(key)}`
+    `${registryUrl()}/attribute/${encodeURIComponent(key)}`
   );
 }
 
 export async function getMetric(name: string): Promise<MetricResponse> {
-  return fetchJSON<MetricResponse>(`${BASE_URL}/registry/metric/${encodeURIComponent(name)}`);
+  return fetchJSON<MetricResponse>(`${registryUrl()}/metric/${encodeURIComponent(name)}`);
 }
 
 export async function getSpan(type: string): Promise<SpanResponse> {
-  return fetchJSON<SpanResponse>(`${BASE_URL}/registry/span/${encodeURIComponent(type)}`);
+  return fetchJSON<SpanResponse>(`${registryUrl()}/span/${encodeURIComponent(type)}`);
 }
 
 export async function getEvent(name: string): Promise<EventResponse> {
-  return fetchJSON<EventResponse>(`${BASE_URL}/registry/event/${encodeURIComponent(name)}`);
+  return fetchJSON<EventResponse>(`${registryUrl()}/event/${encodeURIComponent(name)}`);
 }
 
 export async function getEntity(type: string): Promise<EntityResponse> {
-  return fetchJSON<EntityResponse>(`${BASE_URL}/registry/entity/${encodeURIComponent(type)}`);
+  return fetchJSON<EntityResponse>(`${registryUrl()}/entity/${encodeURIComponent(type)}`);
 }
 
 export async function search(
@@ -231,11 +321,60 @@ export async function search(
   if (stability) searchParams.set('stability', stability);
   if (limit) searchParams.set('limit', limit.toString());
   if (offset) searchParams.set('offset', offset.toString());
-  return fetchJSON<SearchResponse>(`${BASE_URL}/registry/search?${searchParams.toString()}`, {
+  return fetchJSON<SearchResponse>(`${registryUrl()}/search?${searchParams.toString()}`, {
     signal: options?.signal,
   });
 }
 
+export async function listRegistries(): Promise<RegistryInfo[]> {
+  return fetchJSON<RegistryInfo[]>(`${BASE_URL}/registries`);
+}
+
+// Runs live-check on a JSON array of samples or an OTLP-JSON request against the selected registry.
+export async function liveCheck(body: string): Promise<LiveCheckReport> {
+  const selected = getSelectedRegistry();
+  const query = selected ? `?registry=${encodeURIComponent(selected)}` : '';
+  const response = await fetch(`${BASE_URL}/live-check${query}`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body,
+  });
+  if (!response.ok) {
+    const error = (await response.json().catch(() => null)) as { error?: string } | null;
+    throw new Error(error?.error ?? `HTTP error! status: ${response.status}`);
+  }
+  return response.json() as Promise<LiveCheckReport>;
+}
+
+export async function render(request: RenderRequest): Promise<RenderResponse> {
+  const selected = getSelectedRegistry();
+  const query = selected ? `?registry=${encodeURIComponent(selected)}` : '';
+  const response = await fetch(`${BASE_URL}/render${query}`, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(request),
+  });
+  if (!response.ok) {
+    const error = (await response.json().catch(() => null)) as { error?: string } | null;
+    throw new Error(error?.error ?? `HTTP error! status: ${response.status}`);
+  }
+  return response.json() as Promise<RenderResponse>;
+}
+
+export async function getDiagnostics(): Promise<RegistryDiagnostics[]> {
+  return fetchJSON<RegistryDiagnostics[]>(`${BASE_URL}/diagnostics`);
+}
+
+// Calls `onReload` each time a served registry is reloaded. Returns a function closing the
+// subscription.
+export function subscribeToReloads(onReload: (event: ReloadEvent) => void): () => void {
+  const source = new EventSource(`${BASE_URL}/events`);
+  source.addEventListener('reload', (message) => {
+    onReload(JSON.parse((message as MessageEvent<string>).data) as ReloadEvent);
+  });
+  return () => source.close();
+}
+
 export async function getSchema(name: string): Promise<SchemaResponse> {
   return fetchJSON<SchemaResponse>(`${BASE_URL}/schema/${encodeURIComponent(name)}`);
 }
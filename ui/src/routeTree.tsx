@@ -4,6 +4,8 @@ import { Route as SearchRoute } from './routes/search'
 import { Route as StatsRoute } from './routes/stats'
 import { Route as SchemaRoute } from './routes/schema'
 import { Route as ApiDocsRoute } from './routes/api-docs'
+import { Route as LiveCheckRoute } from './routes/live-check'
+import { Route as RenderRoute } from './routes/render'
 import { Route as AttributeRoute } from './routes/attribute.$key'
 import { Route as MetricRoute } from './routes/metric.$name'
 import { Route as SpanRoute } from './routes/span.$type'
@@ -16,6 +18,8 @@ export const routeTree = RootRoute.addChildren([
   StatsRoute,
   SchemaRoute,
   ApiDocsRoute,
+  LiveCheckRoute,
+  RenderRoute,
   AttributeRoute,
   MetricRoute,
   SpanRoute,
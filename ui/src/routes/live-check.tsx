@@ -0,0 +1,154 @@
+import { createRoute } from '@tanstack/react-router'
+import { useState } from 'react'
+import { liveCheck } from '../lib/api'
+import type { LiveCheckFinding, LiveCheckReport, LiveCheckResult } from '../lib/api'
+import { Route as RootRoute } from './__root'
+
+export const Route = createRoute({
+  getParentRoute: () => RootRoute,
+  path: 'live-check',
+  component: LiveCheck,
+})
+
+const EXAMPLE = `[
+  { "attribute": { "name": "http.request.method", "value": "GET" } },
+  { "attribute": { "name": "http.response.status_code", "value": "200" } }
+]`
+
+interface CheckedEntity {
+  path: string
+  findings: LiveCheckFinding[]
+}
+
+// Collects the entities (samples and their nested attributes, events, data points, ...)
+// having a live-check result, with a readable path.
+function checkedEntities(value: unknown, path: string, entities: CheckedEntity[]) {
+  if (Array.isArray(value)) {
+    value.forEach((item, index) => checkedEntities(item, `${path}[${index}]`, entities))
+    return
+  }
+  if (value === null || typeof value !== 'object') return
+  const object = value as Record<string, unknown>
+  const name = typeof object.name === 'string' ? object.name : null
+  const entityPath = name ? `${path} ${name}` : path
+  const result = object.live_check_result as LiveCheckResult | undefined
+  if (result) {
+    entities.push({ path: entityPath, findings: result.all_advice })
+  }
+  for (const [key, child] of Object.entries(object)) {
+    if (key !== 'live_check_result' && typeof child === 'object') {
+      checkedEntities(child, `${entityPath} › ${key}`, entities)
+    }
+  }
+}
+
+const levelBadge: Record<string, string> = {
+  violation: 'badge-error',
+  improvement: 'badge-warning',
+  information: 'badge-info',
+}
+
+function LiveCheck() {
+  const [input, setInput] = useState(EXAMPLE)
+  const [report, setReport] = useState<LiveCheckReport | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [loading, setLoading] = useState(false)
+
+  const runCheck = () => {
+    setLoading(true)
+    setError(null)
+    liveCheck(input)
+      .then(setReport)
+      .catch((err: unknown) => {
+        setReport(null)
+        setError(err instanceof Error ? err.message : 'Unknown error')
+      })
+      .finally(() => setLoading(false))
+  }
+
+  const entities: CheckedEntity[] = []
+  report?.samples.forEach((sample, index) => {
+    for (const [kind, body] of Object.entries(sample)) {
+      checkedEntities(body, `#${index + 1} ${kind}`, entities)
+    }
+  })
+
+  return (
+    <div className="space-y-6">
+      <h1 className="text-3xl font-bold">Live Check</h1>
+      <p className="text-sm text-base-content/70">
+        Paste a JSON array of samples (see the LiveCheckSample schema) or an OTLP-JSON
+        request to check it against the registry.
+      </p>
+
+      <textarea
+        className="textarea textarea-bordered w-full h-64 font-mono text-sm"
+        aria-label="Samples"
+        value={input}
+        onChange={(e) => setInput(e.target.value)}
+      />
+      <button className="btn btn-primary" onClick={runCheck} disabled={loading}>
+        {loading ? <span className="loading loading-spinner loading-sm"></span> : 'Check'}
+      </button>
+
+      {error && (
+        <div className="alert alert-error" role="alert">
+          <span>{error}</span>
+        </div>
+      )}
+
+      {report && (
+        <>
+          <div className="stats stats-vertical lg:stats-horizontal shadow w-full">
+            <div className="stat">
+              <div className="stat-title">Entities</div>
+              <div className="stat-value">{report.statistics.total_entities}</div>
+              <div className="stat-desc">{report.statistics.no_advice_count} without findings</div>
+            </div>
+            {['violation', 'improvement', 'information'].map((level) => (
+              <div key={level} className="stat">
+                <div className="stat-title capitalize">{level}</div>
+                <div className="stat-value">{report.statistics.advice_level_counts[level] ?? 0}</div>
+              </div>
+            ))}
+            <div className="stat">
+              <div className="stat-title">Registry coverage</div>
+              <div className="stat-value">
+                {(report.statistics.registry_coverage * 100).toFixed(1)}%
+              </div>
+            </div>
+          </div>
+
+          <div className="overflow-x-auto">
+            <table className="table table-sm">
+              <thead>
+                <tr>
+                  <th>Entity</th>
+                  <th>Level</th>
+                  <th>Finding</th>
+                  <th>Message</th>
+                </tr>
+              </thead>
+              <tbody>
+                {entities.flatMap((entity) =>
+                  entity.findings.map((finding, index) => (
+                    <tr key={`${entity.path}-${index}`}>
+                      <td className="font-mono text-sm">{entity.path}</td>
+                      <td>
+                        <span className={`badge ${levelBadge[finding.level] ?? ''}`}>
+                          {finding.level}
+                        </span>
+                      </td>
+                      <td className="font-mono text-sm">{finding.id}</td>
+                      <td>{finding.message}</td>
+                    </tr>
+                  ))
+                )}
+              </tbody>
+            </table>
+          </div>
+        </>
+      )}
+    </div>
+  )
+}
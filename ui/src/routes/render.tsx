@@ -0,0 +1,124 @@
+import { createRoute } from '@tanstack/react-router'
+import { useState } from 'react'
+import { render } from '../lib/api'
+import type { RenderResponse } from '../lib/api'
+import { Route as RootRoute } from './__root'
+
+export const Route = createRoute({
+  getParentRoute: () => RootRoute,
+  path: 'render',
+  component: Render,
+})
+
+const EXAMPLE_TEMPLATE = `# Attributes
+
+{% for attribute in ctx %}
+- \`{{ attribute.key }}\`: {{ attribute.brief | trim }}
+{% endfor %}`
+
+const EXAMPLE_CONFIG = `whitespace_control:
+  trim_blocks: true
+  lstrip_blocks: true
+templates:
+  - application_mode: single
+    file_name: attributes.md`
+
+function Render() {
+  const [template, setTemplate] = useState(EXAMPLE_TEMPLATE)
+  const [config, setConfig] = useState(EXAMPLE_CONFIG)
+  const [filter, setFilter] = useState('.registry.attributes | sort_by(.key)')
+  const [result, setResult] = useState<RenderResponse | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [loading, setLoading] = useState(false)
+
+  const runRender = () => {
+    setLoading(true)
+    setError(null)
+    render({
+      template,
+      config: config.trim() ? config : undefined,
+      filter: filter.trim() ? filter : undefined,
+    })
+      .then(setResult)
+      .catch((err: unknown) => {
+        setResult(null)
+        setError(err instanceof Error ? err.message : 'Unknown error')
+      })
+      .finally(() => setLoading(false))
+  }
+
+  return (
+    <div className="space-y-6">
+      <h1 className="text-3xl font-bold">Templates</h1>
+      <p className="text-sm text-base-content/70">
+        Render a Jinja template against the registry. The optional <code>weaver.yaml</code>{' '}
+        configures the template (filter, application_mode, params, file_name), and the jq
+        filter overrides the filter of its templates.
+      </p>
+
+      <div className="grid grid-cols-1 lg:grid-cols-2 gap-4">
+        <label className="form-control">
+          <span className="label-text mb-1">Template</span>
+          <textarea
+            className="textarea textarea-bordered w-full h-64 font-mono text-sm"
+            value={template}
+            onChange={(e) => setTemplate(e.target.value)}
+          />
+        </label>
+        <label className="form-control">
+          <span className="label-text mb-1">weaver.yaml</span>
+          <textarea
+            className="textarea textarea-bordered w-full h-64 font-mono text-sm"
+            value={config}
+            onChange={(e) => setConfig(e.target.value)}
+          />
+        </label>
+      </div>
+      <label className="form-control">
+        <span className="label-text mb-1">jq filter</span>
+        <input
+          type="text"
+          className="input input-bordered w-full font-mono text-sm"
+          value={filter}
+          onChange={(e) => setFilter(e.target.value)}
+        />
+      </label>
+      <button className="btn btn-primary" onClick={runRender} disabled={loading}>
+        {loading ? <span className="loading loading-spinner loading-sm"></span> : 'Render'}
+      </button>
+
+      {error && (
+        <div className="alert alert-error" role="alert">
+          <span>{error}</span>
+        </div>
+      )}
+
+      {result && !result.success && (
+        <div className="alert alert-error" role="alert">
+          <ul className="space-y-2">
+            {result.diagnostics.map((d, i) => (
+              <li key={i} className="font-mono text-sm whitespace-pre-wrap">
+                {d.diagnostic.message}
+              </li>
+            ))}
+          </ul>
+        </div>
+      )}
+
+      {result?.success && result.files.length === 0 && (
+        <div className="alert alert-info" role="status">
+          <span>The filter returned no data, nothing was rendered.</span>
+        </div>
+      )}
+
+      {result?.files.map((file, i) => (
+        <div key={i} className="card bg-base-200">
+          <div className="card-body">
+            <h2 className="card-title font-mono text-base">{file.path}</h2>
+            <pre className="overflow-x-auto text-sm">{file.content}</pre>
+          </div>
+        </div>
+      ))}
+    </div>
+  )
+}
@@ -33,6 +33,14 @@ interface SearchState {
   currentPage: number
 }
 
+// Appends the number of matches of a filter value, when known, to its label.
+const withCount = (
+  label: string,
+  counts: Partial<Record<string, number>> | undefined,
+  value: string,
+) =>
+  counts === undefined ? label : `${label} (${counts[value] ?? 0})`
+
 const parseTypeFilter = (value: string | null): TypeFilter =>
   typeOptions.includes(value as TypeFilter) ? (value as TypeFilter) : 'all'
 
@@ -275,11 +283,13 @@ function Search() {
         />
         <select className="select select-bordered" value={searchType} onChange={handleTypeChange}>
           <option value="all">All Types</option>
-          <option value="attribute">Attributes</option>
-          <option value="metric">Metrics</option>
-          <option value="span">Spans</option>
-          <option value="event">Events</option>
-          <option value="entity">Entities</option>
+          <option value="attribute">
+            {withCount('Attributes', results?.facets.types, 'attribute')}
+          </option>
+          <option value="metric">{withCount('Metrics', results?.facets.types, 'metric')}</option>
+          <option value="span">{withCount('Spans', results?.facets.types, 'span')}</option>
+          <option value="event">{withCount('Events', results?.facets.types, 'event')}</option>
+          <option value="entity">{withCount('Entities', results?.facets.types, 'entity')}</option>
         </select>
         <select
           className="select select-bordered"
@@ -287,12 +297,18 @@ function Search() {
           onChange={handleStabilityChange}
         >
           <option value="">All Stability</option>
-          <option value="stable">Stable</option>
-          <option value="development">Development</option>
-          <option value="alpha">Alpha</option>
-          <option value="beta">Beta</option>
-          <option value="release_candidate">Release Candidate</option>
-          <option value="deprecated">Deprecated</option>
+          <option value="stable">{withCount('Stable', results?.facets.stability, 'stable')}</option>
+          <option value="development">
+            {withCount('Development', results?.facets.stability, 'development')}
+          </option>
+          <option value="alpha">{withCount('Alpha', results?.facets.stability, 'alpha')}</option>
+          <option value="beta">{withCount('Beta', results?.facets.stability, 'beta')}</option>
+          <option value="release_candidate">
+            {withCount('Release Candidate', results?.facets.stability, 'release_candidate')}
+          </option>
+          <option value="deprecated">
+            {withCount('Deprecated', results?.facets.stability, 'deprecated')}
+          </option>
         </select>
       </div>
 